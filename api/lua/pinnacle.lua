@@ -38,6 +38,234 @@ function pinnacle.reload_config()
     client.unary_request(pinnacle_service.ReloadConfig, {})
 end
 
+---@alias Backend
+---| "window" The compositor is running nested in a window
+---| "tty" The compositor is running in a tty
+---| "dummy" The compositor is running without a display for testing
+
+---Get the backend the compositor is running on.
+---
+---This lets you skip configuring things that aren't supported on a given backend,
+---like gamma and power management when running nested in a window.
+---
+---### Example
+---```lua
+---if Pinnacle.backend() == "tty" then
+---    -- Set up things that only work in a tty
+---end
+---```
+---@return Backend?
+function pinnacle.backend()
+    local response = client.unary_request(pinnacle_service.GetBackend, {})
+
+    local backend_values = require("pinnacle.grpc.defs").pinnacle.v0alpha1.Backend
+
+    if response.backend == backend_values.BACKEND_WINDOW then
+        return "window"
+    elseif response.backend == backend_values.BACKEND_TTY then
+        return "tty"
+    elseif response.backend == backend_values.BACKEND_DUMMY then
+        return "dummy"
+    end
+
+    return nil
+end
+
+---Information about XWayland.
+---@class XwaylandInfo
+---@field running boolean Whether XWayland is running
+---@field display integer? The X11 display number, as in `DISPLAY=:N`, if XWayland is running
+
+---Get whether XWayland is running and which X11 display it's on.
+---
+---### Example
+---```lua
+---local xwayland = Pinnacle.xwayland_info()
+---if xwayland.display then
+---    print("X11 apps can connect to DISPLAY=:" .. xwayland.display)
+---end
+---```
+---@return XwaylandInfo
+function pinnacle.xwayland_info()
+    local response = client.unary_request(pinnacle_service.GetXwaylandInfo, {})
+
+    ---@type XwaylandInfo
+    local info = {
+        running = response.running or false,
+        display = response.display,
+    }
+
+    return info
+end
+
+---The mime types advertised by the current selections.
+---@class SelectionMimeTypes
+---@field clipboard string[] The mime types of the clipboard selection
+---@field primary string[] The mime types of the primary selection
+---@field dnd string[] The mime types of the ongoing drag-and-drop, if there is one
+
+---Get the mime types currently advertised by the clipboard, primary selection,
+---and any ongoing drag-and-drop.
+---
+---This does not transfer any data, so it can be used to decide whether or not
+---a selection is worth fetching.
+---
+---### Example
+---```lua
+---local mime_types = Pinnacle.selection_mime_types()
+---for _, mime in ipairs(mime_types.clipboard) do
+---    print(mime)
+---end
+---```
+---@return SelectionMimeTypes
+function pinnacle.selection_mime_types()
+    local response = client.unary_request(pinnacle_service.GetSelectionMimeTypes, {})
+
+    ---@type SelectionMimeTypes
+    local mime_types = {
+        clipboard = response.clipboard or {},
+        primary = response.primary or {},
+        dnd = response.dnd or {},
+    }
+
+    return mime_types
+end
+
+---@class WindowSnapshot
+---@field window WindowHandle
+---@field geometry { x: integer?, y: integer?, width: integer?, height: integer? }?
+---@field tags TagHandle[]
+---@field focused boolean
+---@field floating boolean
+---@field class string?
+---@field title string?
+
+---@class OutputSnapshot
+---@field output OutputHandle
+---@field tags TagHandle[]
+---@field active_tags TagHandle[]
+---@field focused boolean
+
+---A snapshot of key compositor state.
+---@class StateSnapshot
+---@field windows WindowSnapshot[]
+---@field outputs OutputSnapshot[]
+---@field focused_output OutputHandle?
+---@field focused_window WindowHandle?
+
+---Get a snapshot of key compositor state.
+---
+---This returns all windows with their tags and geometry, all outputs with their tags,
+---and the focused output and window in a single call.
+---It is mainly intended for making assertions in config test suites.
+---
+---### Example
+---```lua
+---local snapshot = Pinnacle.state_snapshot()
+---assert(#snapshot.windows == 2)
+---```
+---@return StateSnapshot
+function pinnacle.state_snapshot()
+    local response = client.unary_request(pinnacle_service.GetStateSnapshot, {})
+
+    ---@diagnostic disable-next-line: invisible
+    local window_handle = require("pinnacle.window").handle
+    ---@diagnostic disable-next-line: invisible
+    local tag_handle = require("pinnacle.tag").handle
+    ---@diagnostic disable-next-line: invisible
+    local output_handle = require("pinnacle.output").handle
+
+    ---@type StateSnapshot
+    local snapshot = {
+        windows = {},
+        outputs = {},
+        focused_output = response.focused_output_name
+            and output_handle.new(response.focused_output_name),
+        focused_window = response.focused_window_id
+            and window_handle.new(response.focused_window_id),
+    }
+
+    for _, win in ipairs(response.windows or {}) do
+        table.insert(snapshot.windows, {
+            window = window_handle.new(win.window_id),
+            geometry = win.geometry,
+            tags = tag_handle.new_from_table(win.tag_ids or {}),
+            focused = win.focused or false,
+            floating = win.floating or false,
+            class = win.class,
+            title = win.title,
+        })
+    end
+
+    for _, op in ipairs(response.outputs or {}) do
+        table.insert(snapshot.outputs, {
+            output = output_handle.new(op.output_name),
+            tags = tag_handle.new_from_table(op.tag_ids or {}),
+            active_tags = tag_handle.new_from_table(op.active_tag_ids or {}),
+            focused = op.focused or false,
+        })
+    end
+
+    return snapshot
+end
+
+---A surface returned by `Pinnacle.list_surfaces`.
+---@class SurfaceInfo
+---@field surface_id integer? The surface's `wl_surface` object id. Ids are only unique within a client, so use this together with `client_id` to tell surfaces apart.
+---@field client_id integer? An id for the surface's client that's unique while the compositor is running
+---@field client_pid integer? The pid of the surface's client, if it could be found
+---@field role string? The surface's role, like `xdg_toplevel`, `xdg_popup`, `zwlr_layer_surface_v1`, or `subsurface`
+---@field parent_surface_id integer? The surface id of the toplevel or layer surface this popup belongs to, or of the parent of this subsurface
+---@field window WindowHandle? The window this surface is the main surface of
+---@field layer_namespace string? The namespace of this layer surface
+---@field layer ("background"|"bottom"|"top"|"overlay")? The layer of this layer surface
+---@field output OutputHandle? The output this surface is on
+---@field geometry { x: integer?, y: integer?, width: integer?, height: integer? }? Windows, layer surfaces, and popups are in the global space. Subsurfaces only have a size.
+---@field mapped boolean Whether the surface has a buffer attached
+
+---List every surface the compositor knows about, for debugging.
+---
+---This includes windows that haven't mapped yet, layer surfaces, popups,
+---and subsurfaces, which makes it useful for figuring out why something
+---isn't showing up.
+---
+---### Example
+---```lua
+---for _, surface in ipairs(Pinnacle.list_surfaces()) do
+---    print(surface.surface_id, surface.role, surface.client_pid, surface.mapped)
+---end
+---```
+---@return SurfaceInfo[]
+function pinnacle.list_surfaces()
+    local response = client.unary_request(pinnacle_service.ListSurfaces, {})
+
+    ---@diagnostic disable-next-line: invisible
+    local window_handle = require("pinnacle.window").handle
+    ---@diagnostic disable-next-line: invisible
+    local output_handle = require("pinnacle.output").handle
+
+    ---@type SurfaceInfo[]
+    local surfaces = {}
+
+    for _, surface in ipairs(response.surfaces or {}) do
+        table.insert(surfaces, {
+            surface_id = surface.surface_id,
+            client_id = surface.client_id,
+            client_pid = surface.client_pid,
+            role = surface.role,
+            parent_surface_id = surface.parent_surface_id,
+            window = surface.window_id and window_handle.new(surface.window_id),
+            layer_namespace = surface.layer_namespace,
+            layer = surface.layer,
+            output = surface.output_name and output_handle.new(surface.output_name),
+            geometry = surface.geometry,
+            mapped = surface.mapped or false,
+        })
+    end
+
+    return surfaces
+end
+
 ---Setup a Pinnacle config.
 ---
 ---You must pass in a function that takes in the `Pinnacle` table. This table is how you'll access the other config modules.
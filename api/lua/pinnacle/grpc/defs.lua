@@ -26,6 +26,65 @@ local defs = {}
 ---@class pinnacle.v0alpha1.PingResponse
 ---@field payload string
 
+---@class pinnacle.v0alpha1.GetSelectionMimeTypesRequest
+
+---@class pinnacle.v0alpha1.GetSelectionMimeTypesResponse
+---@field clipboard string[]?
+---@field primary string[]?
+---@field dnd string[]?
+
+---@class pinnacle.v0alpha1.GetStateSnapshotRequest
+
+---@class pinnacle.v0alpha1.GetStateSnapshotResponse.WindowSnapshot
+---@field window_id integer?
+---@field geometry pinnacle.v0alpha1.Geometry?
+---@field tag_ids integer[]?
+---@field focused boolean?
+---@field floating boolean?
+---@field class string?
+---@field title string?
+
+---@class pinnacle.v0alpha1.GetStateSnapshotResponse.OutputSnapshot
+---@field output_name string?
+---@field tag_ids integer[]?
+---@field active_tag_ids integer[]?
+---@field focused boolean?
+
+---@class pinnacle.v0alpha1.GetStateSnapshotResponse
+---@field windows pinnacle.v0alpha1.GetStateSnapshotResponse.WindowSnapshot[]?
+---@field outputs pinnacle.v0alpha1.GetStateSnapshotResponse.OutputSnapshot[]?
+---@field focused_output_name string?
+---@field focused_window_id integer?
+
+---@class pinnacle.v0alpha1.GetBackendRequest
+
+---@class pinnacle.v0alpha1.GetBackendResponse
+---@field backend pinnacle.v0alpha1.Backend?
+
+---@class pinnacle.v0alpha1.GetXwaylandInfoRequest
+
+---@class pinnacle.v0alpha1.GetXwaylandInfoResponse
+---@field running boolean?
+---@field display integer?
+
+---@class pinnacle.v0alpha1.ListSurfacesRequest
+
+---@class pinnacle.v0alpha1.ListSurfacesResponse.Surface
+---@field surface_id integer?
+---@field client_id integer?
+---@field client_pid integer?
+---@field role string?
+---@field parent_surface_id integer?
+---@field window_id integer?
+---@field layer_namespace string?
+---@field layer string?
+---@field output_name string?
+---@field geometry pinnacle.v0alpha1.Geometry?
+---@field mapped boolean?
+
+---@class pinnacle.v0alpha1.ListSurfacesResponse
+---@field surfaces pinnacle.v0alpha1.ListSurfacesResponse.Surface[]?
+
 ---@enum pinnacle.v0alpha1.SetOrToggle
 local pinnacle_v0alpha1_SetOrToggle = {
     SET_OR_TOGGLE_UNSPECIFIED = 0,
@@ -34,6 +93,14 @@ local pinnacle_v0alpha1_SetOrToggle = {
     SET_OR_TOGGLE_TOGGLE = 3,
 }
 
+---@enum pinnacle.v0alpha1.Backend
+local pinnacle_v0alpha1_Backend = {
+    BACKEND_UNSPECIFIED = 0,
+    BACKEND_WINDOW = 1,
+    BACKEND_TTY = 2,
+    BACKEND_DUMMY = 3,
+}
+
 -- Output
 
 ---@class pinnacle.output.v0alpha1.Mode
@@ -54,6 +121,14 @@ local pinnacle_output_v0alpha1_Transform = {
     TRANSFORM_FLIPPED_270 = 8,
 }
 
+---@enum pinnacle.output.v0alpha1.LoneWindowAlignment
+local pinnacle_output_v0alpha1_LoneWindowAlignment = {
+    LONE_WINDOW_ALIGNMENT_UNSPECIFIED = 0,
+    LONE_WINDOW_ALIGNMENT_CENTER = 1,
+    LONE_WINDOW_ALIGNMENT_LEFT = 2,
+    LONE_WINDOW_ALIGNMENT_RIGHT = 3,
+}
+
 ---@class pinnacle.output.v0alpha1.SetLocationRequest
 ---@field output_name string?
 ---@field x integer?
@@ -74,6 +149,50 @@ local pinnacle_output_v0alpha1_Transform = {
 ---@field output_name string?
 ---@field transform pinnacle.output.v0alpha1.Transform
 
+---@class pinnacle.output.v0alpha1.SetOverviewRequest
+---@field output_name string?
+---@field set_or_toggle pinnacle.v0alpha1.SetOrToggle?
+---@field all_tags boolean?
+
+---@class pinnacle.output.v0alpha1.SetMagnificationRequest
+---@field output_name string?
+---@field absolute number?
+---@field relative number?
+
+---@class pinnacle.output.v0alpha1.SetBrightnessRequest
+---@field output_name string?
+---@field absolute number?
+---@field relative number?
+
+---@class pinnacle.output.v0alpha1.SetGammaPresetRequest
+---@field output_name string?
+---@field temperature integer?
+---@field brightness number?
+---@field gamma number?
+
+---@class pinnacle.output.v0alpha1.SetLoneWindowWidthRequest
+---@field output_name string?
+---@field max_width integer?
+---@field alignment pinnacle.output.v0alpha1.LoneWindowAlignment?
+
+---@class pinnacle.output.v0alpha1.FocusOutputUnderCursorRequest
+
+---@class pinnacle.output.v0alpha1.SetFocusLockRequest
+---@field output_name string?
+
+---@class pinnacle.output.v0alpha1.SetPrimaryRequest
+---@field output_name string?
+
+---@class pinnacle.output.v0alpha1.GetFocusStackRequest
+---@field output_name string?
+
+---@class pinnacle.output.v0alpha1.GetFocusStackResponse.Entry
+---@field window_id integer?
+---@field on_active_tag boolean?
+
+---@class pinnacle.output.v0alpha1.GetFocusStackResponse
+---@field entries pinnacle.output.v0alpha1.GetFocusStackResponse.Entry[]?
+
 ---@class pinnacle.output.v0alpha1.GetRequest
 
 ---@class pinnacle.output.v0alpha1.GetResponse
@@ -99,6 +218,11 @@ local pinnacle_output_v0alpha1_Transform = {
 ---@field scale number?
 ---@field transform pinnacle.output.v0alpha1.Transform?
 ---@field serial integer?
+---@field overview boolean?
+---@field magnification number?
+---@field brightness number?
+---@field lone_window_max_width integer?
+---@field lone_window_alignment pinnacle.output.v0alpha1.LoneWindowAlignment?
 
 -- Window
 
@@ -108,6 +232,12 @@ local pinnacle_output_v0alpha1_Transform = {
 ---@class pinnacle.window.v0alpha1.SetGeometryRequest
 ---@field window_id integer?
 ---@field geometry pinnacle.v0alpha1.Geometry?
+---@field relative_to_output string?
+
+---@class pinnacle.window.v0alpha1.SetFloatingGeometryRequest
+---@field window_id integer?
+---@field geometry pinnacle.v0alpha1.Geometry?
+---@field relative_to_output string?
 
 ---@class pinnacle.window.v0alpha1.SetFullscreenRequest
 ---@field window_id integer?
@@ -117,31 +247,112 @@ local pinnacle_output_v0alpha1_Transform = {
 ---@field window_id integer?
 ---@field set_or_toggle pinnacle.v0alpha1.SetOrToggle?
 
+---@class pinnacle.window.v0alpha1.SetMinimizedRequest
+---@field window_id integer?
+---@field set_or_toggle pinnacle.v0alpha1.SetOrToggle?
+
 ---@class pinnacle.window.v0alpha1.SetFloatingRequest
 ---@field window_id integer?
 ---@field set_or_toggle pinnacle.v0alpha1.SetOrToggle?
 
+---@class pinnacle.window.v0alpha1.SetRelativeGeometryRequest
+---@field window_id integer?
+---@field x number?
+---@field y number?
+---@field width number?
+---@field height number?
+
+---@class pinnacle.window.v0alpha1.SnapWindowRequest
+---@field window_id integer?
+---@field region pinnacle.window.v0alpha1.SnapRegion?
+
+---@enum pinnacle.window.v0alpha1.SnapRegion
+local pinnacle_window_v0alpha1_SnapRegion = {
+    SNAP_REGION_UNSPECIFIED = 0,
+    SNAP_REGION_LEFT_HALF = 1,
+    SNAP_REGION_RIGHT_HALF = 2,
+    SNAP_REGION_TOP_HALF = 3,
+    SNAP_REGION_BOTTOM_HALF = 4,
+    SNAP_REGION_TOP_LEFT_QUARTER = 5,
+    SNAP_REGION_TOP_RIGHT_QUARTER = 6,
+    SNAP_REGION_BOTTOM_LEFT_QUARTER = 7,
+    SNAP_REGION_BOTTOM_RIGHT_QUARTER = 8,
+}
+
+---@class pinnacle.window.v0alpha1.PlaceWindowRequest
+---@field window_id integer?
+---@field output_name string?
+---@field placement pinnacle.window.v0alpha1.Placement?
+---@field geometry pinnacle.v0alpha1.Geometry?
+
+---@enum pinnacle.window.v0alpha1.Placement
+local pinnacle_window_v0alpha1_Placement = {
+    PLACEMENT_UNSPECIFIED = 0,
+    PLACEMENT_TILED = 1,
+    PLACEMENT_FLOATING_CENTERED = 2,
+    PLACEMENT_FLOATING_GEOMETRY = 3,
+    PLACEMENT_FULLSCREEN = 4,
+}
+
+---@class pinnacle.window.v0alpha1.SetActivatedRequest
+---@field window_id integer?
+---@field set_or_toggle pinnacle.v0alpha1.SetOrToggle?
+
 ---@class pinnacle.window.v0alpha1.SetFocusedRequest
 ---@field window_id integer?
 ---@field set_or_toggle pinnacle.v0alpha1.SetOrToggle?
+---@field raise boolean?
 
 ---@class pinnacle.window.v0alpha1.MoveToTagRequest
 ---@field window_id integer?
 ---@field tag_id integer?
+---@field follow boolean?
+
+---@class pinnacle.window.v0alpha1.CopyToTagRequest
+---@field window_id integer?
+---@field tag_id integer?
 
 ---@class pinnacle.window.v0alpha1.SetTagRequest
 ---@field window_id integer?
 ---@field tag_id integer?
 ---@field set_or_toggle pinnacle.v0alpha1.SetOrToggle?
 
+---@class pinnacle.window.v0alpha1.ToggleRecentTagRequest
+---@field window_id integer?
+
 ---@class pinnacle.window.v0alpha1.RaiseRequest
 ---@field window_id integer?
 
+---@class pinnacle.window.v0alpha1.LowerRequest
+---@field window_id integer?
+
 ---@class pinnacle.window.v0alpha1.MoveGrabRequest
 ---@field button integer?
+---@field float_tiled boolean?
 
 ---@class pinnacle.window.v0alpha1.ResizeGrabRequest
 ---@field button integer?
+---@field border_width integer?
+
+---@class pinnacle.window.v0alpha1.CancelGrabRequest
+
+---@class pinnacle.window.v0alpha1.SelectWindowRequest
+
+---@class pinnacle.window.v0alpha1.SelectWindowResponse
+---@field window_id integer?
+
+---@class pinnacle.window.v0alpha1.GetGrabRequest
+
+---@class pinnacle.window.v0alpha1.GetGrabResponse
+---@field kind pinnacle.window.v0alpha1.GrabKind?
+---@field window_id integer?
+
+---@enum pinnacle.window.v0alpha1.GrabKind
+local pinnacle_window_v0alpha1_GrabKind = {
+    GRAB_KIND_UNSPECIFIED = 0,
+    GRAB_KIND_MOVE = 1,
+    GRAB_KIND_RESIZE = 2,
+}
 
 ---@class pinnacle.window.v0alpha1.GetRequest
 
@@ -150,6 +361,7 @@ local pinnacle_output_v0alpha1_Transform = {
 
 ---@class pinnacle.window.v0alpha1.GetPropertiesRequest
 ---@field window_id integer?
+---@field relative_to_output string?
 
 ---@class pinnacle.window.v0alpha1.GetPropertiesResponse
 ---@field geometry pinnacle.v0alpha1.Geometry?
@@ -159,6 +371,30 @@ local pinnacle_output_v0alpha1_Transform = {
 ---@field floating boolean?
 ---@field fullscreen_or_maximized pinnacle.window.v0alpha1.FullscreenOrMaximized?
 ---@field tag_ids integer[]?
+---@field urgent boolean?
+---@field activated boolean?
+---@field physical_geometry pinnacle.v0alpha1.Geometry?
+---@field label string?
+---@field content_type pinnacle.window.v0alpha1.ContentType?
+---@field last_focused_ms integer?
+---@field skip_taskbar boolean?
+---@field skip_cycle boolean?
+---@field geometry_pinned boolean?
+---@field is_x11 boolean?
+---@field tiled_edges pinnacle.window.v0alpha1.TiledEdges?
+---@field minimized boolean?
+
+---@class pinnacle.window.v0alpha1.GetSurfaceInfoRequest
+---@field window_id integer?
+
+---@class pinnacle.window.v0alpha1.GetSurfaceInfoResponse
+---@field wl_surface_version integer?
+---@field buffer_scale integer?
+---@field buffer_width integer?
+---@field buffer_height integer?
+---@field uses_viewporter boolean?
+---@field uses_fractional_scale boolean?
+---@field preferred_scale number?
 
 ---@enum pinnacle.window.v0alpha1.FullscreenOrMaximized
 local pinnacle_window_v0alpha1_FullscreenOrMaximized = {
@@ -168,16 +404,44 @@ local pinnacle_window_v0alpha1_FullscreenOrMaximized = {
     FULLSCREEN_OR_MAXIMIZED_MAXIMIZED = 3,
 }
 
+---@enum pinnacle.window.v0alpha1.ContentType
+local pinnacle_window_v0alpha1_ContentType = {
+    CONTENT_TYPE_UNSPECIFIED = 0,
+    CONTENT_TYPE_NONE = 1,
+    CONTENT_TYPE_PHOTO = 2,
+    CONTENT_TYPE_VIDEO = 3,
+    CONTENT_TYPE_GAME = 4,
+}
+
 ---@class pinnacle.window.v0alpha1.AddWindowRuleRequest
 ---@field cond pinnacle.window.v0alpha1.WindowRuleCondition?
 ---@field rule pinnacle.window.v0alpha1.WindowRule?
 
+---@class pinnacle.window.v0alpha1.AddWindowRuleResponse
+---@field rule_id integer?
+
+---@class pinnacle.window.v0alpha1.GetWindowRulesRequest
+
+---@class pinnacle.window.v0alpha1.GetWindowRulesResponse.Entry
+---@field rule_id integer?
+---@field cond pinnacle.window.v0alpha1.WindowRuleCondition?
+---@field rule pinnacle.window.v0alpha1.WindowRule?
+
+---@class pinnacle.window.v0alpha1.GetWindowRulesResponse
+---@field rules pinnacle.window.v0alpha1.GetWindowRulesResponse.Entry[]?
+
+---@class pinnacle.window.v0alpha1.RemoveWindowRuleRequest
+---@field rule_id integer?
+
+---@class pinnacle.window.v0alpha1.ReapplyWindowRulesRequest
+
 ---@class pinnacle.window.v0alpha1.WindowRuleCondition
 ---@field any pinnacle.window.v0alpha1.WindowRuleCondition[]?
 ---@field all pinnacle.window.v0alpha1.WindowRuleCondition[]?
 ---@field classes string[]?
 ---@field titles string[]?
 ---@field tags integer[]?
+---@field content_types pinnacle.window.v0alpha1.ContentType[]?
 
 ---@class pinnacle.window.v0alpha1.WindowRule
 ---@field output string?
@@ -188,6 +452,98 @@ local pinnacle_window_v0alpha1_FullscreenOrMaximized = {
 ---@field y integer?
 ---@field width integer?
 ---@field height integer?
+---@field focus_steal_exempt boolean?
+---@field disable_keybinds boolean?
+---@field focus_on_open boolean?
+---@field skip_taskbar boolean?
+---@field skip_cycle boolean?
+---@field once boolean?
+---@field unfullscreen_on_focus_loss boolean?
+
+---@class pinnacle.window.v0alpha1.EvaluateWindowRuleConditionRequest
+---@field window_id integer?
+---@field cond pinnacle.window.v0alpha1.WindowRuleCondition?
+
+---@class pinnacle.window.v0alpha1.WindowRuleConditionEvaluation
+---@field matched boolean?
+---@field any pinnacle.window.v0alpha1.WindowRuleConditionEvaluation[]?
+---@field all pinnacle.window.v0alpha1.WindowRuleConditionEvaluation[]?
+---@field classes boolean?
+---@field titles boolean?
+---@field tags boolean?
+---@field content_types boolean?
+
+---@class pinnacle.window.v0alpha1.EvaluateWindowRuleConditionResponse
+---@field evaluation pinnacle.window.v0alpha1.WindowRuleConditionEvaluation?
+
+---@class pinnacle.window.v0alpha1.SetFocusNewWindowsRequest
+---@field focus boolean?
+
+---@class pinnacle.window.v0alpha1.SetUnfullscreenOnFocusLossRequest
+---@field unfullscreen boolean?
+
+---@class pinnacle.window.v0alpha1.SetLastWindowFallbackRequest
+---@field command string[]?
+
+---@class pinnacle.window.v0alpha1.OpenNextOnFocusedTagRequest
+
+---@class pinnacle.window.v0alpha1.SetUrgencyTimeoutRequest
+---@field timeout_ms integer?
+
+---@class pinnacle.window.v0alpha1.SetLabelRequest
+---@field window_id integer?
+---@field label string?
+
+---@class pinnacle.window.v0alpha1.SetScaleRequest
+---@field window_id integer?
+---@field scale number?
+
+---@class pinnacle.window.v0alpha1.SetSkipTaskbarRequest
+---@field window_id integer?
+---@field set_or_toggle pinnacle.v0alpha1.SetOrToggle?
+
+---@class pinnacle.window.v0alpha1.SetSkipCycleRequest
+---@field window_id integer?
+---@field set_or_toggle pinnacle.v0alpha1.SetOrToggle?
+
+---@class pinnacle.window.v0alpha1.SetGeometryPinnedRequest
+---@field window_id integer?
+---@field set_or_toggle pinnacle.v0alpha1.SetOrToggle?
+
+---@class pinnacle.window.v0alpha1.SetMaximizeGapsRequest
+---@field window_id integer?
+---@field set_or_toggle pinnacle.v0alpha1.SetOrToggle?
+
+---@class pinnacle.window.v0alpha1.SetTiledEdgesEnabledRequest
+---@field window_id integer?
+---@field set_or_toggle pinnacle.v0alpha1.SetOrToggle?
+
+---@class pinnacle.window.v0alpha1.TiledEdges
+---@field top boolean?
+---@field bottom boolean?
+---@field left boolean?
+---@field right boolean?
+
+---@class pinnacle.window.v0alpha1.FocusUrgentRequest
+
+---@class pinnacle.window.v0alpha1.FocusPreviousRequest
+
+---@class pinnacle.window.v0alpha1.JumpToWindowRequest
+---@field class string?
+---@field title string?
+---@field cycle boolean?
+
+---@class pinnacle.window.v0alpha1.JumpToWindowResponse
+---@field window_id integer?
+
+---@class pinnacle.window.v0alpha1.CaptureRequest
+---@field window_id integer?
+---@field scale number?
+
+---@class pinnacle.window.v0alpha1.CaptureResponse
+---@field width integer?
+---@field height integer?
+---@field pixels string?
 
 -- Tag
 
@@ -198,6 +554,54 @@ local pinnacle_window_v0alpha1_FullscreenOrMaximized = {
 ---@class pinnacle.tag.v0alpha1.SwitchToRequest
 ---@field tag_id integer?
 
+---@class pinnacle.tag.v0alpha1.SwitchToPreviousRequest
+---@field output_name string?
+
+---@class pinnacle.tag.v0alpha1.SetActiveTagsRequest
+---@field output_name string?
+---@field tag_ids integer[]?
+
+---@class pinnacle.tag.v0alpha1.SetGapsRequest
+---@field tag_id integer?
+---@field inner integer?
+---@field outer integer?
+---@field smart boolean?
+
+---@class pinnacle.tag.v0alpha1.SetFloatingHiddenRequest
+---@field tag_id integer?
+---@field set_or_toggle pinnacle.v0alpha1.SetOrToggle?
+
+---@class pinnacle.tag.v0alpha1.SetWindowLimitRequest
+---@field tag_id integer?
+---@field limit integer?
+---@field overflow pinnacle.tag.v0alpha1.OverflowPolicy?
+
+---@enum pinnacle.tag.v0alpha1.OverflowPolicy
+local pinnacle_tag_v0alpha1_OverflowPolicy = {
+    OVERFLOW_POLICY_UNSPECIFIED = 0,
+    OVERFLOW_POLICY_FLOAT = 1,
+    OVERFLOW_POLICY_NEXT_TAG = 2,
+    OVERFLOW_POLICY_REJECT = 3,
+}
+
+---@class pinnacle.tag.v0alpha1.SetTilingDirectionRequest
+---@field tag_id integer?
+---@field direction pinnacle.tag.v0alpha1.TilingDirection?
+
+---@class pinnacle.tag.v0alpha1.ToggleTilingDirectionRequest
+---@field tag_id integer?
+
+---@class pinnacle.tag.v0alpha1.SetDisplayNameRequest
+---@field tag_id integer?
+---@field display_name string?
+
+---@enum pinnacle.tag.v0alpha1.TilingDirection
+local pinnacle_tag_v0alpha1_TilingDirection = {
+    TILING_DIRECTION_UNSPECIFIED = 0,
+    TILING_DIRECTION_HORIZONTAL = 1,
+    TILING_DIRECTION_VERTICAL = 2,
+}
+
 ---@class pinnacle.tag.v0alpha1.AddRequest
 ---@field output_name string?
 ---@field tag_names string[]?
@@ -208,6 +612,9 @@ local pinnacle_window_v0alpha1_FullscreenOrMaximized = {
 ---@class pinnacle.tag.v0alpha1.RemoveRequest
 ---@field tag_ids integer[]?
 
+---@class pinnacle.tag.v0alpha1.SetDefaultOutputTagsRequest
+---@field tag_names string[]?
+
 ---@class pinnacle.tag.v0alpha1.GetRequest
 
 ---@class pinnacle.tag.v0alpha1.GetResponse
@@ -221,6 +628,15 @@ local pinnacle_window_v0alpha1_FullscreenOrMaximized = {
 ---@field name string?
 ---@field output_name string?
 ---@field window_ids integer[]?
+---@field inner_gaps integer?
+---@field outer_gaps integer?
+---@field smart_gaps boolean?
+---@field urgent boolean?
+---@field floating_hidden boolean?
+---@field window_limit integer?
+---@field overflow_policy pinnacle.tag.v0alpha1.OverflowPolicy?
+---@field tiling_direction pinnacle.tag.v0alpha1.TilingDirection?
+---@field display_name string?
 
 -- Input
 
@@ -237,6 +653,9 @@ local pinnacle_input_v0alpha1_Modifier = {
 ---@field modifiers pinnacle.input.v0alpha1.Modifier[]?
 ---@field raw_code integer?
 ---@field xkb_name string?
+---@field held_raw_code integer?
+---@field held_xkb_name string?
+---@field allow_when_disabled boolean?
 
 ---@class pinnacle.input.v0alpha1.SetKeybindResponse
 
@@ -245,6 +664,9 @@ local pinnacle_input_v0alpha1_SetMousebindRequest_MouseEdge = {
     MOUSE_EDGE_UNSPECIFIED = 0,
     MOUSE_EDGE_PRESS = 1,
     MOUSE_EDGE_RELEASE = 2,
+    MOUSE_EDGE_CLICK = 3,
+    MOUSE_EDGE_DOUBLE_CLICK = 4,
+    MOUSE_EDGE_DRAG = 5,
 }
 
 ---@class pinnacle.input.v0alpha1.SetMousebindRequest
@@ -260,10 +682,71 @@ local pinnacle_input_v0alpha1_SetMousebindRequest_MouseEdge = {
 ---@field model string?
 ---@field options string?
 
+---@class pinnacle.input.v0alpha1.SetModifierRemapsRequest.Remap
+---@field from pinnacle.input.v0alpha1.Modifier?
+---@field to pinnacle.input.v0alpha1.Modifier?
+
+---@class pinnacle.input.v0alpha1.SetModifierRemapsRequest
+---@field remaps pinnacle.input.v0alpha1.SetModifierRemapsRequest.Remap[]?
+
+---@class pinnacle.input.v0alpha1.SendKeyRequest
+---@field raw_code integer?
+---@field xkb_name string?
+---@field match_keybinds boolean?
+
+---@class pinnacle.input.v0alpha1.SetIdleActionRequest
+---@field timeout_ms integer?
+
+---@class pinnacle.input.v0alpha1.SetIdleActionResponse
+---@field idle boolean?
+
 ---@class SetRepeatRateRequest
 ---@field rate integer?
 ---@field delay integer?
 
+---@class pinnacle.input.v0alpha1.SetClickSettingsRequest
+---@field double_click_interval integer?
+---@field drag_threshold integer?
+---@field focus_on_release boolean?
+
+---@class pinnacle.input.v0alpha1.SetPointerConfinementRequest
+---@field output_name string?
+
+---@class pinnacle.input.v0alpha1.GetPointerRequest
+
+---@class pinnacle.input.v0alpha1.GetPointerResponse
+---@field x number?
+---@field y number?
+---@field output_name string?
+---@field window_id integer?
+---@field layer_namespace string?
+
+---@class pinnacle.input.v0alpha1.GetKeybindsRequest
+
+---@class pinnacle.input.v0alpha1.GetKeybindsResponse.Keybind
+---@field modifiers pinnacle.input.v0alpha1.Modifier[]?
+---@field key_code integer?
+---@field key_name string?
+---@field held_key_code integer?
+---@field held_key_name string?
+---@field owner_pid integer?
+---@field owner_name string?
+---@field connected boolean?
+
+---@class pinnacle.input.v0alpha1.GetKeybindsResponse
+---@field keybinds pinnacle.input.v0alpha1.GetKeybindsResponse.Keybind[]?
+
+---@class pinnacle.input.v0alpha1.GetSeatsRequest
+
+---@class pinnacle.input.v0alpha1.GetSeatsResponse.Seat
+---@field name string?
+---@field keyboard boolean?
+---@field pointer boolean?
+---@field touch boolean?
+
+---@class pinnacle.input.v0alpha1.GetSeatsResponse
+---@field seats pinnacle.input.v0alpha1.GetSeatsResponse.Seat[]?
+
 ---@enum pinnacle.input.v0alpha1.SetLibinputSettingRequest.AccelProfile
 local pinnacle_input_v0alpha1_SetLibinputSettingRequest_AccelProfile = {
     ACCEL_PROFILE_UNSPECIFIED = 0,
@@ -338,6 +821,7 @@ local pinnacle_input_v0alpha1_SetLibinputSettingRequest_TapButtonMap = {
 ---@field request_id integer?
 ---@field output_name string?
 ---@field geometries pinnacle.v0alpha1.Geometry[]?
+---@field lone_window_gaps integer?
 
 ---@class pinnacle.layout.v0alpha1.LayoutRequest.ExplicitLayout
 ---@field output_name string?
@@ -353,6 +837,33 @@ local pinnacle_input_v0alpha1_SetLibinputSettingRequest_TapButtonMap = {
 ---@field tag_ids integer[]?
 ---@field output_width integer?
 ---@field output_height integer?
+---@field tiling_direction pinnacle.tag.v0alpha1.TilingDirection?
+
+---@class pinnacle.layout.v0alpha1.RelayoutAllRequest
+
+---@class pinnacle.layout.v0alpha1.SetTilingPausedRequest
+---@field set_or_toggle pinnacle.v0alpha1.SetOrToggle?
+
+---@class pinnacle.layout.v0alpha1.ReportedTagLayout
+---@field current_layout string?
+---@field layouts string[]?
+---@field master_factor number?
+---@field master_count integer?
+
+---@class pinnacle.layout.v0alpha1.SetTagLayoutRequest
+---@field tag_id integer?
+---@field layout pinnacle.layout.v0alpha1.ReportedTagLayout?
+
+---@class pinnacle.layout.v0alpha1.GetTagLayoutRequest
+---@field tag_id integer?
+
+---@class pinnacle.layout.v0alpha1.GetTagLayoutResponse
+---@field layout pinnacle.layout.v0alpha1.ReportedTagLayout?
+---@field inner_gaps integer?
+---@field outer_gaps integer?
+---@field smart_gaps boolean?
+---@field tiling_direction pinnacle.tag.v0alpha1.TilingDirection?
+---@field tiling_paused boolean?
 
 -- Render
 
@@ -369,6 +880,32 @@ local pinnacle_render_v0alpha1_Filter = {
 ---@class pinnacle.render.v0alpha1.SetDownscaleFilterRequest
 ---@field filter pinnacle.render.v0alpha1.Filter?
 
+---@class pinnacle.render.v0alpha1.SetFrameTimingsRequest
+---@field interval_ms integer?
+
+---@class pinnacle.render.v0alpha1.WatchFrameTimingsRequest
+
+---@class pinnacle.render.v0alpha1.WatchFrameTimingsResponse
+---@field output_name string?
+---@field frames integer?
+---@field interval_ms integer?
+---@field average_render_time_us integer?
+---@field max_render_time_us integer?
+---@field damage_rects integer?
+---@field direct_scanout_frames integer?
+---@field cursor_plane_frames integer?
+
+---@class pinnacle.render.v0alpha1.SetInactiveOutputDimRequest
+---@field amount number?
+
+---@class pinnacle.render.v0alpha1.GetRendererInfoRequest
+
+---@class pinnacle.render.v0alpha1.GetRendererInfoResponse
+---@field version string?
+---@field vendor string?
+---@field renderer string?
+---@field dmabuf_formats string[]?
+
 -- Signal
 
 ---@enum pinnacle.signal.v0alpha1.StreamControl
@@ -406,6 +943,12 @@ local pinnacle_signal_v0alpha1_StreamControl = {
 ---@field x integer?
 ---@field y integer?
 
+---@class pinnacle.signal.v0alpha1.OutputHeadlessRequest
+---@field control pinnacle.signal.v0alpha1.StreamControl?
+
+---@class pinnacle.signal.v0alpha1.OutputHeadlessResponse
+---@field headless boolean?
+
 ---@class pinnacle.signal.v0alpha1.WindowPointerEnterRequest
 ---@field control pinnacle.signal.v0alpha1.StreamControl?
 
@@ -428,6 +971,7 @@ local pinnacle_signal_v0alpha1_StreamControl = {
 defs.pinnacle = {
     v0alpha1 = {
         SetOrToggle = util.bijective_table(pinnacle_v0alpha1_SetOrToggle),
+        Backend = util.bijective_table(pinnacle_v0alpha1_Backend),
         PinnacleService = {
             ---@type GrpcRequestArgs
             Quit = {
@@ -450,11 +994,49 @@ defs.pinnacle = {
                 request = "pinnacle.v0alpha1.PingRequest",
                 response = "pinnacle.v0alpha1.PingResponse",
             },
+            ---@type GrpcRequestArgs
+            GetSelectionMimeTypes = {
+                service = "pinnacle.v0alpha1.PinnacleService",
+                method = "GetSelectionMimeTypes",
+                request = "pinnacle.v0alpha1.GetSelectionMimeTypesRequest",
+                response = "pinnacle.v0alpha1.GetSelectionMimeTypesResponse",
+            },
+            ---@type GrpcRequestArgs
+            GetStateSnapshot = {
+                service = "pinnacle.v0alpha1.PinnacleService",
+                method = "GetStateSnapshot",
+                request = "pinnacle.v0alpha1.GetStateSnapshotRequest",
+                response = "pinnacle.v0alpha1.GetStateSnapshotResponse",
+            },
+            ---@type GrpcRequestArgs
+            GetBackend = {
+                service = "pinnacle.v0alpha1.PinnacleService",
+                method = "GetBackend",
+                request = "pinnacle.v0alpha1.GetBackendRequest",
+                response = "pinnacle.v0alpha1.GetBackendResponse",
+            },
+            ---@type GrpcRequestArgs
+            GetXwaylandInfo = {
+                service = "pinnacle.v0alpha1.PinnacleService",
+                method = "GetXwaylandInfo",
+                request = "pinnacle.v0alpha1.GetXwaylandInfoRequest",
+                response = "pinnacle.v0alpha1.GetXwaylandInfoResponse",
+            },
+            ---@type GrpcRequestArgs
+            ListSurfaces = {
+                service = "pinnacle.v0alpha1.PinnacleService",
+                method = "ListSurfaces",
+                request = "pinnacle.v0alpha1.ListSurfacesRequest",
+                response = "pinnacle.v0alpha1.ListSurfacesResponse",
+            },
         },
     },
     output = {
         v0alpha1 = {
             Transform = util.bijective_table(pinnacle_output_v0alpha1_Transform),
+            LoneWindowAlignment = util.bijective_table(
+                pinnacle_output_v0alpha1_LoneWindowAlignment
+            ),
             OutputService = {
                 ---@type GrpcRequestArgs
                 SetLocation = {
@@ -485,6 +1067,69 @@ defs.pinnacle = {
                     response = "google.protobuf.Empty",
                 },
                 ---@type GrpcRequestArgs
+                SetOverview = {
+                    service = "pinnacle.output.v0alpha1.OutputService",
+                    method = "SetOverview",
+                    request = "pinnacle.output.v0alpha1.SetOverviewRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                SetMagnification = {
+                    service = "pinnacle.output.v0alpha1.OutputService",
+                    method = "SetMagnification",
+                    request = "pinnacle.output.v0alpha1.SetMagnificationRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                SetBrightness = {
+                    service = "pinnacle.output.v0alpha1.OutputService",
+                    method = "SetBrightness",
+                    request = "pinnacle.output.v0alpha1.SetBrightnessRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                SetGammaPreset = {
+                    service = "pinnacle.output.v0alpha1.OutputService",
+                    method = "SetGammaPreset",
+                    request = "pinnacle.output.v0alpha1.SetGammaPresetRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                SetLoneWindowWidth = {
+                    service = "pinnacle.output.v0alpha1.OutputService",
+                    method = "SetLoneWindowWidth",
+                    request = "pinnacle.output.v0alpha1.SetLoneWindowWidthRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                FocusOutputUnderCursor = {
+                    service = "pinnacle.output.v0alpha1.OutputService",
+                    method = "FocusOutputUnderCursor",
+                    request = "pinnacle.output.v0alpha1.FocusOutputUnderCursorRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                SetFocusLock = {
+                    service = "pinnacle.output.v0alpha1.OutputService",
+                    method = "SetFocusLock",
+                    request = "pinnacle.output.v0alpha1.SetFocusLockRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                SetPrimary = {
+                    service = "pinnacle.output.v0alpha1.OutputService",
+                    method = "SetPrimary",
+                    request = "pinnacle.output.v0alpha1.SetPrimaryRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                GetFocusStack = {
+                    service = "pinnacle.output.v0alpha1.OutputService",
+                    method = "GetFocusStack",
+                    request = "pinnacle.output.v0alpha1.GetFocusStackRequest",
+                    response = "pinnacle.output.v0alpha1.GetFocusStackResponse",
+                },
+                ---@type GrpcRequestArgs
                 Get = {
                     service = "pinnacle.output.v0alpha1.OutputService",
                     method = "Get",
@@ -506,6 +1151,10 @@ defs.pinnacle = {
             FullscreenOrMaximized = util.bijective_table(
                 pinnacle_window_v0alpha1_FullscreenOrMaximized
             ),
+            ContentType = util.bijective_table(pinnacle_window_v0alpha1_ContentType),
+            GrabKind = util.bijective_table(pinnacle_window_v0alpha1_GrabKind),
+            SnapRegion = util.bijective_table(pinnacle_window_v0alpha1_SnapRegion),
+            Placement = util.bijective_table(pinnacle_window_v0alpha1_Placement),
             WindowService = {
                 ---@type GrpcRequestArgs
                 Close = {
@@ -522,6 +1171,13 @@ defs.pinnacle = {
                     response = "google.protobuf.Empty",
                 },
                 ---@type GrpcRequestArgs
+                SetFloatingGeometry = {
+                    service = "pinnacle.window.v0alpha1.WindowService",
+                    method = "SetFloatingGeometry",
+                    request = "pinnacle.window.v0alpha1.SetFloatingGeometryRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
                 SetFullscreen = {
                     service = "pinnacle.window.v0alpha1.WindowService",
                     method = "SetFullscreen",
@@ -536,6 +1192,13 @@ defs.pinnacle = {
                     response = "google.protobuf.Empty",
                 },
                 ---@type GrpcRequestArgs
+                SetMinimized = {
+                    service = "pinnacle.window.v0alpha1.WindowService",
+                    method = "SetMinimized",
+                    request = "pinnacle.window.v0alpha1.SetMinimizedRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
                 SetFloating = {
                     service = "pinnacle.window.v0alpha1.WindowService",
                     method = "SetFloating",
@@ -543,6 +1206,34 @@ defs.pinnacle = {
                     response = "google.protobuf.Empty",
                 },
                 ---@type GrpcRequestArgs
+                SetRelativeGeometry = {
+                    service = "pinnacle.window.v0alpha1.WindowService",
+                    method = "SetRelativeGeometry",
+                    request = "pinnacle.window.v0alpha1.SetRelativeGeometryRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                SnapWindow = {
+                    service = "pinnacle.window.v0alpha1.WindowService",
+                    method = "SnapWindow",
+                    request = "pinnacle.window.v0alpha1.SnapWindowRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                PlaceWindow = {
+                    service = "pinnacle.window.v0alpha1.WindowService",
+                    method = "PlaceWindow",
+                    request = "pinnacle.window.v0alpha1.PlaceWindowRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                SetActivated = {
+                    service = "pinnacle.window.v0alpha1.WindowService",
+                    method = "SetActivated",
+                    request = "pinnacle.window.v0alpha1.SetActivatedRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
                 SetFocused = {
                     service = "pinnacle.window.v0alpha1.WindowService",
                     method = "SetFocused",
@@ -557,6 +1248,13 @@ defs.pinnacle = {
                     response = "google.protobuf.Empty",
                 },
                 ---@type GrpcRequestArgs
+                CopyToTag = {
+                    service = "pinnacle.window.v0alpha1.WindowService",
+                    method = "CopyToTag",
+                    request = "pinnacle.window.v0alpha1.CopyToTagRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
                 SetTag = {
                     service = "pinnacle.window.v0alpha1.WindowService",
                     method = "SetTag",
@@ -564,6 +1262,13 @@ defs.pinnacle = {
                     response = "google.protobuf.Empty",
                 },
                 ---@type GrpcRequestArgs
+                ToggleRecentTag = {
+                    service = "pinnacle.window.v0alpha1.WindowService",
+                    method = "ToggleRecentTag",
+                    request = "pinnacle.window.v0alpha1.ToggleRecentTagRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
                 Raise = {
                     service = "pinnacle.window.v0alpha1.WindowService",
                     method = "Raise",
@@ -571,6 +1276,83 @@ defs.pinnacle = {
                     response = "google.protobuf.Empty",
                 },
                 ---@type GrpcRequestArgs
+                Lower = {
+                    service = "pinnacle.window.v0alpha1.WindowService",
+                    method = "Lower",
+                    request = "pinnacle.window.v0alpha1.LowerRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                SetLabel = {
+                    service = "pinnacle.window.v0alpha1.WindowService",
+                    method = "SetLabel",
+                    request = "pinnacle.window.v0alpha1.SetLabelRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                SetScale = {
+                    service = "pinnacle.window.v0alpha1.WindowService",
+                    method = "SetScale",
+                    request = "pinnacle.window.v0alpha1.SetScaleRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                SetSkipTaskbar = {
+                    service = "pinnacle.window.v0alpha1.WindowService",
+                    method = "SetSkipTaskbar",
+                    request = "pinnacle.window.v0alpha1.SetSkipTaskbarRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                SetSkipCycle = {
+                    service = "pinnacle.window.v0alpha1.WindowService",
+                    method = "SetSkipCycle",
+                    request = "pinnacle.window.v0alpha1.SetSkipCycleRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                SetGeometryPinned = {
+                    service = "pinnacle.window.v0alpha1.WindowService",
+                    method = "SetGeometryPinned",
+                    request = "pinnacle.window.v0alpha1.SetGeometryPinnedRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                SetMaximizeGaps = {
+                    service = "pinnacle.window.v0alpha1.WindowService",
+                    method = "SetMaximizeGaps",
+                    request = "pinnacle.window.v0alpha1.SetMaximizeGapsRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                SetTiledEdgesEnabled = {
+                    service = "pinnacle.window.v0alpha1.WindowService",
+                    method = "SetTiledEdgesEnabled",
+                    request = "pinnacle.window.v0alpha1.SetTiledEdgesEnabledRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                FocusUrgent = {
+                    service = "pinnacle.window.v0alpha1.WindowService",
+                    method = "FocusUrgent",
+                    request = "pinnacle.window.v0alpha1.FocusUrgentRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                FocusPrevious = {
+                    service = "pinnacle.window.v0alpha1.WindowService",
+                    method = "FocusPrevious",
+                    request = "pinnacle.window.v0alpha1.FocusPreviousRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                JumpToWindow = {
+                    service = "pinnacle.window.v0alpha1.WindowService",
+                    method = "JumpToWindow",
+                    request = "pinnacle.window.v0alpha1.JumpToWindowRequest",
+                    response = "pinnacle.window.v0alpha1.JumpToWindowResponse",
+                },
+                ---@type GrpcRequestArgs
                 MoveGrab = {
                     service = "pinnacle.window.v0alpha1.WindowService",
                     method = "MoveGrab",
@@ -585,6 +1367,27 @@ defs.pinnacle = {
                     response = "google.protobuf.Empty",
                 },
                 ---@type GrpcRequestArgs
+                CancelGrab = {
+                    service = "pinnacle.window.v0alpha1.WindowService",
+                    method = "CancelGrab",
+                    request = "pinnacle.window.v0alpha1.CancelGrabRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                GetGrab = {
+                    service = "pinnacle.window.v0alpha1.WindowService",
+                    method = "GetGrab",
+                    request = "pinnacle.window.v0alpha1.GetGrabRequest",
+                    response = "pinnacle.window.v0alpha1.GetGrabResponse",
+                },
+                ---@type GrpcRequestArgs
+                SelectWindow = {
+                    service = "pinnacle.window.v0alpha1.WindowService",
+                    method = "SelectWindow",
+                    request = "pinnacle.window.v0alpha1.SelectWindowRequest",
+                    response = "pinnacle.window.v0alpha1.SelectWindowResponse",
+                },
+                ---@type GrpcRequestArgs
                 Get = {
                     service = "pinnacle.window.v0alpha1.WindowService",
                     method = "Get",
@@ -599,17 +1402,96 @@ defs.pinnacle = {
                     response = "pinnacle.window.v0alpha1.GetPropertiesResponse",
                 },
                 ---@type GrpcRequestArgs
+                GetSurfaceInfo = {
+                    service = "pinnacle.window.v0alpha1.WindowService",
+                    method = "GetSurfaceInfo",
+                    request = "pinnacle.window.v0alpha1.GetSurfaceInfoRequest",
+                    response = "pinnacle.window.v0alpha1.GetSurfaceInfoResponse",
+                },
+                ---@type GrpcRequestArgs
                 AddWindowRule = {
                     service = "pinnacle.window.v0alpha1.WindowService",
                     method = "AddWindowRule",
                     request = "pinnacle.window.v0alpha1.AddWindowRuleRequest",
+                    response = "pinnacle.window.v0alpha1.AddWindowRuleResponse",
+                },
+                ---@type GrpcRequestArgs
+                GetWindowRules = {
+                    service = "pinnacle.window.v0alpha1.WindowService",
+                    method = "GetWindowRules",
+                    request = "pinnacle.window.v0alpha1.GetWindowRulesRequest",
+                    response = "pinnacle.window.v0alpha1.GetWindowRulesResponse",
+                },
+                ---@type GrpcRequestArgs
+                RemoveWindowRule = {
+                    service = "pinnacle.window.v0alpha1.WindowService",
+                    method = "RemoveWindowRule",
+                    request = "pinnacle.window.v0alpha1.RemoveWindowRuleRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                ReapplyWindowRules = {
+                    service = "pinnacle.window.v0alpha1.WindowService",
+                    method = "ReapplyWindowRules",
+                    request = "pinnacle.window.v0alpha1.ReapplyWindowRulesRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                EvaluateWindowRuleCondition = {
+                    service = "pinnacle.window.v0alpha1.WindowService",
+                    method = "EvaluateWindowRuleCondition",
+                    request = "pinnacle.window.v0alpha1.EvaluateWindowRuleConditionRequest",
+                    response = "pinnacle.window.v0alpha1.EvaluateWindowRuleConditionResponse",
+                },
+                ---@type GrpcRequestArgs
+                SetFocusNewWindows = {
+                    service = "pinnacle.window.v0alpha1.WindowService",
+                    method = "SetFocusNewWindows",
+                    request = "pinnacle.window.v0alpha1.SetFocusNewWindowsRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                SetUnfullscreenOnFocusLoss = {
+                    service = "pinnacle.window.v0alpha1.WindowService",
+                    method = "SetUnfullscreenOnFocusLoss",
+                    request = "pinnacle.window.v0alpha1.SetUnfullscreenOnFocusLossRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                SetLastWindowFallback = {
+                    service = "pinnacle.window.v0alpha1.WindowService",
+                    method = "SetLastWindowFallback",
+                    request = "pinnacle.window.v0alpha1.SetLastWindowFallbackRequest",
                     response = "google.protobuf.Empty",
                 },
+                ---@type GrpcRequestArgs
+                OpenNextOnFocusedTag = {
+                    service = "pinnacle.window.v0alpha1.WindowService",
+                    method = "OpenNextOnFocusedTag",
+                    request = "pinnacle.window.v0alpha1.OpenNextOnFocusedTagRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                SetUrgencyTimeout = {
+                    service = "pinnacle.window.v0alpha1.WindowService",
+                    method = "SetUrgencyTimeout",
+                    request = "pinnacle.window.v0alpha1.SetUrgencyTimeoutRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                Capture = {
+                    service = "pinnacle.window.v0alpha1.WindowService",
+                    method = "Capture",
+                    request = "pinnacle.window.v0alpha1.CaptureRequest",
+                    response = "pinnacle.window.v0alpha1.CaptureResponse",
+                },
             },
         },
     },
     tag = {
         v0alpha1 = {
+            OverflowPolicy = util.bijective_table(pinnacle_tag_v0alpha1_OverflowPolicy),
+            TilingDirection = util.bijective_table(pinnacle_tag_v0alpha1_TilingDirection),
             TagService = {
                 ---@type GrpcRequestArgs
                 SetActive = {
@@ -626,6 +1508,62 @@ defs.pinnacle = {
                     response = "google.protobuf.Empty",
                 },
                 ---@type GrpcRequestArgs
+                SwitchToPrevious = {
+                    service = "pinnacle.tag.v0alpha1.TagService",
+                    method = "SwitchToPrevious",
+                    request = "pinnacle.tag.v0alpha1.SwitchToPreviousRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                SetActiveTags = {
+                    service = "pinnacle.tag.v0alpha1.TagService",
+                    method = "SetActiveTags",
+                    request = "pinnacle.tag.v0alpha1.SetActiveTagsRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                SetGaps = {
+                    service = "pinnacle.tag.v0alpha1.TagService",
+                    method = "SetGaps",
+                    request = "pinnacle.tag.v0alpha1.SetGapsRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                SetFloatingHidden = {
+                    service = "pinnacle.tag.v0alpha1.TagService",
+                    method = "SetFloatingHidden",
+                    request = "pinnacle.tag.v0alpha1.SetFloatingHiddenRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                SetWindowLimit = {
+                    service = "pinnacle.tag.v0alpha1.TagService",
+                    method = "SetWindowLimit",
+                    request = "pinnacle.tag.v0alpha1.SetWindowLimitRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                SetTilingDirection = {
+                    service = "pinnacle.tag.v0alpha1.TagService",
+                    method = "SetTilingDirection",
+                    request = "pinnacle.tag.v0alpha1.SetTilingDirectionRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                ToggleTilingDirection = {
+                    service = "pinnacle.tag.v0alpha1.TagService",
+                    method = "ToggleTilingDirection",
+                    request = "pinnacle.tag.v0alpha1.ToggleTilingDirectionRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                SetDisplayName = {
+                    service = "pinnacle.tag.v0alpha1.TagService",
+                    method = "SetDisplayName",
+                    request = "pinnacle.tag.v0alpha1.SetDisplayNameRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
                 Add = {
                     service = "pinnacle.tag.v0alpha1.TagService",
                     method = "Add",
@@ -640,6 +1578,13 @@ defs.pinnacle = {
                     response = "google.protobuf.Empty",
                 },
                 ---@type GrpcRequestArgs
+                SetDefaultOutputTags = {
+                    service = "pinnacle.tag.v0alpha1.TagService",
+                    method = "SetDefaultOutputTags",
+                    request = "pinnacle.tag.v0alpha1.SetDefaultOutputTagsRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
                 Get = {
                     service = "pinnacle.tag.v0alpha1.TagService",
                     method = "Get",
@@ -701,6 +1646,27 @@ defs.pinnacle = {
                     response = "google.protobuf.Empty",
                 },
                 ---@type GrpcRequestArgs
+                SetModifierRemaps = {
+                    service = "pinnacle.input.v0alpha1.InputService",
+                    method = "SetModifierRemaps",
+                    request = "pinnacle.input.v0alpha1.SetModifierRemapsRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                SendKey = {
+                    service = "pinnacle.input.v0alpha1.InputService",
+                    method = "SendKey",
+                    request = "pinnacle.input.v0alpha1.SendKeyRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                SetIdleAction = {
+                    service = "pinnacle.input.v0alpha1.InputService",
+                    method = "SetIdleAction",
+                    request = "pinnacle.input.v0alpha1.SetIdleActionRequest",
+                    response = "pinnacle.input.v0alpha1.SetIdleActionResponse",
+                },
+                ---@type GrpcRequestArgs
                 SetRepeatRate = {
                     service = "pinnacle.input.v0alpha1.InputService",
                     method = "SetRepeatRate",
@@ -708,6 +1674,41 @@ defs.pinnacle = {
                     response = "google.protobuf.Empty",
                 },
                 ---@type GrpcRequestArgs
+                SetClickSettings = {
+                    service = "pinnacle.input.v0alpha1.InputService",
+                    method = "SetClickSettings",
+                    request = "pinnacle.input.v0alpha1.SetClickSettingsRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                SetPointerConfinement = {
+                    service = "pinnacle.input.v0alpha1.InputService",
+                    method = "SetPointerConfinement",
+                    request = "pinnacle.input.v0alpha1.SetPointerConfinementRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                GetPointer = {
+                    service = "pinnacle.input.v0alpha1.InputService",
+                    method = "GetPointer",
+                    request = "pinnacle.input.v0alpha1.GetPointerRequest",
+                    response = "pinnacle.input.v0alpha1.GetPointerResponse",
+                },
+                ---@type GrpcRequestArgs
+                GetSeats = {
+                    service = "pinnacle.input.v0alpha1.InputService",
+                    method = "GetSeats",
+                    request = "pinnacle.input.v0alpha1.GetSeatsRequest",
+                    response = "pinnacle.input.v0alpha1.GetSeatsResponse",
+                },
+                ---@type GrpcRequestArgs
+                GetKeybinds = {
+                    service = "pinnacle.input.v0alpha1.InputService",
+                    method = "GetKeybinds",
+                    request = "pinnacle.input.v0alpha1.GetKeybindsRequest",
+                    response = "pinnacle.input.v0alpha1.GetKeybindsResponse",
+                },
+                ---@type GrpcRequestArgs
                 SetLibinputSetting = {
                     service = "pinnacle.input.v0alpha1.InputService",
                     method = "SetLibinputSetting",
@@ -747,6 +1748,34 @@ defs.pinnacle = {
                     request = "pinnacle.layout.v0alpha1.LayoutRequest",
                     response = "pinnacle.layout.v0alpha1.LayoutResponse",
                 },
+                ---@type GrpcRequestArgs
+                RelayoutAll = {
+                    service = "pinnacle.layout.v0alpha1.LayoutService",
+                    method = "RelayoutAll",
+                    request = "pinnacle.layout.v0alpha1.RelayoutAllRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                SetTilingPaused = {
+                    service = "pinnacle.layout.v0alpha1.LayoutService",
+                    method = "SetTilingPaused",
+                    request = "pinnacle.layout.v0alpha1.SetTilingPausedRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                SetTagLayout = {
+                    service = "pinnacle.layout.v0alpha1.LayoutService",
+                    method = "SetTagLayout",
+                    request = "pinnacle.layout.v0alpha1.SetTagLayoutRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                GetTagLayout = {
+                    service = "pinnacle.layout.v0alpha1.LayoutService",
+                    method = "GetTagLayout",
+                    request = "pinnacle.layout.v0alpha1.GetTagLayoutRequest",
+                    response = "pinnacle.layout.v0alpha1.GetTagLayoutResponse",
+                },
             },
         },
     },
@@ -768,6 +1797,34 @@ defs.pinnacle = {
                     request = "pinnacle.render.v0alpha1.SetDownscaleFilterRequest",
                     response = "google.protobuf.Empty",
                 },
+                ---@type GrpcRequestArgs
+                SetFrameTimings = {
+                    service = "pinnacle.render.v0alpha1.RenderService",
+                    method = "SetFrameTimings",
+                    request = "pinnacle.render.v0alpha1.SetFrameTimingsRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                WatchFrameTimings = {
+                    service = "pinnacle.render.v0alpha1.RenderService",
+                    method = "WatchFrameTimings",
+                    request = "pinnacle.render.v0alpha1.WatchFrameTimingsRequest",
+                    response = "pinnacle.render.v0alpha1.WatchFrameTimingsResponse",
+                },
+                ---@type GrpcRequestArgs
+                SetInactiveOutputDim = {
+                    service = "pinnacle.render.v0alpha1.RenderService",
+                    method = "SetInactiveOutputDim",
+                    request = "pinnacle.render.v0alpha1.SetInactiveOutputDimRequest",
+                    response = "google.protobuf.Empty",
+                },
+                ---@type GrpcRequestArgs
+                GetRendererInfo = {
+                    service = "pinnacle.render.v0alpha1.RenderService",
+                    method = "GetRendererInfo",
+                    request = "pinnacle.render.v0alpha1.GetRendererInfoRequest",
+                    response = "pinnacle.render.v0alpha1.GetRendererInfoResponse",
+                },
             },
         },
     },
@@ -805,6 +1862,13 @@ defs.pinnacle = {
                     response = "pinnacle.signal.v0alpha1.OutputMoveResponse",
                 },
                 ---@type GrpcRequestArgs
+                OutputHeadless = {
+                    service = "pinnacle.signal.v0alpha1.SignalService",
+                    method = "OutputHeadless",
+                    request = "pinnacle.signal.v0alpha1.OutputHeadlessRequest",
+                    response = "pinnacle.signal.v0alpha1.OutputHeadlessResponse",
+                },
+                ---@type GrpcRequestArgs
                 WindowPointerEnter = {
                     service = "pinnacle.signal.v0alpha1.SignalService",
                     method = "WindowPointerEnter",
@@ -59,10 +59,16 @@ local mouse_button_values = {
 local mouse_edge_values = {
     press = 1,
     release = 2,
+    click = 3,
+    double_click = 4,
+    drag = 5,
 }
 ---@alias MouseEdge
 ---| "press" Trigger on mouse button press
 ---| "release" Trigger on mouse button release
+---| "click" Trigger when the button is pressed and released without dragging
+---| "double_click" Trigger when the button is clicked twice in quick succession
+---| "drag" Trigger when the pointer is moved while the button is held
 
 ---Input management.
 ---
@@ -74,6 +80,33 @@ local input = {
 }
 input.mouse_button_values = mouse_button_values
 
+---@param mods Modifier[]
+---@param key Key | string
+---@param action fun()
+---@param allow_when_disabled boolean
+local function set_keybind(mods, key, action, allow_when_disabled)
+    local raw_code = nil
+    local xkb_name = nil
+
+    if type(key) == "number" then
+        raw_code = key
+    elseif type(key) == "string" then
+        xkb_name = key
+    end
+
+    local mod_values = {}
+    for _, mod in ipairs(mods) do
+        table.insert(mod_values, modifier_values[mod])
+    end
+
+    client.server_streaming_request(input_service.SetKeybind, {
+        modifiers = mod_values,
+        raw_code = raw_code,
+        xkb_name = xkb_name,
+        allow_when_disabled = allow_when_disabled,
+    }, action)
+end
+
 ---Set a keybind. If called with an already existing keybind, it gets replaced.
 ---
 ---You must provide three arguments:
@@ -112,6 +145,50 @@ input.mouse_button_values = mouse_button_values
 ---@param key Key | string The key used to trigger the bind
 ---@param action fun() The function to run when the bind is triggered
 function input.keybind(mods, key, action)
+    set_keybind(mods, key, action, false)
+end
+
+---Set a keybind that still triggers while the focused window has keybinds disabled.
+---
+---Otherwise, this is the same as `Input.keybind`. See `WindowRule.disable_keybinds`.
+---
+---### Example
+---```lua
+--- -- Keep `super + 1` switching tags even while a virtual machine is focused
+---Input.keybind_allow_when_disabled({ "super" }, "1", function() end)
+---```
+---
+---@param mods Modifier[] The modifiers that need to be held down for the bind to trigger
+---@param key Key | string The key used to trigger the bind
+---@param action fun() The function to run when the bind is triggered
+function input.keybind_allow_when_disabled(mods, key, action)
+    set_keybind(mods, key, action, true)
+end
+
+---Set a keybind that only triggers while another key is held.
+---
+---This makes a chord where `held` acts like an extra modifier, like holding Space
+---and pressing J. Unlike a regular keybind, `mods` and `held` must both already be
+---held when `key` is pressed. While `held` could start one of these keybinds, pressing
+---it isn't sent to the focused window. If it's released without triggering one,
+---it's sent then, so it can still be typed on its own.
+---
+---`held` and `key` take the same kinds of keys as `Input.keybind`.
+---`held` can't be a modifier; put those in `mods` instead.
+---
+---### Example
+---```lua
+--- -- Hold Space and press h to focus the previous window
+---Input.held_keybind({}, "space", "h", function()
+---    Window.focus_previous()
+---end)
+---```
+---
+---@param mods Modifier[] The modifiers that need to be held down for the bind to trigger
+---@param held Key | string The key that needs to be held down for the bind to trigger
+---@param key Key | string The key used to trigger the bind
+---@param action fun() The function to run when the bind is triggered
+function input.held_keybind(mods, held, key, action)
     local raw_code = nil
     local xkb_name = nil
 
@@ -121,6 +198,15 @@ function input.keybind(mods, key, action)
         xkb_name = key
     end
 
+    local held_raw_code = nil
+    local held_xkb_name = nil
+
+    if type(held) == "number" then
+        held_raw_code = held
+    elseif type(held) == "string" then
+        held_xkb_name = held
+    end
+
     local mod_values = {}
     for _, mod in ipairs(mods) do
         table.insert(mod_values, modifier_values[mod])
@@ -130,12 +216,16 @@ function input.keybind(mods, key, action)
         modifiers = mod_values,
         raw_code = raw_code,
         xkb_name = xkb_name,
+        held_raw_code = held_raw_code,
+        held_xkb_name = held_xkb_name,
     }, action)
 end
 
 ---Set a mousebind. If called with an already existing mousebind, it gets replaced.
 ---
----You must specify whether the keybind happens on button press or button release.
+---You must specify when the mousebind triggers with a `MouseEdge`.
+---"click", "double_click", and "drag" mousebinds prevent both the press and release
+---of their button from reaching windows.
 ---
 ---### Example
 ---```lua
@@ -147,7 +237,7 @@ end
 ---
 ---@param mods Modifier[] The modifiers that need to be held down for the bind to trigger
 ---@param button MouseButton The mouse button used to trigger the bind
----@param edge MouseEdge "press" or "release" to trigger on button press or release
+---@param edge MouseEdge When the bind triggers
 ---@param action fun() The function to run when the bind is triggered
 function input.mousebind(mods, button, edge, action)
     ---@diagnostic disable-next-line: redefined-local
@@ -191,6 +281,105 @@ function input.set_xkb_config(xkb_config)
     client.unary_request(input_service.SetXkbConfig, xkb_config)
 end
 
+---Treat modifiers as other modifiers when matching keybinds and mousebinds.
+---
+---Each `{ from, to }` pair makes holding `from` count as holding `to`. This lets you write
+---keybinds with one primary modifier, like `"super"`, and choose which physical key
+---acts as it. Clients still see the modifiers that are actually held.
+---
+---This replaces any previous remaps. Pass in an empty table to remove them.
+---
+---Caps Lock isn't a modifier; use the `caps:super` option in `Input.set_xkb_config`
+---to make it act as Super.
+---
+---### Example
+---```lua
+----- Use Alt for all Super keybinds, and Super for all Alt ones
+---Input.set_modifier_remaps({ { "alt", "super" }, { "super", "alt" } })
+---```
+---
+---@param remaps { [1]: Modifier, [2]: Modifier }[]
+function input.set_modifier_remaps(remaps)
+    ---@type pinnacle.input.v0alpha1.SetModifierRemapsRequest.Remap[]
+    local remap_values = {}
+    for _, remap in ipairs(remaps) do
+        table.insert(remap_values, {
+            from = modifier_values[remap[1]],
+            to = modifier_values[remap[2]],
+        })
+    end
+
+    client.unary_request(input_service.SetModifierRemaps, { remaps = remap_values })
+end
+
+---Press and release a key on the focused window, as if it were typed on a keyboard.
+---
+---`key` can be anything `Input.keybind` accepts. The key must be in the current keymap.
+---Shift is held down for keys that need it, like uppercase letters.
+---
+---If `match_keybinds` is false or not given, the key goes straight to the focused window.
+---Otherwise it can trigger keybinds, including the kill and reload keybinds, like a real key press.
+---
+---### Example
+---```lua
+--- -- Type some text into the focused window
+---for ch in ("hello"):gmatch(".") do
+---    Input.send_key(ch)
+---end
+---
+---Input.send_key(Input.key.Return)
+---```
+---
+---@param key Key | string The key to send
+---@param match_keybinds boolean? Whether the key can trigger keybinds
+function input.send_key(key, match_keybinds)
+    local raw_code = nil
+    local xkb_name = nil
+
+    if type(key) == "number" then
+        raw_code = key
+    elseif type(key) == "string" then
+        xkb_name = key
+    end
+
+    client.unary_request(input_service.SendKey, {
+        raw_code = raw_code,
+        xkb_name = xkb_name,
+        match_keybinds = match_keybinds or false,
+    })
+end
+
+---Run `on_idle` after `timeout_ms` milliseconds without any input, then `on_resume`
+---when input resumes.
+---
+---This can be called multiple times with different timeouts to add several idle stages,
+---for example to dim outputs after 5 minutes and lock after 10.
+---
+---Windows inhibiting idle, like video players in fullscreen, keep stages from becoming idle.
+---
+---### Example
+---```lua
+--- -- Lock the session after 10 minutes without input
+---Input.on_idle(10 * 60 * 1000, function()
+---    Process.spawn("swaylock")
+---end)
+---```
+---
+---@param timeout_ms integer How long there must be no input for, in milliseconds
+---@param on_idle fun() The function to run when the timeout passes
+---@param on_resume fun()? The function to run when input resumes
+function input.on_idle(timeout_ms, on_idle, on_resume)
+    client.server_streaming_request(input_service.SetIdleAction, {
+        timeout_ms = timeout_ms,
+    }, function(response)
+        if response.idle then
+            on_idle()
+        elseif on_resume then
+            on_resume()
+        end
+    end)
+end
+
 ---Set the keyboard's repeat rate and delay.
 ---
 ---### Example
@@ -207,6 +396,186 @@ function input.set_repeat_rate(rate, delay)
     })
 end
 
+---Set the maximum time between two clicks for them to count as a double click.
+---
+---This is used for "double_click" mousebinds. It defaults to 400ms.
+---
+---### Example
+---```lua
+---Input.set_double_click_interval(300)
+---```
+---
+---@param interval integer The interval in milliseconds
+function input.set_double_click_interval(interval)
+    client.unary_request(input_service.SetClickSettings, { double_click_interval = interval })
+end
+
+---Set how far the pointer must move with a button held for it to count as a drag.
+---
+---This is used for "click" and "drag" mousebinds. It defaults to 8.
+---
+---### Example
+---```lua
+---Input.set_drag_threshold(16)
+---```
+---
+---@param threshold integer The distance in logical pixels
+function input.set_drag_threshold(threshold)
+    client.unary_request(input_service.SetClickSettings, { drag_threshold = threshold })
+end
+
+---Set whether clicking a window focuses it when the button is released
+---instead of when it's pressed.
+---
+---Focusing on release lets you drag things out of a window without focusing it.
+---Releases that end a drag or a `Window.begin_move` or `Window.begin_resize` grab don't change focus.
+---This defaults to focusing on press.
+---
+---### Example
+---```lua
+---Input.set_focus_on_release(true)
+---```
+---
+---@param focus_on_release boolean
+function input.set_focus_on_release(focus_on_release)
+    client.unary_request(input_service.SetClickSettings, { focus_on_release = focus_on_release })
+end
+
+---Confine the pointer to the given output.
+---
+---The pointer will not be able to leave the output until it is released
+---by calling this with no output. If the pointer is currently on another output,
+---it will be moved onto this one.
+---
+---### Example
+---```lua
+---Input.confine_pointer(Output.get_by_name("DP-1"))
+---
+--- -- Let the pointer move freely again
+---Input.confine_pointer()
+---```
+---
+---@param output OutputHandle? The output to confine the pointer to, or nil to release it
+function input.confine_pointer(output)
+    client.unary_request(
+        input_service.SetPointerConfinement,
+        { output_name = output and output.name }
+    )
+end
+
+---The pointer's location and what is under it.
+---@class PointerInfo
+---@field location { x: number, y: number }? The pointer's location in the global space
+---@field output OutputHandle? The output the pointer is on
+---@field window WindowHandle? The window under the pointer
+---@field layer_namespace string? The namespace of the layer surface under the pointer
+
+---Get the pointer's location and what is under it.
+---
+---This is useful for things like custom gestures, or placing a menu
+---on the output the pointer is on.
+---
+---### Example
+---```lua
+---local pointer = Input.get_pointer()
+---if pointer.window then
+---    print(pointer.window:class())
+---end
+---```
+---
+---@return PointerInfo
+function input.get_pointer()
+    local response = client.unary_request(input_service.GetPointer, {})
+
+    ---@type PointerInfo
+    local pointer = {
+        location = response.x and response.y and { x = response.x, y = response.y },
+        output = response.output_name
+            and require("pinnacle.output").handle.new(response.output_name),
+        window = response.window_id and require("pinnacle.window").handle.new(response.window_id),
+        layer_namespace = response.layer_namespace,
+    }
+
+    return pointer
+end
+
+---A seat and its input capabilities.
+---@class SeatInfo
+---@field name string?
+---@field keyboard boolean? Whether the seat has a keyboard
+---@field pointer boolean? Whether the seat has a pointer
+---@field touch boolean? Whether the seat has a touchscreen
+
+---Get all seats and what input capabilities they have.
+---
+---Pinnacle currently only creates one seat, so this always returns one item.
+---This is mostly useful for debugging input issues.
+---
+---### Example
+---```lua
+---for _, seat in ipairs(Input.get_seats()) do
+---    print(seat.name, seat.pointer)
+---end
+---```
+---
+---@return SeatInfo[]
+function input.get_seats()
+    local response = client.unary_request(input_service.GetSeats, {})
+
+    ---@type SeatInfo[]
+    return response.seats or {}
+end
+
+---A keybind that's set and the process that set it.
+---@class KeybindInfo
+---@field mods Modifier[]
+---@field key string The xkb name of the key that triggers the keybind
+---@field held_key string? The xkb name of the key that must be held, if any
+---@field owner_pid integer? The pid of the process that set the keybind
+---@field owner_name string? The name of the process that set the keybind, if it's still running
+---@field connected boolean Whether the process that set the keybind is still listening for it
+
+---Get all set keybinds along with the process that set them.
+---
+---This is useful for finding out why a key combination does more than one thing.
+---
+---### Example
+---```lua
+---for _, bind in ipairs(Input.get_keybinds()) do
+---    print(table.concat(bind.mods, "+"), bind.key, bind.owner_name)
+---end
+---```
+---
+---@return KeybindInfo[]
+function input.get_keybinds()
+    local response = client.unary_request(input_service.GetKeybinds, {})
+
+    local modifier_keys = {}
+    for name, value in pairs(modifier_values) do
+        modifier_keys[value] = name
+    end
+
+    ---@type KeybindInfo[]
+    local keybinds = {}
+    for _, bind in ipairs(response.keybinds or {}) do
+        local mods = {}
+        for _, mod in ipairs(bind.modifiers or {}) do
+            table.insert(mods, modifier_keys[mod])
+        end
+
+        table.insert(keybinds, {
+            mods = mods,
+            key = bind.key_name,
+            held_key = bind.held_key_name,
+            owner_pid = bind.owner_pid,
+            owner_name = bind.owner_name,
+            connected = bind.connected,
+        })
+    end
+
+    return keybinds
+end
+
 local accel_profile_values = {
     flat = 1,
     adaptive = 2,
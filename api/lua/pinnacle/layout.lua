@@ -4,17 +4,53 @@ local layout_service = require("pinnacle.grpc.defs").pinnacle.layout.v0alpha1.La
 
 local mfloor = math.floor
 
+local set_or_toggle = {
+    SET = 1,
+    [true] = 1,
+    UNSET = 2,
+    [false] = 2,
+    TOGGLE = 3,
+}
+
+local tiling_direction_keys = {
+    [1] = "horizontal",
+    [2] = "vertical",
+}
+
+---Master sides on the same end as each side, for each tiling direction
+local oriented_master_sides = {
+    horizontal = { left = "left", right = "right", top = "left", bottom = "right" },
+    vertical = { left = "top", right = "bottom", top = "top", bottom = "bottom" },
+}
+
 ---@class LayoutArgs
 ---@field output OutputHandle
 ---@field windows WindowHandle[]
 ---@field tags TagHandle[]
 ---@field output_width integer
 ---@field output_height integer
+---@field tiling_direction TilingDirection? The tiling direction of the first focused tag, if it has one
 
 ---A layout generator.
 ---@class LayoutGenerator
 ---Generate an array of geometries from the given `LayoutArgs`.
 ---@field layout fun(self: self, args: LayoutArgs): { x: integer, y: integer, width: integer, height: integer }[]
+---The gap this generator leaves between a lone window and the edges of the output.
+---
+---Tags with smart gaps remove this gap from a lone tiled window. If this is nil, there is no gap.
+---@field lone_window_gaps? fun(self: self): integer
+
+---The gap a builtin generator with `gaps` leaves around a window that is laid out on its own.
+---
+---@param gaps integer | { inner: integer, outer: integer }
+---@return integer
+local function lone_window_gaps(gaps)
+    if type(gaps) == "number" then
+        return gaps
+    end
+
+    return gaps.outer + gaps.inner
+end
 
 ---Builtin layout generators.
 ---
@@ -46,6 +82,8 @@ local builtins = {}
 ---This is a float that will be clamped between 0.1 and 0.9.
 ---@field master_factor number
 ---The side the master window(s) will be on.
+---
+---Tags with a tiling direction move this to the matching side on the same end.
 ---@field master_side "left"|"right"|"top"|"bottom"
 ---How many windows the master side will have.
 ---@field master_count integer
@@ -98,6 +136,11 @@ function MasterStack:layout(args)
     local width = args.output_width
     local height = args.output_height
 
+    local master_side = self.master_side
+    if args.tiling_direction then
+        master_side = oriented_master_sides[args.tiling_direction][master_side]
+    end
+
     ---@type { x: integer, y: integer, width: integer, height: integer }[]
     local geos = {}
 
@@ -130,13 +173,13 @@ function MasterStack:layout(args)
 
     local gaps = ((not inner_gaps and outer_gaps) or 0)
 
-    if self.master_side == "left" then
+    if master_side == "left" then
         master_rect, stack_rect =
             rect:split_at("vertical", mfloor(width * master_factor) - mfloor(gaps / 2), gaps)
-    elseif self.master_side == "right" then
+    elseif master_side == "right" then
         stack_rect, master_rect =
             rect:split_at("vertical", mfloor(width * master_factor) - mfloor(gaps / 2), gaps)
-    elseif self.master_side == "top" then
+    elseif master_side == "top" then
         master_rect, stack_rect =
             rect:split_at("horizontal", mfloor(height * master_factor) - mfloor(gaps / 2), gaps)
     else
@@ -166,7 +209,7 @@ function MasterStack:layout(args)
         local len
         local axis
 
-        if self.master_side == "left" or self.master_side == "right" then
+        if master_side == "left" or master_side == "right" then
             coord = master_rect.y
             len = mfloor(master_rect.height / (master_slice_count + 1))
             axis = "horizontal"
@@ -194,7 +237,7 @@ function MasterStack:layout(args)
             local coord
             local len
             local axis
-            if self.master_side == "left" or self.master_side == "right" then
+            if master_side == "left" or master_side == "right" then
                 coord = stack_rect.y
                 len = stack_rect.height / (stack_slice_count + 1)
                 axis = "horizontal"
@@ -228,6 +271,11 @@ function MasterStack:layout(args)
     return geos
 end
 
+---@return integer
+function MasterStack:lone_window_gaps()
+    return lone_window_gaps(self.gaps)
+end
+
 ---Create a master stack layout generator.
 ---
 ---Pass in `settings` to override the defaults.
@@ -377,6 +425,11 @@ function Dwindle:layout(args)
     return geos
 end
 
+---@return integer
+function Dwindle:lone_window_gaps()
+    return lone_window_gaps(self.gaps)
+end
+
 ---Create a dwindle layout generator.
 ---
 ---Pass in `settings` to override the defaults.
@@ -592,6 +645,11 @@ function Corner:layout(args)
     return geos
 end
 
+---@return integer
+function Corner:lone_window_gaps()
+    return lone_window_gaps(self.gaps)
+end
+
 ---Create a corner layout generator.
 ---
 ---Pass in `settings` to override the defaults.
@@ -748,6 +806,11 @@ function Spiral:layout(args)
     return geos
 end
 
+---@return integer
+function Spiral:lone_window_gaps()
+    return lone_window_gaps(self.gaps)
+end
+
 ---Create a spiral layout generator.
 ---
 ---Pass in `settings` to override the defaults.
@@ -951,6 +1014,11 @@ function Fair:layout(args)
     return geos
 end
 
+---@return integer
+function Fair:lone_window_gaps()
+    return lone_window_gaps(self.gaps)
+end
+
 ---Create a fair layout generator.
 ---
 ---Pass in `settings` to override the defaults.
@@ -1001,6 +1069,7 @@ function layout.set_manager(manager)
             tags = tag_handles,
             output_width = response.output_width,
             output_height = response.output_height,
+            tiling_direction = tiling_direction_keys[response.tiling_direction],
         }
 
         local a = manager:get_active(args)
@@ -1016,6 +1085,7 @@ function layout.set_manager(manager)
                 request_id = request_id,
                 geometries = geos,
                 output_name = response.output_name,
+                lone_window_gaps = a.lone_window_gaps and a:lone_window_gaps() or nil,
             },
         })
 
@@ -1042,6 +1112,114 @@ function layout.request_layout(output)
     layout.stream:write_chunk(body, false)
 end
 
+---Lay out every output.
+---
+---This is useful after making a lot of changes at once, like after `Window.reapply_window_rules`.
+---Calling this multiple times in quick succession only lays out each output once.
+---
+---### Example
+---```lua
+---Layout.relayout_all()
+---```
+function layout.relayout_all()
+    client.unary_request(layout_service.RelayoutAll, {})
+end
+
+---Pause or resume tiling.
+---
+---While tiling is paused, the layout manager isn't asked to tile windows.
+---Tiled windows stay where they are and new windows open floating, so you can
+---arrange things by hand. Resuming lays out every output again.
+---
+---### Example
+---```lua
+---Layout.set_tiling_paused(true)
+---```
+---
+---@param paused boolean
+function layout.set_tiling_paused(paused)
+    client.unary_request(layout_service.SetTilingPaused, { set_or_toggle = set_or_toggle[paused] })
+end
+
+---Toggle whether tiling is paused.
+---
+---See `Layout.set_tiling_paused` for what pausing does.
+---
+---### Example
+---```lua
+---Layout.toggle_tiling_paused()
+---```
+function layout.toggle_tiling_paused()
+    client.unary_request(layout_service.SetTilingPaused, { set_or_toggle = set_or_toggle.TOGGLE })
+end
+
+---Layout state that only the layout manager knows about.
+---@class ReportedTagLayout
+---@field current_layout string? The name of the layout the tag is using
+---@field layouts string[]? The names of the layouts the tag cycles through, in order
+---@field master_factor number? How much of the output the master area takes up, from 0.0 to 1.0
+---@field master_count integer? How many windows are in the master area
+
+---Report a tag's layout state to the compositor so other clients can show it.
+---
+---Only the layout manager knows things like which layouts a tag cycles through, so
+---call this whenever they change. This replaces anything previously reported for `tag`.
+---
+---### Example
+---```lua
+---Layout.report_tag_layout(Tag.get("1"), {
+---    current_layout = "master_stack",
+---    layouts = { "master_stack", "dwindle" },
+---    master_factor = 0.5,
+---    master_count = 1,
+---})
+---```
+---
+---@param tag TagHandle
+---@param info ReportedTagLayout
+function layout.report_tag_layout(tag, info)
+    client.unary_request(layout_service.SetTagLayout, { tag_id = tag.id, layout = info })
+end
+
+---Everything about how a tag is laid out.
+---Fields are nil if the tag doesn't exist.
+---@class TagLayoutInfo
+---@field reported ReportedTagLayout? What the layout manager last reported with `Layout.report_tag_layout`
+---@field inner_gaps integer? The gap the compositor puts around each tiled window
+---@field outer_gaps integer? The gap the compositor insets tiled windows from the edges of the output
+---@field smart_gaps boolean? Whether gaps are removed when there is only one tiled window
+---@field tiling_direction TilingDirection? How the layout's master area is split from the stack, or nil if the layout decides
+---@field tiling_paused boolean? Whether tiling is paused
+
+---Get everything about how a tag is laid out in one call.
+---
+---This is useful for layout switchers in bars.
+---
+---### Example
+---```lua
+---local info = Layout.tag_layout(Tag.get("1"))
+---if info.reported and info.reported.current_layout then
+---    print("Using " .. info.reported.current_layout)
+---end
+---```
+---
+---@param tag TagHandle
+---@return TagLayoutInfo
+function layout.tag_layout(tag)
+    ---@type pinnacle.layout.v0alpha1.GetTagLayoutResponse
+    local response = client.unary_request(layout_service.GetTagLayout, { tag_id = tag.id })
+
+    ---@type TagLayoutInfo
+    return {
+        reported = response.layout,
+        inner_gaps = response.inner_gaps,
+        outer_gaps = response.outer_gaps,
+        smart_gaps = response.smart_gaps,
+        tiling_direction = tiling_direction_keys[response.tiling_direction],
+        tiling_paused = response.tiling_paused,
+    }
+end
+
 ---An object that manages layouts.
 ---@class LayoutManager
 ---@field layouts LayoutGenerator[]
@@ -5,6 +5,14 @@
 local client = require("pinnacle.grpc.client")
 local output_service = require("pinnacle.grpc.defs").pinnacle.output.v0alpha1.OutputService
 
+local set_or_toggle = {
+    SET = 1,
+    [true] = 1,
+    UNSET = 2,
+    [false] = 2,
+    TOGGLE = 3,
+}
+
 ---@nodoc
 ---@class OutputHandleModule
 local output_handle = {}
@@ -97,6 +105,40 @@ function output.get_focused()
     return nil
 end
 
+---Focus the output the pointer is currently on.
+---
+---This also moves keyboard focus to that output's focused window.
+---
+---### Example
+---```lua
+---Output.focus_output_under_cursor()
+---```
+function output.focus_output_under_cursor()
+    client.unary_request(output_service.FocusOutputUnderCursor, {})
+end
+
+---Unlock focus after it was locked with `OutputHandle:lock_focus`.
+---
+---### Example
+---```lua
+---Output.unlock_focus()
+---```
+function output.unlock_focus()
+    client.unary_request(output_service.SetFocusLock, {})
+end
+
+---Stop using an output set with `OutputHandle:set_primary` as the primary output.
+---
+---Layer surfaces that don't ask for an output will be placed on the first output again.
+---
+---### Example
+---```lua
+---Output.clear_primary()
+---```
+function output.clear_primary()
+    client.unary_request(output_service.SetPrimary, {})
+end
+
 ---Connect a function to be run with all current and future outputs.
 ---
 ---This method does two things:
@@ -587,6 +629,7 @@ local signal_name_to_SignalName = {
     disconnect = "OutputDisconnect",
     resize = "OutputResize",
     move = "OutputMove",
+    headless = "OutputHeadless",
 }
 
 ---@class OutputSignal Signals related to output events.
@@ -594,6 +637,7 @@ local signal_name_to_SignalName = {
 ---@field disconnect fun(output: OutputHandle)? An output was disconnected.
 ---@field resize fun(output: OutputHandle, logical_width: integer, logical_height: integer)? An output's logical size changed.
 ---@field move fun(output: OutputHandle, x: integer, y: integer)? An output moved.
+---@field headless fun(headless: boolean)? All outputs were disconnected, or an output connected after they were. Once one connects, windows that were on outputs that didn't come back are moved to its active tags.
 
 ---Connect to an output signal.
 ---
@@ -860,6 +904,34 @@ local transform_code_to_name = {
     [8] = "flipped_270",
 }
 
+---Focus this output and keep focus on it until `Output.unlock_focus` is called.
+---
+---While locked, moving the pointer to another output will not focus it,
+---and windows that open on other outputs will not get keyboard focus.
+---
+---### Example
+---```lua
+--- -- Keep focus on the streaming output
+---Output.get_by_name("DP-1"):lock_focus()
+---```
+function OutputHandle:lock_focus()
+    client.unary_request(output_service.SetFocusLock, { output_name = self.name })
+end
+
+---Make this the primary output.
+---
+---Layer surfaces like bars and wallpapers that don't ask for a specific output
+---will be placed on this output instead of whichever one comes first.
+---This applies whenever this output is connected.
+---
+---### Example
+---```lua
+---Output.get_by_name("DP-1"):set_primary()
+---```
+function OutputHandle:set_primary()
+    client.unary_request(output_service.SetPrimary, { output_name = self.name })
+end
+
 ---Set this output's transform.
 ---
 ---@param transform Transform
@@ -870,6 +942,207 @@ function OutputHandle:set_transform(transform)
     )
 end
 
+---Open or close an overview on this output.
+---
+---The overview shows windows side by side, scaled down to fit the output.
+---Clicking on a window focuses it and closes the overview. If the window isn't on
+---an active tag, its first tag will be switched to.
+---
+---If `all_tags` is true, windows on all of this output's tags will be shown,
+---not just those on active tags. This only has an effect when opening the overview.
+---
+---### Example
+---```lua
+----- Show all windows on the focused output
+---Output.get_focused():set_overview(true, true)
+---```
+---
+---@param overview boolean
+---@param all_tags boolean?
+function OutputHandle:set_overview(overview, all_tags)
+    client.unary_request(output_service.SetOverview, {
+        output_name = self.name,
+        set_or_toggle = set_or_toggle[overview],
+        all_tags = all_tags or false,
+    })
+end
+
+---Toggle the overview on this output.
+---
+---See `OutputHandle:set_overview` for more information.
+---
+---### Example
+---```lua
+----- Toggle an overview of windows on active tags
+---Output.get_focused():toggle_overview()
+---```
+---
+---@param all_tags boolean?
+function OutputHandle:toggle_overview(all_tags)
+    client.unary_request(output_service.SetOverview, {
+        output_name = self.name,
+        set_or_toggle = set_or_toggle.TOGGLE,
+        all_tags = all_tags or false,
+    })
+end
+
+---Zoom this output in around the pointer by `magnification`.
+---
+---The magnified view follows the pointer, and input still goes to whatever is under it.
+---Setting this to 1.0 turns the magnifier off. It's clamped to between 1.0 and 16.0.
+---
+---### Example
+---```lua
+----- Toggle the magnifier with Super+Alt+m
+---Input.keybind({ "super", "alt" }, "m", function()
+---    local op = Output.get_focused()
+---    if op then
+---        op:set_magnification((op:magnification() or 1) > 1 and 1 or 2)
+---    end
+---end)
+---```
+---
+---@param magnification number
+function OutputHandle:set_magnification(magnification)
+    client.unary_request(
+        output_service.SetMagnification,
+        { output_name = self.name, absolute = magnification }
+    )
+end
+
+---Increase this output's magnification.
+---
+---This turns the magnifier on if it was off.
+---
+---@param increase_by number
+function OutputHandle:increase_magnification(increase_by)
+    client.unary_request(
+        output_service.SetMagnification,
+        { output_name = self.name, relative = increase_by }
+    )
+end
+
+---Decrease this output's magnification.
+---
+---The magnifier turns off once the magnification reaches 1.0.
+---
+---@param decrease_by number
+function OutputHandle:decrease_magnification(decrease_by)
+    self:increase_magnification(-decrease_by)
+end
+
+---Set the brightness of this output's backlight.
+---
+---`brightness` is a fraction of the maximum brightness and is clamped to between 0.0 and 1.0.
+---This only works on outputs with a backlight, like laptop panels.
+---
+---### Example
+---```lua
+---Output.get_by_name("eDP-1"):set_brightness(0.5)
+---```
+---
+---@param brightness number
+function OutputHandle:set_brightness(brightness)
+    client.unary_request(
+        output_service.SetBrightness,
+        { output_name = self.name, absolute = brightness }
+    )
+end
+
+---Increase the brightness of this output's backlight.
+---
+---### Example
+---```lua
+----- Raise the brightness by 10% with the brightness up key
+---Input.keybind({}, "XF86MonBrightnessUp", function()
+---    local op = Output.get_focused()
+---    if op then
+---        op:increase_brightness(0.1)
+---    end
+---end)
+---```
+---
+---@param increase_by number
+function OutputHandle:increase_brightness(increase_by)
+    client.unary_request(
+        output_service.SetBrightness,
+        { output_name = self.name, relative = increase_by }
+    )
+end
+
+---Decrease the brightness of this output's backlight.
+---
+---@param decrease_by number
+function OutputHandle:decrease_brightness(decrease_by)
+    self:increase_brightness(-decrease_by)
+end
+
+---Set the gamma Pinnacle keeps on this output, like a night light.
+---
+---The preset stays set if this output is unplugged and is applied again when it's plugged
+---back in. Gamma control clients like wlsunset take priority over it while they're running.
+---
+---### Example
+---```lua
+----- Make the screen warmer at night
+---Output.get_focused():set_gamma_preset({ temperature = 4000 })
+---```
+---
+---@param preset { temperature: integer?, brightness: number?, gamma: number? } The color temperature in Kelvin (default 6500), brightness from 0.0 to 1.0 (default 1.0), and gamma (default 1.0)
+function OutputHandle:set_gamma_preset(preset)
+    client.unary_request(output_service.SetGammaPreset, {
+        output_name = self.name,
+        temperature = preset.temperature or 6500,
+        brightness = preset.brightness or 1.0,
+        gamma = preset.gamma or 1.0,
+    })
+end
+
+---Remove this output's gamma preset, resetting its gamma to linear.
+function OutputHandle:remove_gamma_preset()
+    client.unary_request(output_service.SetGammaPreset, { output_name = self.name })
+end
+
+---@enum (key) LoneWindowAlignment
+local lone_window_alignment_name_to_code = {
+    center = 1,
+    left = 2,
+    right = 3,
+}
+
+local lone_window_alignment_code_to_name = {
+    [1] = "center",
+    [2] = "left",
+    [3] = "right",
+}
+
+---Limit how wide the only tiled window on this output can be.
+---
+---This is useful on ultrawide monitors, where a single window stretched across the whole
+---output is awkward to read. By default, a lone tiled window fills the output.
+---
+---### Example
+---```lua
+--- -- Keep a lone window in a 2560 pixel column in the middle of the output
+---Output.get_by_name("DP-1"):set_lone_window_width(2560)
+---```
+---
+---@param max_width integer The most logical pixels wide the window can be
+---@param alignment LoneWindowAlignment? Where the window goes in the space it would otherwise fill. Defaults to "center".
+function OutputHandle:set_lone_window_width(max_width, alignment)
+    client.unary_request(output_service.SetLoneWindowWidth, {
+        output_name = self.name,
+        max_width = max_width,
+        alignment = lone_window_alignment_name_to_code[alignment or "center"],
+    })
+end
+
+---Remove the limit set with `OutputHandle.set_lone_window_width`, letting a lone tiled
+---window fill this output again.
+function OutputHandle:remove_lone_window_width()
+    client.unary_request(output_service.SetLoneWindowWidth, { output_name = self.name })
+end
+
 ---@class Mode
 ---@field pixel_width integer
 ---@field pixel_height integer
@@ -892,6 +1165,10 @@ end
 ---@field scale number?
 ---@field transform Transform?
 ---@field serial integer?
+---@field overview boolean?
+---@field magnification number?
+---@field brightness number?
+---@field lone_window_width { max_width: integer, alignment: LoneWindowAlignment }? How wide the only tiled window can be, or nil if it fills the output
 
 ---Get all properties of this output.
 ---
@@ -907,6 +1184,16 @@ function OutputHandle:props()
     response.modes = response.modes or {}
     response.transform = transform_code_to_name[response.transform]
 
+    if response.lone_window_max_width then
+        response.lone_window_width = {
+            max_width = response.lone_window_max_width,
+            alignment = lone_window_alignment_code_to_name[response.lone_window_alignment]
+                or "center",
+        }
+    end
+    response.lone_window_max_width = nil
+    response.lone_window_alignment = nil
+
     return response
 end
 
@@ -1060,6 +1347,85 @@ function OutputHandle:serial()
     return self:props().serial
 end
 
+---Get whether this output has an overview open.
+---
+---Shorthand for `handle:props().overview`.
+---
+---@return boolean?
+function OutputHandle:overview()
+    return self:props().overview
+end
+
+---Get how much this output is magnified.
+---
+---Shorthand for `handle:props().magnification`.
+---
+---@return number?
+function OutputHandle:magnification()
+    return self:props().magnification
+end
+
+---Get the brightness of this output's backlight from 0.0 to 1.0.
+---
+---Shorthand for `handle:props().brightness`.
+---
+---@return number?
+function OutputHandle:brightness()
+    return self:props().brightness
+end
+
+---Get how wide the only tiled window on this output can be.
+---
+---Shorthand for `handle:props().lone_window_width`.
+---
+---@return { max_width: integer, alignment: LoneWindowAlignment }?
+function OutputHandle:lone_window_width()
+    return self:props().lone_window_width
+end
+
+---@class FocusStackEntry
+---@field window WindowHandle
+---@field on_active_tag boolean Whether the window is on one of the output's active tags
+
+---Get the windows on this output in the order they were focused, from most to least
+---recently focused.
+---
+---This is the same order the compositor uses to pick what to focus when the focused
+---window closes, so it's what an alt-tab switcher should cycle through.
+---
+---### Example
+---```lua
+----- Get the most recently focused window on an active tag other than the focused one
+---local previous = nil
+---local seen = 0
+---for _, entry in ipairs(Output.get_focused():focus_stack()) do
+---    if entry.on_active_tag then
+---        seen = seen + 1
+---        if seen == 2 then
+---            previous = entry.window
+---            break
+---        end
+---    end
+---end
+---```
+---
+---@return FocusStackEntry[]
+function OutputHandle:focus_stack()
+    ---@type pinnacle.output.v0alpha1.GetFocusStackResponse
+    local response = client.unary_request(output_service.GetFocusStack, { output_name = self.name })
+
+    ---@type FocusStackEntry[]
+    local entries = {}
+    for _, entry in ipairs(response.entries or {}) do
+        table.insert(entries, {
+            window = require("pinnacle.window").handle.new(entry.window_id),
+            on_active_tag = entry.on_active_tag or false,
+        })
+    end
+
+    return entries
+end
+
 ---@nodoc
 ---Create a new `OutputHandle` from its raw name.
 ---@param output_name string
@@ -40,4 +40,128 @@ function render.set_downscale_filter(filter)
     )
 end
 
+---Periodically report a summary of frame timings for each output.
+---
+---Every `interval` milliseconds, the compositor will log how many frames each output rendered,
+---how long they took to render, and how many were directly scanned out.
+---Outputs that didn't render anything are reported with 0 frames.
+---Pass nothing or 0 to stop.
+---
+---Summaries can also be received with `Render.watch_frame_timings`.
+---
+---### Example
+---```lua
+---Render.set_frame_timings(5000)
+---```
+---
+---@param interval integer? How often to report frame timings, in milliseconds
+function render.set_frame_timings(interval)
+    client.unary_request(render_service.SetFrameTimings, { interval_ms = interval })
+end
+
+---A summary of the frames rendered on an output over a reporting interval.
+---@class FrameTimings
+---@field output OutputHandle The output the frames were rendered on
+---@field frames integer The number of frames rendered
+---@field interval integer The length of the reporting interval, in milliseconds
+---@field average_render_time integer The average time it took to render a frame, in microseconds
+---@field max_render_time integer The longest time it took to render a frame, in microseconds
+---@field damage_rects integer? The total number of damaged regions redrawn, only tracked when running nested
+---@field direct_scanout_frames integer The number of frames directly scanned out from a client buffer
+---@field cursor_plane_frames integer The number of frames where the cursor was on a hardware cursor plane
+
+---Run a function with every frame timing summary the compositor reports.
+---
+---Summaries are only reported after enabling them with `Render.set_frame_timings`.
+---
+---### Example
+---```lua
+---Render.set_frame_timings(1000)
+---Render.watch_frame_timings(function(timings)
+---    print(timings.output.name .. ": " .. tostring(timings.frames) .. " frames")
+---end)
+---```
+---
+---@param for_each fun(timings: FrameTimings)
+function render.watch_frame_timings(for_each)
+    client.server_streaming_request(
+        render_service.WatchFrameTimings,
+        {},
+        ---@param response pinnacle.render.v0alpha1.WatchFrameTimingsResponse
+        function(response)
+            ---@diagnostic disable-next-line: invisible
+            local output_handle = require("pinnacle.output").handle
+
+            ---@type FrameTimings
+            local timings = {
+                output = output_handle.new(response.output_name),
+                frames = response.frames or 0,
+                interval = response.interval_ms or 0,
+                average_render_time = response.average_render_time_us or 0,
+                max_render_time = response.max_render_time_us or 0,
+                damage_rects = response.damage_rects,
+                direct_scanout_frames = response.direct_scanout_frames or 0,
+                cursor_plane_frames = response.cursor_plane_frames or 0,
+            }
+
+            for_each(timings)
+        end
+    )
+end
+
+---Dim outputs that don't have keyboard focus.
+---
+---This helps show which output is active when focus follows the keyboard.
+---`amount` goes from 0.0, which turns dimming off, to 1.0, which makes unfocused outputs
+---black. Dimming is off by default.
+---
+---### Example
+---```lua
+----- Darken unfocused outputs a little
+---Render.set_inactive_output_dim(0.2)
+---```
+---
+---@param amount number How much to dim unfocused outputs, from 0.0 to 1.0
+function render.set_inactive_output_dim(amount)
+    client.unary_request(render_service.SetInactiveOutputDim, { amount = amount })
+end
+
+---@class RendererInfo
+---@field version string The OpenGL ES version string
+---@field vendor string The company responsible for the GL implementation
+---@field renderer string The GPU or software renderer name
+---@field dmabuf_formats string[] The fourcc codes of dmabuf formats the renderer can import
+
+---Get information about the renderer used for compositing.
+---
+---This is useful to attach to bug reports about rendering issues.
+---When running in a tty, this is the renderer of the primary GPU.
+---When running nested in a window, it's the host's GL implementation.
+---
+---### Example
+---```lua
+---local info = Render.renderer_info()
+---if info then
+---    print(info.version .. " on " .. info.renderer)
+---end
+---```
+---
+---@return RendererInfo | nil info The renderer info, or `nil` if it couldn't be queried
+function render.renderer_info()
+    ---@type pinnacle.render.v0alpha1.GetRendererInfoResponse
+    local response = client.unary_request(render_service.GetRendererInfo, {})
+
+    if not response.version then
+        return nil
+    end
+
+    ---@type RendererInfo
+    return {
+        version = response.version,
+        vendor = response.vendor or "",
+        renderer = response.renderer or "",
+        dmabuf_formats = response.dmabuf_formats or {},
+    }
+end
+
 return render
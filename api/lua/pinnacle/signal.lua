@@ -60,6 +60,17 @@ local signals = {
         ---@type fun(response: table)
         on_response = nil,
     },
+    OutputHeadless = {
+        ---@nodoc
+        ---@type H2Stream?
+        sender = nil,
+        ---@nodoc
+        ---@type (fun(headless: boolean))[]
+        callbacks = {},
+        ---@nodoc
+        ---@type fun(response: table)
+        on_response = nil,
+    },
     WindowPointerEnter = {
         ---@nodoc
         ---@type H2Stream?
@@ -127,6 +138,12 @@ signals.OutputMove.on_response = function(response)
     end
 end
 
+signals.OutputHeadless.on_response = function(response)
+    for _, callback in ipairs(signals.OutputHeadless.callbacks) do
+        callback(response.headless or false)
+    end
+end
+
 signals.WindowPointerEnter.on_response = function(response)
     ---@diagnostic disable-next-line: invisible
     local window_handle = require("pinnacle.window").handle.new(response.window_id)
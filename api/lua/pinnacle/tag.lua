@@ -172,6 +172,92 @@ function tag.remove(tags)
     client.unary_request(tag_service.Remove, { tag_ids = ids })
 end
 
+---Set the tags that are added to newly connected outputs.
+---
+---When an output without any tags is plugged in, tags with these names are added to it
+---and the first one is activated, so the output is usable before your config sets it up.
+---Outputs that have saved tags from a previous connection keep those instead.
+---
+---Passing no names disables default tags.
+---
+---### Example
+---```lua
+--- -- Give new monitors tags 1-9
+---Tag.set_default_output_tags("1", "2", "3", "4", "5", "6", "7", "8", "9")
+---```
+---
+---@param ... string
+---
+---@overload fun(tag_names: string[])
+function tag.set_default_output_tags(...)
+    local tag_names = { ... }
+    if type(tag_names[1]) == "table" then
+        tag_names = tag_names[1] --[=[@as string[]]=]
+    end
+
+    client.unary_request(tag_service.SetDefaultOutputTags, { tag_names = tag_names })
+end
+
+---Set exactly which tags are active on an output, deactivating all others.
+---
+---This happens all at once, so the output is only laid out once.
+---All tags must be on the output; if any aren't, nothing happens.
+---
+---If `output` isn't specified, the focused output is used.
+---
+---### Example
+---```lua
+---local tags = Tag.add(Output.get_focused(), "1", "2", "3")
+---
+--- -- Display tags "1" and "3" and nothing else
+---Tag.set_active_tags({ tags[1], tags[3] })
+---```
+---
+---@param tags TagHandle[]
+---@param output OutputHandle?
+function tag.set_active_tags(tags, output)
+    output = output or require("pinnacle.output").get_focused()
+
+    if not output then
+        return
+    end
+
+    ---@type integer[]
+    local ids = {}
+
+    for _, tg in ipairs(tags) do
+        table.insert(ids, tg.id)
+    end
+
+    client.unary_request(tag_service.SetActiveTags, { output_name = output.name, tag_ids = ids })
+end
+
+---Switch an output back to the tags that were active before its most recent tag switch.
+---
+---The whole set of previously active tags is restored, so this works with multiple
+---active tags. Calling this repeatedly toggles between the two most recent sets of tags.
+---
+---If `output` isn't specified, the focused output is used.
+---
+---### Example
+---```lua
+---Tag.get("1"):switch_to()
+---Tag.get("2"):switch_to()
+---Tag.switch_to_previous() -- Displays tag "1" again
+---Tag.switch_to_previous() -- Displays tag "2" again
+---```
+---
+---@param output OutputHandle?
+function tag.switch_to_previous(output)
+    output = output or require("pinnacle.output").get_focused()
+
+    if not output then
+        return
+    end
+
+    client.unary_request(tag_service.SwitchToPrevious, { output_name = output.name })
+end
+
 ---@type table<string, SignalServiceMethod>
 local signal_name_to_SignalName = {
     active = "TagActive",
@@ -287,11 +373,224 @@ function TagHandle:toggle_active()
     )
 end
 
+---Set the gaps the compositor puts around tiled windows while this tag is focused.
+---
+---`inner` is the gap around each individual window and `outer` is the gap inset from the
+---edges of the output, both in logical pixels. These are applied on top of whatever
+---the layout generator returns, so you may want to disable the layout's own gaps.
+---
+---If multiple tags are focused on an output, the gaps of the first one are used.
+---
+---### Example
+---```lua
+---Tag.get("1"):set_gaps(4, 8)
+---```
+---
+---@param inner integer
+---@param outer integer
+function TagHandle:set_gaps(inner, outer)
+    client.unary_request(tag_service.SetGaps, { tag_id = self.id, inner = inner, outer = outer })
+end
+
+---Set whether this tag's gaps are removed when there is only one tiled window.
+---
+---When enabled, a lone tiled window also loses the gap its layout generator reports
+---leaving around it with `LayoutGenerator:lone_window_gaps`.
+---Gaps come back as soon as a second window is tiled.
+---
+---### Example
+---```lua
+--- -- No gaps around a lone window, gaps around multiple windows
+---local coding = Tag.get("1")
+---coding:set_gaps(4, 8)
+---coding:set_smart_gaps(true)
+---```
+---
+---@param smart boolean
+function TagHandle:set_smart_gaps(smart)
+    client.unary_request(tag_service.SetGaps, { tag_id = self.id, smart = smart })
+end
+
+---Hide or show floating windows with this tag.
+---
+---Hidden floating windows aren't displayed and can't get keyboard focus, so focus moves
+---to a tiled window. When they're shown again, focus returns to them if they had it.
+---Activating a hidden floating window, for example with `Window.focus_previous`,
+---shows floating windows again.
+---
+---### Example
+---```lua
+--- -- Get the floating windows on tag "1" out of the way
+---Tag.get("1"):set_floating_hidden(true)
+---```
+---
+---@param hidden boolean
+function TagHandle:set_floating_hidden(hidden)
+    client.unary_request(
+        tag_service.SetFloatingHidden,
+        { tag_id = self.id, set_or_toggle = set_or_toggle[hidden] }
+    )
+end
+
+---Toggle whether floating windows with this tag are hidden.
+---
+---See `TagHandle:set_floating_hidden`.
+---
+---### Example
+---```lua
+---Input.keybind({ "super" }, "h", function()
+---    for _, tg in ipairs(Output.get_focused():tags()) do
+---        if tg:active() then
+---            tg:toggle_floating_hidden()
+---        end
+---    end
+---end)
+---```
+function TagHandle:toggle_floating_hidden()
+    client.unary_request(
+        tag_service.SetFloatingHidden,
+        { tag_id = self.id, set_or_toggle = set_or_toggle.TOGGLE }
+    )
+end
+
+---@alias OverflowPolicy
+---| "float" Make the window floating
+---| "next_tag" Move the window to the next tag on the same output that isn't full, or make it floating if there is none
+---| "reject" Close the window. If it doesn't close within a few seconds, it floats on the tag instead.
+
+local overflow_policy_values = {
+    float = 1,
+    next_tag = 2,
+    reject = 3,
+}
+
+local overflow_policy_keys = {
+    [1] = "float",
+    [2] = "next_tag",
+    [3] = "reject",
+}
+
+---Limit how many windows can tile on this tag.
+---
+---New windows that would tile on this tag when it already has `limit` tiled windows
+---are handled according to `overflow`, which defaults to making them floating.
+---Windows already on the tag are left alone.
+---
+---### Example
+---```lua
+--- -- Keep tag "1" focused on one task, sending other windows to tag "2"
+---Tag.get("1"):set_window_limit(2, "next_tag")
+---```
+---
+---@param limit integer The most tiled windows the tag can have. Must be at least 1.
+---@param overflow OverflowPolicy? What happens to new windows when the tag is full
+function TagHandle:set_window_limit(limit, overflow)
+    client.unary_request(tag_service.SetWindowLimit, {
+        tag_id = self.id,
+        limit = limit,
+        overflow = overflow and overflow_policy_values[overflow],
+    })
+end
+
+---Remove the window limit set with `TagHandle:set_window_limit`.
+function TagHandle:remove_window_limit()
+    client.unary_request(tag_service.SetWindowLimit, { tag_id = self.id })
+end
+
+---@alias TilingDirection
+---| "horizontal" The master area is beside the stack
+---| "vertical" The master area is above or below the stack
+
+local tiling_direction_values = {
+    horizontal = 1,
+    vertical = 2,
+}
+
+local tiling_direction_keys = {
+    [1] = "horizontal",
+    [2] = "vertical",
+}
+
+---Orient the split between the layout's master area and its stack on this tag.
+---
+---This overrides which side the builtin master stack layout puts the master area on,
+---keeping it on the same end. For example, a layout with the master area on the left
+---puts it at the top when vertical. This lets the same layout work on portrait outputs.
+---Changing it relayouts immediately.
+---
+---### Example
+---```lua
+--- -- Stack windows top to bottom on a portrait monitor
+---Tag.get("1"):set_tiling_direction("vertical")
+---```
+---
+---@param direction TilingDirection
+function TagHandle:set_tiling_direction(direction)
+    client.unary_request(tag_service.SetTilingDirection, {
+        tag_id = self.id,
+        direction = tiling_direction_values[direction],
+    })
+end
+
+---Remove the tiling direction set with `TagHandle:set_tiling_direction`,
+---letting the layout decide again.
+function TagHandle:remove_tiling_direction()
+    client.unary_request(tag_service.SetTilingDirection, { tag_id = self.id })
+end
+
+---Flip this tag's tiling direction between horizontal and vertical.
+---
+---Tags without a tiling direction are treated as horizontal.
+---
+---### Example
+---```lua
+---Tag.get("1"):toggle_tiling_direction()
+---```
+function TagHandle:toggle_tiling_direction()
+    client.unary_request(tag_service.ToggleTilingDirection, { tag_id = self.id })
+end
+
+---Set the name bars and other clients show for this tag.
+---
+---This doesn't change the tag's name, so it can still be found with `Tag.get`
+---using the name it was added with. Display names don't need to be unique.
+---
+---### Example
+---```lua
+---local tags = Tag.add(Output.get_focused(), "1", "2", "3")
+---tags[1]:set_display_name("www")
+---tags[2]:set_display_name("code")
+---tags[3]:set_display_name("chat")
+---```
+---
+---@param display_name string
+function TagHandle:set_display_name(display_name)
+    client.unary_request(tag_service.SetDisplayName, {
+        tag_id = self.id,
+        display_name = display_name,
+    })
+end
+
+---Remove the display name set with `TagHandle:set_display_name`,
+---going back to showing the tag's name.
+function TagHandle:remove_display_name()
+    client.unary_request(tag_service.SetDisplayName, { tag_id = self.id })
+end
+
 ---@class TagProperties
 ---@field active boolean? Whether or not the tag is currently being displayed
 ---@field name string? The name of the tag
 ---@field output OutputHandle? The output the tag is on
 ---@field windows WindowHandle[] The windows that have this tag
+---@field inner_gaps integer? The gap the compositor puts around each tiled window
+---@field outer_gaps integer? The gap the compositor insets tiled windows from the edges of the output
+---@field smart_gaps boolean? Whether gaps are removed when there is only one tiled window
+---@field urgent boolean? Whether any window with this tag wants attention
+---@field floating_hidden boolean? Whether floating windows with this tag are hidden
+---@field window_limit integer? The most windows that can tile on this tag, or `nil` if unlimited
+---@field overflow_policy OverflowPolicy? What happens to new windows when this tag is full, or `nil` if unlimited
+---@field tiling_direction TilingDirection? How the layout's master area is split from the stack, or `nil` if the layout decides
+---@field display_name string? The name shown for this tag, or `nil` if it doesn't have one
 
 ---Get all properties of this tag.
 ---
@@ -307,6 +606,17 @@ function TagHandle:props()
             and require("pinnacle.output").handle.new(response.output_name),
         ---@diagnostic disable-next-line: invisible
         windows = require("pinnacle.window").handle.new_from_table(response.window_ids or {}),
+        inner_gaps = response.inner_gaps,
+        outer_gaps = response.outer_gaps,
+        smart_gaps = response.smart_gaps,
+        urgent = response.urgent,
+        floating_hidden = response.floating_hidden,
+        window_limit = response.window_limit,
+        overflow_policy = response.overflow_policy
+            and overflow_policy_keys[response.overflow_policy],
+        tiling_direction = response.tiling_direction
+            and tiling_direction_keys[response.tiling_direction],
+        display_name = response.display_name,
     }
 end
 
@@ -346,6 +656,52 @@ function TagHandle:windows()
     return self:props().windows
 end
 
+---Get whether any window with this tag wants attention.
+---
+---Shorthand for `handle:props().urgent`.
+---
+---@return boolean?
+function TagHandle:urgent()
+    return self:props().urgent
+end
+
+---Get whether floating windows with this tag are hidden.
+---
+---Shorthand for `handle:props().floating_hidden`.
+---
+---@return boolean?
+function TagHandle:floating_hidden()
+    return self:props().floating_hidden
+end
+
+---Get the most windows that can tile on this tag, or `nil` if it's unlimited.
+---
+---Shorthand for `handle:props().window_limit`.
+---
+---@return integer?
+function TagHandle:window_limit()
+    return self:props().window_limit
+end
+
+---Get how the layout's master area is split from the stack on this tag,
+---or `nil` if the layout decides.
+---
+---Shorthand for `handle:props().tiling_direction`.
+---
+---@return TilingDirection?
+function TagHandle:tiling_direction()
+    return self:props().tiling_direction
+end
+
+---Get the name shown for this tag, or `nil` if it doesn't have one.
+---
+---Shorthand for `handle:props().display_name`.
+---
+---@return string?
+function TagHandle:display_name()
+    return self:props().display_name
+end
+
 ---@nodoc
 ---Create a new `TagHandle` from an id.
 ---@param tag_id integer
@@ -90,22 +90,156 @@ function window.get_focused()
     return nil
 end
 
+---Get the window with the given label.
+---
+---See `WindowHandle.set_label`.
+---
+---### Example
+---```lua
+---local editor = Window.get_by_label("main-editor")
+---```
+---
+---@param label string
+---
+---@return WindowHandle | nil
+function window.get_by_label(label)
+    local handles = window.get_all()
+
+    ---@type (fun(): WindowProperties)[]
+    local requests = {}
+
+    for i, handle in ipairs(handles) do
+        requests[i] = function()
+            return handle:props()
+        end
+    end
+
+    local props = require("pinnacle.util").batch(requests)
+
+    for i, prop in ipairs(props) do
+        if prop.label == label then
+            return handles[i]
+        end
+    end
+
+    return nil
+end
+
+---Focus the window that most recently became urgent.
+---
+---Windows become urgent when they want attention, for example when they're denied
+---activation by focus stealing prevention. This switches to the window's tag if it
+---isn't on an active one, then focuses and raises it, clearing its urgency.
+---
+---This does nothing if there are no urgent windows.
+---
+---### Example
+---```lua
+---Input.keybind({ "super" }, "u", function()
+---    Window.focus_urgent()
+---end)
+---```
+function window.focus_urgent()
+    client.unary_request(window_service.FocusUrgent, {})
+end
+
+---Focus the previously focused window.
+---
+---Calling this repeatedly toggles focus between the two most recently focused windows.
+---Each output keeps its own history, so this picks from windows focused on the focused
+---output. If the previous window is on an inactive tag or has since moved to another
+---output, that tag and output are switched to. Closed windows are skipped.
+---
+---### Example
+---```lua
+---Input.keybind({ "super" }, "Tab", function()
+---    Window.focus_previous()
+---end)
+---```
+function window.focus_previous()
+    client.unary_request(window_service.FocusPrevious, {})
+end
+
+---Jump to the most recently focused window with the given class and/or title.
+---
+---This switches to the window's tag if it isn't on an active one, then focuses and raises it.
+---Windows must match both `class` and `title` if both are given.
+---
+---If the focused window already matches and `cycle` is true, the least recently focused
+---other match is jumped to instead, so calling this repeatedly cycles through all matches.
+---
+---### Example
+---```lua
+----- Raise or run Firefox
+---Input.keybind({ "super" }, "b", function()
+---    if not Window.jump_to({ class = "firefox", cycle = true }) then
+---        Process.spawn("firefox")
+---    end
+---end)
+---```
+---
+---@param args { class: string?, title: string?, cycle: boolean? }
+---
+---@return WindowHandle | nil window The window that was jumped to, or `nil` if no window matched.
+function window.jump_to(args)
+    local response = client.unary_request(window_service.JumpToWindow, {
+        class = args.class,
+        title = args.title,
+        cycle = args.cycle or false,
+    })
+
+    return response.window_id and window_handle.new(response.window_id)
+end
+
+---Pick a window with the keyboard.
+---
+---This draws a letter over every visible window and waits for one of them to be typed.
+---Keys don't go to windows or trigger keybinds until a window is picked.
+---
+---### Example
+---```lua
+----- Focus a window by its hint with Super+f
+---Input.keybind({ "super" }, "f", function()
+---    local win = Window.select()
+---    if win then
+---        win:set_focused(true)
+---    end
+---end)
+---```
+---
+---@return WindowHandle | nil window The window whose letter was typed, or `nil` if Escape was pressed.
+function window.select()
+    local response = client.unary_request(window_service.SelectWindow, {})
+
+    return response.window_id and window_handle.new(response.window_id)
+end
+
 ---Begin moving this window using the specified mouse button.
 ---
 ---The button must be pressed at the time this method is called.
 ---If the button is lifted, the move will end.
 ---
+---If `float_tiled` is true, a tiled window is pulled out of the layout at its current size
+---and follows the pointer instead of swapping places with other tiled windows.
+---Dropping the window over a tiled window on the same output tiles it at that window's position.
+---
 ---### Example
 ---```lua
 ---Input.mousebind({ "super" }, "btn_left", function()
 ---    Window.begin_move("btn_left")
 ---end)
+---
+--- -- Drag windows out of the layout
+---Input.mousebind({ "super", "shift" }, "btn_left", function()
+---    Window.begin_move("btn_left", true)
+---end)
 ---```
 ---@param button MouseButton The button that will initiate the move
-function window.begin_move(button)
+---@param float_tiled boolean? Whether to make a tiled window floating
+function window.begin_move(button, float_tiled)
     ---@diagnostic disable-next-line: redefined-local, invisible
     local button = require("pinnacle.input").mouse_button_values[button]
-    client.unary_request(window_service.MoveGrab, { button = button })
+    client.unary_request(window_service.MoveGrab, { button = button, float_tiled = float_tiled })
 end
 
 ---Begin resizing this window using the specified mouse button.
@@ -113,17 +247,82 @@ end
 ---The button must be pressed at the time this method is called.
 ---If the button is lifted, the resize will end.
 ---
+---By default, the window is resized from the corner of the quadrant the pointer is in.
+---If `border_width` is specified, resizing only starts if the pointer is within that many
+---logical pixels of the window's edges, and the window is resized from the edge or corner
+---the pointer is near.
+---
 ---### Example
 ---```lua
 ---Input.mousebind({ "super" }, "btn_right", function()
 ---    Window.begin_resize("btn_right")
 ---end)
+---
+--- -- Resize windows by dragging within 16 pixels of their edges
+---Input.mousebind({ "super" }, "btn_left", function()
+---    Window.begin_resize("btn_left", 16)
+---end)
 ---```
 ---@param button MouseButton The button that will initiate the resize
-function window.begin_resize(button)
+---@param border_width integer? How close to the edges the pointer must be to start resizing
+function window.begin_resize(button, border_width)
     ---@diagnostic disable-next-line: redefined-local, invisible
     local button = require("pinnacle.input").mouse_button_values[button]
-    client.unary_request(window_service.ResizeGrab, { button = button })
+    client.unary_request(
+        window_service.ResizeGrab,
+        { button = button, border_width = border_width }
+    )
+end
+
+---Cancel the window move or resize that is in progress.
+---
+---A moved floating window goes back to where it was when the move started, and a resized
+---window goes back to its original size. A window that was floated by `begin_move` with
+---`float_tiled` is tiled again. Tiled windows that swapped places with other windows
+---while being moved stay where they are.
+---
+---Nothing happens if no window is being moved or resized.
+---
+---### Example
+---```lua
+----- Cancel moves and resizes with `Escape`
+---Input.keybind({}, "Escape", function()
+---    Window.cancel_grab()
+---end)
+---```
+function window.cancel_grab()
+    client.unary_request(window_service.CancelGrab, {})
+end
+
+---@alias GrabKind
+---| "move" The window is being moved
+---| "resize" The window is being resized
+
+local _grab_kind_keys = {
+    [1] = "move",
+    [2] = "resize",
+}
+
+---Get the window move or resize that is in progress, if any.
+---
+---### Example
+---```lua
+---local grab = Window.get_grab()
+---if grab and grab.kind == "move" then
+---    print(grab.window:class() .. " is being moved")
+---end
+---```
+---
+---@return { kind: GrabKind, window: WindowHandle } | nil
+function window.get_grab()
+    local response = client.unary_request(window_service.GetGrab, {})
+
+    local kind = response.kind and _grab_kind_keys[response.kind]
+    if not kind or not response.window_id then
+        return nil
+    end
+
+    return { kind = kind, window = window_handle.new(response.window_id) }
 end
 
 ---@class WindowRuleCondition
@@ -132,6 +331,7 @@ end
 ---@field classes string[]?
 ---@field titles string[]?
 ---@field tags TagHandle[]?
+---@field content_types ContentType[]?
 
 ---@class WindowRule
 ---@field output OutputHandle?
@@ -142,6 +342,13 @@ end
 ---@field y integer?
 ---@field width integer?
 ---@field height integer?
+---@field focus_steal_exempt boolean? Always allow the window to activate itself, bypassing focus stealing prevention. Focusing a window through the API always works regardless.
+---@field disable_keybinds boolean? Disable keybinds while the window is focused, forwarding the keys to it instead. The kill and reload keybinds and keybinds set with `Input.keybind_allow_when_disabled` still work.
+---@field focus_on_open boolean? Whether the window gets keyboard focus when it opens. This overrides `Window.set_focus_new_windows`.
+---@field skip_taskbar boolean? Leave the window out of taskbars
+---@field skip_cycle boolean? Skip the window when cycling through windows with `Window.focus_previous` or `Window.jump_to`
+---@field once boolean? Only apply this rule to the next window that opens and matches, then remove it. Use this to place a window you're about to spawn as it opens.
+---@field unfullscreen_on_focus_loss boolean? Whether the window leaves fullscreen when it loses keyboard focus. This overrides `Window.set_unfullscreen_on_focus_loss`.
 
 ---@enum (key) FullscreenOrMaximized
 local _fullscreen_or_maximized = {
@@ -156,6 +363,21 @@ local _fullscreen_or_maximized_keys = {
     [3] = "maximized",
 }
 
+---@enum (key) ContentType
+local _content_type = {
+    none = 1,
+    photo = 2,
+    video = 3,
+    game = 4,
+}
+
+local _content_type_keys = {
+    [1] = "none",
+    [2] = "photo",
+    [3] = "video",
+    [4] = "game",
+}
+
 ---@param rule WindowRule
 local function process_window_rule(rule)
     if rule.output then
@@ -186,6 +408,15 @@ local function process_window_rule_cond(cond)
         cond.tags = ids
     end
 
+    if cond.content_types then
+        local content_types = {}
+        for _, content_type in ipairs(cond.content_types) do
+            table.insert(content_types, _content_type[content_type])
+        end
+        ---@diagnostic disable-next-line: assign-type-mismatch
+        cond.content_types = content_types
+    end
+
     if cond.all then
         for _, con in ipairs(cond.all) do
             process_window_rule_cond(con)
@@ -199,6 +430,67 @@ local function process_window_rule_cond(cond)
     end
 end
 
+---The inverse of `process_window_rule`.
+---@param rule pinnacle.window.v0alpha1.WindowRule
+---@return WindowRule
+local function unprocess_window_rule(rule)
+    ---@type WindowRule
+    local ret = {
+        output = rule.output and require("pinnacle.output").handle.new(rule.output),
+        tags = rule.tags and require("pinnacle.tag").handle.new_from_table(rule.tags),
+        floating = rule.floating,
+        fullscreen_or_maximized = _fullscreen_or_maximized_keys[rule.fullscreen_or_maximized],
+        x = rule.x,
+        y = rule.y,
+        width = rule.width,
+        height = rule.height,
+        focus_steal_exempt = rule.focus_steal_exempt,
+        disable_keybinds = rule.disable_keybinds,
+        focus_on_open = rule.focus_on_open,
+        skip_taskbar = rule.skip_taskbar,
+        skip_cycle = rule.skip_cycle,
+        once = rule.once,
+        unfullscreen_on_focus_loss = rule.unfullscreen_on_focus_loss,
+    }
+
+    return ret
+end
+
+---The inverse of `process_window_rule_cond`.
+---@param cond pinnacle.window.v0alpha1.WindowRuleCondition
+---@return WindowRuleCondition
+local function unprocess_window_rule_cond(cond)
+    ---@type WindowRuleCondition
+    local ret = {
+        classes = cond.classes,
+        titles = cond.titles,
+        tags = cond.tags and require("pinnacle.tag").handle.new_from_table(cond.tags),
+    }
+
+    if cond.content_types then
+        ret.content_types = {}
+        for _, content_type in ipairs(cond.content_types) do
+            table.insert(ret.content_types, _content_type_keys[content_type])
+        end
+    end
+
+    if cond.all then
+        ret.all = {}
+        for _, con in ipairs(cond.all) do
+            table.insert(ret.all, unprocess_window_rule_cond(con))
+        end
+    end
+
+    if cond.any then
+        ret.any = {}
+        for _, con in ipairs(cond.any) do
+            table.insert(ret.any, unprocess_window_rule_cond(con))
+        end
+    end
+
+    return ret
+end
+
 ---Add a window rule.
 ---
 ---A window rule defines what properties a window will spawn with given certain conditions.
@@ -288,15 +580,178 @@ end
 ---```
 ---
 ---@param rule { cond: WindowRuleCondition, rule: WindowRule } The condition and rule
+---@return integer | nil rule_id The id of the added rule, or `nil` if the rule was rejected
 function window.add_window_rule(rule)
     process_window_rule(rule.rule)
 
     process_window_rule_cond(rule.cond)
 
-    client.unary_request(window_service.AddWindowRule, {
+    local response = client.unary_request(window_service.AddWindowRule, {
         cond = rule.cond,
         rule = rule.rule,
     })
+
+    return response.rule_id
+end
+
+---Get all added window rules along with their ids, in the order they apply.
+---
+---### Example
+---```lua
+---for _, rule in ipairs(Window.get_window_rules()) do
+---    print(rule.id, rule.cond.classes and rule.cond.classes[1])
+---end
+---```
+---@return { id: integer, cond: WindowRuleCondition, rule: WindowRule }[]
+function window.get_window_rules()
+    local response = client.unary_request(window_service.GetWindowRules, {})
+
+    ---@type { id: integer, cond: WindowRuleCondition, rule: WindowRule }[]
+    local rules = {}
+
+    for _, entry in ipairs(response.rules or {}) do
+        table.insert(rules, {
+            id = entry.rule_id,
+            cond = unprocess_window_rule_cond(entry.cond or {}),
+            rule = unprocess_window_rule(entry.rule or {}),
+        })
+    end
+
+    return rules
+end
+
+---Remove a previously added window rule.
+---
+---Windows that the rule already applied to are not affected.
+---
+---### Example
+---```lua
+---local rule_id = Window.add_window_rule({
+---    cond = { classes = { "firefox" } },
+---    rule = { floating = true },
+---})
+---
+---if rule_id then
+---    Window.remove_window_rule(rule_id)
+---end
+---```
+---@param rule_id integer The id returned from `add_window_rule`
+function window.remove_window_rule(rule_id)
+    client.unary_request(window_service.RemoveWindowRule, { rule_id = rule_id })
+end
+
+---Apply window rules to all open windows again, as if they just opened.
+---
+---Window rules normally only apply when a window opens, so this lets you see the effects
+---of added rules without reopening windows. Applying rules more than once has the same
+---effect as applying them once.
+---
+---### Example
+---```lua
+---Window.add_window_rule({
+---    cond = { classes = { "firefox" } },
+---    rule = { floating = true },
+---})
+---
+----- Float Firefox windows that are already open
+---Window.reapply_window_rules()
+---```
+function window.reapply_window_rules()
+    client.unary_request(window_service.ReapplyWindowRules, {})
+end
+
+---Set whether new windows get keyboard focus when they open.
+---
+---This is on by default. When off, new windows open without taking focus from the
+---currently focused window. Use the `focus_on_open` window rule to override this for
+---specific windows.
+---
+---### Example
+---```lua
+--- -- Don't let new windows steal focus
+---Window.set_focus_new_windows(false)
+---```
+---
+---@param focus boolean
+function window.set_focus_new_windows(focus)
+    client.unary_request(window_service.SetFocusNewWindows, { focus = focus })
+end
+
+---Set whether fullscreen windows leave fullscreen when they lose keyboard focus.
+---
+---This is off by default, so fullscreen windows stay fullscreen when you focus something
+---else, like a window on another output. Use the `unfullscreen_on_focus_loss` window rule
+---to override this for specific windows.
+---
+---### Example
+---```lua
+--- -- Keep windows fullscreen, except for videos in mpv
+---Window.set_unfullscreen_on_focus_loss(false)
+---Window.add_window_rule({
+---    cond = { classes = { "mpv" } },
+---    rule = { unfullscreen_on_focus_loss = true },
+---})
+---```
+---
+---@param unfullscreen boolean
+function window.set_unfullscreen_on_focus_loss(unfullscreen)
+    client.unary_request(
+        window_service.SetUnfullscreenOnFocusLoss,
+        { unfullscreen = unfullscreen }
+    )
+end
+
+---Set a command to spawn when `WindowHandle.close` closes the last open window.
+---
+---This leaves a way back when a stray keybind closes everything.
+---The command is spawned once the window is actually gone, so nothing is spawned
+---if it ignores the close request or another window opens first.
+---Nothing is spawned by default; pass an empty table to go back to that.
+---
+---### Example
+---```lua
+--- -- Always have a terminal to come back to
+---Window.set_last_window_fallback({ "alacritty" })
+---
+--- -- Spawn nothing
+---Window.set_last_window_fallback({})
+---```
+---
+---@param command string[]
+function window.set_last_window_fallback(command)
+    client.unary_request(window_service.SetLastWindowFallback, { command = command })
+end
+
+---Make the next window that opens land on the focused tags, even if a window rule
+---would send it to another output or tag.
+---
+---This only applies to one window. Other window rules still apply to it.
+---
+---### Example
+---```lua
+--- -- Open another browser window here instead of on its usual tag
+---Window.open_next_on_focused_tag()
+---Process.spawn("firefox")
+---```
+function window.open_next_on_focused_tag()
+    client.unary_request(window_service.OpenNextOnFocusedTag, {})
+end
+
+---Set how long windows stay urgent without being focused.
+---
+---Windows that want attention normally stay urgent until they're focused.
+---With a timeout, the urgent flag clears on its own once it elapses.
+---Pass nothing or 0 to keep windows urgent until focused.
+---
+---### Example
+---```lua
+--- -- Stop showing windows as urgent after a minute
+---Window.set_urgency_timeout(60000)
+---```
+---
+---@param timeout integer? The timeout in milliseconds
+function window.set_urgency_timeout(timeout)
+    client.unary_request(window_service.SetUrgencyTimeout, { timeout_ms = timeout })
 end
 
 local signal_name_to_SignalName = {
@@ -381,11 +836,41 @@ end
 ---    focused:set_geometry({ x = 50, y = 300 })      -- Move this window to (50, 300)
 ---    focused:set_geometry({ y = 0, height = 1080 }) -- Move this window to y = 0 and make its height 1080 pixels
 ---    focused:set_geometry({})                       -- Do nothing useful
+---
+---    -- Move this window to (50, 300) from the top left of the focused output
+---    focused:set_geometry({ x = 50, y = 300 }, require("pinnacle.output").get_focused())
+---end
+---```
+---@param geo { x: integer?, y: integer?, width: integer?, height: integer? } The new location and/or size
+---@param relative_to OutputHandle? An output that `x` and `y` are relative to. If nil, they're in the global space.
+function WindowHandle:set_geometry(geo, relative_to)
+    client.unary_request(window_service.SetGeometry, {
+        window_id = self.id,
+        geometry = geo,
+        relative_to_output = relative_to and relative_to.name,
+    })
+end
+
+---Float this window and set its geometry in one step.
+---
+---This works like `WindowHandle.set_geometry`, but tiled windows are floated and moved
+---straight to the new geometry instead of being laid out at a different size first.
+---
+---### Example
+---```lua
+---local focused = Window.get_focused()
+---if focused then
+---    focused:set_floating_geometry({ x = 50, y = 300, width = 800, height = 600 })
 ---end
 ---```
 ---@param geo { x: integer?, y: integer?, width: integer?, height: integer? } The new location and/or size
-function WindowHandle:set_geometry(geo)
-    client.unary_request(window_service.SetGeometry, { window_id = self.id, geometry = geo })
+---@param relative_to OutputHandle? An output that `x` and `y` are relative to. If nil, they're in the global space.
+function WindowHandle:set_floating_geometry(geo, relative_to)
+    client.unary_request(window_service.SetFloatingGeometry, {
+        window_id = self.id,
+        geometry = geo,
+        relative_to_output = relative_to and relative_to.name,
+    })
 end
 
 ---Set this window to fullscreen or not.
@@ -458,91 +943,338 @@ function WindowHandle:toggle_maximized()
     )
 end
 
----Set this window to floating or not.
+---Set this window to minimized or not.
+---
+---Minimized windows are hidden and left out of layouts and focus until they're restored.
+---They're still returned by `Window.get_all`.
 ---
 ---### Example
 ---```lua
 ---local focused = Window.get_focused()
 ---if focused then
----    focused:set_floating(true)
----    focused:set_floating(false)
+---    focused:set_minimized(true)
 ---end
 ---```
 ---
----@param floating boolean
-function WindowHandle:set_floating(floating)
+---@param minimized boolean
+function WindowHandle:set_minimized(minimized)
     client.unary_request(
-        window_service.SetFloating,
-        { window_id = self.id, set_or_toggle = set_or_toggle[floating] }
+        window_service.SetMinimized,
+        { window_id = self.id, set_or_toggle = set_or_toggle[minimized] }
     )
 end
 
----Toggle this window to and from floating.
+---Toggle this window to and from minimized.
 ---
 ---### Example
 ---```lua
----local focused = Window.get_focused()
----if focused then
----    focused:toggle_floating()
+--- -- Restore the first minimized window
+---for _, win in ipairs(Window.get_all()) do
+---    if win:minimized() then
+---        win:toggle_minimized()
+---        break
+---    end
 ---end
 ---```
-function WindowHandle:toggle_floating()
+function WindowHandle:toggle_minimized()
     client.unary_request(
-        window_service.SetFloating,
+        window_service.SetMinimized,
         { window_id = self.id, set_or_toggle = set_or_toggle.TOGGLE }
     )
 end
 
----Focus or unfocus this window.
+---Set this window to floating or not.
 ---
 ---### Example
 ---```lua
 ---local focused = Window.get_focused()
 ---if focused then
----    focused:set_focused(false)
+---    focused:set_floating(true)
+---    focused:set_floating(false)
 ---end
 ---```
 ---
----@param focused boolean
-function WindowHandle:set_focused(focused)
+---@param floating boolean
+function WindowHandle:set_floating(floating)
     client.unary_request(
-        window_service.SetFocused,
-        { window_id = self.id, set_or_toggle = set_or_toggle[focused] }
+        window_service.SetFloating,
+        { window_id = self.id, set_or_toggle = set_or_toggle[floating] }
     )
 end
 
----Toggle this window to and from focused.
+---Toggle this window to and from floating.
 ---
 ---### Example
 ---```lua
 ---local focused = Window.get_focused()
 ---if focused then
----    focused:toggle_focused()
+---    focused:toggle_floating()
 ---end
 ---```
-function WindowHandle:toggle_focused()
+function WindowHandle:toggle_floating()
+    client.unary_request(
+        window_service.SetFloating,
+        { window_id = self.id, set_or_toggle = set_or_toggle.TOGGLE }
+    )
+end
+
+---Float this window at a geometry given as fractions of its output's usable area.
+---
+---`x` and `y` are where the window's top left corner is, and `width` and `height` are its
+---size, all from 0.0 to 1.0. The geometry is resolved again when the output's mode or
+---scale changes, so it adapts to different monitors. Setting an absolute geometry or
+---moving or resizing the window with the pointer replaces it. A geometry that reaches
+---past the edges of the usable area is moved or shrunk to fit inside it.
+---
+---### Example
+---```lua
+----- Float a dropdown terminal at the top center of the output,
+----- 80% of the output wide and 50% tall
+---Window.get_focused():set_relative_geometry({ x = 0.1, y = 0.0, width = 0.8, height = 0.5 })
+---```
+---
+---@param geo { x: number, y: number, width: number, height: number }
+function WindowHandle:set_relative_geometry(geo)
+    client.unary_request(window_service.SetRelativeGeometry, {
+        window_id = self.id,
+        x = geo.x,
+        y = geo.y,
+        width = geo.width,
+        height = geo.height,
+    })
+end
+
+---@alias SnapRegion
+---| "left_half"
+---| "right_half"
+---| "top_half"
+---| "bottom_half"
+---| "top_left_quarter"
+---| "top_right_quarter"
+---| "bottom_left_quarter"
+---| "bottom_right_quarter"
+
+local snap_region_values = {
+    left_half = 1,
+    right_half = 2,
+    top_half = 3,
+    bottom_half = 4,
+    top_left_quarter = 5,
+    top_right_quarter = 6,
+    bottom_left_quarter = 7,
+    bottom_right_quarter = 8,
+}
+
+---Snap this window to a half or quarter of its output's usable area.
+---
+---This floats the window at the region's relative geometry, like
+---`WindowHandle:set_relative_geometry`. Snapping a window to a region again cycles it
+---through related regions: halves go through the quarters inside them, and
+---quarters go clockwise.
+---
+---### Example
+---```lua
+----- Snap the focused window to the left half of the output.
+----- Doing it again moves it to the top left quarter, then the bottom left one.
+---Window.get_focused():snap("left_half")
+---```
+---
+---@param region SnapRegion
+function WindowHandle:snap(region)
+    client.unary_request(window_service.SnapWindow, {
+        window_id = self.id,
+        region = snap_region_values[region],
+    })
+end
+
+---@alias Placement
+---| "tiled" Tile the window after the output's other windows
+---| "floating_centered" Float the window centered in the output's usable area
+---| "floating_geometry" Float the window at the given geometry, relative to the output
+---| "fullscreen" Make the window fullscreen
+
+local placement_values = {
+    tiled = 1,
+    floating_centered = 2,
+    floating_geometry = 3,
+    fullscreen = 4,
+}
+
+---Move this window to an output and place it there.
+---
+---This sets the window's tags to the output's active tags, applies the placement,
+---and lays out both the window's old and new outputs, all in one step.
+---
+---`geo` is only used with `"floating_geometry"`. Its location is relative to the top left
+---corner of the output.
+---
+---### Example
+---```lua
+---local focused = Window.get_focused()
+---if focused then
+---    -- Tile the focused window at the end of the "DP-2" output's windows
+---    focused:place(require("pinnacle.output").get_by_name("DP-2"), "tiled")
+---
+---    -- Float it in the top left corner of the focused output
+---    focused:place(
+---        require("pinnacle.output").get_focused(),
+---        "floating_geometry",
+---        { x = 0, y = 0, width = 800, height = 600 }
+---    )
+---end
+---```
+---
+---@param output OutputHandle
+---@param placement Placement
+---@param geo { x: integer?, y: integer?, width: integer?, height: integer? }?
+function WindowHandle:place(output, placement, geo)
+    client.unary_request(window_service.PlaceWindow, {
+        window_id = self.id,
+        output_name = output.name,
+        placement = placement_values[placement],
+        geometry = geo,
+    })
+end
+
+---Focus or unfocus this window.
+---
+---Focusing a window doesn't raise it. To bring it to the front as well,
+---call `WindowHandle:raise`.
+---
+---### Example
+---```lua
+---local focused = Window.get_focused()
+---if focused then
+---    focused:set_focused(false)
+---end
+---```
+---
+---@param focused boolean
+function WindowHandle:set_focused(focused)
+    client.unary_request(
+        window_service.SetFocused,
+        { window_id = self.id, set_or_toggle = set_or_toggle[focused] }
+    )
+end
+
+---Give this window keyboard focus without raising it.
+---
+---The stacking order is left untouched, so you can type into a window that is
+---below other windows. This is the same as `set_focused(true)`, spelled out.
+---
+---### Example
+---```lua
+---for _, win in ipairs(Window.get_all()) do
+---    if win:class() == "Alacritty" then
+---        win:focus_no_raise()
+---    end
+---end
+---```
+function WindowHandle:focus_no_raise()
+    client.unary_request(
+        window_service.SetFocused,
+        { window_id = self.id, set_or_toggle = set_or_toggle.SET, raise = false }
+    )
+end
+
+---Toggle this window to and from focused.
+---
+---### Example
+---```lua
+---local focused = Window.get_focused()
+---if focused then
+---    focused:toggle_focused()
+---end
+---```
+function WindowHandle:toggle_focused()
     client.unary_request(
         window_service.SetFocused,
         { window_id = self.id, set_or_toggle = set_or_toggle.TOGGLE }
     )
 end
 
+---Set whether this window is drawn as activated without changing keyboard focus.
+---
+---Only one window is activated at a time, so activating this window deactivates the rest.
+---Keyboard focus leaves this window's activated state alone until a window gains keyboard focus.
+---
+---### Example
+---```lua
+--- -- Draw the focused window as inactive while it keeps keyboard focus
+---local focused = Window.get_focused()
+---if focused then
+---    focused:set_activated(false)
+---end
+---```
+---
+---@param activated boolean
+function WindowHandle:set_activated(activated)
+    client.unary_request(
+        window_service.SetActivated,
+        { window_id = self.id, set_or_toggle = set_or_toggle[activated] }
+    )
+end
+
+---Toggle whether this window is drawn as activated without changing keyboard focus.
+---
+---### Example
+---```lua
+---local focused = Window.get_focused()
+---if focused then
+---    focused:toggle_activated()
+---end
+---```
+function WindowHandle:toggle_activated()
+    client.unary_request(
+        window_service.SetActivated,
+        { window_id = self.id, set_or_toggle = set_or_toggle.TOGGLE }
+    )
+end
+
 ---Move this window to the specified tag.
 ---
 ---This will remove all tags from this window and tag it with `tag`.
 ---
+---If `follow` is true, the tag will be switched to and this window focused
+---after it is moved.
+---
 ---### Example
 ---```lua
 --- -- Assume the focused output has the tag "Tag"
 ---local focused = Window.get_focused()
 ---if focused then
 ---    focused:move_to_tag(Tag.get("Tag"))
+---    -- Or, to follow the window to its new tag:
+---    focused:move_to_tag(Tag.get("Tag"), true)
 ---end
 ---```
 ---
 ---@param tag TagHandle The tag to move this window to
-function WindowHandle:move_to_tag(tag)
-    client.unary_request(window_service.MoveToTag, { window_id = self.id, tag_id = tag.id })
+---@param follow boolean? Whether to switch to the tag and focus this window
+function WindowHandle:move_to_tag(tag, follow)
+    client.unary_request(
+        window_service.MoveToTag,
+        { window_id = self.id, tag_id = tag.id, follow = follow }
+    )
+end
+
+---Add the specified tag to this window, keeping the tags it already has.
+---
+---Unlike `WindowHandle:move_to_tag`, this window stays where it is and
+---also shows up on `tag`.
+---
+---### Example
+---```lua
+--- -- Show the focused window on tag "Tag" as well
+---local focused = Window.get_focused()
+---if focused then
+---    focused:copy_to_tag(Tag.get("Tag"))
+---end
+---```
+---
+---@param tag TagHandle The tag to add to this window
+function WindowHandle:copy_to_tag(tag)
+    client.unary_request(window_service.CopyToTag, { window_id = self.id, tag_id = tag.id })
 end
 
 ---Tag or untag the given tag on this window.
@@ -595,6 +1327,25 @@ function WindowHandle:toggle_tag(tag)
     )
 end
 
+---Move this window between the currently and previously active tags on its output.
+---
+---If this window is on a currently active tag, it is moved to the first previously active tag
+---that isn't currently active. Otherwise, it is moved to the first currently active tag.
+---This does nothing if no tags were previously active.
+---
+---### Example
+---```lua
+--- -- Stash the focused window on the last viewed tag, then bring it back
+---local focused = Window.get_focused()
+---if focused then
+---    focused:toggle_recent_tag()
+---    focused:toggle_recent_tag()
+---end
+---```
+function WindowHandle:toggle_recent_tag()
+    client.unary_request(window_service.ToggleRecentTag, { window_id = self.id })
+end
+
 ---Raise a window.
 ---
 ---This will raise a window all the way to the top of the z-stack.
@@ -610,6 +1361,301 @@ function WindowHandle:raise()
     client.unary_request(window_service.Raise, { window_id = self.id })
 end
 
+---Lower a window.
+---
+---This will lower a window all the way to the bottom of the z-stack.
+---It stays above background and bottom layer surfaces like wallpapers.
+---
+---### Example
+---```lua
+---local focused = Window.get_focused()
+---if focused then
+---    focused:lower()
+---end
+---```
+function WindowHandle:lower()
+    client.unary_request(window_service.Lower, { window_id = self.id })
+end
+
+---Give this window a label to find it by later with `Window.get_by_label`.
+---
+---Labels are unique. If another window already has `label`, this does nothing.
+---The label is forgotten when the window closes.
+---
+---Passing in `nil` removes the window's label.
+---
+---### Example
+---```lua
+---Window.get_focused():set_label("main-editor")
+---```
+---
+---@param label string?
+function WindowHandle:set_label(label)
+    client.unary_request(window_service.SetLabel, { window_id = self.id, label = label })
+end
+
+---Render this window at `scale` on top of its output's scale.
+---
+---The window is scaled around the top left corner of its geometry and pointer input
+---is mapped to match. Its size in the layout stays the same, so scaled up windows
+---will overlap what's next to them. This is useful for zooming in on a single window,
+---like a video or a presentation.
+---
+---`scale` is clamped to between 0.25 and 4.0. 1.0 renders the window normally.
+---
+---### Example
+---```lua
+--- -- Zoom the focused window in
+---Window.get_focused():set_scale(1.5)
+---```
+---
+---@param scale number
+function WindowHandle:set_scale(scale)
+    client.unary_request(window_service.SetScale, { window_id = self.id, scale = scale })
+end
+
+---Set whether this window is left out of taskbars.
+---
+---This is useful for scratchpads, overlays, and pickers that shouldn't
+---show up next to regular windows.
+---
+---### Example
+---```lua
+--- -- Hide the focused window from taskbars
+---Window.get_focused():set_skip_taskbar(true)
+---```
+---
+---@param skip boolean
+function WindowHandle:set_skip_taskbar(skip)
+    client.unary_request(
+        window_service.SetSkipTaskbar,
+        { window_id = self.id, set_or_toggle = set_or_toggle[skip] }
+    )
+end
+
+---Toggle whether this window is left out of taskbars.
+---
+---### Example
+---```lua
+---Window.get_focused():toggle_skip_taskbar()
+---```
+function WindowHandle:toggle_skip_taskbar()
+    client.unary_request(
+        window_service.SetSkipTaskbar,
+        { window_id = self.id, set_or_toggle = set_or_toggle.TOGGLE }
+    )
+end
+
+---Set whether this window is skipped when cycling through windows.
+---
+---Skipped windows aren't focused by `Window.focus_previous` or when `Window.jump_to`
+---cycles, but can still be focused directly.
+---
+---### Example
+---```lua
+--- -- Keep the focused window out of alt-tab
+---Window.get_focused():set_skip_cycle(true)
+---```
+---
+---@param skip boolean
+function WindowHandle:set_skip_cycle(skip)
+    client.unary_request(
+        window_service.SetSkipCycle,
+        { window_id = self.id, set_or_toggle = set_or_toggle[skip] }
+    )
+end
+
+---Toggle whether this window is skipped when cycling through windows.
+---
+---### Example
+---```lua
+---Window.get_focused():toggle_skip_cycle()
+---```
+function WindowHandle:toggle_skip_cycle()
+    client.unary_request(
+        window_service.SetSkipCycle,
+        { window_id = self.id, set_or_toggle = set_or_toggle.TOGGLE }
+    )
+end
+
+---Set whether this window's floating geometry is pinned in place.
+---
+---Pinned floating windows can't be moved or resized with the pointer or by their client,
+---and they keep their position relative to their output when the output changes.
+---`WindowHandle.set_geometry` still works on them.
+---
+---### Example
+---```lua
+--- -- Keep a clock in the top right corner
+---local clock = Window.get_focused()
+---clock:set_floating(true)
+---clock:set_geometry({ x = 1700, y = 20, width = 200, height = 80 })
+---clock:set_geometry_pinned(true)
+---```
+---
+---@param pinned boolean
+function WindowHandle:set_geometry_pinned(pinned)
+    client.unary_request(
+        window_service.SetGeometryPinned,
+        { window_id = self.id, set_or_toggle = set_or_toggle[pinned] }
+    )
+end
+
+---Toggle whether this window's floating geometry is pinned in place.
+---
+---### Example
+---```lua
+---Window.get_focused():toggle_geometry_pinned()
+---```
+function WindowHandle:toggle_geometry_pinned()
+    client.unary_request(
+        window_service.SetGeometryPinned,
+        { window_id = self.id, set_or_toggle = set_or_toggle.TOGGLE }
+    )
+end
+
+---Set whether this window keeps gaps around it when maximized.
+---
+---The window is inset by the outer and inner gaps of its output's first focused tag,
+---lining it up with the edges of tiled windows. By default, maximized windows fill
+---the usable area.
+---
+---### Example
+---```lua
+---local focused = Window.get_focused()
+---focused:set_maximize_gaps(true)
+---focused:set_maximized(true)
+---```
+---
+---@param gaps boolean
+function WindowHandle:set_maximize_gaps(gaps)
+    client.unary_request(
+        window_service.SetMaximizeGaps,
+        { window_id = self.id, set_or_toggle = set_or_toggle[gaps] }
+    )
+end
+
+---Toggle whether this window keeps gaps around it when maximized.
+---
+---### Example
+---```lua
+---Window.get_focused():toggle_maximize_gaps()
+---```
+function WindowHandle:toggle_maximize_gaps()
+    client.unary_request(
+        window_service.SetMaximizeGaps,
+        { window_id = self.id, set_or_toggle = set_or_toggle.TOGGLE }
+    )
+end
+
+---Set whether this window is told which of its edges are tiled.
+---
+---Tiled edges are the ones up against the edge of the usable area or another tiled window.
+---When this is off, the window is told none of its edges are tiled, which makes
+---clients that drop their shadows on tiled edges keep them. This is on by default.
+---
+---### Example
+---```lua
+---Window.get_focused():set_tiled_edges_enabled(false)
+---```
+---
+---@param enabled boolean
+function WindowHandle:set_tiled_edges_enabled(enabled)
+    client.unary_request(
+        window_service.SetTiledEdgesEnabled,
+        { window_id = self.id, set_or_toggle = set_or_toggle[enabled] }
+    )
+end
+
+---Toggle whether this window is told which of its edges are tiled.
+---
+---### Example
+---```lua
+---Window.get_focused():toggle_tiled_edges_enabled()
+---```
+function WindowHandle:toggle_tiled_edges_enabled()
+    client.unary_request(
+        window_service.SetTiledEdgesEnabled,
+        { window_id = self.id, set_or_toggle = set_or_toggle.TOGGLE }
+    )
+end
+
+---@class WindowCapture
+---@field width integer The width of the capture in physical pixels
+---@field height integer The height of the capture in physical pixels
+---@field pixels string The captured pixels in RGBA8888 format
+
+---Capture the contents of this window.
+---
+---The window is rendered on its own, so this also works for windows on inactive tags.
+---This is useful for things like thumbnails and overviews.
+---
+---If `scale` is not specified, the window will be captured at the scale of its output.
+---
+---### Example
+---```lua
+---local focused = Window.get_focused()
+---if focused then
+---    local capture = focused:capture(0.5)
+---end
+---```
+---@param scale number? The scale to capture the window at
+---@return WindowCapture | nil capture The capture, or `nil` if the window couldn't be captured
+function WindowHandle:capture(scale)
+    local response =
+        client.unary_request(window_service.Capture, { window_id = self.id, scale = scale })
+
+    if not response.pixels then
+        return nil
+    end
+
+    ---@type WindowCapture
+    local capture = {
+        width = response.width,
+        height = response.height,
+        pixels = response.pixels,
+    }
+
+    return capture
+end
+
+---@class WindowRuleConditionEvaluation
+---@field matched boolean Whether the condition as a whole matched
+---@field any WindowRuleConditionEvaluation[] The evaluations of the conditions in `any`
+---@field all WindowRuleConditionEvaluation[] The evaluations of the conditions in `all`
+---@field classes boolean? Whether `classes` matched, or `nil` if it wasn't specified
+---@field titles boolean? Whether `titles` matched, or `nil` if it wasn't specified
+---@field tags boolean? Whether `tags` matched, or `nil` if it wasn't specified
+---@field content_types boolean? Whether `content_types` matched, or `nil` if it wasn't specified
+
+---Check which parts of a window rule condition match this window.
+---
+---Nothing is applied to the window; this is for debugging why a rule does or doesn't apply.
+---
+---### Example
+---```lua
+---local focused = Window.get_focused()
+---if focused then
+---    local eval = focused:evaluate_rule_condition({ classes = { "firefox" } })
+---    print(eval.matched, eval.classes)
+---end
+---```
+---@param cond WindowRuleCondition
+---@return WindowRuleConditionEvaluation | nil evaluation The evaluation, or `nil` if the window doesn't exist
+function WindowHandle:evaluate_rule_condition(cond)
+    process_window_rule_cond(cond)
+
+    local response = client.unary_request(
+        window_service.EvaluateWindowRuleCondition,
+        { window_id = self.id, cond = cond }
+    )
+
+    ---@type WindowRuleConditionEvaluation?
+    local evaluation = response.evaluation
+
+    return evaluation
+end
+
 ---@class WindowProperties
 ---@field geometry { x: integer?, y: integer?, width: integer?, height: integer? }? The location and size of the window
 ---@field class string? The window's class
@@ -618,16 +1664,37 @@ end
 ---@field floating boolean? Whether or not the window is floating
 ---@field fullscreen_or_maximized FullscreenOrMaximized? Whether the window is fullscreen, maximized, or neither
 ---@field tags TagHandle[]? The tags the window has
+---@field urgent boolean? Whether or not the window wants attention
+---@field activated boolean? Whether the window is drawn as activated, which is usually the same as having keyboard focus
+---@field label string? The label set with `WindowHandle.set_label`
+---@field physical_geometry { x: integer?, y: integer?, width: integer?, height: integer? }? The location and size of the window in physical pixels of the output it's on, relative to the top-left corner of that output
+---@field content_type ContentType? The kind of content the window displays. X11 windows don't have one.
+---@field last_focused_ms integer? When the window last gained keyboard focus, in milliseconds on the compositor's monotonic clock. 0 if it was never focused.
+---@field skip_taskbar boolean? Whether the window is left out of taskbars
+---@field skip_cycle boolean? Whether the window is skipped when cycling through windows
+---@field geometry_pinned boolean? Whether the window's floating geometry is pinned in place
+---@field is_x11 boolean? Whether the window is an X11 window running through XWayland
+---@field tiled_edges { top: boolean?, bottom: boolean?, left: boolean?, right: boolean? }? Which edges the window is told are tiled
+---@field minimized boolean? Whether the window is minimized
 
 ---Get all the properties of this window.
 ---
+---If `relative_to` is given, the location in `geometry` is relative to the top left corner
+---of that output, and `geometry` is nil if the output doesn't exist.
+---
+---@param relative_to OutputHandle?
 ---@return WindowProperties
-function WindowHandle:props()
-    local response = client.unary_request(window_service.GetProperties, { window_id = self.id })
+function WindowHandle:props(relative_to)
+    local response = client.unary_request(window_service.GetProperties, {
+        window_id = self.id,
+        relative_to_output = relative_to and relative_to.name,
+    })
 
     response.fullscreen_or_maximized =
         _fullscreen_or_maximized_keys[response.fullscreen_or_maximized]
 
+    response.content_type = _content_type_keys[response.content_type]
+
     response.tags = response.tag_ids
         ---@diagnostic disable-next-line: invisible
         and require("pinnacle.tag").handle.new_from_table(response.tag_ids)
@@ -636,13 +1703,63 @@ function WindowHandle:props()
     return response
 end
 
+---@class SurfaceInfo
+---@field wl_surface_version integer The version of `wl_surface` the client bound
+---@field buffer_scale integer The buffer scale from `wl_surface.set_buffer_scale`
+---@field buffer_width integer? The width of the last committed buffer in buffer pixels
+---@field buffer_height integer? The height of the last committed buffer in buffer pixels
+---@field uses_viewporter boolean Whether the surface has a source or destination set through `wp_viewporter`
+---@field uses_fractional_scale boolean Whether the client asked for `wp_fractional_scale_v1` updates on the surface
+---@field preferred_scale number? The fractional scale last sent to the surface
+
+---Get how this window's client scales its main surface.
+---
+---This is useful for figuring out why a window looks blurry or wrongly sized,
+---like when an app ignores the fractional scale it's sent.
+---
+---### Example
+---```lua
+---local info = Window.get_focused():surface_info()
+---if info and not info.uses_fractional_scale then
+---    print("this app only scales by integers")
+---end
+---```
+---
+---@return SurfaceInfo | nil info The surface info, or `nil` if the window doesn't exist or has no surface
+function WindowHandle:surface_info()
+    ---@type pinnacle.window.v0alpha1.GetSurfaceInfoResponse
+    local response = client.unary_request(window_service.GetSurfaceInfo, { window_id = self.id })
+
+    if not response.wl_surface_version then
+        return nil
+    end
+
+    ---@type SurfaceInfo
+    return {
+        wl_surface_version = response.wl_surface_version,
+        buffer_scale = response.buffer_scale or 1,
+        buffer_width = response.buffer_width,
+        buffer_height = response.buffer_height,
+        uses_viewporter = response.uses_viewporter or false,
+        uses_fractional_scale = response.uses_fractional_scale or false,
+        preferred_scale = response.preferred_scale,
+    }
+end
+
 ---Get this window's location and size.
 ---
----Shorthand for `handle:props().geometry`.
+---Shorthand for `handle:props(relative_to).geometry`.
 ---
+---### Example
+---```lua
+---local output = require("pinnacle.output").get_focused()
+---local geo = Window.get_focused():geometry(output) -- The location is relative to `output`
+---```
+---
+---@param relative_to OutputHandle? An output that the location is relative to. If nil, it's in the global space.
 ---@return { x: integer?, y: integer?, width: integer?, height: integer? }?
-function WindowHandle:geometry()
-    return self:props().geometry
+function WindowHandle:geometry(relative_to)
+    return self:props(relative_to).geometry
 end
 
 ---Get this window's class.
@@ -699,6 +1816,130 @@ function WindowHandle:tags()
     return self:props().tags
 end
 
+---Get whether or not this window wants attention.
+---
+---Shorthand for `handle:props().urgent`.
+---
+---@return boolean?
+function WindowHandle:urgent()
+    return self:props().urgent
+end
+
+---Get whether this window is drawn as activated.
+---
+---Shorthand for `handle:props().activated`.
+---
+---@return boolean?
+function WindowHandle:activated()
+    return self:props().activated
+end
+
+---Get this window's label.
+---
+---Shorthand for `handle:props().label`.
+---
+---@return string?
+function WindowHandle:label()
+    return self:props().label
+end
+
+---Get this window's location and size in physical pixels.
+---
+---This is in the pixels of the output the window is on, relative to the top-left
+---corner of that output as displayed. If the window spans multiple outputs,
+---the output its tags are on is used.
+---
+---Shorthand for `handle:props().physical_geometry`.
+---
+---@return { x: integer?, y: integer?, width: integer?, height: integer? }?
+function WindowHandle:physical_geometry()
+    return self:props().physical_geometry
+end
+
+---Get the kind of content this window displays.
+---
+---Shorthand for `handle:props().content_type`.
+---
+---@return ContentType?
+function WindowHandle:content_type()
+    return self:props().content_type
+end
+
+---Get when this window last gained keyboard focus.
+---
+---This is in milliseconds on the compositor's monotonic clock, or 0 if the window
+---was never focused.
+---
+---Shorthand for `handle:props().last_focused_ms`.
+---
+---### Example
+---```lua
+--- -- Most recently used windows first, for an alt-tab switcher
+---local windows = Window.get_all()
+---table.sort(windows, function(a, b)
+---    return (a:last_focused_ms() or 0) > (b:last_focused_ms() or 0)
+---end)
+---```
+---
+---@return integer?
+function WindowHandle:last_focused_ms()
+    return self:props().last_focused_ms
+end
+
+---Get whether this window is left out of taskbars.
+---
+---Shorthand for `handle:props().skip_taskbar`.
+---
+---@return boolean?
+function WindowHandle:skip_taskbar()
+    return self:props().skip_taskbar
+end
+
+---Get whether this window is skipped when cycling through windows.
+---
+---Shorthand for `handle:props().skip_cycle`.
+---
+---@return boolean?
+function WindowHandle:skip_cycle()
+    return self:props().skip_cycle
+end
+
+---Get whether this window's floating geometry is pinned in place.
+---
+---Shorthand for `handle:props().geometry_pinned`.
+---
+---@return boolean?
+function WindowHandle:geometry_pinned()
+    return self:props().geometry_pinned
+end
+
+---Get whether this window is an X11 window running through XWayland.
+---
+---Shorthand for `handle:props().is_x11`.
+---
+---@return boolean?
+function WindowHandle:is_x11()
+    return self:props().is_x11
+end
+
+---Get which edges this window is told are tiled.
+---
+---Shorthand for `handle:props().tiled_edges`.
+---
+---@return { top: boolean?, bottom: boolean?, left: boolean?, right: boolean? }?
+function WindowHandle:tiled_edges()
+    return self:props().tiled_edges
+end
+
+---Get whether this window is minimized.
+---
+---Shorthand for `handle:props().minimized`.
+---
+---@return boolean?
+function WindowHandle:minimized()
+    return self:props().minimized
+end
+
 ---@nodoc
 ---Create a new `WindowHandle` from an id.
 ---@param window_id integer
@@ -8,6 +8,8 @@
 //! methods for setting key- and mousebinds, changing xkeyboard settings, and more.
 //! View the struct's documentation for more information.
 
+use std::{sync::OnceLock, time::Duration};
+
 use futures::{future::BoxFuture, FutureExt, StreamExt};
 use num_enum::TryFromPrimitive;
 use pinnacle_api_defs::pinnacle::input::{
@@ -15,15 +17,18 @@ use pinnacle_api_defs::pinnacle::input::{
     v0alpha1::{
         input_service_client::InputServiceClient,
         set_libinput_setting_request::{CalibrationMatrix, Setting},
-        SetKeybindRequest, SetLibinputSettingRequest, SetMousebindRequest, SetRepeatRateRequest,
-        SetXkbConfigRequest,
+        set_modifier_remaps_request::Remap,
+        GetKeybindsRequest, GetPointerRequest, GetSeatsRequest, SendKeyRequest,
+        SetClickSettingsRequest, SetIdleActionRequest, SetKeybindRequest,
+        SetLibinputSettingRequest, SetModifierRemapsRequest, SetMousebindRequest,
+        SetPointerConfinementRequest, SetRepeatRateRequest, SetXkbConfigRequest,
     },
 };
 use tokio::sync::mpsc::UnboundedSender;
 use tonic::transport::Channel;
 use xkbcommon::xkb::Keysym;
 
-use crate::block_on_tokio;
+use crate::{block_on_tokio, output::OutputHandle, window::WindowHandle, ApiModules};
 
 use self::libinput::LibinputSetting;
 
@@ -62,7 +67,11 @@ pub enum Mod {
     Super,
 }
 
-/// Press or release.
+/// When a mousebind should trigger.
+///
+/// [`Click`][MouseEdge::Click], [`DoubleClick`][MouseEdge::DoubleClick], and
+/// [`Drag`][MouseEdge::Drag] mousebinds prevent both the press and release
+/// of their button from reaching windows.
 #[repr(i32)]
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, TryFromPrimitive)]
 pub enum MouseEdge {
@@ -70,6 +79,16 @@ pub enum MouseEdge {
     Press = 1,
     /// Perform actions on button release
     Release,
+    /// Perform actions when the button is pressed and released without dragging
+    Click,
+    /// Perform actions when the button is clicked twice in quick succession
+    ///
+    /// See [`Input::set_double_click_interval`].
+    DoubleClick,
+    /// Perform actions when the pointer is moved while the button is held
+    ///
+    /// See [`Input::set_drag_threshold`].
+    Drag,
 }
 
 /// A struct that lets you define xkeyboard config options.
@@ -97,6 +116,56 @@ pub struct XkbConfig {
 pub struct Input {
     channel: Channel,
     fut_sender: UnboundedSender<BoxFuture<'static, ()>>,
+    api: OnceLock<ApiModules>,
+}
+
+/// The pointer's location and what is under it.
+///
+/// See [`Input::pointer`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PointerInfo {
+    /// The pointer's location in the global space
+    pub location: Option<(f64, f64)>,
+    /// The output the pointer is on
+    pub output: Option<OutputHandle>,
+    /// The window under the pointer
+    pub window: Option<WindowHandle>,
+    /// The namespace of the layer surface under the pointer
+    pub layer_namespace: Option<String>,
+}
+
+/// A seat and its input capabilities.
+///
+/// See [`Input::seats`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct SeatInfo {
+    /// The seat's name
+    pub name: Option<String>,
+    /// Whether the seat has a keyboard
+    pub keyboard: Option<bool>,
+    /// Whether the seat has a pointer
+    pub pointer: Option<bool>,
+    /// Whether the seat has a touchscreen
+    pub touch: Option<bool>,
+}
+
+/// A keybind that's set and the process that set it.
+///
+/// See [`Input::keybinds`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeybindInfo {
+    /// The modifiers that must be held
+    pub mods: Vec<Mod>,
+    /// The key that triggers the keybind
+    pub key: Keysym,
+    /// The key that must be held for the keybind to trigger, if any
+    pub held_key: Option<Keysym>,
+    /// The pid of the process that set the keybind
+    pub owner_pid: Option<i32>,
+    /// The name of the process that set the keybind, if it's still running
+    pub owner_name: Option<String>,
+    /// Whether the process that set the keybind is still listening for it
+    pub connected: bool,
 }
 
 impl Input {
@@ -107,9 +176,14 @@ impl Input {
         Self {
             channel,
             fut_sender,
+            api: OnceLock::new(),
         }
     }
 
+    pub(crate) fn finish_init(&self, api: ApiModules) {
+        self.api.set(api).unwrap();
+    }
+
     fn create_input_client(&self) -> InputServiceClient<Channel> {
         InputServiceClient::new(self.channel.clone())
     }
@@ -156,6 +230,38 @@ impl Input {
         &self,
         mods: impl IntoIterator<Item = Mod>,
         key: impl Key + Send + 'static,
+        action: impl FnMut() + Send + 'static,
+    ) {
+        self.set_keybind(mods, key, false, action);
+    }
+
+    /// Set a keybind that still triggers while the focused window has keybinds disabled.
+    ///
+    /// Otherwise, this is the same as [`Input::keybind`]. See
+    /// [`WindowRule::disable_keybinds`][crate::window::rules::WindowRule::disable_keybinds].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pinnacle_api::input::Mod;
+    ///
+    /// // Keep `Super + 1` switching tags even while a virtual machine is focused
+    /// input.keybind_allow_when_disabled([Mod::Super], '1', || { /* ... */ });
+    /// ```
+    pub fn keybind_allow_when_disabled(
+        &self,
+        mods: impl IntoIterator<Item = Mod>,
+        key: impl Key + Send + 'static,
+        action: impl FnMut() + Send + 'static,
+    ) {
+        self.set_keybind(mods, key, true, action);
+    }
+
+    fn set_keybind(
+        &self,
+        mods: impl IntoIterator<Item = Mod>,
+        key: impl Key + Send + 'static,
+        allow_when_disabled: bool,
         mut action: impl FnMut() + Send + 'static,
     ) {
         let mut client = self.create_input_client();
@@ -171,6 +277,8 @@ impl Input {
                             key: Some(input::v0alpha1::set_keybind_request::Key::RawCode(
                                 key.into_keysym().raw(),
                             )),
+                            held_key: None,
+                            allow_when_disabled: Some(allow_when_disabled),
                         })
                         .await
                         .unwrap()
@@ -186,6 +294,77 @@ impl Input {
             .unwrap();
     }
 
+    /// Set a keybind that only triggers while another key is held.
+    ///
+    /// This makes a chord where `held` acts like an extra modifier, like holding Space
+    /// and pressing J. Unlike a regular keybind, `mods` and `held` must both already be
+    /// held when `key` is pressed. While `held` could start one of these keybinds, pressing
+    /// it isn't sent to the focused window. If it's released without triggering one,
+    /// it's sent then, so it can still be typed on its own.
+    ///
+    /// `held` and `key` take the same kinds of keys as [`Input::keybind`].
+    /// `held` can't be a modifier; put those in `mods` instead.
+    ///
+    /// If called with an already set keybind, it gets replaced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pinnacle_api::input::Mod;
+    ///
+    /// // Hold Space and press h or l to switch between windows like in vim
+    /// input.held_keybind([], "space", 'h', || {
+    ///     window.focus_previous();
+    /// });
+    /// input.held_keybind([Mod::Super], "space", 'l', || { /* ... */ });
+    /// ```
+    pub fn held_keybind(
+        &self,
+        mods: impl IntoIterator<Item = Mod>,
+        held: impl Key,
+        key: impl Key + Send + 'static,
+        mut action: impl FnMut() + Send + 'static,
+    ) {
+        let mut client = self.create_input_client();
+
+        let modifiers = mods.into_iter().map(|modif| modif as i32).collect();
+        let held_raw_code = held.into_keysym().raw();
+
+        self.fut_sender
+            .send(
+                async move {
+                    let mut stream = match client
+                        .set_keybind(SetKeybindRequest {
+                            modifiers,
+                            key: Some(input::v0alpha1::set_keybind_request::Key::RawCode(
+                                key.into_keysym().raw(),
+                            )),
+                            held_key: Some(
+                                input::v0alpha1::set_keybind_request::HeldKey::HeldRawCode(
+                                    held_raw_code,
+                                ),
+                            ),
+                            allow_when_disabled: None,
+                        })
+                        .await
+                    {
+                        Ok(response) => response.into_inner(),
+                        Err(status) => {
+                            eprintln!("ERROR: {status}");
+                            return;
+                        }
+                    };
+
+                    while let Some(Ok(_response)) = stream.next().await {
+                        action();
+                        tokio::task::yield_now().await;
+                    }
+                }
+                .boxed(),
+            )
+            .unwrap();
+    }
+
     /// Set a mousebind.
     ///
     /// If called with an already set mousebind, it gets replaced.
@@ -193,7 +372,8 @@ impl Input {
     /// You must supply:
     /// - `mods`: A list of [`Mod`]s. These must be held down for the keybind to trigger.
     /// - `button`: A [`MouseButton`].
-    /// - `edge`: A [`MouseEdge`]. This allows you to trigger the bind on either mouse press or release.
+    /// - `edge`: A [`MouseEdge`]. This allows you to trigger the bind on mouse press, release,
+    ///   click, double click, or drag.
     /// - `action`: A closure that will be run when the mousebind is triggered.
     ///     - Currently, any captures must be both `Send` and `'static`. If you want to mutate
     ///       something, consider using channels or [`Box::leak`].
@@ -272,6 +452,134 @@ impl Input {
         .unwrap();
     }
 
+    /// Treat modifiers as other modifiers when matching keybinds and mousebinds.
+    ///
+    /// Each `(from, to)` pair makes holding `from` count as holding `to`. This lets you write
+    /// keybinds with one primary modifier, like [`Mod::Super`], and choose which physical key
+    /// acts as it. Clients still see the modifiers that are actually held.
+    ///
+    /// This replaces any previous remaps. Pass in an empty list to remove them.
+    ///
+    /// Caps Lock isn't a modifier; use the `caps:super` option in
+    /// [`set_xkb_config`][Self::set_xkb_config] to make it act as Super.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pinnacle_api::input::Mod;
+    ///
+    /// // Use Alt for all Super keybinds, and Super for all Alt ones
+    /// input.set_modifier_remaps([(Mod::Alt, Mod::Super), (Mod::Super, Mod::Alt)]);
+    /// ```
+    pub fn set_modifier_remaps(&self, remaps: impl IntoIterator<Item = (Mod, Mod)>) {
+        let mut client = self.create_input_client();
+
+        let remaps = remaps
+            .into_iter()
+            .map(|(from, to)| Remap {
+                from: Some(from as i32),
+                to: Some(to as i32),
+            })
+            .collect();
+
+        if let Err(status) =
+            block_on_tokio(client.set_modifier_remaps(SetModifierRemapsRequest { remaps }))
+        {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Press and release a key on the focused window, as if it were typed on a keyboard.
+    ///
+    /// The key must be in the current keymap. Shift is held down for keys that need it,
+    /// like uppercase letters.
+    ///
+    /// If `match_keybinds` is false, the key goes straight to the focused window. Otherwise
+    /// it can trigger keybinds, including the kill and reload keybinds, like a real key press.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Type some text into the focused window
+    /// for ch in "hello".chars() {
+    ///     input.send_key(ch, false);
+    /// }
+    ///
+    /// input.send_key("Return", false);
+    /// ```
+    pub fn send_key(&self, key: impl Key, match_keybinds: bool) {
+        let mut client = self.create_input_client();
+
+        if let Err(status) = block_on_tokio(client.send_key(SendKeyRequest {
+            key: Some(input::v0alpha1::send_key_request::Key::RawCode(
+                key.into_keysym().raw(),
+            )),
+            match_keybinds: Some(match_keybinds),
+        })) {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Run `on_idle` after `timeout` without any input, then `on_resume` when input resumes.
+    ///
+    /// This can be called multiple times with different timeouts to add several idle stages,
+    /// for example to dim outputs after 5 minutes and lock after 10.
+    ///
+    /// Windows inhibiting idle, like video players in fullscreen, keep stages from becoming idle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// // Lock the session after 10 minutes without input
+    /// input.on_idle(
+    ///     Duration::from_secs(10 * 60),
+    ///     || {
+    ///         process.spawn(["swaylock"]);
+    ///     },
+    ///     || {},
+    /// );
+    /// ```
+    pub fn on_idle(
+        &self,
+        timeout: Duration,
+        mut on_idle: impl FnMut() + Send + 'static,
+        mut on_resume: impl FnMut() + Send + 'static,
+    ) {
+        let mut client = self.create_input_client();
+
+        let timeout_ms = u32::try_from(timeout.as_millis()).unwrap_or(u32::MAX);
+
+        self.fut_sender
+            .send(
+                async move {
+                    let mut stream = match client
+                        .set_idle_action(SetIdleActionRequest {
+                            timeout_ms: Some(timeout_ms),
+                        })
+                        .await
+                    {
+                        Ok(response) => response.into_inner(),
+                        Err(status) => {
+                            eprintln!("ERROR: {status}");
+                            return;
+                        }
+                    };
+
+                    while let Some(Ok(response)) = stream.next().await {
+                        match response.idle() {
+                            true => on_idle(),
+                            false => on_resume(),
+                        }
+                        tokio::task::yield_now().await;
+                    }
+                }
+                .boxed(),
+            )
+            .unwrap();
+    }
+
     /// Set the keyboard's repeat rate.
     ///
     /// This allows you to set the time between holding down a key and it repeating
@@ -296,6 +604,234 @@ impl Input {
         .unwrap();
     }
 
+    /// Set the maximum time between two clicks for them to count as a double click.
+    ///
+    /// This is used for [`MouseEdge::DoubleClick`] mousebinds. It defaults to 400ms.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// input.set_double_click_interval(Duration::from_millis(300));
+    /// ```
+    pub fn set_double_click_interval(&self, interval: Duration) {
+        let mut client = self.create_input_client();
+
+        block_on_tokio(client.set_click_settings(SetClickSettingsRequest {
+            double_click_interval: Some(interval.as_millis() as u32),
+            drag_threshold: None,
+            focus_on_release: None,
+        }))
+        .unwrap();
+    }
+
+    /// Set how far the pointer must move with a button held for it to count as a drag,
+    /// in logical pixels.
+    ///
+    /// This is used for [`MouseEdge::Click`] and [`MouseEdge::Drag`] mousebinds.
+    /// It defaults to 8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// input.set_drag_threshold(16);
+    /// ```
+    pub fn set_drag_threshold(&self, threshold: u32) {
+        let mut client = self.create_input_client();
+
+        block_on_tokio(client.set_click_settings(SetClickSettingsRequest {
+            double_click_interval: None,
+            drag_threshold: Some(threshold),
+            focus_on_release: None,
+        }))
+        .unwrap();
+    }
+
+    /// Set whether clicking a window focuses it when the button is released
+    /// instead of when it's pressed.
+    ///
+    /// Focusing on release lets you drag things out of a window without focusing it.
+    /// Releases that end a drag or a [`begin_move`][crate::window::Window::begin_move] or
+    /// [`begin_resize`][crate::window::Window::begin_resize] grab don't change focus.
+    /// This defaults to focusing on press.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// input.set_focus_on_release(true);
+    /// ```
+    pub fn set_focus_on_release(&self, focus_on_release: bool) {
+        let mut client = self.create_input_client();
+
+        if let Err(status) = block_on_tokio(client.set_click_settings(SetClickSettingsRequest {
+            double_click_interval: None,
+            drag_threshold: None,
+            focus_on_release: Some(focus_on_release),
+        })) {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Confine the pointer to the given output.
+    ///
+    /// The pointer will not be able to leave the output until
+    /// [`release_pointer`][Self::release_pointer] is called. If the pointer is currently
+    /// on another output, it will be moved onto this one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// if let Some(op) = output.get_by_name("DP-1") {
+    ///     input.confine_pointer(&op);
+    /// }
+    /// ```
+    pub fn confine_pointer(&self, output: &OutputHandle) {
+        let mut client = self.create_input_client();
+
+        if let Err(status) = block_on_tokio(client.set_pointer_confinement(
+            SetPointerConfinementRequest {
+                output_name: Some(output.name.clone()),
+            },
+        )) {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Let the pointer move freely across outputs again after
+    /// [`confine_pointer`][Self::confine_pointer].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// input.release_pointer();
+    /// ```
+    pub fn release_pointer(&self) {
+        let mut client = self.create_input_client();
+
+        block_on_tokio(
+            client.set_pointer_confinement(SetPointerConfinementRequest { output_name: None }),
+        )
+        .unwrap();
+    }
+
+    /// Get the pointer's location and what is under it.
+    ///
+    /// This is useful for things like custom gestures, or placing a menu
+    /// on the output the pointer is on.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let pointer = input.pointer();
+    ///
+    /// if let Some(win) = pointer.window {
+    ///     println!("The pointer is over {:?}", win.class());
+    /// }
+    /// ```
+    pub fn pointer(&self) -> PointerInfo {
+        block_on_tokio(self.pointer_async())
+    }
+
+    /// The async version of [`Input::pointer`].
+    pub async fn pointer_async(&self) -> PointerInfo {
+        let mut client = self.create_input_client();
+
+        let response = client
+            .get_pointer(GetPointerRequest {})
+            .await
+            .unwrap()
+            .into_inner();
+
+        let api = self.api.get().unwrap();
+
+        PointerInfo {
+            location: response.x.zip(response.y),
+            output: response.output_name.map(|name| api.output.new_handle(name)),
+            window: response.window_id.map(|id| api.window.new_handle(id)),
+            layer_namespace: response.layer_namespace,
+        }
+    }
+
+    /// Get all seats and what input capabilities they have.
+    ///
+    /// Pinnacle currently only creates one seat, so this always returns one item.
+    /// This is mostly useful for debugging input issues.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// for seat in input.seats() {
+    ///     println!("{:?}: pointer = {:?}", seat.name, seat.pointer);
+    /// }
+    /// ```
+    pub fn seats(&self) -> Vec<SeatInfo> {
+        block_on_tokio(self.seats_async())
+    }
+
+    /// The async version of [`Input::seats`].
+    pub async fn seats_async(&self) -> Vec<SeatInfo> {
+        let mut client = self.create_input_client();
+
+        let response = client
+            .get_seats(GetSeatsRequest {})
+            .await
+            .unwrap()
+            .into_inner();
+
+        response
+            .seats
+            .into_iter()
+            .map(|seat| SeatInfo {
+                name: seat.name,
+                keyboard: seat.keyboard,
+                pointer: seat.pointer,
+                touch: seat.touch,
+            })
+            .collect()
+    }
+
+    /// Get all set keybinds along with the process that set them.
+    ///
+    /// This is useful for finding out why a key combination does more than one thing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// for bind in input.keybinds() {
+    ///     println!("{:?} {:?}: {:?}", bind.mods, bind.key, bind.owner_name);
+    /// }
+    /// ```
+    pub fn keybinds(&self) -> Vec<KeybindInfo> {
+        block_on_tokio(self.keybinds_async())
+    }
+
+    /// The async version of [`Input::keybinds`].
+    pub async fn keybinds_async(&self) -> Vec<KeybindInfo> {
+        let mut client = self.create_input_client();
+
+        let response = client
+            .get_keybinds(GetKeybindsRequest {})
+            .await
+            .unwrap()
+            .into_inner();
+
+        response
+            .keybinds
+            .into_iter()
+            .map(|bind| KeybindInfo {
+                mods: bind
+                    .modifiers
+                    .iter()
+                    .filter_map(|modifier| Mod::try_from(*modifier).ok())
+                    .collect(),
+                key: Keysym::new(bind.key_code()),
+                held_key: bind.held_key_code.map(Keysym::new),
+                owner_pid: bind.owner_pid,
+                owner_name: bind.owner_name,
+                connected: bind.connected(),
+            })
+            .collect()
+    }
+
     /// Set a libinput setting.
     ///
     /// From [freedesktop.org](https://www.freedesktop.org/wiki/Software/libinput/):
@@ -12,10 +12,14 @@ use std::{
 };
 
 use futures::{future::BoxFuture, FutureExt};
-use pinnacle_api_defs::pinnacle::layout::v0alpha1::{
-    layout_request::{Body, ExplicitLayout, Geometries},
-    layout_service_client::LayoutServiceClient,
-    LayoutRequest,
+use pinnacle_api_defs::pinnacle::{
+    layout::v0alpha1::{
+        layout_request::{Body, ExplicitLayout, Geometries},
+        layout_service_client::LayoutServiceClient,
+        GetTagLayoutRequest, LayoutRequest, RelayoutAllRequest, SetTagLayoutRequest,
+        SetTilingPausedRequest,
+    },
+    v0alpha1::SetOrToggle,
 };
 use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
 use tokio_stream::StreamExt;
@@ -24,7 +28,7 @@ use tonic::transport::Channel;
 use crate::{
     block_on_tokio,
     output::OutputHandle,
-    tag::TagHandle,
+    tag::{TagHandle, TilingDirection},
     util::{Axis, Geometry},
     window::WindowHandle,
     ApiModules,
@@ -54,6 +58,146 @@ impl Layout {
         self.api.set(api).unwrap();
     }
 
+    /// Lay out every output.
+    ///
+    /// This is useful after making a lot of changes at once, like after
+    /// [`Window::reapply_window_rules`][crate::window::Window::reapply_window_rules].
+    /// Calling this multiple times in quick succession only lays out each output once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// layout.relayout_all();
+    /// ```
+    pub fn relayout_all(&self) {
+        let mut client = self.layout_client.clone();
+        if let Err(status) = block_on_tokio(client.relayout_all(RelayoutAllRequest {})) {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Pause or resume tiling.
+    ///
+    /// While tiling is paused, your layout manager isn't asked to tile windows.
+    /// Tiled windows stay where they are and new windows open floating, so you can
+    /// arrange things by hand. Resuming lays out every output again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// layout.set_tiling_paused(true);
+    /// ```
+    pub fn set_tiling_paused(&self, paused: bool) {
+        let mut client = self.layout_client.clone();
+        if let Err(status) = block_on_tokio(client.set_tiling_paused(SetTilingPausedRequest {
+            set_or_toggle: Some(match paused {
+                true => SetOrToggle::Set,
+                false => SetOrToggle::Unset,
+            } as i32),
+        })) {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Toggle whether tiling is paused.
+    ///
+    /// See [`set_tiling_paused`][Self::set_tiling_paused] for what pausing does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// layout.toggle_tiling_paused();
+    /// ```
+    pub fn toggle_tiling_paused(&self) {
+        let mut client = self.layout_client.clone();
+        if let Err(status) = block_on_tokio(client.set_tiling_paused(SetTilingPausedRequest {
+            set_or_toggle: Some(SetOrToggle::Toggle as i32),
+        })) {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Report a tag's layout state to the compositor so other clients can show it.
+    ///
+    /// Only your layout manager knows things like which layouts a tag cycles through, so
+    /// call this whenever they change. This replaces anything previously reported for `tag`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pinnacle_api::layout::ReportedTagLayout;
+    ///
+    /// let tag = tag.get("1")?;
+    /// layout.report_tag_layout(
+    ///     &tag,
+    ///     ReportedTagLayout {
+    ///         current_layout: Some("master_stack".into()),
+    ///         layouts: vec!["master_stack".into(), "dwindle".into()],
+    ///         master_factor: Some(0.5),
+    ///         master_count: Some(1),
+    ///     },
+    /// );
+    /// ```
+    pub fn report_tag_layout(&self, tag: &TagHandle, layout: ReportedTagLayout) {
+        let mut client = self.layout_client.clone();
+        if let Err(status) = block_on_tokio(client.set_tag_layout(SetTagLayoutRequest {
+            tag_id: Some(tag.id),
+            layout: Some(layout.into()),
+        })) {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Get everything about how a tag is laid out in one call.
+    ///
+    /// This is useful for layout switchers in bars.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let info = layout.tag_layout(&tag.get("1")?);
+    /// if let Some(current) = info.reported.and_then(|layout| layout.current_layout) {
+    ///     println!("Using {current}");
+    /// }
+    /// ```
+    pub fn tag_layout(&self, tag: &TagHandle) -> TagLayoutInfo {
+        block_on_tokio(self.tag_layout_async(tag))
+    }
+
+    /// The async version of [`Layout::tag_layout`].
+    pub async fn tag_layout_async(&self, tag: &TagHandle) -> TagLayoutInfo {
+        let mut client = self.layout_client.clone();
+
+        let response = match client
+            .get_tag_layout(GetTagLayoutRequest {
+                tag_id: Some(tag.id),
+            })
+            .await
+        {
+            Ok(response) => response.into_inner(),
+            Err(status) => {
+                eprintln!("ERROR: {status}");
+                return TagLayoutInfo::default();
+            }
+        };
+
+        TagLayoutInfo {
+            reported: response.layout.map(|layout| ReportedTagLayout {
+                current_layout: layout.current_layout,
+                layouts: layout.layouts,
+                master_factor: layout.master_factor,
+                master_count: layout.master_count,
+            }),
+            inner_gaps: response.inner_gaps,
+            outer_gaps: response.outer_gaps,
+            smart_gaps: response.smart_gaps,
+            tiling_direction: response
+                .tiling_direction
+                .and_then(|direction| direction.try_into().ok()),
+            tiling_paused: response.tiling_paused,
+        }
+    }
+
     /// Consume the given [`LayoutManager`] and set it as the global layout handler.
     ///
     /// This returns a [`LayoutRequester`] that allows you to manually request layouts from
@@ -97,8 +241,15 @@ impl Layout {
                         .collect(),
                     output_width: response.output_width.unwrap_or_default(),
                     output_height: response.output_height.unwrap_or_default(),
+                    tiling_direction: response
+                        .tiling_direction
+                        .and_then(|direction| direction.try_into().ok()),
+                };
+                let (geos, lone_window_gaps) = {
+                    let mut manager = manager.lock().unwrap();
+                    let layout = manager.active_layout(&args);
+                    (layout.layout(&args), layout.lone_window_gaps())
                 };
-                let geos = manager.lock().unwrap().active_layout(&args).layout(&args);
                 from_client
                     .send(LayoutRequest {
                         body: Some(Body::Geometries(Geometries {
@@ -113,6 +264,7 @@ impl Layout {
                                     height: Some(geo.height as i32),
                                 })
                                 .collect(),
+                            lone_window_gaps: Some(lone_window_gaps),
                         })),
                     })
                     .unwrap();
@@ -125,6 +277,51 @@ impl Layout {
     }
 }
 
+/// Layout state that only your layout manager knows about.
+///
+/// See [`Layout::report_tag_layout`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReportedTagLayout {
+    /// The name of the layout the tag is using
+    pub current_layout: Option<String>,
+    /// The names of the layouts the tag cycles through, in order
+    pub layouts: Vec<String>,
+    /// How much of the output the master area takes up, from 0.0 to 1.0
+    pub master_factor: Option<f32>,
+    /// How many windows are in the master area
+    pub master_count: Option<u32>,
+}
+
+impl From<ReportedTagLayout> for pinnacle_api_defs::pinnacle::layout::v0alpha1::ReportedTagLayout {
+    fn from(layout: ReportedTagLayout) -> Self {
+        Self {
+            current_layout: layout.current_layout,
+            layouts: layout.layouts,
+            master_factor: layout.master_factor,
+            master_count: layout.master_count,
+        }
+    }
+}
+
+/// Everything about how a tag is laid out.
+///
+/// See [`Layout::tag_layout`]. Fields are `None` if the tag doesn't exist.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TagLayoutInfo {
+    /// What the layout manager last reported for the tag with [`Layout::report_tag_layout`]
+    pub reported: Option<ReportedTagLayout>,
+    /// The gap the compositor puts around each tiled window
+    pub inner_gaps: Option<u32>,
+    /// The gap the compositor insets tiled windows from the edges of the output
+    pub outer_gaps: Option<u32>,
+    /// Whether gaps are removed when there is only one tiled window
+    pub smart_gaps: Option<bool>,
+    /// How the layout's master area is split from the stack, or `None` if the layout decides
+    pub tiling_direction: Option<TilingDirection>,
+    /// Whether tiling is paused
+    pub tiling_paused: Option<bool>,
+}
+
 /// Arguments that [`LayoutGenerator`]s receive when a layout is requested.
 #[derive(Clone, Debug)]
 pub struct LayoutArgs {
@@ -138,6 +335,10 @@ pub struct LayoutArgs {
     pub output_width: u32,
     /// The height of the layout area, in pixels.
     pub output_height: u32,
+    /// The tiling direction of the first focused tag, if it has one.
+    ///
+    /// See [`TagHandle::set_tiling_direction`].
+    pub tiling_direction: Option<TilingDirection>,
 }
 
 /// Types that can manage layouts.
@@ -150,6 +351,14 @@ pub trait LayoutManager {
 pub trait LayoutGenerator {
     /// Generate a vector of [geometries][Geometry] using the given [`LayoutArgs`].
     fn layout(&self, args: &LayoutArgs) -> Vec<Geometry>;
+
+    /// The gap this generator leaves between a lone window and the edges of the output.
+    ///
+    /// Tags with [smart gaps][crate::tag::TagHandle::set_smart_gaps] remove this gap
+    /// from a lone tiled window. Defaults to no gap.
+    fn lone_window_gaps(&self) -> u32 {
+        0
+    }
 }
 
 /// Gaps between windows.
@@ -172,6 +381,16 @@ pub enum Gaps {
     },
 }
 
+impl Gaps {
+    /// The gap around a window that is laid out on its own.
+    fn around_lone_window(self) -> u32 {
+        match self {
+            Gaps::Absolute(gaps) => gaps,
+            Gaps::Split { inner, outer } => inner + outer,
+        }
+    }
+}
+
 /// A [`LayoutManager`] that keeps track of layouts per output and provides
 /// methods to cycle between them.
 pub struct CyclingLayoutManager {
@@ -319,6 +538,21 @@ pub enum MasterSide {
     Bottom,
 }
 
+impl MasterSide {
+    /// Get the side on the same end as this one with the master area split off in `direction`.
+    ///
+    /// Left and top are swapped for each other, as are right and bottom.
+    pub fn oriented(self, direction: TilingDirection) -> Self {
+        match (direction, self) {
+            (TilingDirection::Horizontal, MasterSide::Top) => MasterSide::Left,
+            (TilingDirection::Horizontal, MasterSide::Bottom) => MasterSide::Right,
+            (TilingDirection::Vertical, MasterSide::Left) => MasterSide::Top,
+            (TilingDirection::Vertical, MasterSide::Right) => MasterSide::Bottom,
+            (_, side) => side,
+        }
+    }
+}
+
 /// A [`LayoutGenerator`] that has one master area to one side and a stack of windows
 /// next to it.
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -335,6 +569,9 @@ pub struct MasterStackLayout {
     pub master_factor: f32,
     /// Which side the master area will be.
     ///
+    /// Tags with a [tiling direction][TagHandle::set_tiling_direction] move this
+    /// to the matching side on the same end.
+    ///
     /// Defaults to [`MasterSide::Left`].
     pub master_side: MasterSide,
     /// How many windows will be in the master area.
@@ -365,6 +602,11 @@ impl LayoutGenerator for MasterStackLayout {
         let width = args.output_width;
         let height = args.output_height;
 
+        let master_side = match args.tiling_direction {
+            Some(direction) => self.master_side.oriented(direction),
+            None => self.master_side,
+        };
+
         let mut geos = Vec::<Geometry>::new();
 
         let (outer_gaps, inner_gaps) = match self.gaps {
@@ -398,7 +640,7 @@ impl LayoutGenerator for MasterStackLayout {
             None => outer_gaps,
         };
 
-        let (master_rect, mut stack_rect) = match self.master_side {
+        let (master_rect, mut stack_rect) = match master_side {
             MasterSide::Left => {
                 let (rect1, rect2) = rect.split_at(
                     Axis::Vertical,
@@ -442,7 +684,7 @@ impl LayoutGenerator for MasterStackLayout {
         };
 
         if master_count > 1 {
-            let (coord, len, axis) = match self.master_side {
+            let (coord, len, axis) = match master_side {
                 MasterSide::Left | MasterSide::Right => (
                     master_rect.y,
                     master_rect.height as f32 / master_count as f32,
@@ -473,7 +715,7 @@ impl LayoutGenerator for MasterStackLayout {
             let mut stack_rect = stack_rect.unwrap();
 
             if stack_count > 1 {
-                let (coord, len, axis) = match self.master_side {
+                let (coord, len, axis) = match master_side {
                     MasterSide::Left | MasterSide::Right => (
                         stack_rect.y,
                         stack_rect.height as f32 / stack_count as f32,
@@ -512,6 +754,10 @@ impl LayoutGenerator for MasterStackLayout {
 
         geos
     }
+
+    fn lone_window_gaps(&self) -> u32 {
+        self.gaps.around_lone_window()
+    }
 }
 
 /// A [`LayoutGenerator`] that lays out windows in a shrinking fashion
@@ -624,6 +870,10 @@ impl LayoutGenerator for DwindleLayout {
 
         geos
     }
+
+    fn lone_window_gaps(&self) -> u32 {
+        self.gaps.around_lone_window()
+    }
 }
 
 /// A [`LayoutGenerator`] that lays out windows in a spiral.
@@ -746,6 +996,10 @@ impl LayoutGenerator for SpiralLayout {
 
         geos
     }
+
+    fn lone_window_gaps(&self) -> u32 {
+        self.gaps.around_lone_window()
+    }
 }
 
 /// Which corner the corner window will in.
@@ -960,6 +1214,10 @@ impl LayoutGenerator for CornerLayout {
 
         geos
     }
+
+    fn lone_window_gaps(&self) -> u32 {
+        self.gaps.around_lone_window()
+    }
 }
 
 /// A [`LayoutGenerator`] that attempts to layout windows such that
@@ -1132,4 +1390,8 @@ impl LayoutGenerator for FairLayout {
 
         geos
     }
+
+    fn lone_window_gaps(&self) -> u32 {
+        self.gaps.around_lone_window()
+    }
 }
@@ -178,7 +178,7 @@ pub async fn connect(
     let input = Box::leak(Box::new(Input::new(channel.clone(), fut_sender.clone())));
     let output = Box::leak(Box::new(Output::new(channel.clone())));
     let tag = Box::leak(Box::new(Tag::new(channel.clone())));
-    let render = Box::leak(Box::new(Render::new(channel.clone())));
+    let render = Box::leak(Box::new(Render::new(channel.clone(), fut_sender.clone())));
     let layout = Box::leak(Box::new(Layout::new(channel.clone(), fut_sender.clone())));
 
     let modules = ApiModules {
@@ -193,10 +193,13 @@ pub async fn connect(
         signal: signal.clone(),
     };
 
+    pinnacle.finish_init(modules.clone());
     window.finish_init(modules.clone());
     output.finish_init(modules.clone());
     tag.finish_init(modules.clone());
     layout.finish_init(modules.clone());
+    render.finish_init(modules.clone());
+    input.finish_init(modules.clone());
     signal.read().await.finish_init(modules.clone());
 
     Ok((modules, fut_recv))
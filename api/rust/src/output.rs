@@ -12,12 +12,19 @@
 use std::{num::NonZeroU32, sync::OnceLock};
 
 use futures::FutureExt;
-use pinnacle_api_defs::pinnacle::output::{
-    self,
-    v0alpha1::{
-        output_service_client::OutputServiceClient, set_scale_request::AbsoluteOrRelative,
-        SetLocationRequest, SetModeRequest, SetScaleRequest, SetTransformRequest,
+use pinnacle_api_defs::pinnacle::{
+    output::{
+        self,
+        v0alpha1::{
+            output_service_client::OutputServiceClient, set_brightness_request,
+            set_magnification_request, set_scale_request::AbsoluteOrRelative,
+            FocusOutputUnderCursorRequest, GetFocusStackRequest, SetBrightnessRequest,
+            SetFocusLockRequest, SetGammaPresetRequest, SetLocationRequest,
+            SetLoneWindowWidthRequest, SetMagnificationRequest, SetModeRequest, SetOverviewRequest,
+            SetPrimaryRequest, SetScaleRequest, SetTransformRequest,
+        },
     },
+    v0alpha1::SetOrToggle,
 };
 use tonic::transport::Channel;
 
@@ -26,6 +33,7 @@ use crate::{
     signal::{OutputSignal, SignalHandle},
     tag::{Tag, TagHandle},
     util::Batch,
+    window::WindowHandle,
     ApiModules,
 };
 
@@ -130,6 +138,46 @@ impl Output {
         )
     }
 
+    /// Focus the output the pointer is currently on.
+    ///
+    /// This also moves keyboard focus to that output's focused window.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// output.focus_output_under_cursor();
+    /// ```
+    pub fn focus_output_under_cursor(&self) {
+        let mut client = self.output_client.clone();
+        block_on_tokio(client.focus_output_under_cursor(FocusOutputUnderCursorRequest {})).unwrap();
+    }
+
+    /// Unlock focus after it was locked with [`OutputHandle::lock_focus`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// output.unlock_focus();
+    /// ```
+    pub fn unlock_focus(&self) {
+        let mut client = self.output_client.clone();
+        block_on_tokio(client.set_focus_lock(SetFocusLockRequest { output_name: None })).unwrap();
+    }
+
+    /// Stop using an output set with [`OutputHandle::set_primary`] as the primary output.
+    ///
+    /// Layer surfaces that don't ask for an output will be placed on the first output again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// output.clear_primary();
+    /// ```
+    pub fn clear_primary(&self) {
+        let mut client = self.output_client.clone();
+        block_on_tokio(client.set_primary(SetPrimaryRequest { output_name: None })).unwrap();
+    }
+
     /// Connect a closure to be run on all current and future outputs.
     ///
     /// When called, `connect_for_all` will do two things:
@@ -171,6 +219,7 @@ impl Output {
             OutputSignal::Disconnect(f) => signal_state.output_disconnect.add_callback(f),
             OutputSignal::Resize(f) => signal_state.output_resize.add_callback(f),
             OutputSignal::Move(f) => signal_state.output_move.add_callback(f),
+            OutputSignal::Headless(f) => signal_state.output_headless.add_callback(f),
         }
     }
 
@@ -874,6 +923,317 @@ impl OutputHandle {
         .unwrap();
     }
 
+    /// Focus this output and keep focus on it until [`Output::unlock_focus`] is called.
+    ///
+    /// While locked, moving the pointer to another output will not focus it,
+    /// and windows that open on other outputs will not get keyboard focus.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Keep focus on the streaming output
+    /// output.get_by_name("DP-1")?.lock_focus();
+    /// ```
+    pub fn lock_focus(&self) {
+        let mut client = self.output_client.clone();
+        if let Err(status) = block_on_tokio(client.set_focus_lock(SetFocusLockRequest {
+            output_name: Some(self.name.clone()),
+        })) {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Make this the primary output.
+    ///
+    /// Layer surfaces like bars and wallpapers that don't ask for a specific output
+    /// will be placed on this output instead of whichever one comes first.
+    /// This applies whenever this output is connected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// output.get_by_name("DP-1")?.set_primary();
+    /// ```
+    pub fn set_primary(&self) {
+        let mut client = self.output_client.clone();
+        block_on_tokio(client.set_primary(SetPrimaryRequest {
+            output_name: Some(self.name.clone()),
+        }))
+        .unwrap();
+    }
+
+    /// Open or close an overview on this output.
+    ///
+    /// The overview shows windows side by side, scaled down to fit the output.
+    /// Clicking on a window focuses it and closes the overview. If the window isn't on
+    /// an active tag, its first tag will be switched to.
+    ///
+    /// If `all_tags` is true, windows on all of this output's tags will be shown,
+    /// not just those on active tags. This only has an effect when opening the overview.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Show all windows on the focused output
+    /// output.get_focused()?.set_overview(true, true);
+    /// ```
+    pub fn set_overview(&self, set: bool, all_tags: bool) {
+        let mut client = self.output_client.clone();
+        block_on_tokio(client.set_overview(SetOverviewRequest {
+            output_name: Some(self.name.clone()),
+            set_or_toggle: Some(match set {
+                true => SetOrToggle::Set,
+                false => SetOrToggle::Unset,
+            } as i32),
+            all_tags: Some(all_tags),
+        }))
+        .unwrap();
+    }
+
+    /// Toggle the overview on this output.
+    ///
+    /// See [`OutputHandle::set_overview`] for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Toggle an overview of windows on active tags
+    /// output.get_focused()?.toggle_overview(false);
+    /// ```
+    pub fn toggle_overview(&self, all_tags: bool) {
+        let mut client = self.output_client.clone();
+        block_on_tokio(client.set_overview(SetOverviewRequest {
+            output_name: Some(self.name.clone()),
+            set_or_toggle: Some(SetOrToggle::Toggle as i32),
+            all_tags: Some(all_tags),
+        }))
+        .unwrap();
+    }
+
+    /// Zoom this output in around the pointer by `magnification`.
+    ///
+    /// The magnified view follows the pointer, and input still goes to whatever is under it.
+    /// Setting this to 1.0 turns the magnifier off. It's clamped to between 1.0 and 16.0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pinnacle_api::input::Mod;
+    ///
+    /// // Toggle the magnifier with Super+Alt+m
+    /// input.keybind([Mod::Super, Mod::Alt], 'm', || {
+    ///     if let Some(op) = output.get_focused() {
+    ///         let zoom = if op.magnification() > Some(1.0) { 1.0 } else { 2.0 };
+    ///         op.set_magnification(zoom);
+    ///     }
+    /// });
+    /// ```
+    pub fn set_magnification(&self, magnification: f32) {
+        let mut client = self.output_client.clone();
+        if let Err(status) = block_on_tokio(client.set_magnification(SetMagnificationRequest {
+            output_name: Some(self.name.clone()),
+            absolute_or_relative: Some(set_magnification_request::AbsoluteOrRelative::Absolute(
+                magnification,
+            )),
+        })) {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Increase this output's magnification by `increase_by`.
+    ///
+    /// This turns the magnifier on if it was off.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// output.get_focused()?.increase_magnification(0.5);
+    /// ```
+    pub fn increase_magnification(&self, increase_by: f32) {
+        let mut client = self.output_client.clone();
+        if let Err(status) = block_on_tokio(client.set_magnification(SetMagnificationRequest {
+            output_name: Some(self.name.clone()),
+            absolute_or_relative: Some(set_magnification_request::AbsoluteOrRelative::Relative(
+                increase_by,
+            )),
+        })) {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Decrease this output's magnification by `decrease_by`.
+    ///
+    /// This simply calls [`OutputHandle::increase_magnification`] with the negative of
+    /// `decrease_by`. The magnifier turns off once the magnification reaches 1.0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// output.get_focused()?.decrease_magnification(0.5);
+    /// ```
+    pub fn decrease_magnification(&self, decrease_by: f32) {
+        self.increase_magnification(-decrease_by);
+    }
+
+    /// Set the brightness of this output's backlight.
+    ///
+    /// `brightness` is a fraction of the maximum brightness and is clamped to between 0.0
+    /// and 1.0. This only works on outputs with a backlight, like laptop panels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// output.get_by_name("eDP-1")?.set_brightness(0.5);
+    /// ```
+    pub fn set_brightness(&self, brightness: f32) {
+        let mut client = self.output_client.clone();
+        if let Err(status) = block_on_tokio(client.set_brightness(SetBrightnessRequest {
+            output_name: Some(self.name.clone()),
+            absolute_or_relative: Some(set_brightness_request::AbsoluteOrRelative::Absolute(
+                brightness,
+            )),
+        })) {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Increase the brightness of this output's backlight by `increase_by`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pinnacle_api::xkbcommon::xkb::Keysym;
+    ///
+    /// // Raise the brightness by 10% with the brightness up key
+    /// input.keybind([], Keysym::XF86_MonBrightnessUp, || {
+    ///     if let Some(op) = output.get_focused() {
+    ///         op.increase_brightness(0.1);
+    ///     }
+    /// });
+    /// ```
+    pub fn increase_brightness(&self, increase_by: f32) {
+        let mut client = self.output_client.clone();
+        if let Err(status) = block_on_tokio(client.set_brightness(SetBrightnessRequest {
+            output_name: Some(self.name.clone()),
+            absolute_or_relative: Some(set_brightness_request::AbsoluteOrRelative::Relative(
+                increase_by,
+            )),
+        })) {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Decrease the brightness of this output's backlight by `decrease_by`.
+    ///
+    /// This simply calls [`OutputHandle::increase_brightness`] with the negative of
+    /// `decrease_by`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// output.get_focused()?.decrease_brightness(0.1);
+    /// ```
+    pub fn decrease_brightness(&self, decrease_by: f32) {
+        self.increase_brightness(-decrease_by);
+    }
+
+    /// Set the gamma Pinnacle keeps on this output, like a night light.
+    ///
+    /// The preset stays set if this output is unplugged and is applied again when it's plugged
+    /// back in. Gamma control clients like wlsunset take priority over it while they're running.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pinnacle_api::output::GammaPreset;
+    ///
+    /// // Make the screen warmer at night
+    /// output.get_focused()?.set_gamma_preset(GammaPreset {
+    ///     temperature: 4000,
+    ///     ..Default::default()
+    /// });
+    /// ```
+    pub fn set_gamma_preset(&self, preset: GammaPreset) {
+        let mut client = self.output_client.clone();
+        if let Err(status) = block_on_tokio(client.set_gamma_preset(SetGammaPresetRequest {
+            output_name: Some(self.name.clone()),
+            temperature: Some(preset.temperature),
+            brightness: Some(preset.brightness),
+            gamma: Some(preset.gamma),
+        })) {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Remove this output's gamma preset, resetting its gamma to linear.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// output.get_focused()?.remove_gamma_preset();
+    /// ```
+    pub fn remove_gamma_preset(&self) {
+        let mut client = self.output_client.clone();
+        if let Err(status) = block_on_tokio(client.set_gamma_preset(SetGammaPresetRequest {
+            output_name: Some(self.name.clone()),
+            temperature: None,
+            brightness: None,
+            gamma: None,
+        })) {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Limit how wide the only tiled window on this output can be.
+    ///
+    /// This is useful on ultrawide monitors, where a single window stretched across the whole
+    /// output is awkward to read. By default, a lone tiled window fills the output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pinnacle_api::output::{LoneWindowAlignment, LoneWindowWidth};
+    ///
+    /// // Keep a lone window in a 2560 pixel column in the middle of the output
+    /// output.get_by_name("DP-1")?.set_lone_window_width(LoneWindowWidth {
+    ///     max_width: 2560,
+    ///     alignment: LoneWindowAlignment::Center,
+    /// });
+    /// ```
+    pub fn set_lone_window_width(&self, width: LoneWindowWidth) {
+        let mut client = self.output_client.clone();
+        if let Err(status) =
+            block_on_tokio(client.set_lone_window_width(SetLoneWindowWidthRequest {
+                output_name: Some(self.name.clone()),
+                max_width: Some(width.max_width),
+                alignment: Some(width.alignment as i32),
+            }))
+        {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Remove the limit set with [`OutputHandle::set_lone_window_width`], letting a lone tiled
+    /// window fill this output again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// output.get_focused()?.remove_lone_window_width();
+    /// ```
+    pub fn remove_lone_window_width(&self) {
+        let mut client = self.output_client.clone();
+        if let Err(status) =
+            block_on_tokio(client.set_lone_window_width(SetLoneWindowWidthRequest {
+                output_name: Some(self.name.clone()),
+                max_width: None,
+                alignment: None,
+            }))
+        {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
     /// Get all properties of this output.
     ///
     /// # Examples
@@ -943,6 +1303,18 @@ impl OutputHandle {
             scale: response.scale,
             transform: response.transform.and_then(|tf| tf.try_into().ok()),
             serial: response.serial,
+            overview: response.overview,
+            magnification: response.magnification,
+            brightness: response.brightness,
+            lone_window_width: response
+                .lone_window_max_width
+                .map(|max_width| LoneWindowWidth {
+                    max_width,
+                    alignment: response
+                        .lone_window_alignment
+                        .and_then(|alignment| alignment.try_into().ok())
+                        .unwrap_or_default(),
+                }),
         }
     }
 
@@ -1142,12 +1514,116 @@ impl OutputHandle {
         self.props_async().await.serial
     }
 
+    /// Get whether this output has an overview open.
+    ///
+    /// Shorthand for `self.props().overview`
+    pub fn overview(&self) -> Option<bool> {
+        self.props().overview
+    }
+
+    /// The async version of [`OutputHandle::overview`].
+    pub async fn overview_async(&self) -> Option<bool> {
+        self.props_async().await.overview
+    }
+
+    /// Get how much this output is magnified.
+    ///
+    /// Shorthand for `self.props().magnification`
+    pub fn magnification(&self) -> Option<f32> {
+        self.props().magnification
+    }
+
+    /// The async version of [`OutputHandle::magnification`].
+    pub async fn magnification_async(&self) -> Option<f32> {
+        self.props_async().await.magnification
+    }
+
+    /// Get the brightness of this output's backlight from 0.0 to 1.0.
+    ///
+    /// Shorthand for `self.props().brightness`
+    pub fn brightness(&self) -> Option<f32> {
+        self.props().brightness
+    }
+
+    /// The async version of [`OutputHandle::brightness`].
+    pub async fn brightness_async(&self) -> Option<f32> {
+        self.props_async().await.brightness
+    }
+
+    /// Get how wide the only tiled window on this output can be.
+    ///
+    /// Shorthand for `self.props().lone_window_width`
+    pub fn lone_window_width(&self) -> Option<LoneWindowWidth> {
+        self.props().lone_window_width
+    }
+
+    /// The async version of [`OutputHandle::lone_window_width`].
+    pub async fn lone_window_width_async(&self) -> Option<LoneWindowWidth> {
+        self.props_async().await.lone_window_width
+    }
+
+    /// Get the windows on this output in the order they were focused, from most to least
+    /// recently focused.
+    ///
+    /// This is the same order the compositor uses to pick what to focus when the focused
+    /// window closes, so it's what an alt-tab switcher should cycle through.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Get the most recently focused window on an active tag other than the focused one
+    /// let previous = output
+    ///     .get_focused()?
+    ///     .focus_stack()
+    ///     .into_iter()
+    ///     .filter(|entry| entry.on_active_tag)
+    ///     .nth(1)
+    ///     .map(|entry| entry.window);
+    /// ```
+    pub fn focus_stack(&self) -> Vec<FocusStackEntry> {
+        block_on_tokio(self.focus_stack_async())
+    }
+
+    /// The async version of [`OutputHandle::focus_stack`].
+    pub async fn focus_stack_async(&self) -> Vec<FocusStackEntry> {
+        let mut client = self.output_client.clone();
+        let response = client
+            .get_focus_stack(GetFocusStackRequest {
+                output_name: Some(self.name.clone()),
+            })
+            .await
+            .unwrap()
+            .into_inner();
+
+        response
+            .entries
+            .into_iter()
+            .filter_map(|entry| {
+                Some(FocusStackEntry {
+                    window: self.api.window.new_handle(entry.window_id?),
+                    on_active_tag: entry.on_active_tag(),
+                })
+            })
+            .collect()
+    }
+
     /// Get this output's unique name (the name of its connector).
     pub fn name(&self) -> &str {
         &self.name
     }
 }
 
+/// A window in an output's focus stack.
+///
+/// See [`OutputHandle::focus_stack`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FocusStackEntry {
+    /// The window
+    pub window: WindowHandle,
+    /// Whether the window is on one of the output's active tags
+    pub on_active_tag: bool,
+}
+
 /// A possible output pixel dimension and refresh rate configuration.
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Default)]
 pub struct Mode {
@@ -1161,6 +1637,49 @@ pub struct Mode {
     pub refresh_rate_millihertz: u32,
 }
 
+/// Gamma that Pinnacle keeps on an output.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GammaPreset {
+    /// The color temperature in Kelvin, from 1000 to 40000. 6500 is neutral.
+    pub temperature: u32,
+    /// How bright the output is, from 0.0 to 1.0.
+    pub brightness: f32,
+    /// The gamma exponent. This must be positive.
+    pub gamma: f32,
+}
+
+impl Default for GammaPreset {
+    fn default() -> Self {
+        Self {
+            temperature: 6500,
+            brightness: 1.0,
+            gamma: 1.0,
+        }
+    }
+}
+
+/// A limit on how wide the only tiled window on an output can be.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct LoneWindowWidth {
+    /// The most logical pixels wide the window can be. This must be positive.
+    pub max_width: u32,
+    /// Where the window goes in the space it would otherwise fill.
+    pub alignment: LoneWindowAlignment,
+}
+
+/// Where a lone tiled window goes when its width is limited.
+#[derive(num_enum::TryFromPrimitive, Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(i32)]
+pub enum LoneWindowAlignment {
+    /// In the middle.
+    #[default]
+    Center = 1,
+    /// Against the left edge.
+    Left,
+    /// Against the right edge.
+    Right,
+}
+
 /// The properties of an output.
 #[non_exhaustive]
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -1204,4 +1723,16 @@ pub struct OutputProperties {
     pub transform: Option<Transform>,
     /// This output's EDID serial number.
     pub serial: Option<u32>,
+    /// Whether this output has an overview open.
+    pub overview: Option<bool>,
+    /// How much this output is magnified. This is 1.0 if the magnifier is off.
+    pub magnification: Option<f32>,
+    /// The brightness of this output's backlight from 0.0 to 1.0.
+    ///
+    /// This is `None` if the output has no backlight.
+    pub brightness: Option<f32>,
+    /// How wide the only tiled window on this output can be.
+    ///
+    /// This is `None` if a lone tiled window fills the output.
+    pub lone_window_width: Option<LoneWindowWidth>,
 }
@@ -4,32 +4,45 @@
 
 //! Compositor management.
 //!
-//! This module provides [`Pinnacle`], which allows you to quit the compositor.
+//! This module provides [`Pinnacle`], which allows you to quit the compositor
+//! and query compositor-wide state like the current backend and selection mime types.
 
-use std::time::Duration;
+use std::{sync::OnceLock, time::Duration};
 
+use num_enum::TryFromPrimitive;
 use pinnacle_api_defs::pinnacle::v0alpha1::{
-    pinnacle_service_client::PinnacleServiceClient, PingRequest, QuitRequest, ReloadConfigRequest,
-    ShutdownWatchRequest, ShutdownWatchResponse,
+    pinnacle_service_client::PinnacleServiceClient, GetBackendRequest,
+    GetSelectionMimeTypesRequest, GetStateSnapshotRequest, GetXwaylandInfoRequest,
+    ListSurfacesRequest, PingRequest, QuitRequest, ReloadConfigRequest, ShutdownWatchRequest,
+    ShutdownWatchResponse,
 };
 use rand::RngCore;
 use tonic::{transport::Channel, Request, Streaming};
 
-use crate::block_on_tokio;
+use crate::{
+    block_on_tokio, output::OutputHandle, tag::TagHandle, util::Geometry, window::WindowHandle,
+    ApiModules,
+};
 
 /// A struct that allows you to quit the compositor.
 #[derive(Debug, Clone)]
 pub struct Pinnacle {
     client: PinnacleServiceClient<Channel>,
+    api: OnceLock<ApiModules>,
 }
 
 impl Pinnacle {
     pub(crate) fn new(channel: Channel) -> Self {
         Self {
             client: PinnacleServiceClient::new(channel),
+            api: OnceLock::new(),
         }
     }
 
+    pub(crate) fn finish_init(&self, api: ApiModules) {
+        self.api.set(api).unwrap();
+    }
+
     /// Quit Pinnacle.
     ///
     /// # Examples
@@ -51,6 +64,261 @@ impl Pinnacle {
         let _ = block_on_tokio(client.reload_config(ReloadConfigRequest {}));
     }
 
+    /// Get the mime types currently advertised by the clipboard, primary selection,
+    /// and any ongoing drag-and-drop.
+    ///
+    /// This does not transfer any data, so it can be used to decide whether or not
+    /// a selection is worth fetching.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mime_types = pinnacle.selection_mime_types();
+    ///
+    /// if mime_types.clipboard.iter().any(|mime| mime == "text/plain") {
+    ///     // Store the clipboard contents
+    /// }
+    /// ```
+    pub fn selection_mime_types(&self) -> SelectionMimeTypes {
+        block_on_tokio(self.selection_mime_types_async())
+    }
+
+    /// The async version of [`Pinnacle::selection_mime_types`].
+    pub async fn selection_mime_types_async(&self) -> SelectionMimeTypes {
+        let mut client = self.client.clone();
+
+        let response = match client
+            .get_selection_mime_types(GetSelectionMimeTypesRequest {})
+            .await
+        {
+            Ok(response) => response.into_inner(),
+            Err(status) => {
+                eprintln!("ERROR: {status}");
+                return SelectionMimeTypes::default();
+            }
+        };
+
+        SelectionMimeTypes {
+            clipboard: response.clipboard,
+            primary: response.primary,
+            dnd: response.dnd,
+        }
+    }
+
+    /// Get a snapshot of key compositor state.
+    ///
+    /// This returns all windows with their tags and geometry, all outputs with their tags,
+    /// and the focused output and window in a single call.
+    /// It is mainly intended for making assertions in config test suites.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let snapshot = pinnacle.state_snapshot();
+    ///
+    /// assert_eq!(snapshot.windows.len(), 2);
+    /// assert!(snapshot.focused_window.is_some());
+    /// ```
+    pub fn state_snapshot(&self) -> StateSnapshot {
+        block_on_tokio(self.state_snapshot_async())
+    }
+
+    /// The async version of [`Pinnacle::state_snapshot`].
+    pub async fn state_snapshot_async(&self) -> StateSnapshot {
+        let mut client = self.client.clone();
+        let api = self.api.get().unwrap();
+
+        let response = match client.get_state_snapshot(GetStateSnapshotRequest {}).await {
+            Ok(response) => response.into_inner(),
+            Err(status) => {
+                eprintln!("ERROR: {status}");
+                return StateSnapshot::default();
+            }
+        };
+
+        let windows = response
+            .windows
+            .into_iter()
+            .map(|win| WindowSnapshot {
+                window: api.window.new_handle(win.window_id()),
+                geometry: win.geometry.map(|geo| Geometry {
+                    x: geo.x(),
+                    y: geo.y(),
+                    width: geo.width() as u32,
+                    height: geo.height() as u32,
+                }),
+                tags: win
+                    .tag_ids
+                    .iter()
+                    .map(|id| api.tag.new_handle(*id))
+                    .collect(),
+                focused: win.focused(),
+                floating: win.floating(),
+                class: win.class,
+                title: win.title,
+            })
+            .collect();
+
+        let outputs = response
+            .outputs
+            .into_iter()
+            .map(|op| OutputSnapshot {
+                output: api.output.new_handle(op.output_name()),
+                tags: op
+                    .tag_ids
+                    .iter()
+                    .map(|id| api.tag.new_handle(*id))
+                    .collect(),
+                active_tags: op
+                    .active_tag_ids
+                    .iter()
+                    .map(|id| api.tag.new_handle(*id))
+                    .collect(),
+                focused: op.focused(),
+            })
+            .collect();
+
+        StateSnapshot {
+            windows,
+            outputs,
+            focused_output: response
+                .focused_output_name
+                .map(|name| api.output.new_handle(name)),
+            focused_window: response
+                .focused_window_id
+                .map(|id| api.window.new_handle(id)),
+        }
+    }
+
+    /// Get the backend the compositor is running on.
+    ///
+    /// This lets you skip configuring things that aren't supported on a given backend,
+    /// like gamma and power management when running nested in a window.
+    ///
+    /// Returns `None` if the compositor could not be queried.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pinnacle_api::pinnacle::Backend;
+    ///
+    /// match pinnacle.backend() {
+    ///     Some(Backend::Window) => { /* Running nested */ }
+    ///     Some(Backend::Tty) => { /* Running in a tty */ }
+    ///     Some(Backend::Dummy) | None => (),
+    /// }
+    /// ```
+    pub fn backend(&self) -> Option<Backend> {
+        block_on_tokio(self.backend_async())
+    }
+
+    /// The async version of [`Pinnacle::backend`].
+    pub async fn backend_async(&self) -> Option<Backend> {
+        let mut client = self.client.clone();
+
+        let response = match client.get_backend(GetBackendRequest {}).await {
+            Ok(response) => response.into_inner(),
+            Err(status) => {
+                eprintln!("ERROR: {status}");
+                return None;
+            }
+        };
+
+        response.backend.unwrap_or_default().try_into().ok()
+    }
+
+    /// Get whether XWayland is running and which X11 display it's on.
+    ///
+    /// Returns `None` if the compositor could not be queried.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pinnacle_api::pinnacle::XwaylandInfo;
+    ///
+    /// if let Some(XwaylandInfo { display: Some(display), .. }) = pinnacle.xwayland_info() {
+    ///     println!("X11 apps can connect to DISPLAY=:{display}");
+    /// }
+    /// ```
+    pub fn xwayland_info(&self) -> Option<XwaylandInfo> {
+        block_on_tokio(self.xwayland_info_async())
+    }
+
+    /// The async version of [`Pinnacle::xwayland_info`].
+    pub async fn xwayland_info_async(&self) -> Option<XwaylandInfo> {
+        let mut client = self.client.clone();
+
+        let response = match client.get_xwayland_info(GetXwaylandInfoRequest {}).await {
+            Ok(response) => response.into_inner(),
+            Err(status) => {
+                eprintln!("ERROR: {status}");
+                return None;
+            }
+        };
+
+        Some(XwaylandInfo {
+            running: response.running(),
+            display: response.display,
+        })
+    }
+
+    /// List every surface the compositor knows about, for debugging.
+    ///
+    /// This includes windows that haven't mapped yet, layer surfaces, popups,
+    /// and subsurfaces, which makes it useful for figuring out why something
+    /// isn't showing up.
+    ///
+    /// Returns an empty list if the compositor could not be queried.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// for surface in pinnacle.list_surfaces() {
+    ///     println!(
+    ///         "{:?} {:?} from pid {:?}, mapped: {}",
+    ///         surface.surface_id, surface.role, surface.client_pid, surface.mapped
+    ///     );
+    /// }
+    /// ```
+    pub fn list_surfaces(&self) -> Vec<SurfaceInfo> {
+        block_on_tokio(self.list_surfaces_async())
+    }
+
+    /// The async version of [`Pinnacle::list_surfaces`].
+    pub async fn list_surfaces_async(&self) -> Vec<SurfaceInfo> {
+        let mut client = self.client.clone();
+        let api = self.api.get().unwrap();
+
+        let response = match client.list_surfaces(ListSurfacesRequest {}).await {
+            Ok(response) => response.into_inner(),
+            Err(status) => {
+                eprintln!("ERROR: {status}");
+                return Vec::new();
+            }
+        };
+
+        response
+            .surfaces
+            .into_iter()
+            .map(|surface| SurfaceInfo {
+                surface_id: surface.surface_id,
+                client_id: surface.client_id,
+                client_pid: surface.client_pid,
+                role: surface.role,
+                parent_surface_id: surface.parent_surface_id,
+                window: surface.window_id.map(|id| api.window.new_handle(id)),
+                layer_namespace: surface.layer_namespace,
+                layer: surface.layer,
+                output: surface.output_name.map(|name| api.output.new_handle(name)),
+                location: surface.geometry.and_then(|geo| Some((geo.x?, geo.y?))),
+                size: surface
+                    .geometry
+                    .and_then(|geo| Some((geo.width? as u32, geo.height? as u32))),
+                mapped: surface.mapped(),
+            })
+            .collect()
+    }
+
     pub(crate) async fn shutdown_watch(&self) -> Streaming<ShutdownWatchResponse> {
         let mut client = self.client.clone();
         client
@@ -81,3 +349,121 @@ impl Pinnacle {
             .ok_or("timed out".to_string())
     }
 }
+
+/// The backend the compositor is running on.
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, TryFromPrimitive)]
+pub enum Backend {
+    /// The compositor is running nested in a window
+    Window = 1,
+    /// The compositor is running in a tty
+    Tty,
+    /// The compositor is running without a display for testing
+    Dummy,
+}
+
+/// Information about XWayland.
+///
+/// See [`Pinnacle::xwayland_info`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct XwaylandInfo {
+    /// Whether XWayland is running
+    pub running: bool,
+    /// The X11 display number, as in `DISPLAY=:N`, if XWayland is running
+    pub display: Option<u32>,
+}
+
+/// A surface returned by [`Pinnacle::list_surfaces`].
+#[derive(Debug, Clone)]
+pub struct SurfaceInfo {
+    /// The surface's `wl_surface` object id. Ids are only unique within a client,
+    /// so use this together with `client_id` to tell surfaces apart.
+    ///
+    /// This is `None` for X11 windows that don't have a surface yet.
+    pub surface_id: Option<u32>,
+    /// An id for the surface's client that's unique while the compositor is running
+    pub client_id: Option<u64>,
+    /// The pid of the surface's client, if it could be found
+    pub client_pid: Option<i32>,
+    /// The surface's role, like `xdg_toplevel`, `xdg_popup`, `zwlr_layer_surface_v1`,
+    /// or `subsurface`
+    pub role: Option<String>,
+    /// The surface id of the toplevel or layer surface this popup belongs to,
+    /// or of the parent of this subsurface
+    pub parent_surface_id: Option<u32>,
+    /// The window this surface is the main surface of
+    pub window: Option<WindowHandle>,
+    /// The namespace of this layer surface
+    pub layer_namespace: Option<String>,
+    /// The layer of this layer surface: `background`, `bottom`, `top`, or `overlay`
+    pub layer: Option<String>,
+    /// The output this surface is on
+    pub output: Option<OutputHandle>,
+    /// The surface's location.
+    ///
+    /// Windows, layer surfaces, and popups are in the global space. This is `None` for
+    /// subsurfaces, windows that aren't in the space, and their popups.
+    pub location: Option<(i32, i32)>,
+    /// The surface's width and height
+    pub size: Option<(u32, u32)>,
+    /// Whether the surface has a buffer attached
+    pub mapped: bool,
+}
+
+/// The mime types advertised by the current selections.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct SelectionMimeTypes {
+    /// The mime types of the clipboard selection
+    pub clipboard: Vec<String>,
+    /// The mime types of the primary selection
+    pub primary: Vec<String>,
+    /// The mime types of the ongoing drag-and-drop, if there is one
+    pub dnd: Vec<String>,
+}
+
+/// A snapshot of key compositor state.
+///
+/// See [`Pinnacle::state_snapshot`].
+#[derive(Debug, Clone, Default)]
+pub struct StateSnapshot {
+    /// All windows
+    pub windows: Vec<WindowSnapshot>,
+    /// All outputs
+    pub outputs: Vec<OutputSnapshot>,
+    /// The focused output
+    pub focused_output: Option<OutputHandle>,
+    /// The focused window
+    pub focused_window: Option<WindowHandle>,
+}
+
+/// The state of a window in a [`StateSnapshot`].
+#[derive(Debug, Clone)]
+pub struct WindowSnapshot {
+    /// A handle to the window
+    pub window: WindowHandle,
+    /// The location and size of the window
+    pub geometry: Option<Geometry>,
+    /// All the tags on the window
+    pub tags: Vec<TagHandle>,
+    /// Whether the window is focused
+    pub focused: bool,
+    /// Whether the window is floating
+    pub floating: bool,
+    /// The window's class
+    pub class: Option<String>,
+    /// The window's title
+    pub title: Option<String>,
+}
+
+/// The state of an output in a [`StateSnapshot`].
+#[derive(Debug, Clone)]
+pub struct OutputSnapshot {
+    /// A handle to the output
+    pub output: OutputHandle,
+    /// All the tags on the output
+    pub tags: Vec<TagHandle>,
+    /// The active tags on the output
+    pub active_tags: Vec<TagHandle>,
+    /// Whether the output is focused
+    pub focused: bool,
+}
@@ -1,16 +1,24 @@
 //! Rendering management.
 
+use std::{sync::OnceLock, time::Duration};
+
+use futures::{future::BoxFuture, FutureExt, StreamExt};
 use pinnacle_api_defs::pinnacle::render::v0alpha1::{
-    render_service_client::RenderServiceClient, SetDownscaleFilterRequest, SetUpscaleFilterRequest,
+    render_service_client::RenderServiceClient, GetRendererInfoRequest, SetDownscaleFilterRequest,
+    SetFrameTimingsRequest, SetInactiveOutputDimRequest, SetUpscaleFilterRequest,
+    WatchFrameTimingsRequest,
 };
+use tokio::sync::mpsc::UnboundedSender;
 use tonic::transport::Channel;
 
-use crate::block_on_tokio;
+use crate::{block_on_tokio, output::OutputHandle, ApiModules};
 
 /// A struct that allows you to manage rendering.
 #[derive(Debug, Clone)]
 pub struct Render {
     client: RenderServiceClient<Channel>,
+    fut_sender: UnboundedSender<BoxFuture<'static, ()>>,
+    api: OnceLock<ApiModules>,
 }
 
 /// What filter to use when scaling.
@@ -26,12 +34,21 @@ pub enum ScalingFilter {
 }
 
 impl Render {
-    pub(crate) fn new(channel: Channel) -> Self {
+    pub(crate) fn new(
+        channel: Channel,
+        fut_sender: UnboundedSender<BoxFuture<'static, ()>>,
+    ) -> Self {
         Self {
             client: RenderServiceClient::new(channel),
+            fut_sender,
+            api: OnceLock::new(),
         }
     }
 
+    pub(crate) fn finish_init(&self, api: ApiModules) {
+        self.api.set(api).unwrap();
+    }
+
     /// Set the upscaling filter that will be used for rendering.
     ///
     /// # Examples
@@ -65,4 +82,185 @@ impl Render {
         }))
         .unwrap();
     }
+
+    /// Periodically report a summary of frame timings for each output, or stop with `None`.
+    ///
+    /// Every `interval`, the compositor will log how many frames each output rendered,
+    /// how long they took to render, and how many were directly scanned out.
+    /// Outputs that didn't render anything are reported with 0 frames.
+    /// This is useful to attach to performance bug reports.
+    ///
+    /// Summaries can also be received with [`Render::watch_frame_timings`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// render.set_frame_timings(Some(Duration::from_secs(5)));
+    /// ```
+    pub fn set_frame_timings(&self, interval: Option<Duration>) {
+        let mut client = self.client.clone();
+        block_on_tokio(client.set_frame_timings(SetFrameTimingsRequest {
+            interval_ms: interval.map(|interval| interval.as_millis() as u32),
+        }))
+        .unwrap();
+    }
+
+    /// Run a closure with every frame timing summary the compositor reports.
+    ///
+    /// Summaries are only reported after enabling them with [`Render::set_frame_timings`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// render.set_frame_timings(Some(Duration::from_secs(1)));
+    /// render.watch_frame_timings(|timings| {
+    ///     println!("{}: {} frames", timings.output.name(), timings.frames);
+    /// });
+    /// ```
+    pub fn watch_frame_timings(&self, mut for_each: impl FnMut(FrameTimings) + Send + 'static) {
+        let mut client = self.client.clone();
+        let api = self.api.get().unwrap().clone();
+
+        self.fut_sender
+            .send(
+                async move {
+                    let mut stream = match client
+                        .watch_frame_timings(WatchFrameTimingsRequest {})
+                        .await
+                    {
+                        Ok(response) => response.into_inner(),
+                        Err(status) => {
+                            eprintln!("ERROR: {status}");
+                            return;
+                        }
+                    };
+
+                    while let Some(Ok(response)) = stream.next().await {
+                        for_each(FrameTimings {
+                            output: api.output.new_handle(response.output_name()),
+                            frames: response.frames(),
+                            interval: Duration::from_millis(response.interval_ms() as u64),
+                            average_render_time: Duration::from_micros(
+                                response.average_render_time_us(),
+                            ),
+                            max_render_time: Duration::from_micros(response.max_render_time_us()),
+                            damage_rects: response.damage_rects,
+                            direct_scanout_frames: response.direct_scanout_frames(),
+                            cursor_plane_frames: response.cursor_plane_frames(),
+                        });
+                        tokio::task::yield_now().await;
+                    }
+                }
+                .boxed(),
+            )
+            .unwrap();
+    }
+
+    /// Dim outputs that don't have keyboard focus.
+    ///
+    /// This helps show which output is active when focus follows the keyboard.
+    /// `amount` goes from 0.0, which turns dimming off, to 1.0, which makes unfocused outputs
+    /// black. Dimming is off by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Darken unfocused outputs a little
+    /// render.set_inactive_output_dim(0.2);
+    /// ```
+    pub fn set_inactive_output_dim(&self, amount: f32) {
+        let mut client = self.client.clone();
+        if let Err(status) =
+            block_on_tokio(client.set_inactive_output_dim(SetInactiveOutputDimRequest {
+                amount: Some(amount),
+            }))
+        {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Get information about the renderer used for compositing.
+    ///
+    /// This is useful to attach to bug reports about rendering issues.
+    /// When running in a tty, this is the renderer of the primary GPU.
+    /// When running nested in a window, it's the host's GL implementation.
+    ///
+    /// Returns `None` if the renderer couldn't be queried.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// if let Some(info) = render.renderer_info() {
+    ///     println!("{} on {}", info.version, info.renderer);
+    /// }
+    /// ```
+    pub fn renderer_info(&self) -> Option<RendererInfo> {
+        block_on_tokio(self.renderer_info_async())
+    }
+
+    /// The async version of [`Render::renderer_info`].
+    pub async fn renderer_info_async(&self) -> Option<RendererInfo> {
+        let mut client = self.client.clone();
+
+        let response = match client.get_renderer_info(GetRendererInfoRequest {}).await {
+            Ok(response) => response.into_inner(),
+            Err(status) => {
+                eprintln!("ERROR: {status}");
+                return None;
+            }
+        };
+
+        Some(RendererInfo {
+            version: response.version().to_string(),
+            vendor: response.vendor().to_string(),
+            renderer: response.renderer().to_string(),
+            dmabuf_formats: response.dmabuf_formats,
+        })
+    }
+}
+
+/// Information about the renderer used for compositing.
+///
+/// See [`Render::renderer_info`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RendererInfo {
+    /// The OpenGL ES version string
+    pub version: String,
+    /// The company responsible for the GL implementation
+    pub vendor: String,
+    /// The GPU or software renderer name
+    pub renderer: String,
+    /// The fourcc codes of dmabuf formats the renderer can import
+    pub dmabuf_formats: Vec<String>,
+}
+
+/// A summary of the frames rendered on an output over a reporting interval.
+///
+/// See [`Render::watch_frame_timings`].
+#[derive(Debug, Clone)]
+pub struct FrameTimings {
+    /// The output the frames were rendered on
+    pub output: OutputHandle,
+    /// The number of frames rendered
+    pub frames: u32,
+    /// The length of the reporting interval
+    pub interval: Duration,
+    /// The average time it took to render a frame
+    pub average_render_time: Duration,
+    /// The longest time it took to render a frame
+    pub max_render_time: Duration,
+    /// The total number of damaged regions that were redrawn.
+    ///
+    /// This is only tracked when running nested in a window.
+    pub damage_rects: Option<u32>,
+    /// The number of frames that were directly scanned out from a client buffer
+    pub direct_scanout_frames: u32,
+    /// The number of frames where the cursor was on a hardware cursor plane
+    ///
+    /// Moving the pointer in these frames doesn't recomposite the rest of the output.
+    pub cursor_plane_frames: u32,
 }
@@ -193,6 +193,22 @@ signals! {
                 }
             },
         }
+        /// All outputs were disconnected, or an output connected after they were.
+        ///
+        /// Callbacks receive whether there are no outputs connected.
+        ///
+        /// Windows stay open while there are no outputs. Once one connects, windows
+        /// that were on outputs that didn't come back are moved to its active tags.
+        OutputHeadless = {
+            enum_name = Headless,
+            callback_type = Box<dyn FnMut(bool) + Send + 'static>,
+            client_request = output_headless,
+            on_response = |response, callbacks, _api| {
+                for callback in callbacks {
+                    callback(response.headless())
+                }
+            },
+        }
     }
     /// Signals relating to window events.
     WindowSignal => {
@@ -259,6 +275,7 @@ pub(crate) struct SignalState {
     pub(crate) output_disconnect: SignalData<OutputDisconnect>,
     pub(crate) output_resize: SignalData<OutputResize>,
     pub(crate) output_move: SignalData<OutputMove>,
+    pub(crate) output_headless: SignalData<OutputHeadless>,
 
     pub(crate) window_pointer_enter: SignalData<WindowPointerEnter>,
     pub(crate) window_pointer_leave: SignalData<WindowPointerLeave>,
@@ -283,6 +300,7 @@ impl SignalState {
             output_disconnect: SignalData::new(client.clone(), fut_sender.clone()),
             output_resize: SignalData::new(client.clone(), fut_sender.clone()),
             output_move: SignalData::new(client.clone(), fut_sender.clone()),
+            output_headless: SignalData::new(client.clone(), fut_sender.clone()),
             window_pointer_enter: SignalData::new(client.clone(), fut_sender.clone()),
             window_pointer_leave: SignalData::new(client.clone(), fut_sender.clone()),
             tag_active: SignalData::new(client.clone(), fut_sender.clone()),
@@ -294,6 +312,7 @@ impl SignalState {
         self.output_disconnect.api.set(api.clone()).unwrap();
         self.output_resize.api.set(api.clone()).unwrap();
         self.output_move.api.set(api.clone()).unwrap();
+        self.output_headless.api.set(api.clone()).unwrap();
         self.window_pointer_enter.api.set(api.clone()).unwrap();
         self.window_pointer_leave.api.set(api.clone()).unwrap();
         self.tag_active.api.set(api.clone()).unwrap();
@@ -304,6 +323,7 @@ impl SignalState {
         self.output_disconnect.reset();
         self.output_resize.reset();
         self.output_move.reset();
+        self.output_headless.reset();
         self.window_pointer_enter.reset();
         self.window_pointer_leave.reset();
         self.tag_active.reset();
@@ -32,12 +32,16 @@
 use std::sync::OnceLock;
 
 use futures::FutureExt;
+use num_enum::TryFromPrimitive;
 use pinnacle_api_defs::pinnacle::{
     tag::{
         self,
         v0alpha1::{
             tag_service_client::TagServiceClient, AddRequest, RemoveRequest, SetActiveRequest,
-            SwitchToRequest,
+            SetActiveTagsRequest, SetDefaultOutputTagsRequest, SetDisplayNameRequest,
+            SetFloatingHiddenRequest, SetGapsRequest, SetTilingDirectionRequest,
+            SetWindowLimitRequest, SwitchToPreviousRequest, SwitchToRequest,
+            ToggleTilingDirectionRequest,
         },
     },
     v0alpha1::SetOrToggle,
@@ -127,6 +131,29 @@ impl Tag {
             .collect()
     }
 
+    /// Set the tags that are added to newly connected outputs.
+    ///
+    /// When an output without any tags is plugged in, tags with these names are added to it
+    /// and the first one is activated, so the output is usable before your config sets it up.
+    /// Outputs that have saved tags from a previous connection keep those instead.
+    ///
+    /// Passing an empty list disables default tags.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Give new monitors tags 1-9
+    /// tag.set_default_output_tags(["1", "2", "3", "4", "5", "6", "7", "8", "9"]);
+    /// ```
+    pub fn set_default_output_tags(&self, tag_names: impl IntoIterator<Item = impl Into<String>>) {
+        let mut client = self.tag_client.clone();
+
+        let tag_names = tag_names.into_iter().map(Into::into).collect();
+
+        block_on_tokio(client.set_default_output_tags(SetDefaultOutputTagsRequest { tag_names }))
+            .unwrap();
+    }
+
     /// Get handles to all tags across all outputs.
     ///
     /// # Examples
@@ -235,6 +262,61 @@ impl Tag {
         block_on_tokio(client.remove(RemoveRequest { tag_ids })).unwrap();
     }
 
+    /// Set exactly which tags are active on `output`, deactivating all others.
+    ///
+    /// This happens all at once, so the output is only laid out once.
+    /// All tags must be on `output`; if any aren't, nothing happens and an error is printed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let op = output.get_focused()?;
+    /// let tags = tag.add(&op, ["1", "2", "3"]);
+    ///
+    /// // Display tags "1" and "3" and nothing else
+    /// tag.set_active_tags(&op, [tags[0].clone(), tags[2].clone()]);
+    /// ```
+    pub fn set_active_tags(
+        &self,
+        output: &OutputHandle,
+        tags: impl IntoIterator<Item = TagHandle>,
+    ) {
+        let tag_ids = tags.into_iter().map(|handle| handle.id).collect::<Vec<_>>();
+
+        let mut client = self.tag_client.clone();
+
+        if let Err(status) = block_on_tokio(client.set_active_tags(SetActiveTagsRequest {
+            output_name: Some(output.name.clone()),
+            tag_ids,
+        })) {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Switch `output` back to the tags that were active before its most recent tag switch.
+    ///
+    /// The whole set of previously active tags is restored, so this works with multiple
+    /// active tags. Calling this repeatedly toggles between the two most recent sets of tags.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let op = output.get_focused()?;
+    ///
+    /// tag.get("1")?.switch_to();
+    /// tag.get("2")?.switch_to();
+    /// tag.switch_to_previous(&op); // Displays tag "1" again
+    /// tag.switch_to_previous(&op); // Displays tag "2" again
+    /// ```
+    pub fn switch_to_previous(&self, output: &OutputHandle) {
+        let mut client = self.tag_client.clone();
+
+        block_on_tokio(client.switch_to_previous(SwitchToPreviousRequest {
+            output_name: Some(output.name.clone()),
+        }))
+        .unwrap();
+    }
+
     /// Connect to a tag signal.
     ///
     /// The compositor will fire off signals that your config can listen for and act upon.
@@ -354,6 +436,242 @@ impl TagHandle {
         .unwrap();
     }
 
+    /// Set the gaps the compositor puts around tiled windows while this tag is focused.
+    ///
+    /// `inner` is the gap around each individual window and `outer` is the gap inset from the
+    /// edges of the output, both in logical pixels. These are applied on top of whatever
+    /// the layout generator returns, so you may want to disable the layout's own gaps.
+    ///
+    /// If multiple tags are focused on an output, the gaps of the first one are used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// tag.get("1")?.set_gaps(4, 8);
+    /// ```
+    pub fn set_gaps(&self, inner: u32, outer: u32) {
+        let mut client = self.tag_client.clone();
+        block_on_tokio(client.set_gaps(SetGapsRequest {
+            tag_id: Some(self.id),
+            inner: Some(inner),
+            outer: Some(outer),
+            smart: None,
+        }))
+        .unwrap();
+    }
+
+    /// Set whether this tag's gaps are removed when there is only one tiled window.
+    ///
+    /// When enabled, a lone tiled window also loses the gap its layout generator reports
+    /// leaving around it with
+    /// [`LayoutGenerator::lone_window_gaps`](crate::layout::LayoutGenerator::lone_window_gaps).
+    /// Gaps come back as soon as a second window is tiled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // No gaps around a lone window, gaps around multiple windows
+    /// let coding = tag.get("1")?;
+    /// coding.set_gaps(4, 8);
+    /// coding.set_smart_gaps(true);
+    /// ```
+    pub fn set_smart_gaps(&self, smart: bool) {
+        let mut client = self.tag_client.clone();
+        block_on_tokio(client.set_gaps(SetGapsRequest {
+            tag_id: Some(self.id),
+            inner: None,
+            outer: None,
+            smart: Some(smart),
+        }))
+        .unwrap();
+    }
+
+    /// Hide or show floating windows with this tag.
+    ///
+    /// Hidden floating windows aren't displayed and can't get keyboard focus, so focus moves
+    /// to a tiled window. When they're shown again, focus returns to them if they had it.
+    /// Activating a hidden floating window, for example with
+    /// [`Window::focus_previous`][crate::window::Window::focus_previous], shows floating windows
+    /// again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Get the floating windows on tag "1" out of the way
+    /// tag.get("1")?.set_floating_hidden(true);
+    /// ```
+    pub fn set_floating_hidden(&self, hidden: bool) {
+        let mut client = self.tag_client.clone();
+        block_on_tokio(client.set_floating_hidden(SetFloatingHiddenRequest {
+            tag_id: Some(self.id),
+            set_or_toggle: Some(match hidden {
+                true => SetOrToggle::Set,
+                false => SetOrToggle::Unset,
+            } as i32),
+        }))
+        .unwrap();
+    }
+
+    /// Toggle whether floating windows with this tag are hidden.
+    ///
+    /// See [`TagHandle::set_floating_hidden`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Declutter the focused output
+    /// for tag in output.get_focused()?.tags() {
+    ///     if tag.active() == Some(true) {
+    ///         tag.toggle_floating_hidden();
+    ///     }
+    /// }
+    /// ```
+    pub fn toggle_floating_hidden(&self) {
+        let mut client = self.tag_client.clone();
+        block_on_tokio(client.set_floating_hidden(SetFloatingHiddenRequest {
+            tag_id: Some(self.id),
+            set_or_toggle: Some(SetOrToggle::Toggle as i32),
+        }))
+        .unwrap();
+    }
+
+    /// Limit how many windows can tile on this tag.
+    ///
+    /// New windows that would tile on this tag when it already has `limit` tiled windows
+    /// are handled according to `overflow`. Windows already on the tag are left alone.
+    ///
+    /// A `limit` of 0 is rejected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pinnacle_api::tag::OverflowPolicy;
+    ///
+    /// // Keep tag "1" focused on one task, sending other windows to tag "2"
+    /// tag.get("1")?.set_window_limit(2, OverflowPolicy::NextTag);
+    /// ```
+    pub fn set_window_limit(&self, limit: u32, overflow: OverflowPolicy) {
+        let mut client = self.tag_client.clone();
+        if let Err(status) = block_on_tokio(client.set_window_limit(SetWindowLimitRequest {
+            tag_id: Some(self.id),
+            limit: Some(limit),
+            overflow: Some(overflow as i32),
+        })) {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Remove the window limit set with [`TagHandle::set_window_limit`].
+    pub fn remove_window_limit(&self) {
+        let mut client = self.tag_client.clone();
+        block_on_tokio(client.set_window_limit(SetWindowLimitRequest {
+            tag_id: Some(self.id),
+            limit: None,
+            overflow: None,
+        }))
+        .unwrap();
+    }
+
+    /// Orient the split between the layout's master area and its stack on this tag.
+    ///
+    /// This overrides which side [`MasterStackLayout`][crate::layout::MasterStackLayout]
+    /// puts the master area on, keeping it on the same end. For example, a layout with the
+    /// master area on the left puts it at the top when vertical. This lets the same layout work
+    /// on portrait outputs. Changing it relayouts immediately.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pinnacle_api::tag::TilingDirection;
+    ///
+    /// // Stack windows top to bottom on a portrait monitor
+    /// tag.get("1")?.set_tiling_direction(TilingDirection::Vertical);
+    /// ```
+    pub fn set_tiling_direction(&self, direction: TilingDirection) {
+        let mut client = self.tag_client.clone();
+        if let Err(status) =
+            block_on_tokio(client.set_tiling_direction(SetTilingDirectionRequest {
+                tag_id: Some(self.id),
+                direction: Some(direction as i32),
+            }))
+        {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Remove the tiling direction set with [`TagHandle::set_tiling_direction`],
+    /// letting the layout decide again.
+    pub fn remove_tiling_direction(&self) {
+        let mut client = self.tag_client.clone();
+        if let Err(status) =
+            block_on_tokio(client.set_tiling_direction(SetTilingDirectionRequest {
+                tag_id: Some(self.id),
+                direction: None,
+            }))
+        {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Flip this tag's tiling direction between horizontal and vertical.
+    ///
+    /// Tags without a tiling direction are treated as horizontal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Flip the layout on the first active tag
+    /// if let Some(tag) = output.get_focused()?.tags().find(|tag| tag.active() == Some(true)) {
+    ///     tag.toggle_tiling_direction();
+    /// }
+    /// ```
+    pub fn toggle_tiling_direction(&self) {
+        let mut client = self.tag_client.clone();
+        if let Err(status) = block_on_tokio(client.toggle_tiling_direction(
+            ToggleTilingDirectionRequest {
+                tag_id: Some(self.id),
+            },
+        )) {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Set the name bars and other clients show for this tag.
+    ///
+    /// This doesn't change the tag's name, so it can still be found with [`Tag::get`]
+    /// using the name it was added with. Display names don't need to be unique.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let tags = tag.add(&output.get_focused()?, ["1", "2", "3"]);
+    ///
+    /// tags[0].set_display_name("www");
+    /// tags[1].set_display_name("code");
+    /// tags[2].set_display_name("chat");
+    /// ```
+    pub fn set_display_name(&self, display_name: impl ToString) {
+        let mut client = self.tag_client.clone();
+        if let Err(status) = block_on_tokio(client.set_display_name(SetDisplayNameRequest {
+            tag_id: Some(self.id),
+            display_name: Some(display_name.to_string()),
+        })) {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Remove the display name set with [`TagHandle::set_display_name`],
+    /// going back to showing the tag's name.
+    pub fn remove_display_name(&self) {
+        let mut client = self.tag_client.clone();
+        if let Err(status) = block_on_tokio(client.set_display_name(SetDisplayNameRequest {
+            tag_id: Some(self.id),
+            display_name: None,
+        })) {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
     /// Remove this tag from its output.
     ///
     /// # Examples
@@ -386,6 +704,16 @@ impl TagHandle {
     ///     active,
     ///     name,
     ///     output,
+    ///     windows,
+    ///     inner_gaps,
+    ///     outer_gaps,
+    ///     smart_gaps,
+    ///     urgent,
+    ///     floating_hidden,
+    ///     window_limit,
+    ///     overflow_policy,
+    ///     tiling_direction,
+    ///     display_name,
     /// } = tag.get("1", None)?.props();
     /// ```
     pub fn props(&self) -> TagProperties {
@@ -416,6 +744,19 @@ impl TagHandle {
                 .into_iter()
                 .map(|id| window.new_handle(id))
                 .collect(),
+            inner_gaps: response.inner_gaps,
+            outer_gaps: response.outer_gaps,
+            smart_gaps: response.smart_gaps,
+            urgent: response.urgent,
+            floating_hidden: response.floating_hidden,
+            window_limit: response.window_limit,
+            overflow_policy: response
+                .overflow_policy
+                .and_then(|policy| policy.try_into().ok()),
+            tiling_direction: response
+                .tiling_direction
+                .and_then(|direction| direction.try_into().ok()),
+            display_name: response.display_name,
         }
     }
 
@@ -466,6 +807,103 @@ impl TagHandle {
     pub async fn windows_async(&self) -> Vec<WindowHandle> {
         self.props_async().await.windows
     }
+
+    /// Get the gap around each tiled window on this tag.
+    ///
+    /// Shorthand for `self.props().inner_gaps`.
+    pub fn inner_gaps(&self) -> Option<u32> {
+        self.props().inner_gaps
+    }
+
+    /// The async version of [`TagHandle::inner_gaps`].
+    pub async fn inner_gaps_async(&self) -> Option<u32> {
+        self.props_async().await.inner_gaps
+    }
+
+    /// Get the gap tiled windows on this tag are inset from the edges of the output.
+    ///
+    /// Shorthand for `self.props().outer_gaps`.
+    pub fn outer_gaps(&self) -> Option<u32> {
+        self.props().outer_gaps
+    }
+
+    /// The async version of [`TagHandle::outer_gaps`].
+    pub async fn outer_gaps_async(&self) -> Option<u32> {
+        self.props_async().await.outer_gaps
+    }
+
+    /// Get whether this tag's gaps are removed when there is only one tiled window.
+    ///
+    /// Shorthand for `self.props().smart_gaps`.
+    pub fn smart_gaps(&self) -> Option<bool> {
+        self.props().smart_gaps
+    }
+
+    /// The async version of [`TagHandle::smart_gaps`].
+    pub async fn smart_gaps_async(&self) -> Option<bool> {
+        self.props_async().await.smart_gaps
+    }
+
+    /// Get whether any window with this tag wants attention.
+    ///
+    /// Shorthand for `self.props().urgent`.
+    pub fn urgent(&self) -> Option<bool> {
+        self.props().urgent
+    }
+
+    /// The async version of [`TagHandle::urgent`].
+    pub async fn urgent_async(&self) -> Option<bool> {
+        self.props_async().await.urgent
+    }
+
+    /// Get whether floating windows with this tag are hidden.
+    ///
+    /// Shorthand for `self.props().floating_hidden`.
+    pub fn floating_hidden(&self) -> Option<bool> {
+        self.props().floating_hidden
+    }
+
+    /// The async version of [`TagHandle::floating_hidden`].
+    pub async fn floating_hidden_async(&self) -> Option<bool> {
+        self.props_async().await.floating_hidden
+    }
+
+    /// Get the most windows that can tile on this tag, or `None` if it's unlimited.
+    ///
+    /// Shorthand for `self.props().window_limit`.
+    pub fn window_limit(&self) -> Option<u32> {
+        self.props().window_limit
+    }
+
+    /// The async version of [`TagHandle::window_limit`].
+    pub async fn window_limit_async(&self) -> Option<u32> {
+        self.props_async().await.window_limit
+    }
+
+    /// Get how the layout's master area is split from the stack on this tag,
+    /// or `None` if the layout decides.
+    ///
+    /// Shorthand for `self.props().tiling_direction`.
+    pub fn tiling_direction(&self) -> Option<TilingDirection> {
+        self.props().tiling_direction
+    }
+
+    /// The async version of [`TagHandle::tiling_direction`].
+    pub async fn tiling_direction_async(&self) -> Option<TilingDirection> {
+        self.props_async().await.tiling_direction
+    }
+
+    /// Get the name shown for this tag, or `None` if it doesn't have one.
+    ///
+    /// Shorthand for `self.props().display_name`.
+    pub fn display_name(&self) -> Option<String> {
+        self.props().display_name
+    }
+
+    /// The async version of [`TagHandle::display_name`].
+    pub async fn display_name_async(&self) -> Option<String> {
+        self.props_async().await.display_name
+    }
 }
 
 /// Properties of a tag.
@@ -479,4 +917,52 @@ pub struct TagProperties {
     pub output: Option<OutputHandle>,
     /// The windows that have this tag
     pub windows: Vec<WindowHandle>,
+    /// The gap the compositor puts around each tiled window
+    pub inner_gaps: Option<u32>,
+    /// The gap the compositor insets tiled windows from the edges of the output
+    pub outer_gaps: Option<u32>,
+    /// Whether gaps are removed when there is only one tiled window
+    pub smart_gaps: Option<bool>,
+    /// Whether any window with this tag wants attention
+    pub urgent: Option<bool>,
+    /// Whether floating windows with this tag are hidden
+    pub floating_hidden: Option<bool>,
+    /// The most windows that can tile on this tag, or `None` if unlimited
+    pub window_limit: Option<u32>,
+    /// What happens to new windows when this tag is full, or `None` if unlimited
+    pub overflow_policy: Option<OverflowPolicy>,
+    /// How the layout's master area is split from the stack, or `None` if the layout decides
+    pub tiling_direction: Option<TilingDirection>,
+    /// The name shown for this tag, or `None` if it doesn't have one
+    pub display_name: Option<String>,
+}
+
+/// Which way a layout splits its master area from the stack.
+///
+/// See [`TagHandle::set_tiling_direction`].
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, TryFromPrimitive)]
+pub enum TilingDirection {
+    /// The master area is beside the stack
+    Horizontal = 1,
+    /// The master area is above or below the stack
+    Vertical,
+}
+
+/// What happens to a new window that would tile on a tag that's at its window limit.
+///
+/// See [`TagHandle::set_window_limit`].
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, TryFromPrimitive)]
+pub enum OverflowPolicy {
+    /// Make the window floating
+    Float = 1,
+    /// Move the window to the next tag on the same output that isn't full,
+    /// or make it floating if there is none
+    NextTag,
+    /// Close the window.
+    ///
+    /// The window is hidden while it closes. If it doesn't close within a few seconds,
+    /// it floats on the tag instead.
+    Reject,
 }
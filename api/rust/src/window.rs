@@ -12,7 +12,7 @@
 //!
 //! This module also allows you to set window rules; see the [rules] module for more information.
 
-use std::sync::OnceLock;
+use std::{sync::OnceLock, time::Duration};
 
 use futures::FutureExt;
 use num_enum::TryFromPrimitive;
@@ -21,10 +21,20 @@ use pinnacle_api_defs::pinnacle::{
     window::{
         self,
         v0alpha1::{
-            window_service_client::WindowServiceClient, AddWindowRuleRequest, CloseRequest,
-            GetRequest, MoveGrabRequest, MoveToTagRequest, RaiseRequest, ResizeGrabRequest,
-            SetFloatingRequest, SetFocusedRequest, SetFullscreenRequest, SetMaximizedRequest,
-            SetTagRequest,
+            window_service_client::WindowServiceClient, AddWindowRuleRequest, CancelGrabRequest,
+            CaptureRequest, CloseRequest, CopyToTagRequest, EvaluateWindowRuleConditionRequest,
+            FocusPreviousRequest, FocusUrgentRequest, GetGrabRequest, GetRequest,
+            GetSurfaceInfoRequest, GetWindowRulesRequest, JumpToWindowRequest, LowerRequest,
+            MoveGrabRequest, MoveToTagRequest, OpenNextOnFocusedTagRequest, PlaceWindowRequest,
+            RaiseRequest, ReapplyWindowRulesRequest, RemoveWindowRuleRequest, ResizeGrabRequest,
+            SelectWindowRequest, SetActivatedRequest, SetFloatingGeometryRequest,
+            SetFloatingRequest, SetFocusNewWindowsRequest, SetFocusedRequest, SetFullscreenRequest,
+            SetGeometryPinnedRequest, SetLabelRequest, SetLastWindowFallbackRequest,
+            SetMaximizeGapsRequest, SetMaximizedRequest, SetMinimizedRequest,
+            SetRelativeGeometryRequest, SetScaleRequest, SetSkipCycleRequest,
+            SetSkipTaskbarRequest, SetTagRequest, SetTiledEdgesEnabledRequest,
+            SetUnfullscreenOnFocusLossRequest, SetUrgencyTimeoutRequest, SnapWindowRequest,
+            ToggleRecentTagRequest,
         },
     },
 };
@@ -33,13 +43,14 @@ use tonic::transport::Channel;
 use crate::{
     block_on_tokio,
     input::MouseButton,
+    output::OutputHandle,
     signal::{SignalHandle, WindowSignal},
     tag::TagHandle,
     util::{Batch, Geometry},
     ApiModules,
 };
 
-use self::rules::{WindowRule, WindowRuleCondition};
+use self::rules::{ConditionEvaluation, WindowRule, WindowRuleCondition, WindowRuleId};
 
 pub mod rules;
 
@@ -77,6 +88,9 @@ impl Window {
     /// This will begin moving the window under the pointer using the specified [`MouseButton`].
     /// The button must be held down at the time this method is called for the move to start.
     ///
+    /// If the pointer isn't over a window, such as when it's over a layer surface,
+    /// an error is printed and nothing happens.
+    ///
     /// This is intended to be used with [`Input::mousebind`][crate::input::Input::mousebind].
     ///
     /// # Examples
@@ -93,6 +107,36 @@ impl Window {
         let mut client = self.window_client.clone();
         if let Err(status) = block_on_tokio(client.move_grab(MoveGrabRequest {
             button: Some(button as u32),
+            float_tiled: None,
+        })) {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Start moving the window with the mouse, making it floating if it's tiled.
+    ///
+    /// This works like [`Window::begin_move`], except a tiled window is pulled out of the layout
+    /// at its current size and follows the pointer instead of swapping places with other
+    /// tiled windows. The remaining tiled windows are laid out again.
+    ///
+    /// Dropping the window over a tiled window on the same output tiles it at that window's
+    /// position. Dropping it anywhere else leaves it floating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pinnacle_api::input::{Mod, MouseButton, MouseEdge};
+    ///
+    /// // Set `Super + Shift + left click` to drag a window out of the layout
+    /// input.mousebind([Mod::Super, Mod::Shift], MouseButton::Left, MouseEdge::Press, || {
+    ///     window.begin_move_floating(MouseButton::Left);
+    /// });
+    /// ```
+    pub fn begin_move_floating(&self, button: MouseButton) {
+        let mut client = self.window_client.clone();
+        if let Err(status) = block_on_tokio(client.move_grab(MoveGrabRequest {
+            button: Some(button as u32),
+            float_tiled: Some(true),
         })) {
             eprintln!("ERROR: {status}");
         }
@@ -103,6 +147,9 @@ impl Window {
     /// This will begin resizing the window under the pointer using the specified [`MouseButton`].
     /// The button must be held down at the time this method is called for the resize to start.
     ///
+    /// If the pointer isn't over a window, such as when it's over a layer surface,
+    /// an error is printed and nothing happens.
+    ///
     /// This is intended to be used with [`Input::mousebind`][crate::input::Input::mousebind].
     ///
     /// # Examples
@@ -117,10 +164,101 @@ impl Window {
     /// ```
     pub fn begin_resize(&self, button: MouseButton) {
         let mut client = self.window_client.clone();
-        block_on_tokio(client.resize_grab(ResizeGrabRequest {
+        if let Err(status) = block_on_tokio(client.resize_grab(ResizeGrabRequest {
             button: Some(button as u32),
-        }))
-        .unwrap();
+            border_width: None,
+        })) {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Start resizing the window with the mouse if the pointer is near its edges.
+    ///
+    /// Unlike [`begin_resize`][Self::begin_resize], which resizes from the corner of
+    /// the quadrant the pointer is in, this only starts resizing if the pointer is within
+    /// `border_width` logical pixels of the window's edges. The window is then resized from
+    /// the edge or corner the pointer is near. Nothing happens if the pointer is
+    /// in the interior of the window.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pinnacle_api::input::{Mod, MouseButton, MouseEdge};
+    ///
+    /// // Resize windows by dragging within 16 pixels of their edges with `Super + left click`
+    /// input.mousebind([Mod::Super], MouseButton::Left, MouseEdge::Press, || {
+    ///     window.begin_resize_from_border(MouseButton::Left, 16);
+    /// });
+    /// ```
+    pub fn begin_resize_from_border(&self, button: MouseButton, border_width: u32) {
+        let mut client = self.window_client.clone();
+        if let Err(status) = block_on_tokio(client.resize_grab(ResizeGrabRequest {
+            button: Some(button as u32),
+            border_width: Some(border_width),
+        })) {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Cancel the window move or resize that is in progress.
+    ///
+    /// A moved floating window goes back to where it was when the move started, and a resized
+    /// window goes back to its original size. A window that was floated by
+    /// [`begin_move_floating`][Self::begin_move_floating] is tiled again. Tiled windows that swapped
+    /// places with other windows while being moved stay where they are.
+    ///
+    /// Nothing happens if no window is being moved or resized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pinnacle_api::xkbcommon::xkb::Keysym;
+    ///
+    /// // Cancel moves and resizes with `Escape`
+    /// input.keybind([], Keysym::Escape, || {
+    ///     window.cancel_grab();
+    /// });
+    /// ```
+    pub fn cancel_grab(&self) {
+        let mut client = self.window_client.clone();
+        if let Err(status) = block_on_tokio(client.cancel_grab(CancelGrabRequest {})) {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Get the window move or resize that is in progress, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pinnacle_api::window::GrabKind;
+    ///
+    /// if let Some(grab) = window.active_grab() {
+    ///     if grab.kind == GrabKind::Move {
+    ///         println!("{:?} is being moved", grab.window.class());
+    ///     }
+    /// }
+    /// ```
+    pub fn active_grab(&self) -> Option<ActiveGrab> {
+        block_on_tokio(self.active_grab_async())
+    }
+
+    /// The async version of [`Window::active_grab`].
+    pub async fn active_grab_async(&self) -> Option<ActiveGrab> {
+        let mut client = self.window_client.clone();
+
+        let response = match client.get_grab(GetGrabRequest {}).await {
+            Ok(response) => response.into_inner(),
+            Err(status) => {
+                eprintln!("ERROR: {status}");
+                return None;
+            }
+        };
+
+        let kind = GrabKind::try_from(response.kind?).ok()?;
+        let window = self.new_handle(response.window_id?);
+
+        Some(ActiveGrab { kind, window })
     }
 
     /// Get all windows.
@@ -167,368 +305,1660 @@ impl Window {
         )
     }
 
-    /// Add a window rule.
+    /// Get the window with the given label.
     ///
-    /// A window rule is a set of criteria that a window must open with.
-    /// For it to apply, a [`WindowRuleCondition`] must evaluate to true for the window in question.
+    /// See [`WindowHandle::set_label`].
     ///
-    /// See the [`rules`] module for more information.
-    pub fn add_window_rule(&self, cond: WindowRuleCondition, rule: WindowRule) {
-        let mut client = self.window_client.clone();
-
-        block_on_tokio(client.add_window_rule(AddWindowRuleRequest {
-            cond: Some(cond.0),
-            rule: Some(rule.0),
-        }))
-        .unwrap();
-    }
-
-    /// Connect to a window signal.
+    /// # Examples
     ///
-    /// The compositor will fire off signals that your config can listen for and act upon.
-    /// You can pass in a [`WindowSignal`] along with a callback and it will get run
-    /// with the necessary arguments every time a signal of that type is received.
-    pub fn connect_signal(&self, signal: WindowSignal) -> SignalHandle {
-        let mut signal_state = block_on_tokio(self.api.get().unwrap().signal.write());
-
-        match signal {
-            WindowSignal::PointerEnter(f) => signal_state.window_pointer_enter.add_callback(f),
-            WindowSignal::PointerLeave(f) => signal_state.window_pointer_leave.add_callback(f),
-        }
-    }
-}
-
-/// A handle to a window.
-///
-/// This allows you to manipulate the window and get its properties.
-#[derive(Debug, Clone)]
-pub struct WindowHandle {
-    id: u32,
-    window_client: WindowServiceClient<Channel>,
-    api: ApiModules,
-}
-
-impl PartialEq for WindowHandle {
-    fn eq(&self, other: &Self) -> bool {
-        self.id == other.id
+    /// ```
+    /// let editor = window.get_by_label("main-editor")?;
+    /// ```
+    pub fn get_by_label(&self, label: impl Into<String>) -> Option<WindowHandle> {
+        block_on_tokio(self.get_by_label_async(label))
     }
-}
-
-impl Eq for WindowHandle {}
 
-impl std::hash::Hash for WindowHandle {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.id.hash(state);
+    /// The async version of [`Window::get_by_label`].
+    pub async fn get_by_label_async(&self, label: impl Into<String>) -> Option<WindowHandle> {
+        let label: String = label.into();
+        self.get_all_async().await.batch_find(
+            |win| win.label_async().boxed(),
+            |win_label| win_label.as_ref() == Some(&label),
+        )
     }
-}
-
-/// Whether a window is fullscreen, maximized, or neither.
-#[repr(i32)]
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, TryFromPrimitive)]
-pub enum FullscreenOrMaximized {
-    /// The window is neither fullscreen nor maximized
-    Neither = 1,
-    /// The window is fullscreen
-    Fullscreen,
-    /// The window is maximized
-    Maximized,
-}
 
-/// Properties of a window.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
-pub struct WindowProperties {
-    /// The location and size of the window
-    pub geometry: Option<Geometry>,
-    /// The window's class
-    pub class: Option<String>,
-    /// The window's title
-    pub title: Option<String>,
-    /// Whether the window is focused or not
-    pub focused: Option<bool>,
-    /// Whether the window is floating or not
+    /// Focus the window that most recently became urgent.
     ///
-    /// Note that a window can still be floating even if it's fullscreen or maximized; those two
-    /// states will just override the floating state.
-    pub floating: Option<bool>,
-    /// Whether the window is fullscreen, maximized, or neither
-    pub fullscreen_or_maximized: Option<FullscreenOrMaximized>,
-    /// All the tags on the window
-    pub tags: Vec<TagHandle>,
-}
-
-impl WindowHandle {
-    /// Send a close request to this window.
+    /// Windows become urgent when they want attention, for example when they're denied
+    /// activation by focus stealing prevention. This switches to the window's tag if it
+    /// isn't on an active one, then focuses and raises it, clearing its urgency.
     ///
-    /// If the window is unresponsive, it may not close.
+    /// This does nothing if there are no urgent windows.
     ///
     /// # Examples
     ///
     /// ```
-    /// // Close the focused window
-    /// window.get_focused()?.close()
+    /// window.focus_urgent();
     /// ```
-    pub fn close(&self) {
-        let mut window_client = self.window_client.clone();
-        block_on_tokio(window_client.close(CloseRequest {
-            window_id: Some(self.id),
-        }))
-        .unwrap();
+    pub fn focus_urgent(&self) {
+        let mut client = self.window_client.clone();
+        block_on_tokio(client.focus_urgent(FocusUrgentRequest {})).unwrap();
     }
 
-    /// Set this window to fullscreen or not.
+    /// Focus the previously focused window.
     ///
-    /// If it is maximized, setting it to fullscreen will remove the maximized state.
+    /// Calling this repeatedly toggles focus between the two most recently focused windows.
+    /// Each output keeps its own history, so this picks from windows focused on the focused
+    /// output. If the previous window is on an inactive tag or has since moved to another
+    /// output, that tag and output are switched to. Closed windows are skipped.
     ///
     /// # Examples
     ///
     /// ```
-    /// // Set the focused window to fullscreen.
-    /// window.get_focused()?.set_fullscreen(true);
+    /// // Bounce between an editor and a terminal
+    /// window.focus_previous();
     /// ```
-    pub fn set_fullscreen(&self, set: bool) {
+    pub fn focus_previous(&self) {
         let mut client = self.window_client.clone();
-        block_on_tokio(client.set_fullscreen(SetFullscreenRequest {
-            window_id: Some(self.id),
-            set_or_toggle: Some(match set {
-                true => SetOrToggle::Set,
-                false => SetOrToggle::Unset,
-            } as i32),
-        }))
-        .unwrap();
+        block_on_tokio(client.focus_previous(FocusPreviousRequest {})).unwrap();
     }
 
-    /// Toggle this window between fullscreen and not.
+    /// Jump to the most recently focused window with the given class and/or title.
     ///
-    /// If it is maximized, toggling it to fullscreen will remove the maximized state.
+    /// This switches to the window's tag if it isn't on an active one, then focuses and raises it.
+    /// Windows must match both `class` and `title` if both are given.
+    ///
+    /// If the focused window already matches and `cycle` is true, the least recently focused
+    /// other match is jumped to instead, so calling this repeatedly cycles through all matches.
+    ///
+    /// Returns the window that was jumped to, or `None` if no window matched.
     ///
     /// # Examples
     ///
     /// ```
-    /// // Toggle the focused window to and from fullscreen.
-    /// window.get_focused()?.toggle_fullscreen();
+    /// // Raise or run Firefox
+    /// if window.jump_to(Some("firefox"), None, true).is_none() {
+    ///     process.spawn(["firefox"]);
+    /// }
     /// ```
-    pub fn toggle_fullscreen(&self) {
+    pub fn jump_to(
+        &self,
+        class: Option<&str>,
+        title: Option<&str>,
+        cycle: bool,
+    ) -> Option<WindowHandle> {
+        block_on_tokio(self.jump_to_async(class, title, cycle))
+    }
+
+    /// The async version of [`Window::jump_to`].
+    pub async fn jump_to_async(
+        &self,
+        class: Option<&str>,
+        title: Option<&str>,
+        cycle: bool,
+    ) -> Option<WindowHandle> {
         let mut client = self.window_client.clone();
-        block_on_tokio(client.set_fullscreen(SetFullscreenRequest {
-            window_id: Some(self.id),
-            set_or_toggle: Some(SetOrToggle::Toggle as i32),
-        }))
-        .unwrap();
+
+        let response = match client
+            .jump_to_window(JumpToWindowRequest {
+                class: class.map(String::from),
+                title: title.map(String::from),
+                cycle: Some(cycle),
+            })
+            .await
+        {
+            Ok(response) => response.into_inner(),
+            Err(status) => {
+                eprintln!("ERROR: {status}");
+                return None;
+            }
+        };
+
+        response.window_id.map(|id| self.new_handle(id))
     }
 
-    /// Set this window to maximized or not.
+    /// Pick a window with the keyboard.
     ///
-    /// If it is fullscreen, setting it to maximized will remove the fullscreen state.
+    /// This draws a letter over every visible window and waits for one of them to be typed.
+    /// Keys don't go to windows or trigger keybinds until a window is picked.
+    ///
+    /// Returns the window whose letter was typed, or `None` if Escape was pressed.
     ///
     /// # Examples
     ///
     /// ```
-    /// // Set the focused window to maximized.
-    /// window.get_focused()?.set_maximized(true);
+    /// use pinnacle_api::input::Mod;
+    ///
+    /// // Focus a window by its hint with Super+f
+    /// input.keybind([Mod::Super], 'f', || {
+    ///     if let Some(win) = window.select() {
+    ///         win.set_focused(true);
+    ///     }
+    /// });
     /// ```
-    pub fn set_maximized(&self, set: bool) {
+    pub fn select(&self) -> Option<WindowHandle> {
+        block_on_tokio(self.select_async())
+    }
+
+    /// The async version of [`Window::select`].
+    pub async fn select_async(&self) -> Option<WindowHandle> {
         let mut client = self.window_client.clone();
-        block_on_tokio(client.set_maximized(SetMaximizedRequest {
-            window_id: Some(self.id),
-            set_or_toggle: Some(match set {
-                true => SetOrToggle::Set,
-                false => SetOrToggle::Unset,
-            } as i32),
-        }))
-        .unwrap();
+
+        let response = match client.select_window(SelectWindowRequest {}).await {
+            Ok(response) => response.into_inner(),
+            Err(status) => {
+                eprintln!("ERROR: {status}");
+                return None;
+            }
+        };
+
+        response.window_id.map(|id| self.new_handle(id))
     }
 
-    /// Toggle this window between maximized and not.
+    /// Add a window rule.
     ///
-    /// If it is fullscreen, toggling it to maximized will remove the fullscreen state.
+    /// A window rule is a set of criteria that a window must open with.
+    /// For it to apply, a [`WindowRuleCondition`] must evaluate to true for the window in question.
+    ///
+    /// See the [`rules`] module for more information.
+    ///
+    /// A rule with a non-positive size, or with only one of its width and height or x and y set,
+    /// is rejected and not added.
+    ///
+    /// Returns the id of the added rule, which can be passed to
+    /// [`remove_window_rule`][Self::remove_window_rule], or `None` if the rule was rejected.
+    pub fn add_window_rule(
+        &self,
+        cond: WindowRuleCondition,
+        rule: WindowRule,
+    ) -> Option<WindowRuleId> {
+        let mut client = self.window_client.clone();
+
+        match block_on_tokio(client.add_window_rule(AddWindowRuleRequest {
+            cond: Some(cond.0),
+            rule: Some(rule.0),
+        })) {
+            Ok(response) => response.into_inner().rule_id.map(WindowRuleId),
+            Err(status) => {
+                eprintln!("ERROR: {status}");
+                None
+            }
+        }
+    }
+
+    /// Get all added window rules along with their ids, in the order they apply.
     ///
     /// # Examples
     ///
     /// ```
-    /// // Toggle the focused window to and from maximized.
-    /// window.get_focused()?.toggle_maximized();
+    /// for (id, cond, rule) in window.get_window_rules() {
+    ///     println!("{id:?}: {cond:?} => {rule:?}");
+    /// }
+    /// ```
+    pub fn get_window_rules(&self) -> Vec<(WindowRuleId, WindowRuleCondition, WindowRule)> {
+        block_on_tokio(self.get_window_rules_async())
+    }
+
+    /// The async version of [`get_window_rules`][Self::get_window_rules].
+    pub async fn get_window_rules_async(
+        &self,
+    ) -> Vec<(WindowRuleId, WindowRuleCondition, WindowRule)> {
+        let mut client = self.window_client.clone();
+
+        client
+            .get_window_rules(GetWindowRulesRequest {})
+            .await
+            .unwrap()
+            .into_inner()
+            .rules
+            .into_iter()
+            .filter_map(|entry| {
+                Some((
+                    WindowRuleId(entry.rule_id?),
+                    WindowRuleCondition(entry.cond.unwrap_or_default()),
+                    WindowRule(entry.rule.unwrap_or_default()),
+                ))
+            })
+            .collect()
+    }
+
+    /// Remove a previously added window rule.
+    ///
+    /// Windows that the rule already applied to are not affected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pinnacle_api::window::rules::{WindowRule, WindowRuleCondition};
+    ///
+    /// let rule_id = window.add_window_rule(
+    ///     WindowRuleCondition::new().classes(["firefox"]),
+    ///     WindowRule::new().floating(true),
+    /// );
+    ///
+    /// if let Some(rule_id) = rule_id {
+    ///     window.remove_window_rule(rule_id);
+    /// }
+    /// ```
+    pub fn remove_window_rule(&self, rule_id: WindowRuleId) {
+        let mut client = self.window_client.clone();
+
+        block_on_tokio(client.remove_window_rule(RemoveWindowRuleRequest {
+            rule_id: Some(rule_id.0),
+        }))
+        .unwrap();
+    }
+
+    /// Apply window rules to all open windows again, as if they just opened.
+    ///
+    /// Window rules normally only apply when a window opens, so this lets you see the effects
+    /// of added rules without reopening windows. Applying rules more than once has the same
+    /// effect as applying them once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pinnacle_api::window::rules::{WindowRule, WindowRuleCondition};
+    ///
+    /// window.add_window_rule(
+    ///     WindowRuleCondition::new().classes(["firefox"]),
+    ///     WindowRule::new().floating(true),
+    /// );
+    ///
+    /// // Float Firefox windows that are already open
+    /// window.reapply_window_rules();
+    /// ```
+    pub fn reapply_window_rules(&self) {
+        let mut client = self.window_client.clone();
+        if let Err(status) =
+            block_on_tokio(client.reapply_window_rules(ReapplyWindowRulesRequest {}))
+        {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Set whether new windows get keyboard focus when they open.
+    ///
+    /// This is on by default. When off, new windows open without taking focus from the
+    /// currently focused window. Use [`WindowRule::focus_on_open`] to override this for
+    /// specific windows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Don't let new windows steal focus
+    /// window.set_focus_new_windows(false);
+    /// ```
+    pub fn set_focus_new_windows(&self, focus: bool) {
+        let mut client = self.window_client.clone();
+
+        block_on_tokio(
+            client.set_focus_new_windows(SetFocusNewWindowsRequest { focus: Some(focus) }),
+        )
+        .unwrap();
+    }
+
+    /// Set whether fullscreen windows leave fullscreen when they lose keyboard focus.
+    ///
+    /// This is off by default, so fullscreen windows stay fullscreen when you focus something
+    /// else, like a window on another output. Use [`WindowRule::unfullscreen_on_focus_loss`]
+    /// to override this for specific windows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pinnacle_api::window::rules::{WindowRule, WindowRuleCondition};
+    ///
+    /// // Keep windows fullscreen, except for videos in mpv
+    /// window.set_unfullscreen_on_focus_loss(false);
+    /// window.add_window_rule(
+    ///     WindowRuleCondition::new().classes(["mpv"]),
+    ///     WindowRule::new().unfullscreen_on_focus_loss(true),
+    /// );
+    /// ```
+    pub fn set_unfullscreen_on_focus_loss(&self, unfullscreen: bool) {
+        let mut client = self.window_client.clone();
+
+        block_on_tokio(
+            client.set_unfullscreen_on_focus_loss(SetUnfullscreenOnFocusLossRequest {
+                unfullscreen: Some(unfullscreen),
+            }),
+        )
+        .unwrap();
+    }
+
+    /// Set a command to spawn when [`WindowHandle::close`] closes the last open window.
+    ///
+    /// This leaves a way back when a stray keybind closes everything.
+    /// The command is spawned once the window is actually gone, so nothing is spawned
+    /// if it ignores the close request or another window opens first.
+    /// Nothing is spawned by default; pass an empty command to go back to that.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Always have a terminal to come back to
+    /// window.set_last_window_fallback(["alacritty"]);
+    ///
+    /// // Spawn nothing
+    /// window.set_last_window_fallback(Vec::<String>::new());
+    /// ```
+    pub fn set_last_window_fallback(&self, command: impl IntoIterator<Item = impl Into<String>>) {
+        let mut client = self.window_client.clone();
+        if let Err(status) = block_on_tokio(client.set_last_window_fallback(
+            SetLastWindowFallbackRequest {
+                command: command.into_iter().map(Into::into).collect(),
+            },
+        )) {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Make the next window that opens land on the focused tags, even if a window rule
+    /// would send it to another output or tag.
+    ///
+    /// This only applies to one window. Other window rules still apply to it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Open another browser window here instead of on its usual tag
+    /// window.open_next_on_focused_tag();
+    /// process.spawn(["firefox"]);
+    /// ```
+    pub fn open_next_on_focused_tag(&self) {
+        let mut client = self.window_client.clone();
+        if let Err(status) =
+            block_on_tokio(client.open_next_on_focused_tag(OpenNextOnFocusedTagRequest {}))
+        {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Set how long windows stay urgent without being focused, or keep them urgent with `None`.
+    ///
+    /// Windows that want attention normally stay urgent until they're focused.
+    /// With a timeout, the urgent flag clears on its own once it elapses.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// // Stop showing windows as urgent after a minute
+    /// window.set_urgency_timeout(Some(Duration::from_secs(60)));
+    /// ```
+    pub fn set_urgency_timeout(&self, timeout: Option<Duration>) {
+        let mut client = self.window_client.clone();
+
+        block_on_tokio(client.set_urgency_timeout(SetUrgencyTimeoutRequest {
+            timeout_ms: timeout.map(|timeout| timeout.as_millis() as u32),
+        }))
+        .unwrap();
+    }
+
+    /// Connect to a window signal.
+    ///
+    /// The compositor will fire off signals that your config can listen for and act upon.
+    /// You can pass in a [`WindowSignal`] along with a callback and it will get run
+    /// with the necessary arguments every time a signal of that type is received.
+    pub fn connect_signal(&self, signal: WindowSignal) -> SignalHandle {
+        let mut signal_state = block_on_tokio(self.api.get().unwrap().signal.write());
+
+        match signal {
+            WindowSignal::PointerEnter(f) => signal_state.window_pointer_enter.add_callback(f),
+            WindowSignal::PointerLeave(f) => signal_state.window_pointer_leave.add_callback(f),
+        }
+    }
+}
+
+/// A handle to a window.
+///
+/// This allows you to manipulate the window and get its properties.
+#[derive(Debug, Clone)]
+pub struct WindowHandle {
+    id: u32,
+    window_client: WindowServiceClient<Channel>,
+    api: ApiModules,
+}
+
+impl PartialEq for WindowHandle {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for WindowHandle {}
+
+impl std::hash::Hash for WindowHandle {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+/// Whether a window is fullscreen, maximized, or neither.
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, TryFromPrimitive)]
+pub enum FullscreenOrMaximized {
+    /// The window is neither fullscreen nor maximized
+    Neither = 1,
+    /// The window is fullscreen
+    Fullscreen,
+    /// The window is maximized
+    Maximized,
+}
+
+/// The kind of content a window displays.
+///
+/// Clients declare this themselves, so windows that don't will have [`ContentType::None`].
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, TryFromPrimitive)]
+pub enum ContentType {
+    /// No particular kind of content
+    None = 1,
+    /// Still pictures
+    Photo,
+    /// Video or animations
+    Video,
+    /// A running game
+    Game,
+}
+
+/// A half or quarter of an output's usable area that a window can be snapped to.
+///
+/// See [`WindowHandle::snap`].
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, TryFromPrimitive)]
+pub enum SnapRegion {
+    /// The left half
+    LeftHalf = 1,
+    /// The right half
+    RightHalf,
+    /// The top half
+    TopHalf,
+    /// The bottom half
+    BottomHalf,
+    /// The top left quarter
+    TopLeftQuarter,
+    /// The top right quarter
+    TopRightQuarter,
+    /// The bottom left quarter
+    BottomLeftQuarter,
+    /// The bottom right quarter
+    BottomRightQuarter,
+}
+
+/// How to place a window with [`WindowHandle::place`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Placement {
+    /// Tile the window after the output's other windows
+    Tiled,
+    /// Float the window centered in the output's usable area
+    FloatingCentered,
+    /// Float the window at this geometry, relative to the top left corner of the output
+    FloatingGeometry(Geometry),
+    /// Make the window fullscreen
+    Fullscreen,
+}
+
+/// What a window move or resize grab is doing.
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, TryFromPrimitive)]
+pub enum GrabKind {
+    /// The window is being moved
+    Move = 1,
+    /// The window is being resized
+    Resize,
+}
+
+/// A window move or resize that is in progress.
+///
+/// See [`Window::active_grab`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ActiveGrab {
+    /// Whether the window is being moved or resized
+    pub kind: GrabKind,
+    /// The window being moved or resized
+    pub window: WindowHandle,
+}
+
+/// How a window's client scales its main surface.
+///
+/// See [`WindowHandle::surface_info`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SurfaceInfo {
+    /// The version of `wl_surface` the client bound
+    pub wl_surface_version: u32,
+    /// The buffer scale from `wl_surface.set_buffer_scale`
+    pub buffer_scale: i32,
+    /// The width and height of the last committed buffer in buffer pixels, if there is one
+    pub buffer_size: Option<(i32, i32)>,
+    /// Whether the surface has a source or destination set through `wp_viewporter`
+    pub uses_viewporter: bool,
+    /// Whether the client asked for `wp_fractional_scale_v1` updates on the surface
+    pub uses_fractional_scale: bool,
+    /// The fractional scale last sent to the surface
+    pub preferred_scale: Option<f64>,
+}
+
+/// Properties of a window.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct WindowProperties {
+    /// The location and size of the window
+    pub geometry: Option<Geometry>,
+    /// The window's class
+    pub class: Option<String>,
+    /// The window's title
+    pub title: Option<String>,
+    /// Whether the window is focused or not
+    pub focused: Option<bool>,
+    /// Whether the window is floating or not
+    ///
+    /// Note that a window can still be floating even if it's fullscreen or maximized; those two
+    /// states will just override the floating state.
+    pub floating: Option<bool>,
+    /// Whether the window is fullscreen, maximized, or neither
+    pub fullscreen_or_maximized: Option<FullscreenOrMaximized>,
+    /// All the tags on the window
+    pub tags: Vec<TagHandle>,
+    /// Whether the window wants attention
+    pub urgent: Option<bool>,
+    /// Whether the window is drawn as activated
+    ///
+    /// This is usually the same as having keyboard focus unless it was changed with
+    /// [`WindowHandle::set_activated`].
+    pub activated: Option<bool>,
+    /// The label set with [`WindowHandle::set_label`]
+    pub label: Option<String>,
+    /// The location and size of the window in physical pixels
+    ///
+    /// This is in the pixels of the output the window is on, relative to the top-left
+    /// corner of that output as displayed. If the window spans multiple outputs,
+    /// the output its tags are on is used.
+    pub physical_geometry: Option<Geometry>,
+    /// The kind of content the window displays
+    ///
+    /// X11 windows don't have a content type.
+    pub content_type: Option<ContentType>,
+    /// When the window last gained keyboard focus on the compositor's monotonic clock
+    ///
+    /// This is zero if the window has never been focused, so sorting by it
+    /// gives the windows in order of least to most recently used.
+    pub last_focused: Option<Duration>,
+    /// Whether the window is left out of taskbars
+    pub skip_taskbar: Option<bool>,
+    /// Whether the window is skipped when cycling through windows
+    pub skip_cycle: Option<bool>,
+    /// Whether the window's floating geometry is pinned in place
+    pub geometry_pinned: Option<bool>,
+    /// Whether the window is an X11 window running through XWayland
+    pub is_x11: Option<bool>,
+    /// Which edges the window is told are tiled
+    pub tiled_edges: Option<TiledEdges>,
+    /// Whether the window is minimized
+    pub minimized: Option<bool>,
+}
+
+bitflags::bitflags! {
+    /// Edges of a window that are up against the edge of the usable area or another tiled
+    /// window.
+    ///
+    /// Clients often drop their shadows and rounded corners on tiled edges.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct TiledEdges: u8 {
+        /// The top edge is tiled.
+        const TOP = 1;
+        /// The bottom edge is tiled.
+        const BOTTOM = 1 << 1;
+        /// The left edge is tiled.
+        const LEFT = 1 << 2;
+        /// The right edge is tiled.
+        const RIGHT = 1 << 3;
+    }
+}
+
+/// The captured contents of a window.
+///
+/// See [`WindowHandle::capture`].
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct WindowCapture {
+    /// The width of the capture in physical pixels
+    pub width: u32,
+    /// The height of the capture in physical pixels
+    pub height: u32,
+    /// The captured pixels in RGBA8888 format
+    pub pixels: Vec<u8>,
+}
+
+impl std::fmt::Debug for WindowCapture {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WindowCapture")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("pixels", &format!("<{} bytes>", self.pixels.len()))
+            .finish()
+    }
+}
+
+impl WindowHandle {
+    /// Send a close request to this window.
+    ///
+    /// If the window is unresponsive, it may not close.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Close the focused window
+    /// window.get_focused()?.close()
+    /// ```
+    pub fn close(&self) {
+        let mut window_client = self.window_client.clone();
+        block_on_tokio(window_client.close(CloseRequest {
+            window_id: Some(self.id),
+        }))
+        .unwrap();
+    }
+
+    /// Set this window to fullscreen or not.
+    ///
+    /// If it is maximized, setting it to fullscreen will remove the maximized state.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Set the focused window to fullscreen.
+    /// window.get_focused()?.set_fullscreen(true);
+    /// ```
+    pub fn set_fullscreen(&self, set: bool) {
+        let mut client = self.window_client.clone();
+        block_on_tokio(client.set_fullscreen(SetFullscreenRequest {
+            window_id: Some(self.id),
+            set_or_toggle: Some(match set {
+                true => SetOrToggle::Set,
+                false => SetOrToggle::Unset,
+            } as i32),
+        }))
+        .unwrap();
+    }
+
+    /// Give this window keyboard focus without raising it.
+    ///
+    /// The stacking order is left untouched, so you can type into a window that is
+    /// below other windows. This is the same as `set_focused(true)`, spelled out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Type into a terminal behind a transparent overlay
+    /// let term = window
+    ///     .get_all()
+    ///     .into_iter()
+    ///     .find(|win| win.class().as_deref() == Some("Alacritty"));
+    ///
+    /// if let Some(term) = term {
+    ///     term.focus_no_raise();
+    /// }
+    /// ```
+    pub fn focus_no_raise(&self) {
+        let mut client = self.window_client.clone();
+        block_on_tokio(client.set_focused(SetFocusedRequest {
+            window_id: Some(self.id),
+            set_or_toggle: Some(SetOrToggle::Set as i32),
+            raise: Some(false),
+        }))
+        .unwrap();
+    }
+
+    /// Toggle this window between fullscreen and not.
+    ///
+    /// If it is maximized, toggling it to fullscreen will remove the maximized state.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Toggle the focused window to and from fullscreen.
+    /// window.get_focused()?.toggle_fullscreen();
+    /// ```
+    pub fn toggle_fullscreen(&self) {
+        let mut client = self.window_client.clone();
+        block_on_tokio(client.set_fullscreen(SetFullscreenRequest {
+            window_id: Some(self.id),
+            set_or_toggle: Some(SetOrToggle::Toggle as i32),
+        }))
+        .unwrap();
+    }
+
+    /// Move this window between the currently and previously active tags on its output.
+    ///
+    /// If this window is on a currently active tag, it is moved to the first previously
+    /// active tag that isn't currently active. Otherwise, it is moved to the first currently
+    /// active tag. This does nothing if no tags were previously active.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Stash the focused window on the last viewed tag, then bring it back
+    /// let focused = window.get_focused()?;
+    /// focused.toggle_recent_tag();
+    /// focused.toggle_recent_tag();
+    /// ```
+    pub fn toggle_recent_tag(&self) {
+        let mut client = self.window_client.clone();
+
+        block_on_tokio(client.toggle_recent_tag(ToggleRecentTagRequest {
+            window_id: Some(self.id),
+        }))
+        .unwrap();
+    }
+
+    /// Set this window to maximized or not.
+    ///
+    /// If it is fullscreen, setting it to maximized will remove the fullscreen state.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Set the focused window to maximized.
+    /// window.get_focused()?.set_maximized(true);
+    /// ```
+    pub fn set_maximized(&self, set: bool) {
+        let mut client = self.window_client.clone();
+        block_on_tokio(client.set_maximized(SetMaximizedRequest {
+            window_id: Some(self.id),
+            set_or_toggle: Some(match set {
+                true => SetOrToggle::Set,
+                false => SetOrToggle::Unset,
+            } as i32),
+        }))
+        .unwrap();
+    }
+
+    /// Toggle this window between maximized and not.
+    ///
+    /// If it is fullscreen, toggling it to maximized will remove the fullscreen state.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Toggle the focused window to and from maximized.
+    /// window.get_focused()?.toggle_maximized();
     /// ```
     pub fn toggle_maximized(&self) {
         let mut client = self.window_client.clone();
-        block_on_tokio(client.set_maximized(SetMaximizedRequest {
+        block_on_tokio(client.set_maximized(SetMaximizedRequest {
+            window_id: Some(self.id),
+            set_or_toggle: Some(SetOrToggle::Toggle as i32),
+        }))
+        .unwrap();
+    }
+
+    /// Set this window to minimized or not.
+    ///
+    /// Minimized windows are hidden and left out of layouts and focus until they're restored.
+    /// They're still returned by [`Window::get_all`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Minimize the focused window.
+    /// window.get_focused()?.set_minimized(true);
+    /// ```
+    pub fn set_minimized(&self, set: bool) {
+        let mut client = self.window_client.clone();
+        block_on_tokio(client.set_minimized(SetMinimizedRequest {
+            window_id: Some(self.id),
+            set_or_toggle: Some(match set {
+                true => SetOrToggle::Set,
+                false => SetOrToggle::Unset,
+            } as i32),
+        }))
+        .unwrap();
+    }
+
+    /// Toggle this window between minimized and not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Restore the first minimized window.
+    /// if let Some(win) = window
+    ///     .get_all()
+    ///     .into_iter()
+    ///     .find(|win| win.minimized() == Some(true))
+    /// {
+    ///     win.toggle_minimized();
+    /// }
+    /// ```
+    pub fn toggle_minimized(&self) {
+        let mut client = self.window_client.clone();
+        block_on_tokio(client.set_minimized(SetMinimizedRequest {
+            window_id: Some(self.id),
+            set_or_toggle: Some(SetOrToggle::Toggle as i32),
+        }))
+        .unwrap();
+    }
+
+    /// Set this window to floating or not.
+    ///
+    /// Floating windows will not be tiled and can be moved around and resized freely.
+    ///
+    /// Note that fullscreen and maximized windows can still be floating; those two states will
+    /// just override the floating state.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Set the focused window to floating.
+    /// window.get_focused()?.set_floating(true);
+    /// ```
+    pub fn set_floating(&self, set: bool) {
+        let mut client = self.window_client.clone();
+        block_on_tokio(client.set_floating(SetFloatingRequest {
+            window_id: Some(self.id),
+            set_or_toggle: Some(match set {
+                true => SetOrToggle::Set,
+                false => SetOrToggle::Unset,
+            } as i32),
+        }))
+        .unwrap();
+    }
+
+    /// Toggle this window to and from floating.
+    ///
+    /// Floating windows will not be tiled and can be moved around and resized freely.
+    ///
+    /// Note that fullscreen and maximized windows can still be floating; those two states will
+    /// just override the floating state.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Toggle the focused window to and from floating.
+    /// window.get_focused()?.toggle_floating();
+    /// ```
+    pub fn toggle_floating(&self) {
+        let mut client = self.window_client.clone();
+        block_on_tokio(client.set_floating(SetFloatingRequest {
+            window_id: Some(self.id),
+            set_or_toggle: Some(SetOrToggle::Toggle as i32),
+        }))
+        .unwrap();
+    }
+
+    /// Float this window at the given geometry in the global space.
+    ///
+    /// Tiled windows are floated and moved there in one step, so they don't get
+    /// laid out at a different size first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pinnacle_api::util::Geometry;
+    ///
+    /// window.get_focused()?.set_floating_geometry(Geometry {
+    ///     x: 50,
+    ///     y: 300,
+    ///     width: 800,
+    ///     height: 600,
+    /// });
+    /// ```
+    pub fn set_floating_geometry(&self, geometry: Geometry) {
+        self.set_floating_geometry_inner(geometry, None);
+    }
+
+    /// Float this window at the given geometry, with its location relative to the top left
+    /// corner of `output`.
+    ///
+    /// This works like [`set_floating_geometry`][Self::set_floating_geometry], but you don't
+    /// need to know where the output is in the global space.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pinnacle_api::util::Geometry;
+    ///
+    /// // Float the focused window 50 pixels in from the top left of the focused output
+    /// let output = output.get_focused()?;
+    /// window.get_focused()?.set_floating_geometry_relative_to(
+    ///     Geometry {
+    ///         x: 50,
+    ///         y: 50,
+    ///         width: 800,
+    ///         height: 600,
+    ///     },
+    ///     &output,
+    /// );
+    /// ```
+    pub fn set_floating_geometry_relative_to(&self, geometry: Geometry, output: &OutputHandle) {
+        self.set_floating_geometry_inner(geometry, Some(output.name.clone()));
+    }
+
+    fn set_floating_geometry_inner(&self, geometry: Geometry, relative_to_output: Option<String>) {
+        let mut client = self.window_client.clone();
+        if let Err(status) =
+            block_on_tokio(client.set_floating_geometry(SetFloatingGeometryRequest {
+                window_id: Some(self.id),
+                relative_to_output,
+                geometry: Some(pinnacle_api_defs::pinnacle::v0alpha1::Geometry {
+                    x: Some(geometry.x),
+                    y: Some(geometry.y),
+                    width: Some(geometry.width as i32),
+                    height: Some(geometry.height as i32),
+                }),
+            }))
+        {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Float this window at a geometry given as fractions of its output's usable area.
+    ///
+    /// `x` and `y` are where the window's top left corner is, and `width` and `height` are its
+    /// size, all from 0.0 to 1.0. The geometry is resolved again when the output's mode or
+    /// scale changes, so it adapts to different monitors. Setting an absolute geometry or
+    /// moving or resizing the window with the pointer replaces it. A geometry that reaches
+    /// past the edges of the usable area is moved or shrunk to fit inside it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Float a dropdown terminal at the top center of the output,
+    /// // 80% of the output wide and 50% tall
+    /// window.get_focused()?.set_relative_geometry(0.1, 0.0, 0.8, 0.5);
+    /// ```
+    pub fn set_relative_geometry(&self, x: f32, y: f32, width: f32, height: f32) {
+        let mut client = self.window_client.clone();
+        if let Err(status) =
+            block_on_tokio(client.set_relative_geometry(SetRelativeGeometryRequest {
+                window_id: Some(self.id),
+                x: Some(x),
+                y: Some(y),
+                width: Some(width),
+                height: Some(height),
+            }))
+        {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Snap this window to a half or quarter of its output's usable area.
+    ///
+    /// This floats the window at the region's relative geometry, like
+    /// [`WindowHandle::set_relative_geometry`]. Snapping a window to a region again cycles it
+    /// through related regions: halves go through the quarters inside them, and
+    /// quarters go clockwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pinnacle_api::window::SnapRegion;
+    ///
+    /// // Snap the focused window to the left half of the output.
+    /// // Doing it again moves it to the top left quarter, then the bottom left one.
+    /// window.get_focused()?.snap(SnapRegion::LeftHalf);
+    /// ```
+    pub fn snap(&self, region: SnapRegion) {
+        let mut client = self.window_client.clone();
+        if let Err(status) = block_on_tokio(client.snap_window(SnapWindowRequest {
+            window_id: Some(self.id),
+            region: Some(region as i32),
+        })) {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Move this window to `output` and place it there.
+    ///
+    /// This sets the window's tags to the output's active tags, applies the placement,
+    /// and lays out both the window's old and new outputs, all in one step.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pinnacle_api::window::Placement;
+    /// use pinnacle_api::util::Geometry;
+    ///
+    /// let focused = window.get_focused()?;
+    ///
+    /// // Tile the focused window at the end of the "DP-2" output's windows
+    /// focused.place(&output.get_by_name("DP-2")?, Placement::Tiled);
+    ///
+    /// // Float it in the top left corner of the focused output
+    /// focused.place(
+    ///     &output.get_focused()?,
+    ///     Placement::FloatingGeometry(Geometry {
+    ///         x: 0,
+    ///         y: 0,
+    ///         width: 800,
+    ///         height: 600,
+    ///     }),
+    /// );
+    /// ```
+    pub fn place(&self, output: &OutputHandle, placement: Placement) {
+        let (placement, geometry) = match placement {
+            Placement::Tiled => (window::v0alpha1::Placement::Tiled, None),
+            Placement::FloatingCentered => (window::v0alpha1::Placement::FloatingCentered, None),
+            Placement::FloatingGeometry(geometry) => (
+                window::v0alpha1::Placement::FloatingGeometry,
+                Some(pinnacle_api_defs::pinnacle::v0alpha1::Geometry {
+                    x: Some(geometry.x),
+                    y: Some(geometry.y),
+                    width: Some(geometry.width as i32),
+                    height: Some(geometry.height as i32),
+                }),
+            ),
+            Placement::Fullscreen => (window::v0alpha1::Placement::Fullscreen, None),
+        };
+
+        let mut client = self.window_client.clone();
+        if let Err(status) = block_on_tokio(client.place_window(PlaceWindowRequest {
+            window_id: Some(self.id),
+            output_name: Some(output.name.clone()),
+            placement: Some(placement as i32),
+            geometry,
+        })) {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Focus or unfocus this window.
+    ///
+    /// Focusing a window doesn't raise it. To bring it to the front as well,
+    /// call [`WindowHandle::raise`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Unfocus the focused window
+    /// window.get_focused()?.set_focused(false);
+    /// ```
+    pub fn set_focused(&self, set: bool) {
+        let mut client = self.window_client.clone();
+        block_on_tokio(client.set_focused(SetFocusedRequest {
+            window_id: Some(self.id),
+            set_or_toggle: Some(match set {
+                true => SetOrToggle::Set,
+                false => SetOrToggle::Unset,
+            } as i32),
+            raise: None,
+        }))
+        .unwrap();
+    }
+
+    /// Toggle this window to and from focused.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Toggle the focused window to and from floating.
+    /// // Calling this a second time will do nothing because there won't
+    /// // be a focused window.
+    /// window.get_focused()?.toggle_focused();
+    /// ```
+    pub fn toggle_focused(&self) {
+        let mut client = self.window_client.clone();
+        block_on_tokio(client.set_focused(SetFocusedRequest {
             window_id: Some(self.id),
             set_or_toggle: Some(SetOrToggle::Toggle as i32),
+            raise: None,
         }))
         .unwrap();
     }
 
-    /// Set this window to floating or not.
+    /// Set whether this window is drawn as activated without changing keyboard focus.
     ///
-    /// Floating windows will not be tiled and can be moved around and resized freely.
+    /// Only one window is activated at a time, so activating this window deactivates the rest.
+    /// Keyboard focus leaves this window's activated state alone
+    /// until a window gains keyboard focus.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Draw the focused window as inactive while it keeps keyboard focus
+    /// window.get_focused()?.set_activated(false);
+    /// ```
+    pub fn set_activated(&self, set: bool) {
+        let mut client = self.window_client.clone();
+        block_on_tokio(client.set_activated(SetActivatedRequest {
+            window_id: Some(self.id),
+            set_or_toggle: Some(match set {
+                true => SetOrToggle::Set,
+                false => SetOrToggle::Unset,
+            } as i32),
+        }))
+        .unwrap();
+    }
+
+    /// Toggle whether this window is drawn as activated without changing keyboard focus.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// window.get_focused()?.toggle_activated();
+    /// ```
+    pub fn toggle_activated(&self) {
+        let mut client = self.window_client.clone();
+        block_on_tokio(client.set_activated(SetActivatedRequest {
+            window_id: Some(self.id),
+            set_or_toggle: Some(SetOrToggle::Toggle as i32),
+        }))
+        .unwrap();
+    }
+
+    /// Move this window to the given `tag`.
+    ///
+    /// This will remove all tags from this window then tag it with `tag`, essentially moving the
+    /// window to that tag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Move the focused window to tag "Code" on the focused output
+    /// window.get_focused()?.move_to_tag(&tag.get("Code", None)?);
+    /// ```
+    pub fn move_to_tag(&self, tag: &TagHandle) {
+        let mut client = self.window_client.clone();
+
+        block_on_tokio(client.move_to_tag(MoveToTagRequest {
+            window_id: Some(self.id),
+            tag_id: Some(tag.id),
+            follow: None,
+        }))
+        .unwrap();
+    }
+
+    /// Move this window to the given `tag`, then switch to that tag and focus this window.
+    ///
+    /// This is like [`WindowHandle::move_to_tag`], but instead of the window
+    /// disappearing from view, the view follows it to its new tag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Send the focused window to tag "Code" on the focused output and follow it there
+    /// window.get_focused()?.move_to_tag_and_follow(&tag.get("Code", None)?);
+    /// ```
+    pub fn move_to_tag_and_follow(&self, tag: &TagHandle) {
+        let mut client = self.window_client.clone();
+
+        block_on_tokio(client.move_to_tag(MoveToTagRequest {
+            window_id: Some(self.id),
+            tag_id: Some(tag.id),
+            follow: Some(true),
+        }))
+        .unwrap();
+    }
+
+    /// Add the given `tag` to this window, keeping the tags it already has.
+    ///
+    /// Unlike [`WindowHandle::move_to_tag`], the window stays where it is and
+    /// also shows up on `tag`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Show the focused window on tag "Code" as well
+    /// window.get_focused()?.copy_to_tag(&tag.get("Code", None)?);
+    /// ```
+    pub fn copy_to_tag(&self, tag: &TagHandle) {
+        let mut client = self.window_client.clone();
+
+        block_on_tokio(client.copy_to_tag(CopyToTagRequest {
+            window_id: Some(self.id),
+            tag_id: Some(tag.id),
+        }))
+        .unwrap();
+    }
+
+    /// Set or unset a tag on this window.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let focused = window.get_focused()?;
+    /// let tg = tag.get("Potato", None)?;
+    ///
+    /// focused.set_tag(&tg, true); // `focused` now has tag "Potato"
+    /// focused.set_tag(&tg, false); // `focused` no longer has tag "Potato"
+    /// ```
+    pub fn set_tag(&self, tag: &TagHandle, set: bool) {
+        let mut client = self.window_client.clone();
+
+        block_on_tokio(client.set_tag(SetTagRequest {
+            window_id: Some(self.id),
+            tag_id: Some(tag.id),
+            set_or_toggle: Some(match set {
+                true => SetOrToggle::Set,
+                false => SetOrToggle::Unset,
+            } as i32),
+        }))
+        .unwrap();
+    }
+
+    /// Toggle a tag on this window.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let focused = window.get_focused()?;
+    /// let tg = tag.get("Potato", None)?;
+    ///
+    /// // Assume `focused` does not have tag `tg`
+    ///
+    /// focused.toggle_tag(&tg); // `focused` now has tag "Potato"
+    /// focused.toggle_tag(&tg); // `focused` no longer has tag "Potato"
+    /// ```
+    pub fn toggle_tag(&self, tag: &TagHandle) {
+        let mut client = self.window_client.clone();
+
+        block_on_tokio(client.set_tag(SetTagRequest {
+            window_id: Some(self.id),
+            tag_id: Some(tag.id),
+            set_or_toggle: Some(SetOrToggle::Toggle as i32),
+        }))
+        .unwrap();
+    }
+
+    /// Raise this window.
+    ///
+    /// This will raise this window all the way to the top of the z-stack.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// window.get_focused()?.raise();
+    /// ```
+    pub fn raise(&self) {
+        let mut client = self.window_client.clone();
+
+        block_on_tokio(client.raise(RaiseRequest {
+            window_id: Some(self.id),
+        }))
+        .unwrap();
+    }
+
+    /// Lower this window.
+    ///
+    /// This will lower this window all the way to the bottom of the z-stack.
+    /// It stays above background and bottom layer surfaces like wallpapers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Push a floating window behind the others
+    /// window.get_focused()?.lower();
+    /// ```
+    pub fn lower(&self) {
+        let mut client = self.window_client.clone();
+
+        block_on_tokio(client.lower(LowerRequest {
+            window_id: Some(self.id),
+        }))
+        .unwrap();
+    }
+
+    /// Give this window a label to find it by later with [`Window::get_by_label`].
+    ///
+    /// Labels are unique. If another window already has `label`, this does nothing.
+    /// The label is forgotten when the window closes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// window.get_focused()?.set_label("main-editor");
+    /// ```
+    pub fn set_label(&self, label: impl Into<String>) {
+        let mut client = self.window_client.clone();
+        block_on_tokio(client.set_label(SetLabelRequest {
+            window_id: Some(self.id),
+            label: Some(label.into()),
+        }))
+        .unwrap();
+    }
+
+    /// Remove this window's label.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// window.get_by_label("main-editor")?.clear_label();
+    /// ```
+    pub fn clear_label(&self) {
+        let mut client = self.window_client.clone();
+        block_on_tokio(client.set_label(SetLabelRequest {
+            window_id: Some(self.id),
+            label: None,
+        }))
+        .unwrap();
+    }
+
+    /// Render this window at `scale` on top of its output's scale.
+    ///
+    /// The window is scaled around the top left corner of its geometry and pointer input
+    /// is mapped to match. Its size in the layout stays the same, so scaled up windows
+    /// will overlap what's next to them. This is useful for zooming in on a single window,
+    /// like a video or a presentation.
+    ///
+    /// `scale` is clamped to between 0.25 and 4.0. 1.0 renders the window normally.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Zoom the focused window in
+    /// window.get_focused()?.set_scale(1.5);
+    /// ```
+    pub fn set_scale(&self, scale: f64) {
+        let mut client = self.window_client.clone();
+        if let Err(status) = block_on_tokio(client.set_scale(SetScaleRequest {
+            window_id: Some(self.id),
+            scale: Some(scale),
+        })) {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Set whether this window is left out of taskbars.
+    ///
+    /// This is useful for scratchpads, overlays, and pickers that shouldn't
+    /// show up next to regular windows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Hide the focused window from taskbars
+    /// window.get_focused()?.set_skip_taskbar(true);
+    /// ```
+    pub fn set_skip_taskbar(&self, skip: bool) {
+        let mut client = self.window_client.clone();
+        if let Err(status) = block_on_tokio(client.set_skip_taskbar(SetSkipTaskbarRequest {
+            window_id: Some(self.id),
+            set_or_toggle: Some(match skip {
+                true => SetOrToggle::Set,
+                false => SetOrToggle::Unset,
+            } as i32),
+        })) {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Toggle whether this window is left out of taskbars.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// window.get_focused()?.toggle_skip_taskbar();
+    /// ```
+    pub fn toggle_skip_taskbar(&self) {
+        let mut client = self.window_client.clone();
+        if let Err(status) = block_on_tokio(client.set_skip_taskbar(SetSkipTaskbarRequest {
+            window_id: Some(self.id),
+            set_or_toggle: Some(SetOrToggle::Toggle as i32),
+        })) {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Set whether this window is skipped when cycling through windows.
+    ///
+    /// Skipped windows aren't focused by [`Window::focus_previous`] or when
+    /// [`Window::jump_to`] cycles, but can still be focused directly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Keep the focused window out of alt-tab
+    /// window.get_focused()?.set_skip_cycle(true);
+    /// ```
+    pub fn set_skip_cycle(&self, skip: bool) {
+        let mut client = self.window_client.clone();
+        if let Err(status) = block_on_tokio(client.set_skip_cycle(SetSkipCycleRequest {
+            window_id: Some(self.id),
+            set_or_toggle: Some(match skip {
+                true => SetOrToggle::Set,
+                false => SetOrToggle::Unset,
+            } as i32),
+        })) {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Toggle whether this window is skipped when cycling through windows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// window.get_focused()?.toggle_skip_cycle();
+    /// ```
+    pub fn toggle_skip_cycle(&self) {
+        let mut client = self.window_client.clone();
+        if let Err(status) = block_on_tokio(client.set_skip_cycle(SetSkipCycleRequest {
+            window_id: Some(self.id),
+            set_or_toggle: Some(SetOrToggle::Toggle as i32),
+        })) {
+            eprintln!("ERROR: {status}");
+        }
+    }
+
+    /// Set whether this window's floating geometry is pinned in place.
     ///
-    /// Note that fullscreen and maximized windows can still be floating; those two states will
-    /// just override the floating state.
+    /// Pinned floating windows can't be moved or resized with the pointer or by their client,
+    /// and they keep their position relative to their output when the output changes.
+    /// [`set_relative_geometry`][Self::set_relative_geometry] still works on them.
     ///
     /// # Examples
     ///
     /// ```
-    /// // Set the focused window to floating.
-    /// window.get_focused()?.set_floating(true);
+    /// // Keep a clock in the top right corner
+    /// let clock = window.get_focused()?;
+    /// clock.set_relative_geometry(0.85, 0.0, 0.15, 0.1);
+    /// clock.set_geometry_pinned(true);
     /// ```
-    pub fn set_floating(&self, set: bool) {
+    pub fn set_geometry_pinned(&self, pinned: bool) {
         let mut client = self.window_client.clone();
-        block_on_tokio(client.set_floating(SetFloatingRequest {
+        if let Err(status) = block_on_tokio(client.set_geometry_pinned(SetGeometryPinnedRequest {
             window_id: Some(self.id),
-            set_or_toggle: Some(match set {
+            set_or_toggle: Some(match pinned {
                 true => SetOrToggle::Set,
                 false => SetOrToggle::Unset,
             } as i32),
-        }))
-        .unwrap();
+        })) {
+            eprintln!("ERROR: {status}");
+        }
     }
 
-    /// Toggle this window to and from floating.
-    ///
-    /// Floating windows will not be tiled and can be moved around and resized freely.
-    ///
-    /// Note that fullscreen and maximized windows can still be floating; those two states will
-    /// just override the floating state.
+    /// Toggle whether this window's floating geometry is pinned in place.
     ///
     /// # Examples
     ///
     /// ```
-    /// // Toggle the focused window to and from floating.
-    /// window.get_focused()?.toggle_floating();
+    /// window.get_focused()?.toggle_geometry_pinned();
     /// ```
-    pub fn toggle_floating(&self) {
+    pub fn toggle_geometry_pinned(&self) {
         let mut client = self.window_client.clone();
-        block_on_tokio(client.set_floating(SetFloatingRequest {
+        if let Err(status) = block_on_tokio(client.set_geometry_pinned(SetGeometryPinnedRequest {
             window_id: Some(self.id),
             set_or_toggle: Some(SetOrToggle::Toggle as i32),
-        }))
-        .unwrap();
+        })) {
+            eprintln!("ERROR: {status}");
+        }
     }
 
-    /// Focus or unfocus this window.
+    /// Set whether this window keeps gaps around it when maximized.
+    ///
+    /// The window is inset by the outer and inner gaps of its output's first focused tag,
+    /// lining it up with the edges of tiled windows. By default, maximized windows fill
+    /// the usable area.
     ///
     /// # Examples
     ///
     /// ```
-    /// // Unfocus the focused window
-    /// window.get_focused()?.set_focused(false);
+    /// let focused = window.get_focused()?;
+    /// focused.set_maximize_gaps(true);
+    /// focused.set_maximized(true);
     /// ```
-    pub fn set_focused(&self, set: bool) {
+    pub fn set_maximize_gaps(&self, gaps: bool) {
         let mut client = self.window_client.clone();
-        block_on_tokio(client.set_focused(SetFocusedRequest {
+        if let Err(status) = block_on_tokio(client.set_maximize_gaps(SetMaximizeGapsRequest {
             window_id: Some(self.id),
-            set_or_toggle: Some(match set {
+            set_or_toggle: Some(match gaps {
                 true => SetOrToggle::Set,
                 false => SetOrToggle::Unset,
             } as i32),
-        }))
-        .unwrap();
+        })) {
+            eprintln!("ERROR: {status}");
+        }
     }
 
-    /// Toggle this window to and from focused.
+    /// Toggle whether this window keeps gaps around it when maximized.
     ///
     /// # Examples
     ///
     /// ```
-    /// // Toggle the focused window to and from floating.
-    /// // Calling this a second time will do nothing because there won't
-    /// // be a focused window.
-    /// window.get_focused()?.toggle_focused();
+    /// window.get_focused()?.toggle_maximize_gaps();
     /// ```
-    pub fn toggle_focused(&self) {
+    pub fn toggle_maximize_gaps(&self) {
         let mut client = self.window_client.clone();
-        block_on_tokio(client.set_focused(SetFocusedRequest {
+        if let Err(status) = block_on_tokio(client.set_maximize_gaps(SetMaximizeGapsRequest {
             window_id: Some(self.id),
             set_or_toggle: Some(SetOrToggle::Toggle as i32),
-        }))
-        .unwrap();
+        })) {
+            eprintln!("ERROR: {status}");
+        }
     }
 
-    /// Move this window to the given `tag`.
+    /// Set whether this window is told which of its edges are tiled.
     ///
-    /// This will remove all tags from this window then tag it with `tag`, essentially moving the
-    /// window to that tag.
+    /// Tiled edges are the ones up against the edge of the usable area or another tiled window.
+    /// When this is off, the window is told none of its edges are tiled, which makes
+    /// clients that drop their shadows on tiled edges keep them. This is on by default.
     ///
     /// # Examples
     ///
     /// ```
-    /// // Move the focused window to tag "Code" on the focused output
-    /// window.get_focused()?.move_to_tag(&tag.get("Code", None)?);
+    /// window.get_focused()?.set_tiled_edges_enabled(false);
     /// ```
-    pub fn move_to_tag(&self, tag: &TagHandle) {
+    pub fn set_tiled_edges_enabled(&self, enabled: bool) {
         let mut client = self.window_client.clone();
-
-        block_on_tokio(client.move_to_tag(MoveToTagRequest {
-            window_id: Some(self.id),
-            tag_id: Some(tag.id),
-        }))
-        .unwrap();
+        if let Err(status) =
+            block_on_tokio(client.set_tiled_edges_enabled(SetTiledEdgesEnabledRequest {
+                window_id: Some(self.id),
+                set_or_toggle: Some(match enabled {
+                    true => SetOrToggle::Set,
+                    false => SetOrToggle::Unset,
+                } as i32),
+            }))
+        {
+            eprintln!("ERROR: {status}");
+        }
     }
 
-    /// Set or unset a tag on this window.
+    /// Toggle whether this window is told which of its edges are tiled.
     ///
     /// # Examples
     ///
     /// ```
-    /// let focused = window.get_focused()?;
-    /// let tg = tag.get("Potato", None)?;
-    ///
-    /// focused.set_tag(&tg, true); // `focused` now has tag "Potato"
-    /// focused.set_tag(&tg, false); // `focused` no longer has tag "Potato"
+    /// window.get_focused()?.toggle_tiled_edges_enabled();
     /// ```
-    pub fn set_tag(&self, tag: &TagHandle, set: bool) {
+    pub fn toggle_tiled_edges_enabled(&self) {
         let mut client = self.window_client.clone();
-
-        block_on_tokio(client.set_tag(SetTagRequest {
-            window_id: Some(self.id),
-            tag_id: Some(tag.id),
-            set_or_toggle: Some(match set {
-                true => SetOrToggle::Set,
-                false => SetOrToggle::Unset,
-            } as i32),
-        }))
-        .unwrap();
+        if let Err(status) =
+            block_on_tokio(client.set_tiled_edges_enabled(SetTiledEdgesEnabledRequest {
+                window_id: Some(self.id),
+                set_or_toggle: Some(SetOrToggle::Toggle as i32),
+            }))
+        {
+            eprintln!("ERROR: {status}");
+        }
     }
 
-    /// Toggle a tag on this window.
+    /// Capture the contents of this window.
     ///
-    /// # Examples
+    /// The window is rendered on its own, so this also works for windows on inactive tags.
+    /// This is useful for things like thumbnails and overviews.
     ///
-    /// ```
-    /// let focused = window.get_focused()?;
-    /// let tg = tag.get("Potato", None)?;
+    /// If `scale` is `None`, the window will be captured at the scale of its output.
     ///
-    /// // Assume `focused` does not have tag `tg`
+    /// Returns `None` if the window doesn't exist or couldn't be captured.
+    ///
+    /// # Examples
     ///
-    /// focused.toggle_tag(&tg); // `focused` now has tag "Potato"
-    /// focused.toggle_tag(&tg); // `focused` no longer has tag "Potato"
     /// ```
-    pub fn toggle_tag(&self, tag: &TagHandle) {
+    /// // Capture the focused window at half scale
+    /// let capture = window.get_focused()?.capture(0.5)?;
+    /// ```
+    pub fn capture(&self, scale: impl Into<Option<f32>>) -> Option<WindowCapture> {
+        block_on_tokio(self.capture_async(scale))
+    }
+
+    /// The async version of [`capture`][Self::capture].
+    pub async fn capture_async(&self, scale: impl Into<Option<f32>>) -> Option<WindowCapture> {
         let mut client = self.window_client.clone();
 
-        block_on_tokio(client.set_tag(SetTagRequest {
-            window_id: Some(self.id),
-            tag_id: Some(tag.id),
-            set_or_toggle: Some(SetOrToggle::Toggle as i32),
-        }))
-        .unwrap();
+        let response = match client
+            .capture(CaptureRequest {
+                window_id: Some(self.id),
+                scale: scale.into(),
+            })
+            .await
+        {
+            Ok(response) => response.into_inner(),
+            Err(status) => {
+                eprintln!("ERROR: {status}");
+                return None;
+            }
+        };
+
+        Some(WindowCapture {
+            width: response.width(),
+            height: response.height(),
+            pixels: response.pixels?,
+        })
     }
 
-    /// Raise this window.
+    /// Check which parts of a window rule condition match this window.
     ///
-    /// This will raise this window all the way to the top of the z-stack.
+    /// Nothing is applied to the window; this is for debugging why a rule does or doesn't apply.
+    ///
+    /// Returns `None` if the window doesn't exist.
     ///
     /// # Examples
     ///
     /// ```
-    /// window.get_focused()?.raise();
+    /// use pinnacle_api::window::rules::WindowRuleCondition;
+    ///
+    /// let cond = WindowRuleCondition::new().classes(["firefox"]);
+    /// let eval = window.get_focused()?.evaluate_rule_condition(cond)?;
+    /// println!("matched: {}, classes: {:?}", eval.matched, eval.classes);
     /// ```
-    pub fn raise(&self) {
+    pub fn evaluate_rule_condition(
+        &self,
+        cond: WindowRuleCondition,
+    ) -> Option<ConditionEvaluation> {
+        block_on_tokio(self.evaluate_rule_condition_async(cond))
+    }
+
+    /// The async version of [`evaluate_rule_condition`][Self::evaluate_rule_condition].
+    pub async fn evaluate_rule_condition_async(
+        &self,
+        cond: WindowRuleCondition,
+    ) -> Option<ConditionEvaluation> {
         let mut client = self.window_client.clone();
 
-        block_on_tokio(client.raise(RaiseRequest {
-            window_id: Some(self.id),
-        }))
-        .unwrap();
+        let response = match client
+            .evaluate_window_rule_condition(EvaluateWindowRuleConditionRequest {
+                window_id: Some(self.id),
+                cond: Some(cond.0),
+            })
+            .await
+        {
+            Ok(response) => response.into_inner(),
+            Err(status) => {
+                eprintln!("ERROR: {status}");
+                return None;
+            }
+        };
+
+        response.evaluation.map(ConditionEvaluation::from)
     }
 
     /// Get all properties of this window.
@@ -546,6 +1976,18 @@ impl WindowHandle {
     ///     floating,
     ///     fullscreen_or_maximized,
     ///     tags,
+    ///     urgent,
+    ///     activated,
+    ///     label,
+    ///     physical_geometry,
+    ///     content_type,
+    ///     last_focused,
+    ///     skip_taskbar,
+    ///     skip_cycle,
+    ///     geometry_pinned,
+    ///     is_x11,
+    ///     tiled_edges,
+    ///     minimized,
     /// } = window.get_focused()?.props();
     /// ```
     pub fn props(&self) -> WindowProperties {
@@ -554,11 +1996,16 @@ impl WindowHandle {
 
     /// The async version of [`props`][Self::props].
     pub async fn props_async(&self) -> WindowProperties {
+        self.props_inner_async(None).await
+    }
+
+    async fn props_inner_async(&self, relative_to_output: Option<String>) -> WindowProperties {
         let mut client = self.window_client.clone();
 
         let response = match client
             .get_properties(window::v0alpha1::GetPropertiesRequest {
                 window_id: Some(self.id),
+                relative_to_output,
             })
             .await
         {
@@ -575,6 +2022,17 @@ impl WindowHandle {
             .try_into()
             .ok();
 
+        let content_type = response.content_type.unwrap_or_default().try_into().ok();
+
+        let tiled_edges = response.tiled_edges.map(|edges| {
+            let mut tiled_edges = TiledEdges::empty();
+            tiled_edges.set(TiledEdges::TOP, edges.top());
+            tiled_edges.set(TiledEdges::BOTTOM, edges.bottom());
+            tiled_edges.set(TiledEdges::LEFT, edges.left());
+            tiled_edges.set(TiledEdges::RIGHT, edges.right());
+            tiled_edges
+        });
+
         let geometry = response.geometry.map(|geo| Geometry {
             x: geo.x(),
             y: geo.y(),
@@ -582,6 +2040,13 @@ impl WindowHandle {
             height: geo.height() as u32,
         });
 
+        let physical_geometry = response.physical_geometry.map(|geo| Geometry {
+            x: geo.x(),
+            y: geo.y(),
+            width: geo.width() as u32,
+            height: geo.height() as u32,
+        });
+
         WindowProperties {
             geometry,
             class: response.class,
@@ -594,6 +2059,18 @@ impl WindowHandle {
                 .into_iter()
                 .map(|id| self.api.tag.new_handle(id))
                 .collect(),
+            urgent: response.urgent,
+            activated: response.activated,
+            label: response.label,
+            physical_geometry,
+            content_type,
+            last_focused: response.last_focused_ms.map(Duration::from_millis),
+            skip_taskbar: response.skip_taskbar,
+            skip_cycle: response.skip_cycle,
+            geometry_pinned: response.geometry_pinned,
+            is_x11: response.is_x11,
+            tiled_edges,
+            minimized: response.minimized,
         }
     }
 
@@ -609,6 +2086,28 @@ impl WindowHandle {
         self.props_async().await.geometry
     }
 
+    /// Get this window's location and size, with its location relative to the top left
+    /// corner of `output`.
+    ///
+    /// Returns `None` if the window or output doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let output = output.get_focused()?;
+    /// let geo = window.get_focused()?.geometry_relative_to(&output);
+    /// ```
+    pub fn geometry_relative_to(&self, output: &OutputHandle) -> Option<Geometry> {
+        block_on_tokio(self.geometry_relative_to_async(output))
+    }
+
+    /// The async version of [`geometry_relative_to`][Self::geometry_relative_to].
+    pub async fn geometry_relative_to_async(&self, output: &OutputHandle) -> Option<Geometry> {
+        self.props_inner_async(Some(output.name.clone()))
+            .await
+            .geometry
+    }
+
     /// Get this window's class.
     ///
     /// Shorthand for `self.props().class`.
@@ -680,4 +2179,205 @@ impl WindowHandle {
     pub async fn tags_async(&self) -> Vec<TagHandle> {
         self.props_async().await.tags
     }
+
+    /// Get whether or not this window wants attention.
+    ///
+    /// Shorthand for `self.props().urgent`.
+    pub fn urgent(&self) -> Option<bool> {
+        self.props().urgent
+    }
+
+    /// The async version of [`urgent`][Self::urgent].
+    pub async fn urgent_async(&self) -> Option<bool> {
+        self.props_async().await.urgent
+    }
+
+    /// Get whether this window is drawn as activated.
+    ///
+    /// Shorthand for `self.props().activated`.
+    pub fn activated(&self) -> Option<bool> {
+        self.props().activated
+    }
+
+    /// The async version of [`activated`][Self::activated].
+    pub async fn activated_async(&self) -> Option<bool> {
+        self.props_async().await.activated
+    }
+
+    /// Get this window's label.
+    ///
+    /// Shorthand for `self.props().label`.
+    pub fn label(&self) -> Option<String> {
+        self.props().label
+    }
+
+    /// The async version of [`label`][Self::label].
+    pub async fn label_async(&self) -> Option<String> {
+        self.props_async().await.label
+    }
+
+    /// Get this window's location and size in physical pixels.
+    ///
+    /// Shorthand for `self.props().physical_geometry`.
+    pub fn physical_geometry(&self) -> Option<Geometry> {
+        self.props().physical_geometry
+    }
+
+    /// The async version of [`physical_geometry`][Self::physical_geometry].
+    pub async fn physical_geometry_async(&self) -> Option<Geometry> {
+        self.props_async().await.physical_geometry
+    }
+
+    /// Get the kind of content this window displays.
+    ///
+    /// Shorthand for `self.props().content_type`.
+    pub fn content_type(&self) -> Option<ContentType> {
+        self.props().content_type
+    }
+
+    /// The async version of [`content_type`][Self::content_type].
+    pub async fn content_type_async(&self) -> Option<ContentType> {
+        self.props_async().await.content_type
+    }
+
+    /// Get when this window last gained keyboard focus.
+    ///
+    /// This is zero if the window has never been focused.
+    ///
+    /// Shorthand for `self.props().last_focused`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Most recently used windows first, for an alt-tab switcher
+    /// let mut windows = window.get_all();
+    /// windows.sort_by_key(|win| std::cmp::Reverse(win.last_focused()));
+    /// ```
+    pub fn last_focused(&self) -> Option<Duration> {
+        self.props().last_focused
+    }
+
+    /// The async version of [`last_focused`][Self::last_focused].
+    pub async fn last_focused_async(&self) -> Option<Duration> {
+        self.props_async().await.last_focused
+    }
+
+    /// Get how this window's client scales its main surface.
+    ///
+    /// This is useful for figuring out why a window looks blurry or wrongly sized,
+    /// like when an app ignores the fractional scale it's sent.
+    ///
+    /// Returns `None` if the window doesn't exist or has no surface.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// if let Some(info) = window.get_focused()?.surface_info() {
+    ///     if !info.uses_fractional_scale {
+    ///         println!("this app only scales by integers");
+    ///     }
+    /// }
+    /// ```
+    pub fn surface_info(&self) -> Option<SurfaceInfo> {
+        block_on_tokio(self.surface_info_async())
+    }
+
+    /// The async version of [`surface_info`][Self::surface_info].
+    pub async fn surface_info_async(&self) -> Option<SurfaceInfo> {
+        let mut client = self.window_client.clone();
+
+        let response = match client
+            .get_surface_info(GetSurfaceInfoRequest {
+                window_id: Some(self.id),
+            })
+            .await
+        {
+            Ok(response) => response.into_inner(),
+            Err(status) => {
+                eprintln!("ERROR: {status}");
+                return None;
+            }
+        };
+
+        Some(SurfaceInfo {
+            wl_surface_version: response.wl_surface_version?,
+            buffer_scale: response.buffer_scale(),
+            buffer_size: response.buffer_width.zip(response.buffer_height),
+            uses_viewporter: response.uses_viewporter(),
+            uses_fractional_scale: response.uses_fractional_scale(),
+            preferred_scale: response.preferred_scale,
+        })
+    }
+
+    /// Get whether this window is left out of taskbars.
+    ///
+    /// Shorthand for `self.props().skip_taskbar`.
+    pub fn skip_taskbar(&self) -> Option<bool> {
+        self.props().skip_taskbar
+    }
+
+    /// The async version of [`skip_taskbar`][Self::skip_taskbar].
+    pub async fn skip_taskbar_async(&self) -> Option<bool> {
+        self.props_async().await.skip_taskbar
+    }
+
+    /// Get whether this window is skipped when cycling through windows.
+    ///
+    /// Shorthand for `self.props().skip_cycle`.
+    pub fn skip_cycle(&self) -> Option<bool> {
+        self.props().skip_cycle
+    }
+
+    /// The async version of [`skip_cycle`][Self::skip_cycle].
+    pub async fn skip_cycle_async(&self) -> Option<bool> {
+        self.props_async().await.skip_cycle
+    }
+
+    /// Get whether this window's floating geometry is pinned in place.
+    ///
+    /// Shorthand for `self.props().geometry_pinned`.
+    pub fn geometry_pinned(&self) -> Option<bool> {
+        self.props().geometry_pinned
+    }
+
+    /// The async version of [`geometry_pinned`][Self::geometry_pinned].
+    pub async fn geometry_pinned_async(&self) -> Option<bool> {
+        self.props_async().await.geometry_pinned
+    }
+
+    /// Get whether this window is an X11 window running through XWayland.
+    ///
+    /// Shorthand for `self.props().is_x11`.
+    pub fn is_x11(&self) -> Option<bool> {
+        self.props().is_x11
+    }
+
+    /// The async version of [`is_x11`][Self::is_x11].
+    pub async fn is_x11_async(&self) -> Option<bool> {
+        self.props_async().await.is_x11
+    }
+
+    /// Get which edges this window is told are tiled.
+    ///
+    /// Shorthand for `self.props().tiled_edges`.
+    pub fn tiled_edges(&self) -> Option<TiledEdges> {
+        self.props().tiled_edges
+    }
+
+    /// The async version of [`tiled_edges`][Self::tiled_edges].
+    pub async fn tiled_edges_async(&self) -> Option<TiledEdges> {
+        self.props_async().await.tiled_edges
+    }
+
+    /// Get whether this window is minimized.
+    ///
+    /// Shorthand for `self.props().minimized`.
+    pub fn minimized(&self) -> Option<bool> {
+        self.props().minimized
+    }
+
+    /// The async version of [`minimized`][Self::minimized].
+    pub async fn minimized_async(&self) -> Option<bool> {
+        self.props_async().await.minimized
+    }
 }
@@ -90,6 +90,17 @@
 //!
 //! Now, you must have both tags "1" and "2" active and spawn a window for the condition to apply.
 //!
+//! ### `WindowRuleCondition::content_types`
+//! Clients can declare what kind of content they show, like video or a game.
+//! You can use `content_types` to match on that.
+//!
+//! ```
+//! let cond = WindowRuleCondition::new().content_types([ContentType::Game]);
+//! ```
+//!
+//! Like `classes`, passing in multiple content types at the top level will cause the condition
+//! to always fail.
+//!
 //! ### `WindowRuleCondition::any`
 //! Now we can get to ways to compose more complex conditions.
 //!
@@ -209,12 +220,21 @@
 //!
 //! Note: this only applies to floating windows; tiled windows' geometry will be overridden by
 //! layouting.
+//!
+//! The width and height must be set together and be non-zero, as must the x- and y-coordinates.
+//! Rules that don't satisfy this are rejected.
 
 use pinnacle_api_defs::pinnacle::window;
 
 use crate::{output::OutputHandle, tag::TagHandle};
 
-use super::FullscreenOrMaximized;
+use super::{ContentType, FullscreenOrMaximized};
+
+/// The id of an added window rule.
+///
+/// See [`Window::add_window_rule`][crate::window::Window::add_window_rule].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WindowRuleId(pub(super) u32);
 
 /// A condition for a [`WindowRule`] to apply to a window.
 ///
@@ -362,6 +382,38 @@ impl WindowRuleCondition {
         self.0.tags = tags.into_iter().map(|tag| tag.id).collect();
         self
     }
+
+    /// This condition requires that the window's content type matches.
+    ///
+    /// Clients usually declare their content type when they open,
+    /// but they can change it at any time.
+    ///
+    /// When used in a top level condition or inside of [`WindowRuleCondition::all`],
+    /// *all* content types must match (this is impossible for more than one).
+    ///
+    /// When used in [`WindowRuleCondition::any`], at least one of the
+    /// provided content types must match.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pinnacle_api::window::{rules::WindowRuleCondition, ContentType};
+    ///
+    /// // `cond` will be true if the window opens as a game
+    /// let cond = WindowRuleCondition::new().content_types([ContentType::Game]);
+    ///
+    /// // The following will be true if the window shows either video or a game
+    /// let media = WindowRuleCondition::new().any([
+    ///     WindowRuleCondition::new().content_types([ContentType::Video, ContentType::Game])
+    /// ]);
+    /// ```
+    pub fn content_types(mut self, content_types: impl IntoIterator<Item = ContentType>) -> Self {
+        self.0.content_types = content_types
+            .into_iter()
+            .map(|content_type| content_type as i32)
+            .collect();
+        self
+    }
 }
 
 /// A window rule.
@@ -522,4 +574,178 @@ impl WindowRule {
         self.0.height = Some(height as i32);
         self
     }
+
+    /// This rule will exempt windows from focus stealing prevention.
+    ///
+    /// Normally, a window can only activate itself if it has a valid activation token
+    /// from a recent user interaction. Exempt windows can always activate themselves,
+    /// which is useful for things like password prompts.
+    ///
+    /// This only affects activation requested by clients. Focusing a window through
+    /// the API always works regardless.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pinnacle_api::window::rules::WindowRule;
+    ///
+    /// // Allow the window to always raise itself
+    /// let rule = WindowRule::new().focus_steal_exempt(true);
+    /// ```
+    pub fn focus_steal_exempt(mut self, exempt: bool) -> Self {
+        self.0.focus_steal_exempt = Some(exempt);
+        self
+    }
+
+    /// This rule will disable keybinds while windows are focused.
+    ///
+    /// Keys that would trigger keybinds are forwarded to the window instead.
+    /// This is useful for things like virtual machines and nested compositors.
+    /// The kill and reload config keybinds still work, as do keybinds set with
+    /// [`Input::keybind_allow_when_disabled`][crate::input::Input::keybind_allow_when_disabled].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pinnacle_api::window::rules::{WindowRule, WindowRuleCondition};
+    ///
+    /// // Send all keys to virt-manager while it's focused
+    /// window.add_window_rule(
+    ///     WindowRuleCondition::new().classes(["virt-manager"]),
+    ///     WindowRule::new().disable_keybinds(true),
+    /// );
+    /// ```
+    pub fn disable_keybinds(mut self, disable: bool) -> Self {
+        self.0.disable_keybinds = Some(disable);
+        self
+    }
+
+    /// This rule will force windows to take keyboard focus or not when they open.
+    ///
+    /// This overrides [`Window::set_focus_new_windows`][crate::window::Window::set_focus_new_windows].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pinnacle_api::window::rules::WindowRule;
+    ///
+    /// // Open the window without focusing it
+    /// let rule = WindowRule::new().focus_on_open(false);
+    /// ```
+    pub fn focus_on_open(mut self, focus: bool) -> Self {
+        self.0.focus_on_open = Some(focus);
+        self
+    }
+
+    /// This rule will leave windows out of taskbars.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pinnacle_api::window::rules::{WindowRule, WindowRuleCondition};
+    ///
+    /// // Keep the scratchpad terminal out of taskbars and alt-tab
+    /// window.add_window_rule(
+    ///     WindowRuleCondition::new().classes(["scratchpad"]),
+    ///     WindowRule::new().skip_taskbar(true).skip_cycle(true),
+    /// );
+    /// ```
+    pub fn skip_taskbar(mut self, skip: bool) -> Self {
+        self.0.skip_taskbar = Some(skip);
+        self
+    }
+
+    /// This rule will skip windows when cycling through windows.
+    ///
+    /// See [`WindowHandle::set_skip_cycle`][crate::window::WindowHandle::set_skip_cycle].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pinnacle_api::window::rules::WindowRule;
+    ///
+    /// let rule = WindowRule::new().skip_cycle(true);
+    /// ```
+    pub fn skip_cycle(mut self, skip: bool) -> Self {
+        self.0.skip_cycle = Some(skip);
+        self
+    }
+
+    /// This rule will set whether windows leave fullscreen when they lose keyboard focus.
+    ///
+    /// This overrides [`Window::set_unfullscreen_on_focus_loss`][crate::window::Window::set_unfullscreen_on_focus_loss].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pinnacle_api::window::rules::WindowRule;
+    ///
+    /// // Take videos out of fullscreen when focusing something else
+    /// let rule = WindowRule::new().unfullscreen_on_focus_loss(true);
+    /// ```
+    pub fn unfullscreen_on_focus_loss(mut self, unfullscreen: bool) -> Self {
+        self.0.unfullscreen_on_focus_loss = Some(unfullscreen);
+        self
+    }
+
+    /// This rule will only apply to the next window that opens and matches,
+    /// then it will be removed.
+    ///
+    /// Use this to place a window you're about to spawn as it opens, instead of moving it
+    /// after it appears. Rules that apply once aren't used by
+    /// [`Window::reapply_window_rules`][crate::window::Window::reapply_window_rules].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pinnacle_api::window::rules::WindowRule;
+    ///
+    /// // Force the next matching window to open floating, but not any after it
+    /// let rule = WindowRule::new().floating(true).once();
+    /// ```
+    pub fn once(mut self) -> Self {
+        self.0.once = Some(true);
+        self
+    }
+}
+
+/// Which parts of a [`WindowRuleCondition`] matched a window.
+///
+/// Fields for sub-conditions that weren't specified are `None`.
+///
+/// See [`WindowHandle::evaluate_rule_condition`][crate::window::WindowHandle::evaluate_rule_condition].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ConditionEvaluation {
+    /// Whether the condition as a whole matched
+    pub matched: bool,
+    /// The evaluations of the conditions in [`WindowRuleCondition::any`]
+    pub any: Option<Vec<ConditionEvaluation>>,
+    /// The evaluations of the conditions in [`WindowRuleCondition::all`]
+    pub all: Option<Vec<ConditionEvaluation>>,
+    /// Whether [`WindowRuleCondition::classes`] matched
+    pub classes: Option<bool>,
+    /// Whether [`WindowRuleCondition::titles`] matched
+    pub titles: Option<bool>,
+    /// Whether [`WindowRuleCondition::tags`] matched
+    pub tags: Option<bool>,
+    /// Whether [`WindowRuleCondition::content_types`] matched
+    pub content_types: Option<bool>,
+}
+
+impl From<window::v0alpha1::WindowRuleConditionEvaluation> for ConditionEvaluation {
+    fn from(eval: window::v0alpha1::WindowRuleConditionEvaluation) -> Self {
+        let nested = |evals: Vec<window::v0alpha1::WindowRuleConditionEvaluation>| {
+            (!evals.is_empty()).then(|| evals.into_iter().map(ConditionEvaluation::from).collect())
+        };
+
+        Self {
+            matched: eval.matched(),
+            any: nested(eval.any),
+            all: nested(eval.all),
+            classes: eval.classes,
+            titles: eval.titles,
+            tags: eval.tags,
+            content_types: eval.content_types,
+        }
+    }
 }
@@ -66,6 +66,7 @@ pub mod pinnacle {
                 OutputDisconnectRequest,
                 OutputResizeRequest,
                 OutputMoveRequest,
+                OutputHeadlessRequest,
                 WindowPointerEnterRequest,
                 WindowPointerLeaveRequest,
                 TagActiveRequest
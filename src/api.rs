@@ -2,44 +2,74 @@ pub mod layout;
 pub mod signal;
 pub mod window;
 
-use std::{ffi::OsString, pin::Pin, process::Stdio};
+use std::{
+    ffi::OsString,
+    hash::{DefaultHasher, Hash, Hasher},
+    pin::Pin,
+    process::Stdio,
+    time::Duration,
+};
 
 use pinnacle_api_defs::pinnacle::{
     input::v0alpha1::{
-        input_service_server,
+        get_keybinds_response, get_seats_response, input_service_server,
         set_libinput_setting_request::{AccelProfile, ClickMethod, ScrollMethod, TapButtonMap},
         set_mousebind_request::MouseEdge,
-        SetKeybindRequest, SetKeybindResponse, SetLibinputSettingRequest, SetMousebindRequest,
-        SetMousebindResponse, SetRepeatRateRequest, SetXkbConfigRequest,
+        GetKeybindsRequest, GetKeybindsResponse, GetPointerRequest, GetPointerResponse,
+        GetSeatsRequest, GetSeatsResponse, Modifier, SendKeyRequest, SetClickSettingsRequest,
+        SetIdleActionRequest, SetIdleActionResponse, SetKeybindRequest, SetKeybindResponse,
+        SetLibinputSettingRequest, SetModifierRemapsRequest, SetMousebindRequest,
+        SetMousebindResponse, SetPointerConfinementRequest, SetRepeatRateRequest,
+        SetXkbConfigRequest,
     },
     output::{
         self,
         v0alpha1::{
-            output_service_server, set_scale_request::AbsoluteOrRelative, SetLocationRequest,
-            SetModeRequest, SetScaleRequest, SetTransformRequest,
+            get_focus_stack_response, output_service_server, set_brightness_request,
+            set_magnification_request, set_scale_request::AbsoluteOrRelative,
+            FocusOutputUnderCursorRequest, GetFocusStackRequest, GetFocusStackResponse,
+            SetBrightnessRequest, SetFocusLockRequest, SetGammaPresetRequest, SetLocationRequest,
+            SetLoneWindowWidthRequest, SetMagnificationRequest, SetModeRequest, SetOverviewRequest,
+            SetPrimaryRequest, SetScaleRequest, SetTransformRequest,
         },
     },
     process::v0alpha1::{process_service_server, SetEnvRequest, SpawnRequest, SpawnResponse},
     render::v0alpha1::{
-        render_service_server, Filter, SetDownscaleFilterRequest, SetUpscaleFilterRequest,
+        render_service_server, Filter, GetRendererInfoRequest, GetRendererInfoResponse,
+        SetDownscaleFilterRequest, SetFrameTimingsRequest, SetInactiveOutputDimRequest,
+        SetUpscaleFilterRequest, WatchFrameTimingsRequest, WatchFrameTimingsResponse,
     },
     tag::{
         self,
         v0alpha1::{
             tag_service_server, AddRequest, AddResponse, RemoveRequest, SetActiveRequest,
-            SwitchToRequest,
+            SetActiveTagsRequest, SetDefaultOutputTagsRequest, SetDisplayNameRequest,
+            SetFloatingHiddenRequest, SetGapsRequest, SetTilingDirectionRequest,
+            SetWindowLimitRequest, SwitchToPreviousRequest, SwitchToRequest,
+            ToggleTilingDirectionRequest,
         },
     },
     v0alpha1::{
-        pinnacle_service_server, PingRequest, PingResponse, QuitRequest, ReloadConfigRequest,
-        SetOrToggle, ShutdownWatchRequest, ShutdownWatchResponse,
+        get_state_snapshot_response::{OutputSnapshot, WindowSnapshot},
+        list_surfaces_response, pinnacle_service_server, Backend, Geometry, GetBackendRequest,
+        GetBackendResponse, GetSelectionMimeTypesRequest, GetSelectionMimeTypesResponse,
+        GetStateSnapshotRequest, GetStateSnapshotResponse, GetXwaylandInfoRequest,
+        GetXwaylandInfoResponse, ListSurfacesRequest, ListSurfacesResponse, PingRequest,
+        PingResponse, QuitRequest, ReloadConfigRequest, SetOrToggle, ShutdownWatchRequest,
+        ShutdownWatchResponse,
     },
 };
 use smithay::{
-    backend::renderer::TextureFilter,
+    backend::renderer::{utils::with_renderer_surface_state, TextureFilter},
+    desktop::{layer_map_for_output, space::SpaceElement, PopupManager},
     input::keyboard::XkbConfig,
     output::Scale,
-    reexports::{calloop, input as libinput},
+    reexports::{
+        calloop, input as libinput,
+        wayland_server::{protocol::wl_surface::WlSurface, Resource},
+    },
+    utils::{IsAlive, Logical, Point},
+    wayland::{compositor, seat::WaylandFocus, shell::wlr_layer},
 };
 use sysinfo::ProcessRefreshKind;
 use tokio::{
@@ -53,11 +83,12 @@ use tracing::{debug, error, info, trace, warn};
 
 use crate::{
     backend::BackendData,
+    backlight::Backlight,
     config::ConnectorSavedState,
-    input::ModifierMask,
-    output::OutputName,
-    state::{State, WithState},
-    tag::{Tag, TagId},
+    input::{KeybindOwner, ModifierMask},
+    output::{GammaPreset, LoneWindowAlignment, LoneWindowWidth, OutputName},
+    state::{Pinnacle, State, WithState},
+    tag::{Gaps, OverflowPolicy, Tag, TagId, TilingDirection, WindowLimit},
 };
 
 type ResponseStream<T> = Pin<Box<dyn Stream<Item = Result<T, Status>> + Send>>;
@@ -226,6 +257,292 @@ impl pinnacle_service_server::PinnacleService for PinnacleService {
             state.pinnacle.config.shutdown_sender.replace(sender);
         })
     }
+
+    async fn get_selection_mime_types(
+        &self,
+        _request: Request<GetSelectionMimeTypesRequest>,
+    ) -> Result<Response<GetSelectionMimeTypesResponse>, Status> {
+        run_unary(&self.sender, |state| GetSelectionMimeTypesResponse {
+            clipboard: state.pinnacle.clipboard_mime_types.clone(),
+            primary: state.pinnacle.primary_selection_mime_types.clone(),
+            dnd: state.pinnacle.dnd_mime_types.clone(),
+        })
+        .await
+    }
+
+    async fn get_state_snapshot(
+        &self,
+        _request: Request<GetStateSnapshotRequest>,
+    ) -> Result<Response<GetStateSnapshotResponse>, Status> {
+        run_unary(&self.sender, |state| {
+            let pinnacle = &state.pinnacle;
+
+            let focused_output = pinnacle.focused_output().cloned();
+            let focused_window = focused_output
+                .as_ref()
+                .and_then(|output| pinnacle.focused_window(output));
+
+            let windows = pinnacle
+                .windows
+                .iter()
+                .map(|win| {
+                    let loc = pinnacle.space.element_location(win);
+                    let size = win.geometry().size;
+
+                    WindowSnapshot {
+                        window_id: Some(win.with_state(|state| state.id.0)),
+                        geometry: Some(Geometry {
+                            x: loc.map(|loc| loc.x),
+                            y: loc.map(|loc| loc.y),
+                            width: Some(size.w),
+                            height: Some(size.h),
+                        }),
+                        tag_ids: win
+                            .with_state(|state| state.tags.iter().map(|tag| tag.id().0).collect()),
+                        focused: Some(focused_window.as_ref() == Some(win)),
+                        floating: Some(
+                            win.with_state(|state| state.floating_or_tiled.is_floating()),
+                        ),
+                        class: win.class(),
+                        title: win.title(),
+                    }
+                })
+                .collect();
+
+            let outputs = pinnacle
+                .space
+                .outputs()
+                .map(|output| OutputSnapshot {
+                    output_name: Some(output.name()),
+                    tag_ids: output
+                        .with_state(|state| state.tags.iter().map(|tag| tag.id().0).collect()),
+                    active_tag_ids: output
+                        .with_state(|state| state.focused_tags().map(|tag| tag.id().0).collect()),
+                    focused: Some(focused_output.as_ref() == Some(output)),
+                })
+                .collect();
+
+            GetStateSnapshotResponse {
+                windows,
+                outputs,
+                focused_output_name: focused_output.map(|output| output.name()),
+                focused_window_id: focused_window.map(|win| win.with_state(|state| state.id.0)),
+            }
+        })
+        .await
+    }
+
+    async fn get_backend(
+        &self,
+        _request: Request<GetBackendRequest>,
+    ) -> Result<Response<GetBackendResponse>, Status> {
+        run_unary(&self.sender, |state| {
+            let backend = match &state.backend {
+                crate::backend::Backend::Winit(_) => Backend::Window,
+                crate::backend::Backend::Udev(_) => Backend::Tty,
+                #[cfg(feature = "testing")]
+                crate::backend::Backend::Dummy(_) => Backend::Dummy,
+            };
+
+            GetBackendResponse {
+                backend: Some(backend as i32),
+            }
+        })
+        .await
+    }
+
+    async fn get_xwayland_info(
+        &self,
+        _request: Request<GetXwaylandInfoRequest>,
+    ) -> Result<Response<GetXwaylandInfoResponse>, Status> {
+        run_unary(&self.sender, |state| {
+            let running = state.pinnacle.xwm.is_some();
+
+            GetXwaylandInfoResponse {
+                running: Some(running),
+                display: state.pinnacle.xdisplay.filter(|_| running),
+            }
+        })
+        .await
+    }
+
+    async fn list_surfaces(
+        &self,
+        _request: Request<ListSurfacesRequest>,
+    ) -> Result<Response<ListSurfacesResponse>, Status> {
+        run_unary(&self.sender, |state| {
+            let pinnacle = &state.pinnacle;
+            let mut surfaces = Vec::new();
+
+            for win in pinnacle.windows.iter().chain(pinnacle.new_windows.iter()) {
+                let loc = pinnacle.space.element_location(win);
+                let size = win.geometry().size;
+
+                let surface = list_surfaces_response::Surface {
+                    window_id: Some(win.with_state(|state| state.id.0)),
+                    output_name: win.output(pinnacle).map(|output| output.name()),
+                    geometry: Some(Geometry {
+                        x: loc.map(|loc| loc.x),
+                        y: loc.map(|loc| loc.y),
+                        width: Some(size.w),
+                        height: Some(size.h),
+                    }),
+                    ..Default::default()
+                };
+
+                match win.wl_surface() {
+                    Some(wl_surface) => {
+                        push_surface_tree(pinnacle, &mut surfaces, &wl_surface, None, surface)
+                    }
+                    None => surfaces.push(list_surfaces_response::Surface {
+                        mapped: Some(false),
+                        ..surface
+                    }),
+                }
+            }
+
+            for output in pinnacle.space.outputs() {
+                let output_loc = output.current_location();
+                let map = layer_map_for_output(output);
+
+                for layer in map.layers() {
+                    let geometry = map.layer_geometry(layer).map(|geo| Geometry {
+                        x: Some(output_loc.x + geo.loc.x),
+                        y: Some(output_loc.y + geo.loc.y),
+                        width: Some(geo.size.w),
+                        height: Some(geo.size.h),
+                    });
+
+                    let layer_name = match layer.layer() {
+                        wlr_layer::Layer::Background => "background",
+                        wlr_layer::Layer::Bottom => "bottom",
+                        wlr_layer::Layer::Top => "top",
+                        wlr_layer::Layer::Overlay => "overlay",
+                    };
+
+                    let surface = list_surfaces_response::Surface {
+                        layer_namespace: Some(layer.namespace().to_string()),
+                        layer: Some(layer_name.to_string()),
+                        output_name: Some(output.name()),
+                        geometry,
+                        ..Default::default()
+                    };
+
+                    push_surface_tree(pinnacle, &mut surfaces, layer.wl_surface(), None, surface);
+                }
+            }
+
+            ListSurfacesResponse { surfaces }
+        })
+        .await
+    }
+}
+
+/// Add `wl_surface`, built on top of `surface`, to `surfaces` along with its popups
+/// and subsurfaces.
+///
+/// `parent` is the surface `wl_surface` is a subsurface of.
+fn push_surface_tree(
+    pinnacle: &Pinnacle,
+    surfaces: &mut Vec<list_surfaces_response::Surface>,
+    wl_surface: &WlSurface,
+    parent: Option<&WlSurface>,
+    surface: list_surfaces_response::Surface,
+) {
+    let output_name = surface.output_name.clone();
+    let loc: Option<Point<i32, Logical>> = surface
+        .geometry
+        .and_then(|geo| Some((geo.x?, geo.y?).into()));
+
+    surfaces.push(surface_entry(pinnacle, wl_surface, parent, surface));
+
+    // This already includes nested popups, with offsets from the geometry of `wl_surface`
+    if parent.is_none() {
+        for (popup, offset) in PopupManager::popups_for_surface(wl_surface) {
+            let size = popup.geometry().size;
+            let popup_loc = loc.map(|loc| loc + offset);
+            let popup_surface = list_surfaces_response::Surface {
+                output_name: output_name.clone(),
+                geometry: Some(Geometry {
+                    x: popup_loc.map(|loc| loc.x),
+                    y: popup_loc.map(|loc| loc.y),
+                    width: Some(size.w),
+                    height: Some(size.h),
+                }),
+                ..Default::default()
+            };
+
+            surfaces.push(surface_entry(
+                pinnacle,
+                popup.wl_surface(),
+                Some(wl_surface),
+                popup_surface,
+            ));
+            push_subsurfaces(pinnacle, surfaces, popup.wl_surface(), &output_name);
+        }
+    }
+
+    push_subsurfaces(pinnacle, surfaces, wl_surface, &output_name);
+}
+
+fn push_subsurfaces(
+    pinnacle: &Pinnacle,
+    surfaces: &mut Vec<list_surfaces_response::Surface>,
+    wl_surface: &WlSurface,
+    output_name: &Option<String>,
+) {
+    for child in compositor::get_children(wl_surface) {
+        let surface = list_surfaces_response::Surface {
+            output_name: output_name.clone(),
+            ..Default::default()
+        };
+        push_surface_tree(pinnacle, surfaces, &child, Some(wl_surface), surface);
+    }
+}
+
+/// Fill in the parts of `surface` that come from `wl_surface` itself.
+fn surface_entry(
+    pinnacle: &Pinnacle,
+    wl_surface: &WlSurface,
+    parent: Option<&WlSurface>,
+    surface: list_surfaces_response::Surface,
+) -> list_surfaces_response::Surface {
+    let (mapped, size) = with_renderer_surface_state(wl_surface, |state| {
+        (state.buffer().is_some(), state.surface_size())
+    })
+    .unwrap_or_default();
+
+    let client = wl_surface.client();
+
+    let client_pid = client
+        .as_ref()
+        .and_then(|client| client.get_credentials(&pinnacle.display_handle).ok())
+        .map(|credentials| credentials.pid);
+
+    // Object ids are only unique within a client, so identify the client too
+    let client_id = client.map(|client| {
+        let mut hasher = DefaultHasher::new();
+        client.id().hash(&mut hasher);
+        hasher.finish()
+    });
+
+    list_surfaces_response::Surface {
+        surface_id: Some(wl_surface.id().protocol_id()),
+        client_id,
+        client_pid,
+        role: compositor::get_role(wl_surface).map(|role| role.to_string()),
+        parent_surface_id: parent.map(|parent| parent.id().protocol_id()),
+        geometry: surface.geometry.or_else(|| {
+            size.map(|size| Geometry {
+                x: None,
+                y: None,
+                width: Some(size.w),
+                height: Some(size.h),
+            })
+        }),
+        mapped: Some(mapped),
+        ..surface
+    }
 }
 
 pub struct InputService {
@@ -242,13 +559,24 @@ impl InputService {
 impl input_service_server::InputService for InputService {
     type SetKeybindStream = ResponseStream<SetKeybindResponse>;
     type SetMousebindStream = ResponseStream<SetMousebindResponse>;
+    type SetIdleActionStream = ResponseStream<SetIdleActionResponse>;
 
     async fn set_keybind(
         &self,
         request: Request<SetKeybindRequest>,
     ) -> Result<Response<Self::SetKeybindStream>, Status> {
+        let owner = KeybindOwner {
+            pid: request
+                .extensions()
+                .get::<tonic::transport::server::UdsConnectInfo>()
+                .and_then(|info| info.peer_cred)
+                .and_then(|cred| cred.pid()),
+        };
+
         let request = request.into_inner();
 
+        let allow_when_disabled = request.allow_when_disabled();
+
         // TODO: impl From<&[Modifier]> for ModifierMask
         let modifiers = request
             .modifiers()
@@ -271,33 +599,72 @@ impl input_service_server::InputService for InputService {
             .key
             .ok_or_else(|| Status::invalid_argument("no key specified"))?;
 
-        use pinnacle_api_defs::pinnacle::input::v0alpha1::set_keybind_request::Key;
+        let keysym_from_name = |s: &str| {
+            if s.chars().count() == 1 {
+                let Some(ch) = s.chars().next() else { unreachable!() };
+                xkbcommon::xkb::Keysym::from_char(ch)
+            } else {
+                xkbcommon::xkb::keysym_from_name(s, xkbcommon::xkb::KEYSYM_NO_FLAGS)
+            }
+        };
+
+        use pinnacle_api_defs::pinnacle::input::v0alpha1::set_keybind_request::{HeldKey, Key};
         let keysym = match key {
             Key::RawCode(num) => {
                 debug!("Set keybind: {:?}, raw {}", modifiers, num);
                 xkbcommon::xkb::Keysym::new(num)
             }
             Key::XkbName(s) => {
-                if s.chars().count() == 1 {
-                    let Some(ch) = s.chars().next() else { unreachable!() };
-                    let keysym = xkbcommon::xkb::Keysym::from_char(ch);
-                    debug!("Set keybind: {:?}, {:?}", modifiers, keysym);
-                    keysym
-                } else {
-                    let keysym =
-                        xkbcommon::xkb::keysym_from_name(&s, xkbcommon::xkb::KEYSYM_NO_FLAGS);
-                    debug!("Set keybind: {:?}, {:?}", modifiers, keysym);
-                    keysym
-                }
+                let keysym = keysym_from_name(&s);
+                debug!("Set keybind: {:?}, {:?}", modifiers, keysym);
+                keysym
             }
         };
 
+        let held_keysym = request.held_key.map(|held_key| match held_key {
+            HeldKey::HeldRawCode(num) => xkbcommon::xkb::Keysym::new(num),
+            HeldKey::HeldXkbName(s) => keysym_from_name(&s),
+        });
+
+        if let Some(held_keysym) = held_keysym {
+            if held_keysym.is_modifier_key() {
+                return Err(Status::invalid_argument(
+                    "the held key is a modifier; use `modifiers` instead",
+                ));
+            }
+            if held_keysym == keysym {
+                return Err(Status::invalid_argument(
+                    "the held key can't be the key that triggers the keybind",
+                ));
+            }
+        }
+
         run_server_streaming(&self.sender, move |state, sender| {
-            state
-                .pinnacle
-                .input_state
-                .keybinds
-                .insert((modifiers, keysym), sender);
+            let input_state = &mut state.pinnacle.input_state;
+            input_state
+                .keybind_owners
+                .insert((modifiers, held_keysym, keysym), owner);
+            if allow_when_disabled {
+                input_state
+                    .keybinds_allowed_when_disabled
+                    .insert((modifiers, held_keysym, keysym));
+            } else {
+                input_state.keybinds_allowed_when_disabled.remove(&(
+                    modifiers,
+                    held_keysym,
+                    keysym,
+                ));
+            }
+            match held_keysym {
+                Some(held_keysym) => {
+                    input_state
+                        .held_keybinds
+                        .insert((modifiers, held_keysym, keysym), sender);
+                }
+                None => {
+                    input_state.keybinds.insert((modifiers, keysym), sender);
+                }
+            }
         })
     }
 
@@ -333,7 +700,7 @@ impl input_service_server::InputService for InputService {
         let edge = request.edge();
 
         if let MouseEdge::Unspecified = edge {
-            return Err(Status::invalid_argument("press or release not specified"));
+            return Err(Status::invalid_argument("mouse edge not specified"));
         }
 
         run_server_streaming(&self.sender, move |state, sender| {
@@ -368,6 +735,101 @@ impl input_service_server::InputService for InputService {
         .await
     }
 
+    async fn send_key(&self, request: Request<SendKeyRequest>) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        let match_keybinds = request.match_keybinds();
+
+        use pinnacle_api_defs::pinnacle::input::v0alpha1::send_key_request::Key;
+        let keysym = match request
+            .key
+            .ok_or_else(|| Status::invalid_argument("no key specified"))?
+        {
+            Key::RawCode(num) => xkbcommon::xkb::Keysym::new(num),
+            Key::XkbName(s) => {
+                if s.chars().count() == 1 {
+                    let Some(ch) = s.chars().next() else { unreachable!() };
+                    xkbcommon::xkb::Keysym::from_char(ch)
+                } else {
+                    xkbcommon::xkb::keysym_from_name(&s, xkbcommon::xkb::KEYSYM_NO_FLAGS)
+                }
+            }
+        };
+
+        if keysym.raw() == xkbcommon::xkb::keysyms::KEY_NoSymbol {
+            return Err(Status::invalid_argument("unknown key"));
+        }
+
+        run_unary(&self.sender, move |state| {
+            if !state.send_synthetic_key(keysym, match_keybinds) {
+                return Err(Status::invalid_argument(format!(
+                    "no key in the current keymap types {}",
+                    xkbcommon::xkb::keysym_get_name(keysym)
+                )));
+            }
+
+            Ok(())
+        })
+        .await?
+        .into_inner()
+        .map(Response::new)
+    }
+
+    async fn set_idle_action(
+        &self,
+        request: Request<SetIdleActionRequest>,
+    ) -> Result<Response<Self::SetIdleActionStream>, Status> {
+        let request = request.into_inner();
+
+        let timeout = match request.timeout_ms {
+            Some(0) | None => {
+                return Err(Status::invalid_argument(
+                    "idle timeout must be at least 1ms",
+                ))
+            }
+            Some(ms) => Duration::from_millis(ms as u64),
+        };
+
+        run_server_streaming(&self.sender, move |state, sender| {
+            state.pinnacle.add_idle_stage(timeout, sender);
+        })
+    }
+
+    async fn set_modifier_remaps(
+        &self,
+        request: Request<SetModifierRemapsRequest>,
+    ) -> Result<Response<()>, Status> {
+        use pinnacle_api_defs::pinnacle::input::v0alpha1::Modifier;
+
+        let to_mask = |modifier: Modifier| match modifier {
+            Modifier::Unspecified => Err(Status::invalid_argument("unspecified modifier")),
+            Modifier::Shift => Ok(ModifierMask::SHIFT),
+            Modifier::Ctrl => Ok(ModifierMask::CTRL),
+            Modifier::Alt => Ok(ModifierMask::ALT),
+            Modifier::Super => Ok(ModifierMask::SUPER),
+        };
+
+        let mut remaps = Vec::<(ModifierMask, ModifierMask)>::new();
+
+        for remap in request.into_inner().remaps {
+            let from = to_mask(remap.from())?;
+            let to = to_mask(remap.to())?;
+
+            if remaps.iter().any(|(prev_from, _)| *prev_from == from) {
+                return Err(Status::invalid_argument(format!(
+                    "{from:?} was remapped more than once"
+                )));
+            }
+
+            remaps.push((from, to));
+        }
+
+        run_unary_no_response(&self.sender, move |state| {
+            state.pinnacle.input_state.modifier_remaps = remaps;
+        })
+        .await
+    }
+
     async fn set_repeat_rate(
         &self,
         request: Request<SetRepeatRateRequest>,
@@ -389,6 +851,185 @@ impl input_service_server::InputService for InputService {
         .await
     }
 
+    async fn set_click_settings(
+        &self,
+        request: Request<SetClickSettingsRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        run_unary_no_response(&self.sender, move |state| {
+            let settings = &mut state.pinnacle.input_state.click_settings;
+
+            if let Some(interval) = request.double_click_interval {
+                settings.double_click_interval = Duration::from_millis(interval as u64);
+            }
+
+            if let Some(threshold) = request.drag_threshold {
+                settings.drag_threshold = threshold as f64;
+            }
+
+            if let Some(focus_on_release) = request.focus_on_release {
+                settings.focus_on_release = focus_on_release;
+            }
+        })
+        .await
+    }
+
+    async fn set_pointer_confinement(
+        &self,
+        request: Request<SetPointerConfinementRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        let output_name = request.output_name.map(OutputName);
+
+        run_unary(&self.sender, move |state| {
+            let output = match output_name {
+                Some(output_name) => {
+                    let Some(output) = output_name.output(&state.pinnacle) else {
+                        return Err(Status::invalid_argument(format!(
+                            "output {} does not exist",
+                            output_name.0
+                        )));
+                    };
+                    Some(output)
+                }
+                None => None,
+            };
+
+            state.set_pointer_confinement(output);
+
+            Ok(())
+        })
+        .await?
+        .into_inner()
+        .map(Response::new)
+    }
+
+    async fn get_pointer(
+        &self,
+        _request: Request<GetPointerRequest>,
+    ) -> Result<Response<GetPointerResponse>, Status> {
+        run_unary(&self.sender, move |state| {
+            let Some(location) = state
+                .pinnacle
+                .seat
+                .get_pointer()
+                .map(|ptr| ptr.current_location())
+            else {
+                return GetPointerResponse::default();
+            };
+
+            let output_name = state
+                .pinnacle
+                .space
+                .output_under(location)
+                .next()
+                .map(|output| output.name());
+
+            let focus = state
+                .pointer_focus_target_under(location)
+                .map(|(focus, _)| focus);
+
+            let window_id = focus
+                .as_ref()
+                .and_then(|focus| focus.window_for(state))
+                .map(|win| win.with_state(|state| state.id.0));
+
+            let layer_namespace = focus
+                .as_ref()
+                .and_then(|focus| focus.layer_for(state))
+                .map(|layer| layer.namespace().to_string());
+
+            GetPointerResponse {
+                x: Some(location.x),
+                y: Some(location.y),
+                output_name,
+                window_id,
+                layer_namespace,
+            }
+        })
+        .await
+    }
+
+    async fn get_seats(
+        &self,
+        _request: Request<GetSeatsRequest>,
+    ) -> Result<Response<GetSeatsResponse>, Status> {
+        run_unary(&self.sender, move |state| {
+            let seat = &state.pinnacle.seat;
+
+            GetSeatsResponse {
+                seats: vec![get_seats_response::Seat {
+                    name: Some(seat.name().to_string()),
+                    keyboard: Some(seat.get_keyboard().is_some()),
+                    pointer: Some(seat.get_pointer().is_some()),
+                    touch: Some(seat.get_touch().is_some()),
+                }],
+            }
+        })
+        .await
+    }
+
+    async fn get_keybinds(
+        &self,
+        _request: Request<GetKeybindsRequest>,
+    ) -> Result<Response<GetKeybindsResponse>, Status> {
+        run_unary(&self.sender, move |state| {
+            let input_state = &state.pinnacle.input_state;
+
+            let keybinds = input_state
+                .keybinds
+                .iter()
+                .map(|((mods, key), sender)| (*mods, None, *key, sender))
+                .chain(
+                    input_state
+                        .held_keybinds
+                        .iter()
+                        .map(|((mods, held, key), sender)| (*mods, Some(*held), *key, sender)),
+                )
+                .map(|(mods, held_key, key, sender)| {
+                    let owner_pid = input_state
+                        .keybind_owners
+                        .get(&(mods, held_key, key))
+                        .and_then(|owner| owner.pid);
+
+                    let owner_name = owner_pid.and_then(|pid| {
+                        let pid = sysinfo::Pid::from_u32(pid as u32);
+                        let system = &mut state.pinnacle.system_processes;
+                        system.refresh_process_specifics(pid, ProcessRefreshKind::new());
+                        system.process(pid).map(|proc| proc.name().to_string())
+                    });
+
+                    let modifiers = [
+                        (ModifierMask::SHIFT, Modifier::Shift),
+                        (ModifierMask::CTRL, Modifier::Ctrl),
+                        (ModifierMask::ALT, Modifier::Alt),
+                        (ModifierMask::SUPER, Modifier::Super),
+                    ]
+                    .into_iter()
+                    .filter(|(mask, _)| mods.contains(*mask))
+                    .map(|(_, modifier)| modifier as i32)
+                    .collect();
+
+                    get_keybinds_response::Keybind {
+                        modifiers,
+                        key_code: Some(key.raw()),
+                        key_name: Some(xkbcommon::xkb::keysym_get_name(key)),
+                        held_key_code: held_key.map(|held| held.raw()),
+                        held_key_name: held_key.map(xkbcommon::xkb::keysym_get_name),
+                        owner_pid,
+                        owner_name,
+                        connected: Some(!sender.is_closed()),
+                    }
+                })
+                .collect();
+
+            GetKeybindsResponse { keybinds }
+        })
+        .await
+    }
+
     async fn set_libinput_setting(
         &self,
         request: Request<SetLibinputSettingRequest>,
@@ -739,16 +1380,21 @@ impl tag_service_server::TagService for TagService {
                 return;
             };
 
+            let Some(output) = tag.output(&state.pinnacle) else {
+                return;
+            };
+
+            let previous_active =
+                output.with_state(|op_state| op_state.focused_tags().cloned().collect::<Vec<_>>());
+
             match set_or_toggle {
-                SetOrToggle::Set => tag.set_active(true, state),
-                SetOrToggle::Unset => tag.set_active(false, state),
-                SetOrToggle::Toggle => tag.set_active(!tag.active(), state),
+                SetOrToggle::Set => tag.set_active(true, &mut state.pinnacle),
+                SetOrToggle::Unset => tag.set_active(false, &mut state.pinnacle),
+                SetOrToggle::Toggle => tag.set_active(!tag.active(), &mut state.pinnacle),
                 SetOrToggle::Unspecified => unreachable!(),
             }
 
-            let Some(output) = tag.output(&state.pinnacle) else {
-                return;
-            };
+            output.with_state_mut(|op_state| op_state.set_previous_active_tags(previous_active));
 
             state.pinnacle.fixup_xwayland_window_layering();
 
@@ -775,10 +1421,12 @@ impl tag_service_server::TagService for TagService {
             };
 
             output.with_state_mut(|op_state| {
+                let previous_active = op_state.focused_tags().cloned().collect::<Vec<_>>();
                 for op_tag in op_state.tags.iter_mut() {
-                    op_tag.set_active(false, state);
+                    op_tag.set_active(false, &mut state.pinnacle);
                 }
-                tag.set_active(true, state);
+                tag.set_active(true, &mut state.pinnacle);
+                op_state.set_previous_active_tags(previous_active);
             });
 
             state.pinnacle.fixup_xwayland_window_layering();
@@ -790,7 +1438,10 @@ impl tag_service_server::TagService for TagService {
         .await
     }
 
-    async fn add(&self, request: Request<AddRequest>) -> Result<Response<AddResponse>, Status> {
+    async fn switch_to_previous(
+        &self,
+        request: Request<SwitchToPreviousRequest>,
+    ) -> Result<Response<()>, Status> {
         let request = request.into_inner();
 
         let output_name = OutputName(
@@ -799,24 +1450,325 @@ impl tag_service_server::TagService for TagService {
                 .ok_or_else(|| Status::invalid_argument("no output specified"))?,
         );
 
-        run_unary(&self.sender, move |state| {
-            let new_tags = request
-                .tag_names
-                .into_iter()
-                .map(Tag::new)
-                .collect::<Vec<_>>();
+        run_unary_no_response(&self.sender, move |state| {
+            let Some(output) = output_name.output(&state.pinnacle) else {
+                return;
+            };
 
-            let tag_ids = new_tags
-                .iter()
-                .map(|tag| tag.id())
-                .map(|id| id.0)
-                .collect::<Vec<_>>();
+            output.with_state_mut(|op_state| {
+                // Tags may have been removed since they were last active
+                let previous_active = op_state
+                    .previous_active_tags
+                    .iter()
+                    .filter(|tag| op_state.tags.contains(tag))
+                    .cloned()
+                    .collect::<Vec<_>>();
+
+                if previous_active.is_empty() {
+                    return;
+                }
 
-            state
-                .pinnacle
-                .config
-                .connector_saved_states
-                .entry(output_name.clone())
+                let current_active = op_state.focused_tags().cloned().collect::<Vec<_>>();
+
+                for op_tag in op_state.tags.iter() {
+                    op_tag.set_active(previous_active.contains(op_tag), &mut state.pinnacle);
+                }
+
+                op_state.previous_active_tags = current_active;
+            });
+
+            state.pinnacle.fixup_xwayland_window_layering();
+
+            state.pinnacle.request_layout(&output);
+            state.update_focus(&output);
+            state.schedule_render(&output);
+        })
+        .await
+    }
+
+    async fn set_active_tags(
+        &self,
+        request: Request<SetActiveTagsRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        let output_name = OutputName(
+            request
+                .output_name
+                .ok_or_else(|| Status::invalid_argument("no output specified"))?,
+        );
+
+        let tag_ids = request.tag_ids.into_iter().map(TagId).collect::<Vec<_>>();
+
+        run_unary(&self.sender, move |state| {
+            let Some(output) = output_name.output(&state.pinnacle) else {
+                return Err(Status::invalid_argument(format!(
+                    "output {} does not exist",
+                    output_name.0
+                )));
+            };
+
+            let tags = tag_ids
+                .into_iter()
+                .map(|id| {
+                    id.tag(&state.pinnacle)
+                        .filter(|tag| tag.output(&state.pinnacle).as_ref() == Some(&output))
+                        .ok_or_else(|| {
+                            Status::invalid_argument(format!(
+                                "tag {} is not on output {}",
+                                id.0, output_name.0
+                            ))
+                        })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            output.with_state_mut(|op_state| {
+                let previous_active = op_state.focused_tags().cloned().collect::<Vec<_>>();
+                for op_tag in op_state.tags.iter() {
+                    op_tag.set_active(tags.contains(op_tag), &mut state.pinnacle);
+                }
+                op_state.set_previous_active_tags(previous_active);
+            });
+
+            state.pinnacle.fixup_xwayland_window_layering();
+
+            state.pinnacle.request_layout(&output);
+            state.update_focus(&output);
+            state.schedule_render(&output);
+
+            Ok(())
+        })
+        .await?
+        .into_inner()
+        .map(Response::new)
+    }
+
+    async fn set_gaps(&self, request: Request<SetGapsRequest>) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        let tag_id = TagId(
+            request
+                .tag_id
+                .ok_or_else(|| Status::invalid_argument("no tag specified"))?,
+        );
+
+        run_unary_no_response(&self.sender, move |state| {
+            let Some(tag) = tag_id.tag(&state.pinnacle) else { return };
+
+            let gaps = tag.gaps();
+            tag.set_gaps(Gaps {
+                inner: request.inner.unwrap_or(gaps.inner),
+                outer: request.outer.unwrap_or(gaps.outer),
+                smart: request.smart.unwrap_or(gaps.smart),
+            });
+
+            let Some(output) = tag.output(&state.pinnacle) else {
+                return;
+            };
+
+            state.pinnacle.request_layout(&output);
+            state.schedule_render(&output);
+        })
+        .await
+    }
+
+    async fn set_floating_hidden(
+        &self,
+        request: Request<SetFloatingHiddenRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        let tag_id = TagId(
+            request
+                .tag_id
+                .ok_or_else(|| Status::invalid_argument("no tag specified"))?,
+        );
+
+        let set_or_toggle = request.set_or_toggle();
+
+        if set_or_toggle == SetOrToggle::Unspecified {
+            return Err(Status::invalid_argument("unspecified set or toggle"));
+        }
+
+        run_unary_no_response(&self.sender, move |state| {
+            let Some(tag) = tag_id.tag(&state.pinnacle) else {
+                return;
+            };
+
+            match set_or_toggle {
+                SetOrToggle::Set => tag.set_floating_hidden(true),
+                SetOrToggle::Unset => tag.set_floating_hidden(false),
+                SetOrToggle::Toggle => tag.set_floating_hidden(!tag.floating_hidden()),
+                SetOrToggle::Unspecified => unreachable!(),
+            }
+
+            let Some(output) = tag.output(&state.pinnacle) else {
+                return;
+            };
+
+            state.pinnacle.fixup_xwayland_window_layering();
+            state.pinnacle.update_suspended_windows();
+
+            // Hidden floating windows are skipped when finding the focused window,
+            // so this moves focus to a shown window and back once they're shown again
+            state.update_focus(&output);
+            state.schedule_render(&output);
+        })
+        .await
+    }
+
+    async fn set_window_limit(
+        &self,
+        request: Request<SetWindowLimitRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        let tag_id = TagId(
+            request
+                .tag_id
+                .ok_or_else(|| Status::invalid_argument("no tag specified"))?,
+        );
+
+        let overflow = match request.overflow() {
+            tag::v0alpha1::OverflowPolicy::Unspecified | tag::v0alpha1::OverflowPolicy::Float => {
+                OverflowPolicy::Float
+            }
+            tag::v0alpha1::OverflowPolicy::NextTag => OverflowPolicy::NextTag,
+            tag::v0alpha1::OverflowPolicy::Reject => OverflowPolicy::Reject,
+        };
+
+        let limit = match request.limit {
+            Some(0) => return Err(Status::invalid_argument("window limit must be at least 1")),
+            Some(max) => Some(WindowLimit { max, overflow }),
+            None => None,
+        };
+
+        run_unary_no_response(&self.sender, move |state| {
+            let Some(tag) = tag_id.tag(&state.pinnacle) else {
+                return;
+            };
+
+            tag.set_window_limit(limit);
+        })
+        .await
+    }
+
+    async fn set_tiling_direction(
+        &self,
+        request: Request<SetTilingDirectionRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        let tag_id = TagId(
+            request
+                .tag_id
+                .ok_or_else(|| Status::invalid_argument("no tag specified"))?,
+        );
+
+        let direction = match request.direction() {
+            tag::v0alpha1::TilingDirection::Unspecified => None,
+            tag::v0alpha1::TilingDirection::Horizontal => Some(TilingDirection::Horizontal),
+            tag::v0alpha1::TilingDirection::Vertical => Some(TilingDirection::Vertical),
+        };
+
+        run_unary_no_response(&self.sender, move |state| {
+            let Some(tag) = tag_id.tag(&state.pinnacle) else {
+                return;
+            };
+
+            tag.set_tiling_direction(direction);
+
+            let Some(output) = tag.output(&state.pinnacle) else {
+                return;
+            };
+
+            state.pinnacle.request_layout(&output);
+            state.schedule_render(&output);
+        })
+        .await
+    }
+
+    async fn toggle_tiling_direction(
+        &self,
+        request: Request<ToggleTilingDirectionRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        let tag_id = TagId(
+            request
+                .tag_id
+                .ok_or_else(|| Status::invalid_argument("no tag specified"))?,
+        );
+
+        run_unary_no_response(&self.sender, move |state| {
+            let Some(tag) = tag_id.tag(&state.pinnacle) else {
+                return;
+            };
+
+            tag.set_tiling_direction(Some(match tag.tiling_direction() {
+                Some(TilingDirection::Vertical) => TilingDirection::Horizontal,
+                Some(TilingDirection::Horizontal) | None => TilingDirection::Vertical,
+            }));
+
+            let Some(output) = tag.output(&state.pinnacle) else {
+                return;
+            };
+
+            state.pinnacle.request_layout(&output);
+            state.schedule_render(&output);
+        })
+        .await
+    }
+
+    async fn set_display_name(
+        &self,
+        request: Request<SetDisplayNameRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        let tag_id = TagId(
+            request
+                .tag_id
+                .ok_or_else(|| Status::invalid_argument("no tag specified"))?,
+        );
+
+        run_unary_no_response(&self.sender, move |state| {
+            let Some(tag) = tag_id.tag(&state.pinnacle) else {
+                return;
+            };
+
+            tag.set_display_name(request.display_name);
+        })
+        .await
+    }
+
+    async fn add(&self, request: Request<AddRequest>) -> Result<Response<AddResponse>, Status> {
+        let request = request.into_inner();
+
+        let output_name = OutputName(
+            request
+                .output_name
+                .ok_or_else(|| Status::invalid_argument("no output specified"))?,
+        );
+
+        run_unary(&self.sender, move |state| {
+            let new_tags = request
+                .tag_names
+                .into_iter()
+                .map(Tag::new)
+                .collect::<Vec<_>>();
+
+            let tag_ids = new_tags
+                .iter()
+                .map(|tag| tag.id())
+                .map(|id| id.0)
+                .collect::<Vec<_>>();
+
+            state
+                .pinnacle
+                .config
+                .connector_saved_states
+                .entry(output_name.clone())
                 .or_default()
                 .tags
                 .extend(new_tags.clone());
@@ -830,13 +1782,7 @@ impl tag_service_server::TagService for TagService {
 
             for tag in new_tags {
                 for window in state.pinnacle.windows.iter() {
-                    window.with_state_mut(|state| {
-                        for win_tag in state.tags.iter_mut() {
-                            if win_tag.id() == tag.id() {
-                                *win_tag = tag.clone();
-                            }
-                        }
-                    });
+                    window.with_state_mut(|state| state.tags.replace(tag.clone()));
                 }
             }
 
@@ -873,6 +1819,26 @@ impl tag_service_server::TagService for TagService {
                     conn_saved_state.tags.retain(|tag| tag != tag_to_remove);
                 }
             }
+
+            for tag_to_remove in tags_to_remove.iter() {
+                state
+                    .pinnacle
+                    .layout_state
+                    .reported_tag_layouts
+                    .remove(&tag_to_remove.id());
+            }
+        })
+        .await
+    }
+
+    async fn set_default_output_tags(
+        &self,
+        request: Request<SetDefaultOutputTagsRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        run_unary_no_response(&self.sender, move |state| {
+            state.pinnacle.config.default_output_tags = request.tag_names;
         })
         .await
     }
@@ -917,6 +1883,18 @@ impl tag_service_server::TagService for TagService {
                 .map(|output| output.name());
             let active = tag.as_ref().map(|tag| tag.active());
             let name = tag.as_ref().map(|tag| tag.name());
+            let display_name = tag.as_ref().and_then(|tag| tag.display_name());
+            let gaps = tag.as_ref().map(|tag| tag.gaps());
+            let floating_hidden = tag.as_ref().map(|tag| tag.floating_hidden());
+            let window_limit = tag.as_ref().and_then(|tag| tag.window_limit());
+            let tiling_direction = tag.as_ref().and_then(|tag| tag.tiling_direction());
+            let urgent = tag.as_ref().map(|tag| {
+                state.pinnacle.windows.iter().any(|win| {
+                    win.with_state(|win_state| {
+                        win_state.urgent.is_some() && win_state.tags.contains(tag)
+                    })
+                })
+            });
             let window_ids = tag
                 .as_ref()
                 .map(|tag| {
@@ -938,12 +1916,35 @@ impl tag_service_server::TagService for TagService {
                 name,
                 output_name,
                 window_ids,
+                inner_gaps: gaps.map(|gaps| gaps.inner),
+                outer_gaps: gaps.map(|gaps| gaps.outer),
+                smart_gaps: gaps.map(|gaps| gaps.smart),
+                urgent,
+                floating_hidden,
+                window_limit: window_limit.map(|limit| limit.max),
+                overflow_policy: window_limit.map(|limit| match limit.overflow {
+                    OverflowPolicy::Float => tag::v0alpha1::OverflowPolicy::Float as i32,
+                    OverflowPolicy::NextTag => tag::v0alpha1::OverflowPolicy::NextTag as i32,
+                    OverflowPolicy::Reject => tag::v0alpha1::OverflowPolicy::Reject as i32,
+                }),
+                tiling_direction: tiling_direction
+                    .map(|direction| tag::v0alpha1::TilingDirection::from(direction) as i32),
+                display_name,
             }
         })
         .await
     }
 }
 
+impl From<TilingDirection> for tag::v0alpha1::TilingDirection {
+    fn from(direction: TilingDirection) -> Self {
+        match direction {
+            TilingDirection::Horizontal => tag::v0alpha1::TilingDirection::Horizontal,
+            TilingDirection::Vertical => tag::v0alpha1::TilingDirection::Vertical,
+        }
+    }
+}
+
 pub struct OutputService {
     sender: StateFnSender,
 }
@@ -1117,9 +2118,376 @@ impl output_service_server::OutputService for OutputService {
         .await
     }
 
-    async fn get(
+    async fn set_overview(
         &self,
-        _request: Request<output::v0alpha1::GetRequest>,
+        request: Request<SetOverviewRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        let set_or_toggle = request.set_or_toggle();
+
+        if set_or_toggle == SetOrToggle::Unspecified {
+            return Err(Status::invalid_argument("unspecified set or toggle"));
+        }
+
+        let all_tags = request.all_tags();
+
+        let Some(output_name) = request.output_name else {
+            return Err(Status::invalid_argument("output_name was null"));
+        };
+
+        run_unary_no_response(&self.sender, move |state| {
+            let Some(output) = OutputName(output_name).output(&state.pinnacle) else {
+                return;
+            };
+
+            let is_open = state
+                .pinnacle
+                .overview(&output)
+                .is_some_and(|overview| overview.is_open());
+
+            let set = match set_or_toggle {
+                SetOrToggle::Set => true,
+                SetOrToggle::Unset => false,
+                SetOrToggle::Toggle => !is_open,
+                SetOrToggle::Unspecified => unreachable!(),
+            };
+
+            if set {
+                state.pinnacle.open_overview(&output, all_tags);
+            } else {
+                state.pinnacle.close_overview(&output);
+            }
+
+            state.schedule_render(&output);
+        })
+        .await
+    }
+
+    async fn set_magnification(
+        &self,
+        request: Request<SetMagnificationRequest>,
+    ) -> Result<Response<()>, Status> {
+        let SetMagnificationRequest {
+            output_name: Some(output_name),
+            absolute_or_relative: Some(absolute_or_relative),
+        } = request.into_inner()
+        else {
+            return Err(Status::invalid_argument(
+                "output_name or absolute_or_relative were null",
+            ));
+        };
+
+        let (set_magnification_request::AbsoluteOrRelative::Absolute(value)
+        | set_magnification_request::AbsoluteOrRelative::Relative(value)) = absolute_or_relative;
+
+        if !value.is_finite() {
+            return Err(Status::invalid_argument("magnification must be finite"));
+        }
+
+        run_unary_no_response(&self.sender, move |state| {
+            let Some(output) = OutputName(output_name).output(&state.pinnacle) else {
+                return;
+            };
+
+            let mut magnification = output.with_state(|state| state.magnification.unwrap_or(1.0));
+
+            match absolute_or_relative {
+                set_magnification_request::AbsoluteOrRelative::Absolute(abs) => {
+                    magnification = abs as f64
+                }
+                set_magnification_request::AbsoluteOrRelative::Relative(rel) => {
+                    magnification += rel as f64
+                }
+            }
+
+            let magnification = magnification.clamp(1.0, 16.0);
+
+            output.with_state_mut(|state| {
+                state.magnification = (magnification > 1.0).then_some(magnification);
+            });
+
+            state.schedule_render(&output);
+        })
+        .await
+    }
+
+    async fn set_brightness(
+        &self,
+        request: Request<SetBrightnessRequest>,
+    ) -> Result<Response<()>, Status> {
+        let SetBrightnessRequest {
+            output_name: Some(output_name),
+            absolute_or_relative: Some(absolute_or_relative),
+        } = request.into_inner()
+        else {
+            return Err(Status::invalid_argument(
+                "output_name or absolute_or_relative were null",
+            ));
+        };
+
+        let (set_brightness_request::AbsoluteOrRelative::Absolute(value)
+        | set_brightness_request::AbsoluteOrRelative::Relative(value)) = absolute_or_relative;
+
+        if !value.is_finite() {
+            return Err(Status::invalid_argument("brightness must be finite"));
+        }
+
+        let Some(backlight) = Backlight::for_output(&output_name) else {
+            return Err(Status::failed_precondition(format!(
+                "output {output_name} has no backlight"
+            )));
+        };
+
+        let to_status = |err: std::io::Error| match err.kind() {
+            std::io::ErrorKind::PermissionDenied => Status::permission_denied(format!(
+                "no permission to write to backlight {}; make sure you're using a \
+                logind or seatd session, or add a udev rule that lets the `video` group \
+                write to `/sys/class/backlight/*/brightness`",
+                backlight.name()
+            )),
+            _ => Status::internal(format!(
+                "failed to set brightness of backlight {}: {err}",
+                backlight.name()
+            )),
+        };
+
+        let brightness = match absolute_or_relative {
+            set_brightness_request::AbsoluteOrRelative::Absolute(abs) => abs,
+            set_brightness_request::AbsoluteOrRelative::Relative(rel) => {
+                backlight.brightness().map_err(to_status)? + rel
+            }
+        };
+
+        backlight.set_brightness(brightness).map_err(to_status)?;
+
+        Ok(Response::new(()))
+    }
+
+    async fn set_gamma_preset(
+        &self,
+        request: Request<SetGammaPresetRequest>,
+    ) -> Result<Response<()>, Status> {
+        let SetGammaPresetRequest {
+            output_name,
+            temperature,
+            brightness,
+            gamma,
+        } = request.into_inner();
+
+        let output_name =
+            OutputName(output_name.ok_or_else(|| Status::invalid_argument("no output specified"))?);
+
+        let preset = if temperature.is_none() && brightness.is_none() && gamma.is_none() {
+            None
+        } else {
+            let default = GammaPreset::default();
+            let preset = GammaPreset {
+                temperature: temperature.unwrap_or(default.temperature),
+                brightness: brightness.unwrap_or(default.brightness),
+                gamma: gamma.unwrap_or(default.gamma),
+            };
+
+            if !(1000..=40000).contains(&preset.temperature) {
+                return Err(Status::invalid_argument(
+                    "temperature must be between 1000 and 40000",
+                ));
+            }
+
+            if !(0.0..=1.0).contains(&preset.brightness) {
+                return Err(Status::invalid_argument(
+                    "brightness must be between 0.0 and 1.0",
+                ));
+            }
+
+            if !(preset.gamma.is_finite() && preset.gamma > 0.0) {
+                return Err(Status::invalid_argument("gamma must be positive"));
+            }
+
+            Some(preset)
+        };
+
+        run_unary_no_response(&self.sender, move |state| {
+            match preset {
+                Some(preset) => {
+                    state
+                        .pinnacle
+                        .gamma_presets
+                        .insert(output_name.clone(), preset);
+                }
+                None => {
+                    state.pinnacle.gamma_presets.remove(&output_name);
+                }
+            }
+
+            // Unplugged outputs get their preset when they're plugged back in
+            if let Some(output) = output_name.output(&state.pinnacle) {
+                state.apply_gamma_preset(&output);
+            }
+        })
+        .await
+    }
+
+    async fn set_lone_window_width(
+        &self,
+        request: Request<SetLoneWindowWidthRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        let alignment = match request.alignment() {
+            output::v0alpha1::LoneWindowAlignment::Unspecified
+            | output::v0alpha1::LoneWindowAlignment::Center => LoneWindowAlignment::Center,
+            output::v0alpha1::LoneWindowAlignment::Left => LoneWindowAlignment::Left,
+            output::v0alpha1::LoneWindowAlignment::Right => LoneWindowAlignment::Right,
+        };
+
+        let output_name = OutputName(
+            request
+                .output_name
+                .ok_or_else(|| Status::invalid_argument("no output specified"))?,
+        );
+
+        let width = match request.max_width {
+            Some(0) => return Err(Status::invalid_argument("max width must be positive")),
+            Some(max_width) => Some(LoneWindowWidth {
+                max_width: i32::try_from(max_width).unwrap_or(i32::MAX),
+                alignment,
+            }),
+            None => None,
+        };
+
+        run_unary_no_response(&self.sender, move |state| {
+            let Some(output) = output_name.output(&state.pinnacle) else {
+                return;
+            };
+
+            output.with_state_mut(|state| state.lone_window_width = width);
+
+            state.pinnacle.request_layout(&output);
+            state.schedule_render(&output);
+        })
+        .await
+    }
+
+    async fn focus_output_under_cursor(
+        &self,
+        _request: Request<FocusOutputUnderCursorRequest>,
+    ) -> Result<Response<()>, Status> {
+        run_unary_no_response(&self.sender, move |state| {
+            let Some(pointer_loc) = state
+                .pinnacle
+                .seat
+                .get_pointer()
+                .map(|ptr| ptr.current_location())
+            else {
+                return;
+            };
+
+            let Some(output) = state
+                .pinnacle
+                .space
+                .output_under(pointer_loc)
+                .next()
+                .cloned()
+            else {
+                return;
+            };
+
+            state.pinnacle.output_focus_stack.set_focus(output.clone());
+            state.update_focus(&output);
+            state.schedule_render(&output);
+        })
+        .await
+    }
+
+    async fn set_focus_lock(
+        &self,
+        request: Request<SetFocusLockRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        let output_name = request.output_name.map(OutputName);
+
+        run_unary(&self.sender, move |state| {
+            let Some(output_name) = output_name else {
+                state.pinnacle.output_focus_stack.set_locked(None);
+                return Ok(());
+            };
+
+            let Some(output) = output_name.output(&state.pinnacle) else {
+                return Err(Status::invalid_argument(format!(
+                    "output {} does not exist",
+                    output_name.0
+                )));
+            };
+
+            state
+                .pinnacle
+                .output_focus_stack
+                .set_locked(Some(output.clone()));
+            state.update_focus(&output);
+            state.schedule_render(&output);
+
+            Ok(())
+        })
+        .await?
+        .into_inner()
+        .map(Response::new)
+    }
+
+    async fn set_primary(
+        &self,
+        request: Request<SetPrimaryRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        let output_name = request.output_name.map(OutputName);
+
+        run_unary_no_response(&self.sender, move |state| {
+            state.pinnacle.config.primary_output = output_name;
+        })
+        .await
+    }
+
+    async fn get_focus_stack(
+        &self,
+        request: Request<GetFocusStackRequest>,
+    ) -> Result<Response<GetFocusStackResponse>, Status> {
+        let request = request.into_inner();
+
+        let output_name = OutputName(
+            request
+                .output_name
+                .ok_or_else(|| Status::invalid_argument("no output specified"))?,
+        );
+
+        run_unary(&self.sender, move |state| {
+            let Some(output) = output_name.output(&state.pinnacle) else {
+                return GetFocusStackResponse::default();
+            };
+
+            let entries = output.with_state(|state| {
+                state
+                    .focus_stack
+                    .stack
+                    .iter()
+                    .rev()
+                    .filter(|win| win.alive() && !win.is_x11_override_redirect())
+                    .map(|win| get_focus_stack_response::Entry {
+                        window_id: Some(win.with_state(|state| state.id.0)),
+                        on_active_tag: Some(win.is_on_active_tag()),
+                    })
+                    .collect()
+            });
+
+            GetFocusStackResponse { entries }
+        })
+        .await
+    }
+
+    async fn get(
+        &self,
+        _request: Request<output::v0alpha1::GetRequest>,
     ) -> Result<Response<output::v0alpha1::GetResponse>, Status> {
         run_unary(&self.sender, move |state| {
             let output_names = state
@@ -1240,6 +2608,36 @@ impl output_service_server::OutputService for OutputService {
                 output.with_state(|state| state.serial.map(|serial| serial.get()))
             });
 
+            let overview = output.as_ref().map(|output| {
+                state
+                    .pinnacle
+                    .overview(output)
+                    .is_some_and(|overview| overview.is_open())
+            });
+
+            let magnification = output
+                .as_ref()
+                .map(|output| output.with_state(|state| state.magnification.unwrap_or(1.0)) as f32);
+
+            let brightness = output
+                .as_ref()
+                .and_then(Backlight::for_output_cached)
+                .and_then(|backlight| backlight.brightness().ok());
+
+            let lone_window_width = output
+                .as_ref()
+                .and_then(|output| output.with_state(|state| state.lone_window_width));
+
+            let lone_window_max_width = lone_window_width.map(|width| width.max_width as u32);
+
+            let lone_window_alignment = lone_window_width.map(|width| {
+                (match width.alignment {
+                    LoneWindowAlignment::Center => output::v0alpha1::LoneWindowAlignment::Center,
+                    LoneWindowAlignment::Left => output::v0alpha1::LoneWindowAlignment::Left,
+                    LoneWindowAlignment::Right => output::v0alpha1::LoneWindowAlignment::Right,
+                }) as i32
+            });
+
             output::v0alpha1::GetPropertiesResponse {
                 make,
                 model,
@@ -1257,6 +2655,11 @@ impl output_service_server::OutputService for OutputService {
                 scale,
                 transform,
                 serial,
+                overview,
+                magnification,
+                brightness,
+                lone_window_max_width,
+                lone_window_alignment,
             }
         })
         .await
@@ -1275,6 +2678,8 @@ impl RenderService {
 
 #[tonic::async_trait]
 impl render_service_server::RenderService for RenderService {
+    type WatchFrameTimingsStream = ResponseStream<WatchFrameTimingsResponse>;
+
     async fn set_upscale_filter(
         &self,
         request: Request<SetUpscaleFilterRequest>,
@@ -1324,4 +2729,69 @@ impl render_service_server::RenderService for RenderService {
         })
         .await
     }
+
+    async fn set_frame_timings(
+        &self,
+        request: Request<SetFrameTimingsRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        let interval = Duration::from_millis(request.interval_ms() as u64);
+
+        run_unary_no_response(&self.sender, move |state| {
+            state.pinnacle.set_frame_timings_interval(Some(interval));
+        })
+        .await
+    }
+
+    async fn watch_frame_timings(
+        &self,
+        _request: Request<WatchFrameTimingsRequest>,
+    ) -> Result<Response<Self::WatchFrameTimingsStream>, Status> {
+        run_server_streaming(&self.sender, |state, sender| {
+            state.pinnacle.frame_timings.add_watcher(sender);
+        })
+    }
+
+    async fn set_inactive_output_dim(
+        &self,
+        request: Request<SetInactiveOutputDimRequest>,
+    ) -> Result<Response<()>, Status> {
+        let amount = request.into_inner().amount();
+
+        if !(0.0..=1.0).contains(&amount) {
+            return Err(Status::invalid_argument(
+                "dim amount must be between 0.0 and 1.0",
+            ));
+        }
+
+        run_unary_no_response(&self.sender, move |state| {
+            state.pinnacle.config.inactive_output_dim = amount;
+            for output in state.pinnacle.space.outputs().cloned().collect::<Vec<_>>() {
+                state.schedule_render(&output);
+            }
+        })
+        .await
+    }
+
+    async fn get_renderer_info(
+        &self,
+        _request: Request<GetRendererInfoRequest>,
+    ) -> Result<Response<GetRendererInfoResponse>, Status> {
+        run_unary(&self.sender, move |state| {
+            let info = state.backend.renderer_info().map_err(|err| {
+                Status::unavailable(format!("failed to get renderer info: {err}"))
+            })?;
+
+            Ok(GetRendererInfoResponse {
+                version: Some(info.version),
+                vendor: Some(info.vendor),
+                renderer: Some(info.renderer),
+                dmabuf_formats: info.dmabuf_formats,
+            })
+        })
+        .await?
+        .into_inner()
+        .map(Response::new)
+    }
 }
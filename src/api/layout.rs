@@ -1,12 +1,19 @@
-use pinnacle_api_defs::pinnacle::layout::v0alpha1::{
-    layout_request::{self, ExplicitLayout},
-    layout_service_server, LayoutRequest, LayoutResponse,
+use pinnacle_api_defs::pinnacle::{
+    layout::v0alpha1::{
+        layout_request::{self, ExplicitLayout},
+        layout_service_server, GetTagLayoutRequest, GetTagLayoutResponse, LayoutRequest,
+        LayoutResponse, RelayoutAllRequest, SetTagLayoutRequest, SetTilingPausedRequest,
+    },
+    tag,
+    v0alpha1::SetOrToggle,
 };
 use tonic::{Request, Response, Status, Streaming};
 
-use crate::output::OutputName;
+use crate::{output::OutputName, tag::TagId};
 
-use super::{run_bidirectional_streaming, ResponseStream, StateFnSender};
+use super::{
+    run_bidirectional_streaming, run_unary, run_unary_no_response, ResponseStream, StateFnSender,
+};
 
 pub struct LayoutService {
     sender: StateFnSender,
@@ -68,4 +75,105 @@ impl layout_service_server::LayoutService for LayoutService {
             },
         )
     }
+
+    async fn relayout_all(
+        &self,
+        _request: Request<RelayoutAllRequest>,
+    ) -> Result<Response<()>, Status> {
+        run_unary_no_response(&self.sender, |state| {
+            state.pinnacle.relayout_all();
+        })
+        .await
+    }
+
+    async fn set_tiling_paused(
+        &self,
+        request: Request<SetTilingPausedRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        let set_or_toggle = request.set_or_toggle();
+
+        if set_or_toggle == SetOrToggle::Unspecified {
+            return Err(Status::invalid_argument("unspecified set or toggle"));
+        }
+
+        run_unary_no_response(&self.sender, move |state| {
+            let paused = match set_or_toggle {
+                SetOrToggle::Set => true,
+                SetOrToggle::Unset => false,
+                SetOrToggle::Toggle => !state.pinnacle.layout_state.tiling_paused,
+                SetOrToggle::Unspecified => unreachable!(),
+            };
+
+            state.pinnacle.set_tiling_paused(paused);
+        })
+        .await
+    }
+
+    async fn set_tag_layout(
+        &self,
+        request: Request<SetTagLayoutRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        let tag_id = TagId(
+            request
+                .tag_id
+                .ok_or_else(|| Status::invalid_argument("no tag specified"))?,
+        );
+
+        let layout = request.layout.unwrap_or_default();
+
+        run_unary_no_response(&self.sender, move |state| {
+            if tag_id.tag(&state.pinnacle).is_none() {
+                return;
+            }
+
+            state
+                .pinnacle
+                .layout_state
+                .reported_tag_layouts
+                .insert(tag_id, layout);
+        })
+        .await
+    }
+
+    async fn get_tag_layout(
+        &self,
+        request: Request<GetTagLayoutRequest>,
+    ) -> Result<Response<GetTagLayoutResponse>, Status> {
+        let request = request.into_inner();
+
+        let tag_id = TagId(
+            request
+                .tag_id
+                .ok_or_else(|| Status::invalid_argument("no tag specified"))?,
+        );
+
+        run_unary(&self.sender, move |state| {
+            let Some(tag) = tag_id.tag(&state.pinnacle) else {
+                return GetTagLayoutResponse::default();
+            };
+
+            let gaps = tag.gaps();
+
+            GetTagLayoutResponse {
+                layout: state
+                    .pinnacle
+                    .layout_state
+                    .reported_tag_layouts
+                    .get(&tag_id)
+                    .cloned(),
+                inner_gaps: Some(gaps.inner),
+                outer_gaps: Some(gaps.outer),
+                smart_gaps: Some(gaps.smart),
+                tiling_direction: tag
+                    .tiling_direction()
+                    .map(|direction| tag::v0alpha1::TilingDirection::from(direction) as i32),
+                tiling_paused: Some(state.pinnacle.layout_state.tiling_paused),
+            }
+        })
+        .await
+    }
 }
@@ -2,10 +2,10 @@ use std::collections::VecDeque;
 
 use pinnacle_api_defs::pinnacle::signal::v0alpha1::{
     signal_service_server, OutputConnectRequest, OutputConnectResponse, OutputDisconnectRequest,
-    OutputDisconnectResponse, OutputMoveRequest, OutputMoveResponse, OutputResizeRequest,
-    OutputResizeResponse, SignalRequest, StreamControl, TagActiveRequest, TagActiveResponse,
-    WindowPointerEnterRequest, WindowPointerEnterResponse, WindowPointerLeaveRequest,
-    WindowPointerLeaveResponse,
+    OutputDisconnectResponse, OutputHeadlessRequest, OutputHeadlessResponse, OutputMoveRequest,
+    OutputMoveResponse, OutputResizeRequest, OutputResizeResponse, SignalRequest, StreamControl,
+    TagActiveRequest, TagActiveResponse, WindowPointerEnterRequest, WindowPointerEnterResponse,
+    WindowPointerLeaveRequest, WindowPointerLeaveResponse,
 };
 use tokio::{sync::mpsc::UnboundedSender, task::JoinHandle};
 use tonic::{Request, Response, Status, Streaming};
@@ -22,6 +22,7 @@ pub struct SignalState {
     pub output_disconnect: SignalData<OutputDisconnectResponse, VecDeque<OutputDisconnectResponse>>,
     pub output_resize: SignalData<OutputResizeResponse, VecDeque<OutputResizeResponse>>,
     pub output_move: SignalData<OutputMoveResponse, VecDeque<OutputMoveResponse>>,
+    pub output_headless: SignalData<OutputHeadlessResponse, VecDeque<OutputHeadlessResponse>>,
 
     // Window
     pub window_pointer_enter:
@@ -39,6 +40,7 @@ impl SignalState {
         self.output_disconnect.disconnect();
         self.output_resize.disconnect();
         self.output_move.disconnect();
+        self.output_headless.disconnect();
         self.window_pointer_enter.disconnect();
         self.window_pointer_leave.disconnect();
     }
@@ -188,6 +190,7 @@ impl signal_service_server::SignalService for SignalService {
     type OutputDisconnectStream = ResponseStream<OutputDisconnectResponse>;
     type OutputResizeStream = ResponseStream<OutputResizeResponse>;
     type OutputMoveStream = ResponseStream<OutputMoveResponse>;
+    type OutputHeadlessStream = ResponseStream<OutputHeadlessResponse>;
 
     type WindowPointerEnterStream = ResponseStream<WindowPointerEnterResponse>;
     type WindowPointerLeaveStream = ResponseStream<WindowPointerLeaveResponse>;
@@ -238,6 +241,17 @@ impl signal_service_server::SignalService for SignalService {
         })
     }
 
+    async fn output_headless(
+        &self,
+        request: Request<Streaming<OutputHeadlessRequest>>,
+    ) -> Result<Response<Self::OutputHeadlessStream>, Status> {
+        let in_stream = request.into_inner();
+
+        start_signal_stream(self.sender.clone(), in_stream, |state| {
+            &mut state.pinnacle.signal_state.output_headless
+        })
+    }
+
     async fn window_pointer_enter(
         &self,
         request: Request<Streaming<WindowPointerEnterRequest>>,
@@ -1,29 +1,52 @@
-use std::num::NonZeroU32;
+use std::{num::NonZeroU32, time::Duration};
 
 use pinnacle_api_defs::pinnacle::{
     v0alpha1::{Geometry, SetOrToggle},
     window::{
         self,
         v0alpha1::{
-            window_service_server, AddWindowRuleRequest, CloseRequest, FullscreenOrMaximized,
-            MoveGrabRequest, MoveToTagRequest, RaiseRequest, ResizeGrabRequest, SetFloatingRequest,
-            SetFocusedRequest, SetFullscreenRequest, SetGeometryRequest, SetMaximizedRequest,
-            SetTagRequest, WindowRule, WindowRuleCondition,
+            get_window_rules_response, window_service_server, AddWindowRuleRequest,
+            AddWindowRuleResponse, CancelGrabRequest, CaptureRequest, CaptureResponse,
+            CloseRequest, ContentType, CopyToTagRequest, EvaluateWindowRuleConditionRequest,
+            EvaluateWindowRuleConditionResponse, FocusPreviousRequest, FocusUrgentRequest,
+            FullscreenOrMaximized, GetGrabRequest, GetGrabResponse, GetSurfaceInfoRequest,
+            GetSurfaceInfoResponse, GetWindowRulesRequest, GetWindowRulesResponse,
+            JumpToWindowRequest, JumpToWindowResponse, LowerRequest, MoveGrabRequest,
+            MoveToTagRequest, OpenNextOnFocusedTagRequest, PlaceWindowRequest, Placement,
+            RaiseRequest, ReapplyWindowRulesRequest, RemoveWindowRuleRequest, ResizeGrabRequest,
+            SelectWindowRequest, SelectWindowResponse, SetActivatedRequest,
+            SetFloatingGeometryRequest, SetFloatingRequest, SetFocusNewWindowsRequest,
+            SetFocusedRequest, SetFullscreenRequest, SetGeometryPinnedRequest, SetGeometryRequest,
+            SetLabelRequest, SetLastWindowFallbackRequest, SetMaximizeGapsRequest,
+            SetMaximizedRequest, SetMinimizedRequest, SetRelativeGeometryRequest, SetScaleRequest,
+            SetSkipCycleRequest, SetSkipTaskbarRequest, SetTagRequest, SetTiledEdgesEnabledRequest,
+            SetUnfullscreenOnFocusLossRequest, SetUrgencyTimeoutRequest, SnapRegion,
+            SnapWindowRequest, ToggleRecentTagRequest, WindowRule, WindowRuleCondition,
+            WindowRuleConditionEvaluation,
         },
     },
 };
 use smithay::{
-    desktop::{space::SpaceElement, WindowSurface},
+    desktop::space::SpaceElement,
     reexports::wayland_protocols::xdg::shell::server,
-    utils::{Point, Rectangle, SERIAL_COUNTER},
+    utils::{Point, Rectangle, Scale, SERIAL_COUNTER},
     wayland::seat::WaylandFocus,
 };
 use tonic::{Request, Response, Status};
-use tracing::{error, warn};
+use tracing::warn;
 
 use crate::{
-    focus::keyboard::KeyboardFocusTarget, output::OutputName, state::WithState, tag::TagId,
-    window::window_state::WindowId,
+    grab::GrabKind,
+    output::OutputName,
+    state::{State, WithState},
+    tag::{Tag, TagId, TagSet},
+    window::{
+        rules::WindowRuleId,
+        window_state::{
+            self, FloatingOrTiled, RelativeGeometry, TiledEdges, WindowElementState, WindowId,
+        },
+        WindowElement,
+    },
 };
 
 use super::{run_unary, run_unary_no_response, StateFnSender};
@@ -54,18 +77,18 @@ impl window_service_server::WindowService for WindowService {
                 return;
             };
 
-            match window.underlying_surface() {
-                WindowSurface::Wayland(toplevel) => toplevel.send_close(),
-                WindowSurface::X11(surface) => {
-                    if !surface.is_override_redirect() {
-                        if let Err(err) = surface.close() {
-                            error!("failed to close x11 window: {err}");
-                        }
-                    } else {
-                        warn!("tried to close OR window");
-                    }
-                }
-            }
+            let is_last_window = state
+                .pinnacle
+                .windows
+                .iter()
+                .filter(|win| !win.is_x11_override_redirect())
+                .all(|win| win == &window);
+
+            // The fallback is spawned once the window is actually destroyed,
+            // as the client can ignore the close request
+            window.with_state_mut(|state| state.closed_as_last_window = is_last_window);
+
+            window.close();
         })
         .await
     }
@@ -85,43 +108,31 @@ impl window_service_server::WindowService for WindowService {
         );
 
         let geometry = request.geometry.unwrap_or_default();
-        let x = geometry.x;
-        let y = geometry.y;
-        let width = geometry.width;
-        let height = geometry.height;
+        let relative_to = request.relative_to_output.map(OutputName);
 
         run_unary_no_response(&self.sender, move |state| {
-            let Some(window) = window_id.window(&state.pinnacle) else {
-                return;
-            };
-
-            // TODO: with no x or y, defaults unmapped windows to 0, 0
-            let mut window_loc = state
-                .pinnacle
-                .space
-                .element_location(&window)
-                .unwrap_or_default();
-            window_loc.x = x.unwrap_or(window_loc.x);
-            window_loc.y = y.unwrap_or(window_loc.y);
+            set_window_geometry(state, window_id, geometry, relative_to, false);
+        })
+        .await
+    }
 
-            let mut window_size = window.geometry().size;
-            window_size.w = width.unwrap_or(window_size.w);
-            window_size.h = height.unwrap_or(window_size.h);
+    async fn set_floating_geometry(
+        &self,
+        request: Request<SetFloatingGeometryRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
 
-            let rect = Rectangle::from_loc_and_size(window_loc, window_size);
+        let window_id = WindowId(
+            request
+                .window_id
+                .ok_or_else(|| Status::invalid_argument("no window specified"))?,
+        );
 
-            window.with_state_mut(|state| {
-                use crate::window::window_state::FloatingOrTiled;
-                state.floating_or_tiled = match state.floating_or_tiled {
-                    FloatingOrTiled::Floating(_) => FloatingOrTiled::Floating(rect),
-                    FloatingOrTiled::Tiled(_) => FloatingOrTiled::Tiled(Some(rect)),
-                }
-            });
+        let geometry = request.geometry.unwrap_or_default();
+        let relative_to = request.relative_to_output.map(OutputName);
 
-            for output in state.pinnacle.space.outputs_for_element(&window) {
-                state.pinnacle.request_layout(&output);
-                state.schedule_render(&output);
-            }
+        run_unary_no_response(&self.sender, move |state| {
+            set_window_geometry(state, window_id, geometry, relative_to, true);
         })
         .await
     }
@@ -224,6 +235,48 @@ impl window_service_server::WindowService for WindowService {
         .await
     }
 
+    async fn set_minimized(
+        &self,
+        request: Request<SetMinimizedRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        let window_id = WindowId(
+            request
+                .window_id
+                .ok_or_else(|| Status::invalid_argument("no window specified"))?,
+        );
+
+        let set_or_toggle = request.set_or_toggle();
+
+        if set_or_toggle == SetOrToggle::Unspecified {
+            return Err(Status::invalid_argument("unspecified set or toggle"));
+        }
+
+        run_unary_no_response(&self.sender, move |state| {
+            let Some(window) = window_id.window(&state.pinnacle) else {
+                return;
+            };
+
+            let minimized = match set_or_toggle {
+                SetOrToggle::Set => true,
+                SetOrToggle::Unset => false,
+                SetOrToggle::Toggle => !window.is_minimized(),
+                SetOrToggle::Unspecified => unreachable!(),
+            };
+
+            state.pinnacle.set_window_minimized(&window, minimized);
+
+            let Some(output) = window.output(&state.pinnacle) else {
+                return;
+            };
+
+            state.update_focus(&output);
+            state.schedule_render(&output);
+        })
+        .await
+    }
+
     async fn set_floating(
         &self,
         request: Request<SetFloatingRequest>,
@@ -263,6 +316,10 @@ impl window_service_server::WindowService for WindowService {
                 SetOrToggle::Unspecified => unreachable!(),
             }
 
+            // Windows floated again go back to their relative geometry if they have one
+            pinnacle.apply_relative_geometry(&window);
+            pinnacle.pin_relative_geometry(&window);
+
             let Some(output) = window.output(pinnacle) else {
                 return;
             };
@@ -273,6 +330,237 @@ impl window_service_server::WindowService for WindowService {
         .await
     }
 
+    async fn set_relative_geometry(
+        &self,
+        request: Request<SetRelativeGeometryRequest>,
+    ) -> Result<Response<()>, Status> {
+        let SetRelativeGeometryRequest {
+            window_id: Some(window_id),
+            x: Some(x),
+            y: Some(y),
+            width: Some(width),
+            height: Some(height),
+        } = request.into_inner()
+        else {
+            return Err(Status::invalid_argument(
+                "window_id, x, y, width, or height were null",
+            ));
+        };
+
+        if ![x, y].iter().all(|val| (0.0..=1.0).contains(val)) {
+            return Err(Status::invalid_argument(
+                "x and y must be between 0.0 and 1.0",
+            ));
+        }
+
+        if ![width, height].iter().all(|val| *val > 0.0 && *val <= 1.0) {
+            return Err(Status::invalid_argument(
+                "width and height must be greater than 0.0 and at most 1.0",
+            ));
+        }
+
+        let window_id = WindowId(window_id);
+
+        run_unary_no_response(&self.sender, move |state| {
+            let Some(window) = window_id.window(&state.pinnacle) else {
+                return;
+            };
+
+            window.with_state_mut(|state| {
+                state.relative_geometry = Some(RelativeGeometry {
+                    x: x as f64,
+                    y: y as f64,
+                    width: width as f64,
+                    height: height as f64,
+                });
+                state.relative_geometry_from_pin = false;
+            });
+
+            if !window.with_state(|state| state.floating_or_tiled.is_floating()) {
+                window.toggle_floating();
+            }
+
+            state.pinnacle.apply_relative_geometry(&window);
+
+            if let Some(output) = window.output(&state.pinnacle) {
+                state.schedule_render(&output);
+            }
+        })
+        .await
+    }
+
+    async fn snap_window(
+        &self,
+        request: Request<SnapWindowRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        let window_id = WindowId(
+            request
+                .window_id
+                .ok_or_else(|| Status::invalid_argument("no window specified"))?,
+        );
+
+        let region = match request.region() {
+            SnapRegion::Unspecified => {
+                return Err(Status::invalid_argument("unspecified snap region"));
+            }
+            SnapRegion::LeftHalf => window_state::SnapRegion::LeftHalf,
+            SnapRegion::RightHalf => window_state::SnapRegion::RightHalf,
+            SnapRegion::TopHalf => window_state::SnapRegion::TopHalf,
+            SnapRegion::BottomHalf => window_state::SnapRegion::BottomHalf,
+            SnapRegion::TopLeftQuarter => window_state::SnapRegion::TopLeftQuarter,
+            SnapRegion::TopRightQuarter => window_state::SnapRegion::TopRightQuarter,
+            SnapRegion::BottomLeftQuarter => window_state::SnapRegion::BottomLeftQuarter,
+            SnapRegion::BottomRightQuarter => window_state::SnapRegion::BottomRightQuarter,
+        };
+
+        run_unary_no_response(&self.sender, move |state| {
+            let Some(window) = window_id.window(&state.pinnacle) else {
+                return;
+            };
+
+            state.pinnacle.snap_window(&window, region);
+
+            if let Some(output) = window.output(&state.pinnacle) {
+                state.schedule_render(&output);
+            }
+        })
+        .await
+    }
+
+    async fn place_window(
+        &self,
+        request: Request<PlaceWindowRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        let window_id = WindowId(
+            request
+                .window_id
+                .ok_or_else(|| Status::invalid_argument("no window specified"))?,
+        );
+
+        let placement = request.placement();
+
+        if placement == Placement::Unspecified {
+            return Err(Status::invalid_argument("unspecified placement"));
+        }
+
+        let output_name = OutputName(
+            request
+                .output_name
+                .ok_or_else(|| Status::invalid_argument("no output specified"))?,
+        );
+
+        let geometry = request.geometry.unwrap_or_default();
+
+        run_unary_no_response(&self.sender, move |state| {
+            let Some(window) = window_id.window(&state.pinnacle) else {
+                return;
+            };
+            let Some(output) = output_name.output(&state.pinnacle) else {
+                return;
+            };
+
+            let old_output = window.output(&state.pinnacle);
+
+            window.place_on_output(&output);
+
+            let fullscreen_or_maximized = window.with_state(|state| state.fullscreen_or_maximized);
+
+            match placement {
+                Placement::Fullscreen => {
+                    if !fullscreen_or_maximized.is_fullscreen() {
+                        window.toggle_fullscreen();
+                    }
+                }
+                _ => match fullscreen_or_maximized {
+                    FullscreenOrMaximized::Neither => (),
+                    FullscreenOrMaximized::Fullscreen => window.toggle_fullscreen(),
+                    FullscreenOrMaximized::Maximized => window.toggle_maximized(),
+                },
+            }
+
+            match placement {
+                Placement::Tiled => {
+                    if window.with_state(|state| state.floating_or_tiled.is_floating()) {
+                        window.toggle_floating();
+                    }
+
+                    // Tiled windows are laid out in window order, so this puts it last
+                    if let Some(index) =
+                        state.pinnacle.windows.iter().position(|win| win == &window)
+                    {
+                        let window = state.pinnacle.windows.remove(index);
+                        state.pinnacle.windows.push(window);
+                    }
+                }
+                Placement::FloatingCentered => {
+                    window.with_state_mut(|state| state.relative_geometry = None);
+                    state.pinnacle.float_window_centered(&window);
+                    state.pinnacle.pin_relative_geometry(&window);
+                }
+                Placement::FloatingGeometry => {
+                    set_window_geometry(state, window_id, geometry, Some(output_name), true);
+                }
+                Placement::Fullscreen | Placement::Unspecified => (),
+            }
+
+            for op in old_output.into_iter().filter(|op| op != &output) {
+                state.pinnacle.request_layout(&op);
+                state.schedule_render(&op);
+            }
+
+            state.pinnacle.request_layout(&output);
+            state.schedule_render(&output);
+        })
+        .await
+    }
+
+    async fn set_activated(
+        &self,
+        request: Request<SetActivatedRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        let window_id = WindowId(
+            request
+                .window_id
+                .ok_or_else(|| Status::invalid_argument("no window specified"))?,
+        );
+
+        let set_or_toggle = request.set_or_toggle();
+
+        if set_or_toggle == SetOrToggle::Unspecified {
+            return Err(Status::invalid_argument("unspecified set or toggle"));
+        }
+
+        run_unary_no_response(&self.sender, move |state| {
+            let Some(window) = window_id.window(&state.pinnacle) else {
+                return;
+            };
+
+            if window.is_x11_override_redirect() {
+                return;
+            }
+
+            let activated = match set_or_toggle {
+                SetOrToggle::Set => true,
+                SetOrToggle::Unset => false,
+                SetOrToggle::Toggle => !window.is_activated(),
+                SetOrToggle::Unspecified => unreachable!(),
+            };
+
+            state.pinnacle.set_window_activated(&window, activated);
+
+            if let Some(output) = window.output(&state.pinnacle) {
+                state.schedule_render(&output);
+            }
+        })
+        .await
+    }
+
     async fn set_focused(
         &self,
         request: Request<SetFocusedRequest>,
@@ -291,6 +579,8 @@ impl window_service_server::WindowService for WindowService {
             return Err(Status::invalid_argument("unspecified set or toggle"));
         }
 
+        let raise = request.raise();
+
         run_unary_no_response(&self.sender, move |state| {
             let Some(window) = window_id.window(&state.pinnacle) else {
                 return;
@@ -304,22 +594,29 @@ impl window_service_server::WindowService for WindowService {
                 return;
             };
 
+            let focusing = match set_or_toggle {
+                SetOrToggle::Set => true,
+                SetOrToggle::Unset => false,
+                SetOrToggle::Toggle => {
+                    state.pinnacle.focused_window(&output).as_ref() != Some(&window)
+                }
+                SetOrToggle::Unspecified => unreachable!(),
+            };
+
+            // Explicitly focusing a window takes the activated state back from pinned windows
             for win in state.pinnacle.space.elements() {
+                if focusing {
+                    win.with_state_mut(|state| state.activation_pinned = false);
+                } else if win.with_state(|state| state.activation_pinned) {
+                    continue;
+                }
                 win.set_activate(false);
             }
 
             match set_or_toggle {
                 SetOrToggle::Set => {
                     window.set_activate(true);
-                    output.with_state_mut(|state| state.focus_stack.set_focus(window.clone()));
-                    state.pinnacle.output_focus_stack.set_focus(output.clone());
-                    if let Some(keyboard) = state.pinnacle.seat.get_keyboard() {
-                        keyboard.set_focus(
-                            state,
-                            Some(KeyboardFocusTarget::Window(window)),
-                            SERIAL_COUNTER.next_serial(),
-                        );
-                    }
+                    state.focus_window(&window, raise);
                 }
                 SetOrToggle::Unset => {
                     if state.pinnacle.focused_window(&output) == Some(window) {
@@ -337,15 +634,7 @@ impl window_service_server::WindowService for WindowService {
                         }
                     } else {
                         window.set_activate(true);
-                        output.with_state_mut(|state| state.focus_stack.set_focus(window.clone()));
-                        state.pinnacle.output_focus_stack.set_focus(output.clone());
-                        if let Some(keyboard) = state.pinnacle.seat.get_keyboard() {
-                            keyboard.set_focus(
-                                state,
-                                Some(KeyboardFocusTarget::Window(window)),
-                                SERIAL_COUNTER.next_serial(),
-                            );
-                        }
+                        state.focus_window(&window, raise);
                     }
                 }
                 SetOrToggle::Unspecified => unreachable!(),
@@ -380,23 +669,30 @@ impl window_service_server::WindowService for WindowService {
                 .ok_or_else(|| Status::invalid_argument("no tag specified"))?,
         );
 
+        let follow = request.follow();
+
         run_unary_no_response(&self.sender, move |state| {
-            let pinnacle = &mut state.pinnacle;
-            let Some(window) = window_id.window(pinnacle) else {
+            let Some(window) = window_id.window(&state.pinnacle) else {
                 return;
             };
-            let Some(tag) = tag_id.tag(pinnacle) else { return };
-            window.with_state_mut(|state| {
-                state.tags = vec![tag.clone()];
+            let Some(tag) = tag_id.tag(&state.pinnacle) else { return };
+
+            change_window_tags(state, &window, &tag, |tags| {
+                *tags = TagSet::from_iter([tag.clone()]);
             });
-            let Some(output) = tag.output(pinnacle) else { return };
-            pinnacle.request_layout(&output);
-            state.schedule_render(&output);
+
+            if follow {
+                // Switches to the window's new tag if it isn't active
+                state.activate_window(&window);
+            }
         })
         .await
     }
 
-    async fn set_tag(&self, request: Request<SetTagRequest>) -> Result<Response<()>, Status> {
+    async fn copy_to_tag(
+        &self,
+        request: Request<CopyToTagRequest>,
+    ) -> Result<Response<()>, Status> {
         let request = request.into_inner();
 
         let window_id = WindowId(
@@ -411,46 +707,124 @@ impl window_service_server::WindowService for WindowService {
                 .ok_or_else(|| Status::invalid_argument("no tag specified"))?,
         );
 
-        let set_or_toggle = request.set_or_toggle();
-
-        if set_or_toggle == SetOrToggle::Unspecified {
-            return Err(Status::invalid_argument("unspecified set or toggle"));
-        }
-
         run_unary_no_response(&self.sender, move |state| {
-            let pinnacle = &mut state.pinnacle;
-            let Some(window) = window_id.window(pinnacle) else {
+            let Some(window) = window_id.window(&state.pinnacle) else {
                 return;
             };
-            let Some(tag) = tag_id.tag(pinnacle) else { return };
-
-            // TODO: turn state.tags into a hashset
-            match set_or_toggle {
-                SetOrToggle::Set => window.with_state_mut(|state| {
-                    state.tags.retain(|tg| tg != &tag);
-                    state.tags.push(tag.clone());
-                }),
-                SetOrToggle::Unset => window.with_state_mut(|state| {
-                    state.tags.retain(|tg| tg != &tag);
-                }),
-                SetOrToggle::Toggle => window.with_state_mut(|state| {
-                    if !state.tags.contains(&tag) {
-                        state.tags.push(tag.clone());
-                    } else {
-                        state.tags.retain(|tg| tg != &tag);
-                    }
-                }),
-                SetOrToggle::Unspecified => unreachable!(),
-            }
+            let Some(tag) = tag_id.tag(&state.pinnacle) else { return };
 
-            let Some(output) = tag.output(pinnacle) else { return };
-            pinnacle.request_layout(&output);
-            state.schedule_render(&output);
+            change_window_tags(state, &window, &tag, |tags| {
+                tags.insert(tag.clone());
+            });
         })
         .await
     }
 
-    async fn raise(&self, request: Request<RaiseRequest>) -> Result<Response<()>, Status> {
+    async fn set_tag(&self, request: Request<SetTagRequest>) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        let window_id = WindowId(
+            request
+                .window_id
+                .ok_or_else(|| Status::invalid_argument("no window specified"))?,
+        );
+
+        let tag_id = TagId(
+            request
+                .tag_id
+                .ok_or_else(|| Status::invalid_argument("no tag specified"))?,
+        );
+
+        let set_or_toggle = request.set_or_toggle();
+
+        if set_or_toggle == SetOrToggle::Unspecified {
+            return Err(Status::invalid_argument("unspecified set or toggle"));
+        }
+
+        run_unary_no_response(&self.sender, move |state| {
+            let Some(window) = window_id.window(&state.pinnacle) else {
+                return;
+            };
+            let Some(tag) = tag_id.tag(&state.pinnacle) else { return };
+
+            change_window_tags(state, &window, &tag, |tags| match set_or_toggle {
+                SetOrToggle::Set => {
+                    tags.insert(tag.clone());
+                }
+                SetOrToggle::Unset => {
+                    tags.remove(&tag);
+                }
+                SetOrToggle::Toggle => tags.toggle(tag.clone()),
+                SetOrToggle::Unspecified => unreachable!(),
+            });
+        })
+        .await
+    }
+
+    async fn toggle_recent_tag(
+        &self,
+        request: Request<ToggleRecentTagRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        let window_id = WindowId(
+            request
+                .window_id
+                .ok_or_else(|| Status::invalid_argument("no window specified"))?,
+        );
+
+        run_unary_no_response(&self.sender, move |state| {
+            let pinnacle = &mut state.pinnacle;
+            let Some(window) = window_id.window(pinnacle) else {
+                warn!("`toggle_recent_tag` was called on a nonexistent window");
+                return;
+            };
+            let Some(output) = window.output(pinnacle) else { return };
+
+            // Both lists follow the output's tag order so the destination is deterministic
+            let (current_active, previous_active) = output.with_state(|op_state| {
+                let current_active = op_state
+                    .tags
+                    .iter()
+                    .filter(|tag| tag.active())
+                    .cloned()
+                    .collect::<Vec<_>>();
+                let previous_active = op_state
+                    .tags
+                    .iter()
+                    .filter(|tag| op_state.previous_active_tags.contains(tag))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                (current_active, previous_active)
+            });
+
+            let on_current = window
+                .with_state(|state| state.tags.iter().any(|tag| current_active.contains(tag)));
+
+            let dest = if on_current {
+                previous_active
+                    .into_iter()
+                    .find(|tag| !current_active.contains(tag))
+            } else {
+                current_active.into_iter().next()
+            };
+
+            let Some(dest) = dest else {
+                return;
+            };
+
+            window.with_state_mut(|state| {
+                state.tags = TagSet::from_iter([dest]);
+            });
+
+            pinnacle.request_layout(&output);
+            state.update_focus(&output);
+            state.schedule_render(&output);
+        })
+        .await
+    }
+
+    async fn raise(&self, request: Request<RaiseRequest>) -> Result<Response<()>, Status> {
         let request = request.into_inner();
 
         let window_id = WindowId(
@@ -471,6 +845,399 @@ impl window_service_server::WindowService for WindowService {
         .await
     }
 
+    async fn lower(&self, request: Request<LowerRequest>) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        let window_id = WindowId(
+            request
+                .window_id
+                .ok_or_else(|| Status::invalid_argument("no window specified"))?,
+        );
+
+        run_unary_no_response(&self.sender, move |state| {
+            let pinnacle = &mut state.pinnacle;
+            let Some(window) = window_id.window(pinnacle) else {
+                warn!("`lower` was called on a nonexistent window");
+                return;
+            };
+
+            pinnacle.lower_window(window);
+        })
+        .await
+    }
+
+    async fn set_label(&self, request: Request<SetLabelRequest>) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        let window_id = WindowId(
+            request
+                .window_id
+                .ok_or_else(|| Status::invalid_argument("no window specified"))?,
+        );
+
+        let label = request.label;
+
+        run_unary_no_response(&self.sender, move |state| {
+            let Some(window) = window_id.window(&state.pinnacle) else {
+                warn!("`set_label` was called on a nonexistent window");
+                return;
+            };
+
+            if let Some(label) = label.as_ref() {
+                let taken = state.pinnacle.windows.iter().any(|win| {
+                    win != &window && win.with_state(|state| state.label.as_ref() == Some(label))
+                });
+
+                if taken {
+                    warn!("Another window already has the label \"{label}\"");
+                    return;
+                }
+            }
+
+            window.with_state_mut(|state| state.label = label);
+        })
+        .await
+    }
+
+    async fn set_skip_taskbar(
+        &self,
+        request: Request<SetSkipTaskbarRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        let window_id = WindowId(
+            request
+                .window_id
+                .ok_or_else(|| Status::invalid_argument("no window specified"))?,
+        );
+
+        let set_or_toggle = request.set_or_toggle();
+
+        if set_or_toggle == SetOrToggle::Unspecified {
+            return Err(Status::invalid_argument("unspecified set or toggle"));
+        }
+
+        run_unary_no_response(&self.sender, move |state| {
+            let Some(window) = window_id.window(&state.pinnacle) else {
+                return;
+            };
+
+            window.with_state_mut(|state| {
+                state.skip_taskbar = match set_or_toggle {
+                    SetOrToggle::Set => true,
+                    SetOrToggle::Unset => false,
+                    SetOrToggle::Toggle => !state.skip_taskbar,
+                    SetOrToggle::Unspecified => unreachable!(),
+                };
+            });
+        })
+        .await
+    }
+
+    async fn set_skip_cycle(
+        &self,
+        request: Request<SetSkipCycleRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        let window_id = WindowId(
+            request
+                .window_id
+                .ok_or_else(|| Status::invalid_argument("no window specified"))?,
+        );
+
+        let set_or_toggle = request.set_or_toggle();
+
+        if set_or_toggle == SetOrToggle::Unspecified {
+            return Err(Status::invalid_argument("unspecified set or toggle"));
+        }
+
+        run_unary_no_response(&self.sender, move |state| {
+            let Some(window) = window_id.window(&state.pinnacle) else {
+                return;
+            };
+
+            window.with_state_mut(|state| {
+                state.skip_cycle = match set_or_toggle {
+                    SetOrToggle::Set => true,
+                    SetOrToggle::Unset => false,
+                    SetOrToggle::Toggle => !state.skip_cycle,
+                    SetOrToggle::Unspecified => unreachable!(),
+                };
+            });
+        })
+        .await
+    }
+
+    async fn set_geometry_pinned(
+        &self,
+        request: Request<SetGeometryPinnedRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        let window_id = WindowId(
+            request
+                .window_id
+                .ok_or_else(|| Status::invalid_argument("no window specified"))?,
+        );
+
+        let set_or_toggle = request.set_or_toggle();
+
+        if set_or_toggle == SetOrToggle::Unspecified {
+            return Err(Status::invalid_argument("unspecified set or toggle"));
+        }
+
+        run_unary_no_response(&self.sender, move |state| {
+            let Some(window) = window_id.window(&state.pinnacle) else {
+                return;
+            };
+
+            window.with_state_mut(|state| {
+                state.geometry_pinned = match set_or_toggle {
+                    SetOrToggle::Set => true,
+                    SetOrToggle::Unset => false,
+                    SetOrToggle::Toggle => !state.geometry_pinned,
+                    SetOrToggle::Unspecified => unreachable!(),
+                };
+            });
+
+            state.pinnacle.pin_relative_geometry(&window);
+            state.pinnacle.unpin_relative_geometry(&window);
+        })
+        .await
+    }
+
+    async fn set_maximize_gaps(
+        &self,
+        request: Request<SetMaximizeGapsRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        let window_id = WindowId(
+            request
+                .window_id
+                .ok_or_else(|| Status::invalid_argument("no window specified"))?,
+        );
+
+        let set_or_toggle = request.set_or_toggle();
+
+        if set_or_toggle == SetOrToggle::Unspecified {
+            return Err(Status::invalid_argument("unspecified set or toggle"));
+        }
+
+        run_unary_no_response(&self.sender, move |state| {
+            let Some(window) = window_id.window(&state.pinnacle) else {
+                return;
+            };
+
+            window.with_state_mut(|state| {
+                state.maximize_gaps = match set_or_toggle {
+                    SetOrToggle::Set => true,
+                    SetOrToggle::Unset => false,
+                    SetOrToggle::Toggle => !state.maximize_gaps,
+                    SetOrToggle::Unspecified => unreachable!(),
+                };
+            });
+
+            if !window.with_state(|state| state.fullscreen_or_maximized.is_maximized()) {
+                return;
+            }
+
+            let Some(output) = window.output(&state.pinnacle) else {
+                return;
+            };
+
+            state.pinnacle.request_layout(&output);
+            state.schedule_render(&output);
+        })
+        .await
+    }
+
+    async fn set_tiled_edges_enabled(
+        &self,
+        request: Request<SetTiledEdgesEnabledRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        let window_id = WindowId(
+            request
+                .window_id
+                .ok_or_else(|| Status::invalid_argument("no window specified"))?,
+        );
+
+        let set_or_toggle = request.set_or_toggle();
+
+        if set_or_toggle == SetOrToggle::Unspecified {
+            return Err(Status::invalid_argument("unspecified set or toggle"));
+        }
+
+        run_unary_no_response(&self.sender, move |state| {
+            let Some(window) = window_id.window(&state.pinnacle) else {
+                return;
+            };
+
+            let edges = window.with_state_mut(|state| {
+                state.tiled_edges_enabled = match set_or_toggle {
+                    SetOrToggle::Set => true,
+                    SetOrToggle::Unset => false,
+                    SetOrToggle::Toggle => !state.tiled_edges_enabled,
+                    SetOrToggle::Unspecified => unreachable!(),
+                };
+                state.tiled_edges
+            });
+
+            window.set_tiled_edges(edges);
+
+            // Laying out sends the new states in a configure
+            let Some(output) = window.output(&state.pinnacle) else {
+                return;
+            };
+
+            state.pinnacle.request_layout(&output);
+            state.schedule_render(&output);
+        })
+        .await
+    }
+
+    async fn set_scale(&self, request: Request<SetScaleRequest>) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        let window_id = WindowId(
+            request
+                .window_id
+                .ok_or_else(|| Status::invalid_argument("no window specified"))?,
+        );
+
+        let scale = request
+            .scale
+            .ok_or_else(|| Status::invalid_argument("no scale specified"))?;
+
+        if !scale.is_finite() {
+            return Err(Status::invalid_argument("scale must be finite"));
+        }
+
+        let scale = scale.clamp(
+            *WindowElementState::SCALE_RANGE.start(),
+            *WindowElementState::SCALE_RANGE.end(),
+        );
+
+        run_unary_no_response(&self.sender, move |state| {
+            let Some(window) = window_id.window(&state.pinnacle) else {
+                warn!("`set_scale` was called on a nonexistent window");
+                return;
+            };
+
+            window.with_state_mut(|state| state.scale = scale);
+
+            // The pointer may now be over a different surface or location
+            state.update_pointer_focus();
+
+            if let Some(output) = window.output(&state.pinnacle) {
+                state.schedule_render(&output);
+            }
+        })
+        .await
+    }
+
+    async fn focus_urgent(
+        &self,
+        _request: Request<FocusUrgentRequest>,
+    ) -> Result<Response<()>, Status> {
+        run_unary_no_response(&self.sender, move |state| {
+            let Some(window) = state
+                .pinnacle
+                .windows
+                .iter()
+                .filter_map(|win| win.with_state(|state| state.urgent).map(|time| (win, time)))
+                .max_by_key(|(_, time)| *time)
+                .map(|(win, _)| win.clone())
+            else {
+                return;
+            };
+
+            state.activate_window(&window);
+        })
+        .await
+    }
+
+    async fn focus_previous(
+        &self,
+        _request: Request<FocusPreviousRequest>,
+    ) -> Result<Response<()>, Status> {
+        run_unary_no_response(&self.sender, move |state| {
+            let Some(window) = state.pinnacle.previously_focused_window() else {
+                return;
+            };
+
+            state.activate_window(&window);
+        })
+        .await
+    }
+
+    async fn jump_to_window(
+        &self,
+        request: Request<JumpToWindowRequest>,
+    ) -> Result<Response<JumpToWindowResponse>, Status> {
+        let request = request.into_inner();
+
+        let cycle = request.cycle();
+        let class = request.class;
+        let title = request.title;
+
+        if class.is_none() && title.is_none() {
+            return Err(Status::invalid_argument("no class or title specified"));
+        }
+
+        run_unary(&self.sender, move |state| {
+            let matches = |win: &WindowElement| {
+                !win.is_x11_override_redirect()
+                    && class
+                        .as_ref()
+                        .map_or(true, |class| win.class().as_ref() == Some(class))
+                    && title
+                        .as_ref()
+                        .map_or(true, |title| win.title().as_ref() == Some(title))
+            };
+
+            let focused = state
+                .pinnacle
+                .focused_output()
+                .and_then(|output| state.pinnacle.focused_window(output))
+                .filter(|win| matches(win));
+
+            let candidates = state
+                .pinnacle
+                .windows
+                .iter()
+                .filter(|win| matches(win) && Some(*win) != focused.as_ref())
+                .filter(|win| !cycle || !win.with_state(|state| state.skip_cycle))
+                .cloned()
+                .collect::<Vec<_>>();
+
+            let last_focused = |win: &WindowElement| win.with_state(|state| state.last_focused);
+
+            let window = match focused {
+                Some(focused) if !cycle => Some(focused),
+                // Windows that were never focused have a `last_focused` of `None`,
+                // so they're visited first when cycling
+                Some(focused) => candidates
+                    .into_iter()
+                    .min_by_key(last_focused)
+                    .or(Some(focused)),
+                None => candidates.into_iter().max_by_key(last_focused),
+            };
+
+            if let Some(window) = window.as_ref() {
+                state.activate_window(window);
+            }
+
+            JumpToWindowResponse {
+                window_id: window.map(|win| win.with_state(|state| state.id.0)),
+            }
+        })
+        .await
+    }
+
     async fn move_grab(&self, request: Request<MoveGrabRequest>) -> Result<Response<()>, Status> {
         let request = request.into_inner();
 
@@ -478,31 +1245,44 @@ impl window_service_server::WindowService for WindowService {
             .button
             .ok_or_else(|| Status::invalid_argument("no button specified"))?;
 
-        run_unary_no_response(&self.sender, move |state| {
+        let float_tiled = request.float_tiled.unwrap_or_default();
+
+        run_unary(&self.sender, move |state| {
             let Some(pointer_location) = state
                 .pinnacle
                 .seat
                 .get_pointer()
                 .map(|ptr| ptr.current_location())
             else {
-                return;
+                return Err(Status::failed_precondition("the seat has no pointer"));
             };
             let Some((pointer_focus, _)) = state.pointer_focus_target_under(pointer_location)
             else {
-                return;
+                return Err(Status::failed_precondition("nothing is under the pointer"));
             };
             let Some(window) = pointer_focus.window_for(state) else {
-                tracing::info!("Move grabs are currently not implemented for non-windows");
-                return;
+                return Err(Status::unimplemented(
+                    "move grabs are not implemented for non-windows",
+                ));
             };
             let Some(wl_surf) = window.wl_surface() else {
-                return;
+                return Err(Status::failed_precondition("the window has no surface"));
             };
             let seat = state.pinnacle.seat.clone();
 
-            state.move_request_server(&wl_surf, &seat, SERIAL_COUNTER.next_serial(), button);
+            state.move_request_server(
+                &wl_surf,
+                &seat,
+                SERIAL_COUNTER.next_serial(),
+                button,
+                float_tiled,
+            );
+
+            Ok(())
         })
-        .await
+        .await?
+        .into_inner()
+        .map(Response::new)
     }
 
     async fn resize_grab(
@@ -515,27 +1295,29 @@ impl window_service_server::WindowService for WindowService {
             .button
             .ok_or_else(|| Status::invalid_argument("no button specified"))?;
 
-        run_unary_no_response(&self.sender, move |state| {
+        let border_width = request.border_width;
+
+        run_unary(&self.sender, move |state| {
             let Some(pointer_loc) = state
                 .pinnacle
                 .seat
                 .get_pointer()
                 .map(|ptr| ptr.current_location())
             else {
-                return;
+                return Err(Status::failed_precondition("the seat has no pointer"));
             };
             let Some((pointer_focus, window_loc)) = state.pointer_focus_target_under(pointer_loc)
             else {
-                return;
+                return Err(Status::failed_precondition("nothing is under the pointer"));
             };
             let Some(window) = pointer_focus.window_for(state) else {
-                tracing::info!("Move grabs are currently not implemented for non-windows");
-                return;
+                return Err(Status::unimplemented(
+                    "resize grabs are not implemented for non-windows",
+                ));
             };
             let Some(wl_surf) = window.wl_surface() else {
-                return;
+                return Err(Status::failed_precondition("the window has no surface"));
             };
-
             let window_geometry = window.geometry();
             let window_x = window_loc.x as f64;
             let window_y = window_loc.y as f64;
@@ -546,45 +1328,124 @@ impl window_service_server::WindowService for WindowService {
             let full_width = window_x + window_width;
             let full_height = window_y + window_height;
 
-            let edges = match pointer_loc {
-                Point { x, y, .. }
-                    if (window_x..=half_width).contains(&x)
-                        && (window_y..=half_height).contains(&y) =>
-                {
-                    server::xdg_toplevel::ResizeEdge::TopLeft
-                }
-                Point { x, y, .. }
-                    if (half_width..=full_width).contains(&x)
-                        && (window_y..=half_height).contains(&y) =>
-                {
-                    server::xdg_toplevel::ResizeEdge::TopRight
-                }
-                Point { x, y, .. }
-                    if (window_x..=half_width).contains(&x)
-                        && (half_height..=full_height).contains(&y) =>
-                {
-                    server::xdg_toplevel::ResizeEdge::BottomLeft
+            let edges = if let Some(border_width) = border_width {
+                let border_width = border_width as f64;
+                let Point { x, y, .. } = pointer_loc;
+
+                let left = x < window_x + border_width;
+                let right = x >= full_width - border_width;
+                let top = y < window_y + border_width;
+                let bottom = y >= full_height - border_width;
+
+                match (top, bottom, left, right) {
+                    (true, _, true, _) => server::xdg_toplevel::ResizeEdge::TopLeft,
+                    (true, _, _, true) => server::xdg_toplevel::ResizeEdge::TopRight,
+                    (_, true, true, _) => server::xdg_toplevel::ResizeEdge::BottomLeft,
+                    (_, true, _, true) => server::xdg_toplevel::ResizeEdge::BottomRight,
+                    (true, ..) => server::xdg_toplevel::ResizeEdge::Top,
+                    (_, true, ..) => server::xdg_toplevel::ResizeEdge::Bottom,
+                    (.., true, _) => server::xdg_toplevel::ResizeEdge::Left,
+                    (.., true) => server::xdg_toplevel::ResizeEdge::Right,
+                    // The pointer is in the interior of the window
+                    _ => return Ok(()),
                 }
-                Point { x, y, .. }
-                    if (half_width..=full_width).contains(&x)
-                        && (half_height..=full_height).contains(&y) =>
-                {
-                    server::xdg_toplevel::ResizeEdge::BottomRight
+            } else {
+                match pointer_loc {
+                    Point { x, y, .. }
+                        if (window_x..=half_width).contains(&x)
+                            && (window_y..=half_height).contains(&y) =>
+                    {
+                        server::xdg_toplevel::ResizeEdge::TopLeft
+                    }
+                    Point { x, y, .. }
+                        if (half_width..=full_width).contains(&x)
+                            && (window_y..=half_height).contains(&y) =>
+                    {
+                        server::xdg_toplevel::ResizeEdge::TopRight
+                    }
+                    Point { x, y, .. }
+                        if (window_x..=half_width).contains(&x)
+                            && (half_height..=full_height).contains(&y) =>
+                    {
+                        server::xdg_toplevel::ResizeEdge::BottomLeft
+                    }
+                    Point { x, y, .. }
+                        if (half_width..=full_width).contains(&x)
+                            && (half_height..=full_height).contains(&y) =>
+                    {
+                        server::xdg_toplevel::ResizeEdge::BottomRight
+                    }
+                    _ => server::xdg_toplevel::ResizeEdge::None,
                 }
-                _ => server::xdg_toplevel::ResizeEdge::None,
             };
 
-            state.resize_request_server(
-                &wl_surf,
-                &state.pinnacle.seat.clone(),
-                SERIAL_COUNTER.next_serial(),
-                edges.into(),
-                button,
-            );
+            state.resize_request_server(
+                &wl_surf,
+                &state.pinnacle.seat.clone(),
+                SERIAL_COUNTER.next_serial(),
+                edges.into(),
+                button,
+            );
+
+            Ok(())
+        })
+        .await?
+        .into_inner()
+        .map(Response::new)
+    }
+
+    async fn cancel_grab(
+        &self,
+        _request: Request<CancelGrabRequest>,
+    ) -> Result<Response<()>, Status> {
+        run_unary_no_response(&self.sender, move |state| {
+            state.cancel_grab();
+        })
+        .await
+    }
+
+    async fn get_grab(
+        &self,
+        _request: Request<GetGrabRequest>,
+    ) -> Result<Response<GetGrabResponse>, Status> {
+        run_unary(&self.sender, move |state| {
+            let Some(grab) = state.pinnacle.active_grab.as_ref() else {
+                return GetGrabResponse::default();
+            };
+
+            let kind = match grab.kind {
+                GrabKind::Move => window::v0alpha1::GrabKind::Move,
+                GrabKind::Resize => window::v0alpha1::GrabKind::Resize,
+            };
+
+            GetGrabResponse {
+                kind: Some(kind as i32),
+                window_id: Some(grab.window.with_state(|state| state.id.0)),
+            }
         })
         .await
     }
 
+    async fn select_window(
+        &self,
+        _request: Request<SelectWindowRequest>,
+    ) -> Result<Response<SelectWindowResponse>, Status> {
+        let receiver = run_unary(&self.sender, move |state| {
+            let (sender, receiver) = tokio::sync::oneshot::channel();
+            state.show_window_hints(sender);
+            receiver
+        })
+        .await?
+        .into_inner();
+
+        // The sender is dropped without sending if the compositor shuts down
+        let window_id = receiver.await.ok().flatten();
+
+        Ok(Response::new(SelectWindowResponse {
+            window_id: window_id.map(|id| id.0),
+        }))
+    }
+
     async fn get(
         &self,
         _request: Request<window::v0alpha1::GetRequest>,
@@ -614,6 +1475,8 @@ impl window_service_server::WindowService for WindowService {
                 .ok_or_else(|| Status::invalid_argument("no window specified"))?,
         );
 
+        let relative_to = request.relative_to_output.map(OutputName);
+
         run_unary(&self.sender, move |state| {
             let pinnacle = &state.pinnacle;
             let window = window_id.window(pinnacle);
@@ -622,17 +1485,27 @@ impl window_service_server::WindowService for WindowService {
 
             let height = window.as_ref().map(|win| win.geometry().size.h);
 
-            let x = window
-                .as_ref()
-                .and_then(|win| state.pinnacle.space.element_location(win))
-                .map(|loc| loc.x);
+            // A geometry relative to an output that doesn't exist is left unset
+            let origin = match relative_to {
+                Some(output_name) => output_name
+                    .output(pinnacle)
+                    .and_then(|output| pinnacle.space.output_geometry(&output))
+                    .map(|geo| geo.loc),
+                None => Some(Point::default()),
+            };
 
-            let y = window
+            let loc = window
                 .as_ref()
                 .and_then(|win| state.pinnacle.space.element_location(win))
-                .map(|loc| loc.y);
+                .zip(origin)
+                .map(|(loc, origin)| loc - origin);
 
-            let geometry = if width.is_none() && height.is_none() && x.is_none() && y.is_none() {
+            let x = loc.map(|loc| loc.x);
+            let y = loc.map(|loc| loc.y);
+
+            let geometry = if origin.is_none()
+                || (width.is_none() && height.is_none() && x.is_none() && y.is_none())
+            {
                 None
             } else {
                 Some(Geometry {
@@ -643,6 +1516,31 @@ impl window_service_server::WindowService for WindowService {
                 })
             };
 
+            let physical_geometry = window.as_ref().and_then(|win| {
+                let output = win.output(pinnacle)?;
+                let output_geo = pinnacle.space.output_geometry(&output)?;
+
+                // Outputs have their own scales, so go from the output's logical size
+                // to its mode's size as displayed instead of scaling global coordinates
+                let output_size = output
+                    .current_transform()
+                    .transform_size(output.current_mode()?.size);
+                let scale = (
+                    output_size.w as f64 / output_geo.size.w.max(1) as f64,
+                    output_size.h as f64 / output_geo.size.h.max(1) as f64,
+                );
+
+                let loc = pinnacle.space.element_location(win)? - output_geo.loc;
+                let rect = Rectangle::from_loc_and_size(loc, win.geometry().size)
+                    .to_physical_precise_round(scale);
+                Some(Geometry {
+                    x: Some(rect.loc.x),
+                    y: Some(rect.loc.y),
+                    width: Some(rect.size.w),
+                    height: Some(rect.size.h),
+                })
+            });
+
             let class = window.as_ref().and_then(|win| win.class());
             let title = window.as_ref().and_then(|win| win.title());
 
@@ -682,6 +1580,53 @@ impl window_service_server::WindowService for WindowService {
                 })
                 .unwrap_or_default();
 
+            let urgent = window
+                .as_ref()
+                .map(|win| win.with_state(|state| state.urgent.is_some()));
+
+            let label = window
+                .as_ref()
+                .and_then(|win| win.with_state(|state| state.label.clone()));
+
+            let content_type = window
+                .as_ref()
+                .and_then(|win| win.content_type())
+                .map(|content_type| ContentType::from(content_type) as i32);
+
+            let last_focused_ms = window.as_ref().map(|win| {
+                win.with_state(|state| state.last_focused)
+                    .map(|time| time.as_millis() as u64)
+                    .unwrap_or(0)
+            });
+
+            let skip_taskbar = window
+                .as_ref()
+                .map(|win| win.with_state(|state| state.skip_taskbar));
+
+            let skip_cycle = window
+                .as_ref()
+                .map(|win| win.with_state(|state| state.skip_cycle));
+
+            let geometry_pinned = window
+                .as_ref()
+                .map(|win| win.with_state(|state| state.geometry_pinned));
+
+            let is_x11 = window.as_ref().map(|win| win.x11_surface().is_some());
+
+            let tiled_edges = window.as_ref().map(|win| {
+                let edges = win.with_state(|state| state.sent_tiled_edges());
+                window::v0alpha1::TiledEdges {
+                    top: Some(edges.contains(TiledEdges::TOP)),
+                    bottom: Some(edges.contains(TiledEdges::BOTTOM)),
+                    left: Some(edges.contains(TiledEdges::LEFT)),
+                    right: Some(edges.contains(TiledEdges::RIGHT)),
+                }
+            });
+
+            let minimized = window.as_ref().map(|win| win.is_minimized());
+
+            let activated = window.as_ref().map(|win| win.is_activated());
+
             window::v0alpha1::GetPropertiesResponse {
                 geometry,
                 class,
@@ -690,6 +1635,51 @@ impl window_service_server::WindowService for WindowService {
                 floating,
                 fullscreen_or_maximized,
                 tag_ids,
+                urgent,
+                activated,
+                physical_geometry,
+                label,
+                content_type,
+                last_focused_ms,
+                skip_taskbar,
+                skip_cycle,
+                geometry_pinned,
+                is_x11,
+                tiled_edges,
+                minimized,
+            }
+        })
+        .await
+    }
+
+    async fn get_surface_info(
+        &self,
+        request: Request<GetSurfaceInfoRequest>,
+    ) -> Result<Response<GetSurfaceInfoResponse>, Status> {
+        let request = request.into_inner();
+
+        let window_id = WindowId(
+            request
+                .window_id
+                .ok_or_else(|| Status::invalid_argument("no window specified"))?,
+        );
+
+        run_unary(&self.sender, move |state| {
+            let Some(info) = window_id
+                .window(&state.pinnacle)
+                .and_then(|win| win.surface_info())
+            else {
+                return GetSurfaceInfoResponse::default();
+            };
+
+            GetSurfaceInfoResponse {
+                wl_surface_version: Some(info.wl_surface_version),
+                buffer_scale: Some(info.buffer_scale),
+                buffer_width: info.buffer_size.map(|size| size.w),
+                buffer_height: info.buffer_size.map(|size| size.h),
+                uses_viewporter: Some(info.uses_viewporter),
+                uses_fractional_scale: Some(info.uses_fractional_scale),
+                preferred_scale: info.preferred_scale,
             }
         })
         .await
@@ -698,7 +1688,7 @@ impl window_service_server::WindowService for WindowService {
     async fn add_window_rule(
         &self,
         request: Request<AddWindowRuleRequest>,
-    ) -> Result<Response<()>, Status> {
+    ) -> Result<Response<AddWindowRuleResponse>, Status> {
         let request = request.into_inner();
 
         let cond = request
@@ -709,13 +1699,390 @@ impl window_service_server::WindowService for WindowService {
         let rule = request
             .rule
             .ok_or_else(|| Status::invalid_argument("no rule specified"))?
+            .try_into()?;
+
+        run_unary(&self.sender, move |state| {
+            let rule_id = WindowRuleId::next();
+            state
+                .pinnacle
+                .config
+                .window_rules
+                .push((rule_id, cond, rule));
+
+            AddWindowRuleResponse {
+                rule_id: Some(rule_id.0),
+            }
+        })
+        .await
+    }
+
+    async fn get_window_rules(
+        &self,
+        _request: Request<GetWindowRulesRequest>,
+    ) -> Result<Response<GetWindowRulesResponse>, Status> {
+        run_unary(&self.sender, move |state| {
+            let rules = state
+                .pinnacle
+                .config
+                .window_rules
+                .iter()
+                .map(|(rule_id, cond, rule)| get_window_rules_response::Entry {
+                    rule_id: Some(rule_id.0),
+                    cond: Some(cond.clone().into()),
+                    rule: Some(rule.clone().into()),
+                })
+                .collect();
+
+            GetWindowRulesResponse { rules }
+        })
+        .await
+    }
+
+    async fn remove_window_rule(
+        &self,
+        request: Request<RemoveWindowRuleRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        let rule_id = WindowRuleId(
+            request
+                .rule_id
+                .ok_or_else(|| Status::invalid_argument("no rule specified"))?,
+        );
+
+        run_unary_no_response(&self.sender, move |state| {
+            state
+                .pinnacle
+                .config
+                .window_rules
+                .retain(|(id, _, _)| *id != rule_id);
+        })
+        .await
+    }
+
+    async fn reapply_window_rules(
+        &self,
+        _request: Request<ReapplyWindowRulesRequest>,
+    ) -> Result<Response<()>, Status> {
+        run_unary_no_response(&self.sender, move |state| {
+            state.pinnacle.reapply_window_rules();
+
+            for output in state.pinnacle.space.outputs().cloned().collect::<Vec<_>>() {
+                state.schedule_render(&output);
+            }
+        })
+        .await
+    }
+
+    async fn evaluate_window_rule_condition(
+        &self,
+        request: Request<EvaluateWindowRuleConditionRequest>,
+    ) -> Result<Response<EvaluateWindowRuleConditionResponse>, Status> {
+        let request = request.into_inner();
+
+        let window_id = WindowId(
+            request
+                .window_id
+                .ok_or_else(|| Status::invalid_argument("no window specified"))?,
+        );
+
+        let cond: crate::window::rules::WindowRuleCondition = request
+            .cond
+            .ok_or_else(|| Status::invalid_argument("no condition specified"))?
             .into();
 
+        run_unary(&self.sender, move |state| {
+            let evaluation = window_id
+                .window(&state.pinnacle)
+                .map(|window| cond.evaluate(&state.pinnacle, &window).into());
+
+            EvaluateWindowRuleConditionResponse { evaluation }
+        })
+        .await
+    }
+
+    async fn set_focus_new_windows(
+        &self,
+        request: Request<SetFocusNewWindowsRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        let focus = request
+            .focus
+            .ok_or_else(|| Status::invalid_argument("no focus specified"))?;
+
         run_unary_no_response(&self.sender, move |state| {
-            state.pinnacle.config.window_rules.push((cond, rule));
+            state.pinnacle.config.focus_new_windows = focus;
         })
         .await
     }
+
+    async fn set_unfullscreen_on_focus_loss(
+        &self,
+        request: Request<SetUnfullscreenOnFocusLossRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        let unfullscreen = request
+            .unfullscreen
+            .ok_or_else(|| Status::invalid_argument("no unfullscreen specified"))?;
+
+        run_unary_no_response(&self.sender, move |state| {
+            state.pinnacle.config.unfullscreen_on_focus_loss = unfullscreen;
+        })
+        .await
+    }
+
+    async fn set_last_window_fallback(
+        &self,
+        request: Request<SetLastWindowFallbackRequest>,
+    ) -> Result<Response<()>, Status> {
+        let command = request.into_inner().command;
+
+        run_unary_no_response(&self.sender, move |state| {
+            state.pinnacle.config.last_window_fallback = command;
+        })
+        .await
+    }
+
+    async fn open_next_on_focused_tag(
+        &self,
+        _request: Request<OpenNextOnFocusedTagRequest>,
+    ) -> Result<Response<()>, Status> {
+        run_unary_no_response(&self.sender, |state| {
+            state.pinnacle.config.open_next_on_focused_tag = true;
+        })
+        .await
+    }
+
+    async fn set_urgency_timeout(
+        &self,
+        request: Request<SetUrgencyTimeoutRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        let timeout = Some(Duration::from_millis(request.timeout_ms() as u64))
+            .filter(|timeout| !timeout.is_zero());
+
+        run_unary_no_response(&self.sender, move |state| {
+            state.pinnacle.config.urgency_timeout = timeout;
+        })
+        .await
+    }
+
+    async fn capture(
+        &self,
+        request: Request<CaptureRequest>,
+    ) -> Result<Response<CaptureResponse>, Status> {
+        let request = request.into_inner();
+
+        let window_id = WindowId(
+            request
+                .window_id
+                .ok_or_else(|| Status::invalid_argument("no window specified"))?,
+        );
+
+        let scale = request.scale;
+
+        if scale.is_some_and(|scale| !scale.is_normal() || scale < 0.0) {
+            return Err(Status::invalid_argument(
+                "scale must be positive and finite",
+            ));
+        }
+
+        run_unary(&self.sender, move |state| {
+            let Some(window) = window_id.window(&state.pinnacle) else {
+                return CaptureResponse::default();
+            };
+
+            let scale = scale.map(f64::from).unwrap_or_else(|| {
+                window
+                    .output(&state.pinnacle)
+                    .map(|op| op.current_scale().fractional_scale())
+                    .unwrap_or(1.0)
+            });
+
+            match state.backend.capture_window(&window, Scale::from(scale)) {
+                Ok((size, pixels)) => CaptureResponse {
+                    width: Some(size.w as u32),
+                    height: Some(size.h as u32),
+                    pixels: Some(pixels),
+                },
+                Err(err) => {
+                    warn!("Failed to capture window: {err}");
+                    CaptureResponse::default()
+                }
+            }
+        })
+        .await
+    }
+}
+
+/// Change the tags of `window` with `change`, then lay out and render both the output
+/// it was on and the output of `tag`, which may be different.
+fn change_window_tags(
+    state: &mut State,
+    window: &WindowElement,
+    tag: &Tag,
+    change: impl FnOnce(&mut TagSet),
+) {
+    let old_output = window.output(&state.pinnacle);
+
+    window.with_state_mut(|state| change(&mut state.tags));
+
+    let mut outputs = old_output.into_iter().collect::<Vec<_>>();
+    if let Some(output) = tag.output(&state.pinnacle) {
+        if !outputs.contains(&output) {
+            outputs.push(output);
+        }
+    }
+
+    for output in outputs {
+        state.pinnacle.request_layout(&output);
+        state.schedule_render(&output);
+    }
+}
+
+/// Set the floating geometry of the window with `window_id`, or the geometry it floats at
+/// if it's tiled.
+///
+/// If `relative_to` is set, `geometry`'s location is relative to that output's location.
+/// If `float` is true, tiled windows are floated at the new geometry right away.
+fn set_window_geometry(
+    state: &mut State,
+    window_id: WindowId,
+    geometry: Geometry,
+    relative_to: Option<OutputName>,
+    float: bool,
+) {
+    let Some(window) = window_id.window(&state.pinnacle) else {
+        return;
+    };
+
+    let origin = match relative_to {
+        Some(output_name) => {
+            let Some(output_geo) = output_name
+                .output(&state.pinnacle)
+                .and_then(|output| state.pinnacle.space.output_geometry(&output))
+            else {
+                warn!(
+                    "Output {} doesn't exist, not setting geometry",
+                    output_name.0
+                );
+                return;
+            };
+            output_geo.loc
+        }
+        None => Point::default(),
+    };
+
+    let Geometry {
+        x,
+        y,
+        width,
+        height,
+    } = geometry;
+    let x = x.map(|x| x + origin.x);
+    let y = y.map(|y| y + origin.y);
+
+    // Start from the location the window is about to move to, if it's waiting on a commit,
+    // so geometry set more than once before then builds on the last request.
+    //
+    // Windows that aren't mapped, like ones on inactive tags, don't have a location
+    // in the space. Fall back to the location they'll be mapped at, or the
+    // location of their output if they don't have one yet.
+    //
+    // Floating locations are fractional, so coordinates that aren't set keep their
+    // fractional part instead of being rounded to whole logical pixels.
+    let (target_loc, stored_loc) = window.with_state(|state| {
+        let stored_loc = match state.floating_or_tiled {
+            FloatingOrTiled::Floating(rect) => Some(rect.loc),
+            FloatingOrTiled::Tiled(rect) => rect.map(|rect| rect.loc),
+        };
+        (state.target_loc, stored_loc)
+    });
+
+    let mut window_loc = target_loc
+        .map(|loc| loc.to_f64())
+        .or_else(|| {
+            state
+                .pinnacle
+                .space
+                .element_location(&window)
+                .map(|loc| window.precise_loc(loc))
+        })
+        .or(stored_loc)
+        .or_else(|| {
+            window
+                .output(&state.pinnacle)
+                .and_then(|op| state.pinnacle.space.output_geometry(&op))
+                .map(|geo| geo.loc.to_f64())
+        })
+        .unwrap_or_default();
+    window_loc.x = x.map_or(window_loc.x, f64::from);
+    window_loc.y = y.map_or(window_loc.y, f64::from);
+
+    let mut window_size = window.geometry().size.to_f64();
+    window_size.w = width.map_or(window_size.w, f64::from);
+    window_size.h = height.map_or(window_size.h, f64::from);
+
+    let rect = Rectangle::from_loc_and_size(window_loc, window_size);
+
+    window.with_state_mut(|state| {
+        state.floating_or_tiled = match state.floating_or_tiled {
+            FloatingOrTiled::Floating(_) => FloatingOrTiled::Floating(rect),
+            FloatingOrTiled::Tiled(_) => FloatingOrTiled::Tiled(Some(rect)),
+        };
+        state.relative_geometry = None;
+    });
+
+    // Floating a tiled window restores its stored floating geometry, which is now `rect`.
+    // The window goes straight there on the next layout instead of being tiled first.
+    if float && window.with_state(|state| state.floating_or_tiled.is_tiled()) {
+        window.toggle_floating();
+    }
+
+    // Pinned windows stay where the config put them
+    state.pinnacle.pin_relative_geometry(&window);
+
+    // The new geometry is sent to the window as a pending target location on the next
+    // layout and gets mapped on commit. For windows on inactive tags,
+    // this happens when one of their tags becomes active.
+    let mut outputs = state.pinnacle.space.outputs_for_element(&window);
+    if let Some(output) = window.output(&state.pinnacle) {
+        if !outputs.contains(&output) {
+            outputs.push(output);
+        }
+    }
+
+    for output in outputs {
+        state.pinnacle.request_layout(&output);
+        state.schedule_render(&output);
+    }
+}
+
+impl From<crate::window::rules::ConditionEvaluation> for WindowRuleConditionEvaluation {
+    fn from(eval: crate::window::rules::ConditionEvaluation) -> Self {
+        WindowRuleConditionEvaluation {
+            matched: Some(eval.matched),
+            any: eval
+                .cond_any
+                .into_iter()
+                .flatten()
+                .map(WindowRuleConditionEvaluation::from)
+                .collect(),
+            all: eval
+                .cond_all
+                .into_iter()
+                .flatten()
+                .map(WindowRuleConditionEvaluation::from)
+                .collect(),
+            classes: eval.class,
+            titles: eval.title,
+            tags: eval.tag,
+            content_types: eval.content_type,
+        }
+    }
 }
 
 impl From<WindowRuleCondition> for crate::window::rules::WindowRuleCondition {
@@ -755,18 +2122,130 @@ impl From<WindowRuleCondition> for crate::window::rules::WindowRuleCondition {
             false => Some(cond.tags.into_iter().map(TagId).collect::<Vec<_>>()),
         };
 
+        let content_type = match cond.content_types.is_empty() {
+            true => None,
+            false => Some(
+                cond.content_types
+                    .into_iter()
+                    .filter_map(
+                        |content_type| match ContentType::try_from(content_type).ok()? {
+                            ContentType::Unspecified => None,
+                            ContentType::None => Some(crate::window::ContentType::None),
+                            ContentType::Photo => Some(crate::window::ContentType::Photo),
+                            ContentType::Video => Some(crate::window::ContentType::Video),
+                            ContentType::Game => Some(crate::window::ContentType::Game),
+                        },
+                    )
+                    .collect::<Vec<_>>(),
+            ),
+        };
+
         crate::window::rules::WindowRuleCondition {
             cond_any,
             cond_all,
             class,
             title,
             tag,
+            content_type,
+        }
+    }
+}
+
+impl From<crate::window::rules::WindowRuleCondition> for WindowRuleCondition {
+    fn from(cond: crate::window::rules::WindowRuleCondition) -> Self {
+        WindowRuleCondition {
+            any: cond
+                .cond_any
+                .into_iter()
+                .flatten()
+                .map(WindowRuleCondition::from)
+                .collect(),
+            all: cond
+                .cond_all
+                .into_iter()
+                .flatten()
+                .map(WindowRuleCondition::from)
+                .collect(),
+            classes: cond.class.unwrap_or_default(),
+            titles: cond.title.unwrap_or_default(),
+            tags: cond
+                .tag
+                .into_iter()
+                .flatten()
+                .map(|tag_id| tag_id.0)
+                .collect(),
+            content_types: cond
+                .content_type
+                .into_iter()
+                .flatten()
+                .map(|content_type| ContentType::from(content_type) as i32)
+                .collect(),
+        }
+    }
+}
+
+impl From<crate::window::ContentType> for ContentType {
+    fn from(content_type: crate::window::ContentType) -> Self {
+        match content_type {
+            crate::window::ContentType::None => ContentType::None,
+            crate::window::ContentType::Photo => ContentType::Photo,
+            crate::window::ContentType::Video => ContentType::Video,
+            crate::window::ContentType::Game => ContentType::Game,
+        }
+    }
+}
+
+impl From<crate::window::rules::WindowRule> for WindowRule {
+    fn from(rule: crate::window::rules::WindowRule) -> Self {
+        let fullscreen_or_maximized = rule.fullscreen_or_maximized.map(|fs_or_max| {
+            let fs_or_max = match fs_or_max {
+                crate::window::window_state::FullscreenOrMaximized::Neither => {
+                    FullscreenOrMaximized::Neither
+                }
+                crate::window::window_state::FullscreenOrMaximized::Fullscreen => {
+                    FullscreenOrMaximized::Fullscreen
+                }
+                crate::window::window_state::FullscreenOrMaximized::Maximized => {
+                    FullscreenOrMaximized::Maximized
+                }
+            };
+            fs_or_max as i32
+        });
+
+        WindowRule {
+            output: rule.output.map(|output_name| output_name.0),
+            tags: rule
+                .tags
+                .into_iter()
+                .flatten()
+                .map(|tag_id| tag_id.0)
+                .collect(),
+            floating: rule
+                .floating_or_tiled
+                .map(|floating_or_tiled| match floating_or_tiled {
+                    crate::window::rules::FloatingOrTiled::Floating => true,
+                    crate::window::rules::FloatingOrTiled::Tiled => false,
+                }),
+            fullscreen_or_maximized,
+            x: rule.location.map(|(x, _)| x),
+            y: rule.location.map(|(_, y)| y),
+            width: rule.size.map(|(w, _)| u32::from(w) as i32),
+            height: rule.size.map(|(_, h)| u32::from(h) as i32),
+            focus_steal_exempt: rule.focus_steal_exempt,
+            disable_keybinds: rule.disable_keybinds,
+            focus_on_open: rule.focus_on_open,
+            skip_taskbar: rule.skip_taskbar,
+            skip_cycle: rule.skip_cycle,
+            once: rule.once,
+            unfullscreen_on_focus_loss: rule.unfullscreen_on_focus_loss,
         }
     }
 }
 
-impl From<WindowRule> for crate::window::rules::WindowRule {
-    fn from(rule: WindowRule) -> Self {
+impl TryFrom<WindowRule> for crate::window::rules::WindowRule {
+    type Error = Status;
+
+    fn try_from(rule: WindowRule) -> Result<Self, Self::Error> {
         let fullscreen_or_maximized = match rule.fullscreen_or_maximized() {
             FullscreenOrMaximized::Unspecified => None,
             FullscreenOrMaximized::Neither => {
@@ -788,23 +2267,48 @@ impl From<WindowRule> for crate::window::rules::WindowRule {
             true => crate::window::rules::FloatingOrTiled::Floating,
             false => crate::window::rules::FloatingOrTiled::Tiled,
         });
-        let size = rule.width.and_then(|w| {
-            rule.height.and_then(|h| {
-                Some((
-                    NonZeroU32::try_from(w as u32).ok()?,
-                    NonZeroU32::try_from(h as u32).ok()?,
+        let size = match (rule.width, rule.height) {
+            (Some(w), Some(h)) => {
+                let to_size = |dim: i32| {
+                    u32::try_from(dim)
+                        .ok()
+                        .and_then(NonZeroU32::new)
+                        .ok_or_else(|| {
+                            Status::invalid_argument(format!(
+                                "invalid size {w}x{h}: width and height must be positive"
+                            ))
+                        })
+                };
+                Some((to_size(w)?, to_size(h)?))
+            }
+            (None, None) => None,
+            _ => {
+                return Err(Status::invalid_argument(
+                    "both width and height must be specified",
                 ))
-            })
-        });
-        let location = rule.x.and_then(|x| rule.y.map(|y| (x, y)));
+            }
+        };
+        // Negative coordinates are fine as outputs can be placed left of or above the origin
+        let location = match (rule.x, rule.y) {
+            (Some(x), Some(y)) => Some((x, y)),
+            (None, None) => None,
+            _ => return Err(Status::invalid_argument("both x and y must be specified")),
+        };
 
-        crate::window::rules::WindowRule {
+        Ok(crate::window::rules::WindowRule {
             output,
             tags,
             floating_or_tiled,
             fullscreen_or_maximized,
             size,
             location,
-        }
+            focus_steal_exempt: rule.focus_steal_exempt,
+            disable_keybinds: rule.disable_keybinds,
+            focus_on_open: rule.focus_on_open,
+            skip_taskbar: rule.skip_taskbar,
+            skip_cycle: rule.skip_cycle,
+            once: rule.once,
+            unfullscreen_on_focus_loss: rule.unfullscreen_on_focus_loss,
+        })
     }
 }
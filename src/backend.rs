@@ -1,7 +1,8 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 
-use std::time::Duration;
+use std::{ffi::CStr, time::Duration};
 
+use anyhow::anyhow;
 use smithay::{
     backend::{
         allocator::dmabuf::Dmabuf,
@@ -10,6 +11,7 @@ use smithay::{
                 default_primary_scanout_output_compare, utils::select_dmabuf_feedback,
                 RenderElementStates,
             },
+            gles::{ffi, GlesRenderer, GlesTexture},
             ImportDma, Renderer, TextureFilter,
         },
     },
@@ -25,6 +27,7 @@ use smithay::{
     input::pointer::CursorImageStatus,
     output::Output,
     reexports::wayland_server::protocol::wl_surface::WlSurface,
+    utils::{Physical, Scale, Size},
     wayland::{
         dmabuf::{DmabufGlobal, DmabufHandler, DmabufState, ImportNotifier},
         fractional_scale::with_fractional_scale,
@@ -102,6 +105,59 @@ impl Backend {
         }
     }
 
+    /// Render the given window offscreen and read back its pixels in RGBA8888.
+    pub fn capture_window(
+        &mut self,
+        window: &WindowElement,
+        scale: Scale<f64>,
+    ) -> anyhow::Result<(Size<i32, Physical>, Vec<u8>)> {
+        match self {
+            Backend::Winit(winit) => {
+                let capture = crate::render::capture_window::<_, GlesTexture>(
+                    winit.backend.renderer(),
+                    window,
+                    scale,
+                );
+
+                // Rebind the EGL surface as the capture bound an offscreen buffer
+                if let Err(err) = winit.backend.bind() {
+                    error!("Failed to rebind EGL surface after window capture: {err}");
+                }
+
+                capture
+            }
+            Backend::Udev(udev) => {
+                let mut renderer = udev
+                    .gpu_manager
+                    .single_renderer(&udev.primary_gpu)
+                    .map_err(|err| anyhow!("{err}"))?;
+
+                crate::render::capture_window::<_, GlesTexture>(&mut renderer, window, scale)
+            }
+            #[cfg(feature = "testing")]
+            Backend::Dummy(_) => Err(anyhow!("window capture is not supported on this backend")),
+        }
+    }
+
+    /// Get information about the renderer used for compositing.
+    ///
+    /// On the udev backend, this is the renderer of the primary GPU.
+    pub fn renderer_info(&mut self) -> anyhow::Result<RendererInfo> {
+        match self {
+            Backend::Winit(winit) => RendererInfo::from_gles(winit.backend.renderer()),
+            Backend::Udev(udev) => {
+                let mut renderer = udev
+                    .gpu_manager
+                    .single_renderer(&udev.primary_gpu)
+                    .map_err(|err| anyhow!("{err}"))?;
+
+                RendererInfo::from_gles(renderer.as_mut())
+            }
+            #[cfg(feature = "testing")]
+            Backend::Dummy(_) => Err(anyhow!("the dummy backend has no renderer")),
+        }
+    }
+
     /// Returns `true` if the backend is [`Winit`].
     ///
     /// [`Winit`]: Backend::Winit
@@ -119,6 +175,53 @@ impl Backend {
     }
 }
 
+/// Information about a renderer for debugging.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RendererInfo {
+    /// The OpenGL ES version string
+    pub version: String,
+    /// The company responsible for the GL implementation
+    pub vendor: String,
+    /// The GPU or software renderer name
+    pub renderer: String,
+    /// The fourcc codes of dmabuf formats the renderer can import
+    pub dmabuf_formats: Vec<String>,
+}
+
+impl RendererInfo {
+    fn from_gles(renderer: &mut GlesRenderer) -> anyhow::Result<Self> {
+        let mut dmabuf_formats = renderer
+            .dmabuf_formats()
+            .map(|format| format!("{:?}", format.code))
+            .collect::<Vec<_>>();
+        dmabuf_formats.sort();
+        dmabuf_formats.dedup();
+
+        let [version, vendor, renderer] = renderer
+            .with_context(|gl| {
+                [ffi::VERSION, ffi::VENDOR, ffi::RENDERER].map(|name| {
+                    // SAFETY: `GetString` returns a static, null-terminated string or null
+                    unsafe {
+                        let string = gl.GetString(name);
+                        if string.is_null() {
+                            String::new()
+                        } else {
+                            CStr::from_ptr(string.cast()).to_string_lossy().into_owned()
+                        }
+                    }
+                })
+            })
+            .map_err(|err| anyhow!("{err}"))?;
+
+        Ok(Self {
+            version,
+            vendor,
+            renderer,
+            dmabuf_formats,
+        })
+    }
+}
+
 pub trait BackendData: 'static {
     fn seat_name(&self) -> String;
     fn reset_buffers(&mut self, output: &Output);
@@ -156,6 +259,19 @@ impl BackendData for Backend {
     }
 }
 
+/// Get the time between refreshes of `output` to send in presentation feedback.
+///
+/// This is zero if the output's refresh rate isn't known, which is what
+/// `wp_presentation_feedback.presented` expects in that case.
+pub fn refresh_interval(output: &Output) -> Duration {
+    output
+        .current_mode()
+        .filter(|mode| mode.refresh > 0)
+        // `refresh` is in millihertz
+        .map(|mode| Duration::from_nanos(1_000_000_000_000 / mode.refresh as u64))
+        .unwrap_or_default()
+}
+
 /// Update surface primary scanout outputs and send frames and dmabuf feedback to visible windows
 /// and layers.
 pub fn post_repaint(
@@ -1,6 +1,4 @@
-use pinnacle_api_defs::pinnacle::signal::v0alpha1::{
-    OutputConnectResponse, OutputDisconnectResponse,
-};
+use pinnacle_api_defs::pinnacle::signal::v0alpha1::OutputConnectResponse;
 use smithay::backend::renderer::test::DummyRenderer;
 use smithay::backend::renderer::ImportMemWl;
 use smithay::reexports::wayland_server::protocol::wl_surface::WlSurface;
@@ -156,20 +154,14 @@ impl Pinnacle {
 
         self.space.map_output(&output, (0, 0));
 
+        self.add_default_tags(&output);
+
         self.signal_state.output_connect.signal(|buf| {
             buf.push_back(OutputConnectResponse {
                 output_name: Some(output.name()),
             });
         });
-    }
 
-    pub fn remove_output(&mut self, output: &Output) {
-        self.space.unmap_output(output);
-
-        self.signal_state.output_disconnect.signal(|buffer| {
-            buffer.push_back(OutputDisconnectResponse {
-                output_name: Some(output.name()),
-            })
-        });
+        self.leave_headless(&output);
     }
 }
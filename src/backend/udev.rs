@@ -7,13 +7,11 @@ use std::{
     collections::{HashMap, HashSet},
     ffi::OsString,
     path::{Path, PathBuf},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use anyhow::{anyhow, ensure, Context};
-use pinnacle_api_defs::pinnacle::signal::v0alpha1::{
-    OutputConnectResponse, OutputDisconnectResponse,
-};
+use pinnacle_api_defs::pinnacle::signal::v0alpha1::OutputConnectResponse;
 use smithay::{
     backend::{
         allocator::{
@@ -51,10 +49,7 @@ use smithay::{
         vulkan::{self, version::Version, PhysicalDevice},
         SwapBuffersError,
     },
-    desktop::{
-        layer_map_for_output,
-        utils::{send_frames_surface_tree, OutputPresentationFeedback},
-    },
+    desktop::utils::{send_frames_surface_tree, OutputPresentationFeedback},
     input::pointer::CursorImageStatus,
     output::{Output, PhysicalProperties, Subpixel},
     reexports::{
@@ -91,8 +86,8 @@ use crate::{
     config::ConnectorSavedState,
     output::OutputName,
     render::{
-        pointer::PointerElement, pointer_render_elements, take_presentation_feedback,
-        OutputRenderElement,
+        frame_timing::FrameTiming, pointer::PointerElement, pointer_render_elements,
+        take_presentation_feedback, OutputRenderElement,
     },
     state::{Pinnacle, State, SurfaceDmabufFeedback, WithState},
 };
@@ -1123,6 +1118,20 @@ impl Udev {
 
         device.surfaces.insert(crtc, surface);
 
+        // A newly connected output starts with linear gamma, so set its preset again
+        if let Some(preset) = pinnacle
+            .gamma_presets
+            .get(&OutputName(output.name()))
+            .copied()
+        {
+            if let Err(err) = self.apply_gamma_preset(&output, Some(preset)) {
+                warn!(
+                    "Failed to apply gamma preset for output {}: {err}",
+                    output.name()
+                );
+            }
+        }
+
         pinnacle.change_output_state(&output, Some(wl_mode), None, None, Some(position));
 
         // If there is saved connector state, the connector was previously plugged in.
@@ -1137,12 +1146,15 @@ impl Udev {
             output.with_state_mut(|state| state.tags = tags.clone());
             pinnacle.change_output_state(&output, None, None, *scale, Some(*loc));
         } else {
+            pinnacle.add_default_tags(&output);
             pinnacle.signal_state.output_connect.signal(|buffer| {
                 buffer.push_back(OutputConnectResponse {
                     output_name: Some(output.name()),
                 })
             });
         }
+
+        pinnacle.leave_headless(&output);
     }
 
     /// A display was unplugged.
@@ -1185,19 +1197,7 @@ impl Udev {
                 },
             );
 
-            // TODO: extract into a `remove_output` function and unify with dummy backend
-            for layer in layer_map_for_output(&output).layers() {
-                layer.layer_surface().send_close();
-            }
-
-            pinnacle.space.unmap_output(&output);
-            pinnacle.gamma_control_manager_state.output_removed(&output);
-
-            pinnacle.signal_state.output_disconnect.signal(|buffer| {
-                buffer.push_back(OutputDisconnectResponse {
-                    output_name: Some(output.name()),
-                })
-            });
+            pinnacle.remove_output(&output);
         }
     }
 
@@ -1300,26 +1300,24 @@ impl Udev {
                         .map(|metadata| metadata.sequence)
                         .unwrap_or(0);
 
-                    let (clock, flags) = if let Some(tp) = tp {
-                        (
+                    let (clock, flags) = match tp {
+                        // The page flip was timestamped with the same clock as presentation time
+                        Some(tp) => (
                             tp.into(),
                             wp_presentation_feedback::Kind::Vsync
                                 | wp_presentation_feedback::Kind::HwClock
                                 | wp_presentation_feedback::Kind::HwCompletion,
-                        )
-                    } else {
-                        (pinnacle.clock.now(), wp_presentation_feedback::Kind::Vsync)
+                        ),
+                        // The flip still completed in hardware, but its timestamp is unusable
+                        None if metadata.is_some() => (
+                            pinnacle.clock.now(),
+                            wp_presentation_feedback::Kind::Vsync
+                                | wp_presentation_feedback::Kind::HwCompletion,
+                        ),
+                        None => (pinnacle.clock.now(), wp_presentation_feedback::Kind::Vsync),
                     };
 
-                    feedback.presented(
-                        clock,
-                        output
-                            .current_mode()
-                            .map(|mode| Duration::from_secs_f64(1000f64 / mode.refresh as f64))
-                            .unwrap_or_default(),
-                        seq as u64,
-                        flags,
-                    );
+                    feedback.presented(clock, super::refresh_interval(&output), seq as u64, flags);
                 }
             }
             Err(err) => {
@@ -1484,14 +1482,23 @@ impl Udev {
             }
         }
 
+        let overview_animating = pinnacle.refresh_overview(output);
+        let overview_targets = pinnacle.overview_targets(output);
+
         output_render_elements.extend(crate::render::output_render_elements(
             output,
             &mut renderer,
             &pinnacle.space,
             &windows,
+            &overview_targets,
+            pinnacle.window_hints.as_ref(),
+            pinnacle.output_dim(output),
+            pointer_location,
         ));
 
         let result = (|| -> Result<bool, SwapBuffersError> {
+            let render_start = Instant::now();
+
             let render_frame_result = render_frame(
                 &mut surface.compositor,
                 &mut renderer,
@@ -1505,6 +1512,21 @@ impl Udev {
                 }
             }
 
+            if !render_frame_result.is_empty {
+                pinnacle.frame_timings.record(
+                    output,
+                    FrameTiming {
+                        render_time: render_start.elapsed(),
+                        damage_rects: None,
+                        direct_scanout: matches!(
+                            render_frame_result.primary_element,
+                            PrimaryPlaneElement::Element(_)
+                        ),
+                        cursor_plane: render_frame_result.cursor_element.is_some(),
+                    },
+                );
+            }
+
             handle_pending_screencopy(
                 &mut renderer,
                 output,
@@ -1547,7 +1569,12 @@ impl Udev {
         })();
 
         match result {
-            Ok(true) => surface.render_state = RenderState::WaitingForVblank { dirty: false },
+            // Keep rendering while the overview animates
+            Ok(true) => {
+                surface.render_state = RenderState::WaitingForVblank {
+                    dirty: overview_animating,
+                }
+            }
             Ok(false) | Err(_) => surface.render_state = RenderState::Idle,
         }
     }
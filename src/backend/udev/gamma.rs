@@ -6,7 +6,10 @@ use smithay::backend::drm::DrmDevice;
 use smithay::reexports::drm::control::{crtc, Device};
 use smithay::{backend::session::Session, output::Output};
 
-use crate::backend::udev::{render_surface_for_output, PendingGammaChange};
+use crate::{
+    backend::udev::{render_surface_for_output, PendingGammaChange},
+    output::GammaPreset,
+};
 
 use super::{Udev, UdevOutputData};
 
@@ -48,6 +51,23 @@ impl Udev {
         ret
     }
 
+    /// Set the gamma of `output` to `preset`, or reset it to linear if there is none.
+    pub fn apply_gamma_preset(
+        &mut self,
+        output: &Output,
+        preset: Option<GammaPreset>,
+    ) -> anyhow::Result<()> {
+        let Some(preset) = preset else {
+            return self.set_gamma(output, None);
+        };
+
+        let gamma_size = self.gamma_size(output)? as usize;
+        ensure!(gamma_size != 0, "setting gamma is not supported");
+
+        let [red, green, blue] = preset.ramps(gamma_size);
+        self.set_gamma(output, Some([&red, &green, &blue]))
+    }
+
     pub(super) fn set_gamma_internal(
         drm_device: &DrmDevice,
         crtc: &crtc::Handle,
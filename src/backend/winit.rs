@@ -1,6 +1,10 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 
-use std::{ffi::OsString, path::PathBuf, time::Duration};
+use std::{
+    ffi::OsString,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
 
 use anyhow::{anyhow, ensure};
 use smithay::{
@@ -40,7 +44,10 @@ use smithay::{
 use tracing::{error, trace, warn};
 
 use crate::{
-    render::{pointer::PointerElement, pointer_render_elements, take_presentation_feedback},
+    render::{
+        frame_timing::FrameTiming, pointer::PointerElement, pointer_render_elements,
+        take_presentation_feedback,
+    },
     state::{State, WithState},
 };
 
@@ -293,19 +300,19 @@ impl State {
                 .is_some_and(|sc| !sc.overlay_cursor())
         });
 
+        let pointer_location = self
+            .pinnacle
+            .seat
+            .get_pointer()
+            .map(|ptr| ptr.current_location())
+            .unwrap_or((0.0, 0.0).into());
+
         // If there isn't a pending screencopy that doesn't want to overlay the cursor,
         // render it.
         //
         // This will cause the cursor to disappear for a frame if there is one though,
         // but it shouldn't meaningfully affect anything.
         if !pending_screencopy_without_cursor {
-            let pointer_location = self
-                .pinnacle
-                .seat
-                .get_pointer()
-                .map(|ptr| ptr.current_location())
-                .unwrap_or((0.0, 0.0).into());
-
             let pointer_render_elements = pointer_render_elements(
                 output,
                 winit.backend.renderer(),
@@ -318,13 +325,25 @@ impl State {
             output_render_elements.extend(pointer_render_elements);
         }
 
+        // Redraw everything while the overview animates, as windows move every frame
+        if self.pinnacle.refresh_overview(output) {
+            *full_redraw = (*full_redraw).max(1);
+        }
+        let overview_targets = self.pinnacle.overview_targets(output);
+
         output_render_elements.extend(crate::render::output_render_elements(
             output,
             winit.backend.renderer(),
             &self.pinnacle.space,
             &windows,
+            &overview_targets,
+            self.pinnacle.window_hints.as_ref(),
+            self.pinnacle.output_dim(output),
+            pointer_location,
         ));
 
+        let render_start = Instant::now();
+
         let render_res = winit.backend.bind().and_then(|_| {
             let age = if *full_redraw > 0 {
                 0
@@ -354,6 +373,16 @@ impl State {
 
                 let has_rendered = render_output_result.damage.is_some();
                 if let Some(damage) = render_output_result.damage {
+                    self.pinnacle.frame_timings.record(
+                        output,
+                        FrameTiming {
+                            render_time: render_start.elapsed(),
+                            damage_rects: Some(damage.len()),
+                            direct_scanout: false,
+                            cursor_plane: false,
+                        },
+                    );
+
                     if let Err(err) = winit.backend.submit(Some(damage)) {
                         error!("Failed to submit buffer: {}", err);
                     }
@@ -380,10 +409,7 @@ impl State {
                     );
                     output_presentation_feedback.presented(
                         time,
-                        output
-                            .current_mode()
-                            .map(|mode| Duration::from_secs_f64(1000f64 / mode.refresh as f64))
-                            .unwrap_or_default(),
+                        super::refresh_interval(output),
                         0,
                         wp_presentation_feedback::Kind::Vsync,
                     );
@@ -0,0 +1,119 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Screen brightness control through `/sys/class/backlight`.
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use smithay::output::Output;
+
+use crate::state::WithState;
+
+const BACKLIGHT_DIR: &str = "/sys/class/backlight";
+
+/// A backlight device in sysfs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backlight {
+    path: PathBuf,
+}
+
+impl Backlight {
+    /// Find the backlight for the output with the given connector name.
+    ///
+    /// Backlights attached to the output's DRM connector are used if there are any.
+    /// Otherwise, internal panels like eDP use the first backlight found, preferring
+    /// firmware, then platform, then raw backlights like systemd-backlight does.
+    pub fn for_output(output_name: &str) -> Option<Self> {
+        let backlights = std::fs::read_dir(BACKLIGHT_DIR)
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| Self { path: entry.path() })
+            .collect::<Vec<_>>();
+
+        let connector_suffix = format!("-{output_name}");
+        if let Some(backlight) = backlights.iter().find(|backlight| {
+            std::fs::canonicalize(backlight.path.join("device")).is_ok_and(|device| {
+                device
+                    .file_name()
+                    .is_some_and(|name| name.to_string_lossy().ends_with(&connector_suffix))
+            })
+        }) {
+            return Some(backlight.clone());
+        }
+
+        let is_internal = ["eDP", "LVDS", "DSI"]
+            .iter()
+            .any(|prefix| output_name.starts_with(prefix));
+
+        if !is_internal {
+            return None;
+        }
+
+        backlights
+            .into_iter()
+            .min_by_key(|backlight| match backlight.kind().as_deref() {
+                Some("firmware") => 0,
+                Some("platform") => 1,
+                Some("raw") => 2,
+                _ => 3,
+            })
+    }
+
+    /// Find the backlight for `output` like [`Backlight::for_output`], reusing the one
+    /// found the last time.
+    ///
+    /// Looking up a backlight scans sysfs, and an output's backlight doesn't change
+    /// while it's connected.
+    pub fn for_output_cached(output: &Output) -> Option<Self> {
+        if let Some(backlight) = output.with_state(|state| state.backlight.clone()) {
+            return backlight;
+        }
+
+        let backlight = Self::for_output(&output.name());
+        output.with_state_mut(|state| state.backlight = Some(backlight.clone()));
+        backlight
+    }
+
+    fn kind(&self) -> Option<String> {
+        std::fs::read_to_string(self.path.join("type"))
+            .ok()
+            .map(|kind| kind.trim().to_string())
+    }
+
+    fn read_value(path: &Path) -> io::Result<u32> {
+        std::fs::read_to_string(path)?
+            .trim()
+            .parse()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// Get the brightness as a fraction of the maximum brightness.
+    pub fn brightness(&self) -> io::Result<f32> {
+        let brightness = Self::read_value(&self.path.join("brightness"))?;
+        let max = Self::read_value(&self.path.join("max_brightness"))?;
+
+        if max == 0 {
+            return Ok(0.0);
+        }
+
+        Ok(brightness as f32 / max as f32)
+    }
+
+    /// Set the brightness as a fraction of the maximum brightness, clamped to 0.0..=1.0.
+    pub fn set_brightness(&self, brightness: f32) -> io::Result<()> {
+        let max = Self::read_value(&self.path.join("max_brightness"))?;
+        let value = (brightness.clamp(0.0, 1.0) * max as f32).round() as u32;
+
+        std::fs::write(self.path.join("brightness"), value.to_string())
+    }
+
+    /// The name of this backlight, like `intel_backlight`.
+    pub fn name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+}
@@ -7,12 +7,13 @@ use crate::{
     output::OutputName,
     state::Pinnacle,
     tag::Tag,
-    window::rules::{WindowRule, WindowRuleCondition},
+    window::rules::{WindowRule, WindowRuleCondition, WindowRuleId},
 };
 use std::{
     collections::HashMap,
     path::{Path, PathBuf},
     process::Stdio,
+    time::Duration,
 };
 
 use anyhow::Context;
@@ -182,8 +183,8 @@ pub enum Key {
 /// The current state of configuration.
 #[derive(Default, Debug)]
 pub struct Config {
-    /// Window rules and conditions on when those rules should apply
-    pub window_rules: Vec<(WindowRuleCondition, WindowRule)>,
+    /// Window rules and conditions on when those rules should apply, in the order they were added
+    pub window_rules: Vec<(WindowRuleId, WindowRuleCondition, WindowRule)>,
     /// Saved states when outputs are disconnected
     pub connector_saved_states: HashMap<OutputName, ConnectorSavedState>,
 
@@ -195,6 +196,36 @@ pub struct Config {
 
     pub no_config: bool,
     config_dir: Option<PathBuf>,
+
+    /// Whether new windows get keyboard focus when they open.
+    ///
+    /// This can be overridden per window with window rules.
+    pub focus_new_windows: bool,
+
+    /// Whether fullscreen windows leave fullscreen when they lose keyboard focus.
+    ///
+    /// This can be overridden per window with window rules.
+    pub unfullscreen_on_focus_loss: bool,
+
+    /// The names of tags added to newly connected outputs that have no tags.
+    pub default_output_tags: Vec<String>,
+
+    /// How long windows stay urgent without being focused, or `None` to keep them urgent.
+    pub urgency_timeout: Option<Duration>,
+
+    /// The output layer surfaces are placed on when they don't ask for one.
+    ///
+    /// This doesn't need to be connected; it's used whenever it is.
+    pub primary_output: Option<OutputName>,
+
+    /// How much outputs without focus are dimmed, from 0.0 (not at all) to 1.0 (black)
+    pub inactive_output_dim: f32,
+
+    /// The command spawned when the config closes the last open window, or empty to spawn nothing
+    pub last_window_fallback: Vec<String>,
+
+    /// Whether the next window that opens ignores window rules that would place it elsewhere
+    pub open_next_on_focused_tag: bool,
 }
 
 impl Config {
@@ -202,6 +233,7 @@ impl Config {
         Config {
             no_config,
             config_dir,
+            focus_new_windows: true,
             ..Default::default()
         }
     }
@@ -215,6 +247,14 @@ impl Config {
     pub(crate) fn clear(&mut self, loop_handle: &LoopHandle<State>) {
         self.window_rules.clear();
         self.connector_saved_states.clear();
+        self.focus_new_windows = true;
+        self.unfullscreen_on_focus_loss = false;
+        self.default_output_tags.clear();
+        self.urgency_timeout = None;
+        self.primary_output = None;
+        self.inactive_output_dim = 0.0;
+        self.last_window_fallback.clear();
+        self.open_next_on_focused_tag = false;
         if let Some(join_handle) = self.config_join_handle.take() {
             join_handle.abort();
         }
@@ -287,10 +327,15 @@ impl Pinnacle {
 
         self.input_state.clear();
 
+        self.idle_state.clear(&self.loop_handle);
+
         self.config.clear(&self.loop_handle);
 
         self.signal_state.clear();
 
+        self.layout_state.tiling_paused = false;
+        self.layout_state.reported_tag_layouts.clear();
+
         let config_dir_clone = config_dir.as_ref().map(|dir| dir.as_ref().to_path_buf());
         let load_default_config = |pinnacle: &mut Pinnacle, reason: &str| {
             match &config_dir_clone {
@@ -1,6 +1,11 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 
-use smithay::{output::Output, utils::SERIAL_COUNTER};
+use std::time::Duration;
+
+use smithay::{
+    output::Output,
+    utils::{IsAlive, SERIAL_COUNTER},
+};
 use tracing::warn;
 
 use crate::{
@@ -13,12 +18,50 @@ pub mod pointer;
 
 impl State {
     /// Update the keyboard focus.
+    ///
+    /// If the previously focused window loses focus and is fullscreen, it leaves fullscreen
+    /// when the config or its window rules ask for that.
     pub fn update_focus(&mut self, output: &Output) {
         let current_focus = self.pinnacle.focused_window(output);
 
+        if let Some(previous_focus) = self
+            .pinnacle
+            .focus_history
+            .last()
+            .filter(|win| Some(*win) != current_focus.as_ref())
+            .cloned()
+        {
+            self.unfullscreen_on_focus_loss(&previous_focus);
+        }
+
         if let Some(win) = &current_focus {
             assert!(!win.is_x11_override_redirect());
 
+            let newly_focused = self.pinnacle.focus_history.last() != Some(win);
+            let now = Duration::from(self.pinnacle.clock.now());
+
+            win.with_state_mut(|state| {
+                state.urgent = None;
+                if newly_focused {
+                    state.last_focused = Some(now);
+                }
+            });
+
+            let windows = &self.pinnacle.windows;
+            self.pinnacle
+                .focus_history
+                .retain(|w| w != win && windows.contains(w));
+            self.pinnacle.focus_history.push(win.clone());
+            output.with_state_mut(|state| {
+                state
+                    .focus_history
+                    .retain(|w| w != win && windows.contains(w));
+                state.focus_history.push(win.clone());
+            });
+
+            // Gaining focus takes the activated state back from pinned windows
+            self.pinnacle.activate_focused_window(win, newly_focused);
+
             if let Some(toplevel) = win.toplevel() {
                 toplevel.send_configure();
             }
@@ -34,9 +77,108 @@ impl State {
                 SERIAL_COUNTER.next_serial(),
             );
     }
+
+    /// Take `window` out of fullscreen if it should leave fullscreen because it lost focus.
+    fn unfullscreen_on_focus_loss(&mut self, window: &WindowElement) {
+        if !window.alive()
+            || !window.with_state(|state| state.fullscreen_or_maximized.is_fullscreen())
+            || !self.pinnacle.should_unfullscreen_on_focus_loss(window)
+        {
+            return;
+        }
+
+        window.toggle_fullscreen();
+
+        if let Some(output) = window.output(&self.pinnacle) {
+            self.pinnacle.request_layout(&output);
+            self.schedule_render(&output);
+        }
+    }
+
+    /// Focus and raise `window`.
+    ///
+    /// If the window isn't on an active tag, this switches its output to the window's first tag.
+    /// If it's a hidden floating window, floating windows are shown again on its active tags.
+    /// If it's minimized, it's restored.
+    pub fn activate_window(&mut self, window: &WindowElement) {
+        if window.is_x11_override_redirect() {
+            return;
+        }
+
+        let Some(output) = window.output(&self.pinnacle) else {
+            return;
+        };
+
+        self.pinnacle.set_window_minimized(window, false);
+
+        if window.is_floating_hidden() {
+            window.with_state(|state| {
+                for tag in state.tags.iter().filter(|tag| tag.active()) {
+                    tag.set_floating_hidden(false);
+                }
+            });
+        } else if !window.is_on_active_tag() {
+            if let Some(tag) = window.with_state(|state| state.tags.first().cloned()) {
+                output.with_state_mut(|op_state| {
+                    let previous_active = op_state.focused_tags().cloned().collect::<Vec<_>>();
+                    for op_tag in op_state.tags.iter_mut() {
+                        op_tag.set_active(false, &mut self.pinnacle);
+                    }
+                    tag.set_active(true, &mut self.pinnacle);
+                    op_state.set_previous_active_tags(previous_active);
+                });
+
+                self.pinnacle.fixup_xwayland_window_layering();
+                self.pinnacle.request_layout(&output);
+            }
+        }
+
+        self.focus_window(window, true);
+        self.schedule_render(&output);
+    }
+
+    /// Give `window` keyboard focus, raising it if `raise` is true.
+    ///
+    /// Unlike [`State::activate_window`], this doesn't switch tags, and the stacking order
+    /// is left untouched when not raising.
+    pub fn focus_window(&mut self, window: &WindowElement, raise: bool) {
+        let Some(output) = window.output(&self.pinnacle) else {
+            return;
+        };
+
+        output.with_state_mut(|state| state.focus_stack.set_focus(window.clone()));
+        self.pinnacle.output_focus_stack.set_focus(output.clone());
+        if raise {
+            self.pinnacle.raise_window(window.clone(), true);
+        }
+        self.update_focus(&output);
+    }
 }
 
 impl Pinnacle {
+    /// Get the most recently focused window on the focused output that isn't the currently
+    /// focused one.
+    ///
+    /// This uses the focus history of the focused output, so windows focused on other
+    /// outputs aren't picked. Closed windows, minimized windows, and windows that skip
+    /// cycling are skipped.
+    pub fn previously_focused_window(&self) -> Option<WindowElement> {
+        let output = self.focused_output()?;
+        let current_focus = self.focused_window(output);
+
+        output.with_state(|state| {
+            state
+                .focus_history
+                .iter()
+                .rev()
+                .filter(|win| Some(*win) != current_focus.as_ref())
+                .filter(|win| !win.with_state(|state| state.skip_cycle))
+                .filter(|win| !win.is_minimized())
+                .find(|win| self.windows.contains(win))
+                .cloned()
+        })
+    }
+
     /// Get the currently focused window on `output`.
     ///
     /// This returns the topmost window on the keyboard focus stack that is on an active tag.
@@ -83,6 +225,25 @@ impl Pinnacle {
         self.fixup_xwayland_window_layering();
     }
 
+    /// Lower a window to the bottom of the z-index stack.
+    ///
+    /// The window is still drawn above background and bottom layer surfaces.
+    ///
+    /// This does nothing if the window is unmapped.
+    pub fn lower_window(&mut self, window: WindowElement) {
+        if self.space.elements().all(|win| win != &window) {
+            warn!("Tried to lower an unmapped window");
+            return;
+        }
+
+        self.z_index_stack.retain(|win| win != &window);
+        self.z_index_stack.insert(0, window);
+
+        // The space can only raise elements, so raise everything else above the window
+        self.fixup_z_layering();
+        self.fixup_xwayland_window_layering();
+    }
+
     /// Get the currently focused output, or the first mapped output if there is none, or None.
     pub fn focused_output(&self) -> Option<&Output> {
         self.output_focus_stack
@@ -95,14 +256,45 @@ impl Pinnacle {
 #[derive(Debug, Clone, Default)]
 pub struct OutputFocusStack {
     stack: Vec<Output>,
+    /// An output that focus is locked to. While set, no other output can be focused.
+    locked: Option<Output>,
 }
 
 impl OutputFocusStack {
     // Set the new focused output.
+    //
+    // This does nothing if focus is locked to a different output.
     pub fn set_focus(&mut self, output: Output) {
+        if self.locked.as_ref().is_some_and(|locked| locked != &output) {
+            return;
+        }
+
         self.stack.retain(|op| op != &output);
         self.stack.push(output);
     }
+
+    /// Lock focus to `output`, focusing it, or unlock focus if `None`.
+    pub fn set_locked(&mut self, output: Option<Output>) {
+        self.locked = None;
+
+        if let Some(output) = output {
+            self.set_focus(output.clone());
+            self.locked = Some(output);
+        }
+    }
+
+    /// Get the output focus is locked to, if any.
+    pub fn locked(&self) -> Option<&Output> {
+        self.locked.as_ref()
+    }
+
+    /// Remove `output` from this stack, unlocking focus if it was locked to it.
+    pub fn remove(&mut self, output: &Output) {
+        self.stack.retain(|op| op != output);
+        if self.locked.as_ref() == Some(output) {
+            self.locked = None;
+        }
+    }
 }
 
 /// A stack of windows, with the top one being the one in focus.
@@ -123,6 +315,14 @@ impl WindowKeyboardFocusStack {
         self.focused = true;
     }
 
+    /// Add `window` to the bottom of this stack without focusing it.
+    ///
+    /// If it's already in the stack, it will be moved to the bottom.
+    pub fn add_unfocused(&mut self, window: WindowElement) {
+        self.stack.retain(|win| win != &window);
+        self.stack.insert(0, window);
+    }
+
     /// Unset the focus by marking this stack as unfocused.
     ///
     /// This will cause [`Self::current_focus`] to return `None`.
@@ -3,14 +3,86 @@
 pub mod move_grab;
 pub mod resize_grab;
 
+use std::time::Duration;
+
 use smithay::{
     input::pointer::{GrabStartData, PointerHandle},
     reexports::wayland_server::{protocol::wl_surface::WlSurface, Resource},
-    utils::Serial,
+    utils::{Serial, SERIAL_COUNTER},
     wayland::seat::WaylandFocus,
 };
 
-use crate::state::State;
+use crate::{
+    state::{Pinnacle, State},
+    window::WindowElement,
+};
+
+/// What an interactive grab is doing to its window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrabKind {
+    Move,
+    Resize,
+}
+
+/// An interactive move or resize that is in progress.
+#[derive(Debug, Clone)]
+pub struct ActiveGrab {
+    pub kind: GrabKind,
+    /// The window being moved or resized
+    pub window: WindowElement,
+    /// Whether the grab is being canceled, in which case the window should be
+    /// put back where it was when the grab started
+    pub canceled: bool,
+}
+
+impl Pinnacle {
+    /// Take the active grab if it's a `kind` grab on `window`.
+    ///
+    /// Grabs call this when they end to find out if they were canceled.
+    pub fn take_active_grab(
+        &mut self,
+        kind: GrabKind,
+        window: &WindowElement,
+    ) -> Option<ActiveGrab> {
+        if self
+            .active_grab
+            .as_ref()
+            .is_some_and(|grab| grab.kind == kind && &grab.window == window)
+        {
+            self.active_grab.take()
+        } else {
+            None
+        }
+    }
+}
+
+impl State {
+    /// Cancel the active move or resize grab, putting its window back where it started.
+    ///
+    /// Tiled windows that swapped places with others while being moved stay where they are.
+    ///
+    /// Returns whether there was a grab to cancel.
+    pub fn cancel_grab(&mut self) -> bool {
+        let Some(grab) = self.pinnacle.active_grab.as_mut() else {
+            return false;
+        };
+        grab.canceled = true;
+
+        if let Some(pointer) = self.pinnacle.seat.get_pointer() {
+            pointer.unset_grab(
+                self,
+                SERIAL_COUNTER.next_serial(),
+                Duration::from(self.pinnacle.clock.now()).as_millis() as u32,
+                true,
+            );
+        }
+
+        // Unsetting the grab takes it, but clear it in case it was already gone
+        self.pinnacle.active_grab = None;
+
+        true
+    }
+}
 
 /// Returns the [GrabStartData] from a pointer grab, if any.
 pub fn pointer_grab_start_data(
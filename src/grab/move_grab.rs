@@ -19,6 +19,7 @@ use smithay::{
 use tracing::{debug, warn};
 
 use crate::{
+    grab::{ActiveGrab, GrabKind},
     state::{State, WithState},
     window::{window_state::FloatingOrTiled, WindowElement},
 };
@@ -28,7 +29,119 @@ pub struct MoveSurfaceGrab {
     pub start_data: GrabStartData<State>,
     /// The window being moved
     pub window: WindowElement,
-    pub initial_window_loc: Point<i32, Logical>,
+    /// Where the window was when the grab started, keeping a floating window's
+    /// fractional location
+    pub initial_window_loc: Point<f64, Logical>,
+    /// Whether the window gets tiled when dropped over a tiled window
+    pub tile_on_drop: bool,
+    /// Whether the window was tiled and got floated when the grab started
+    pub floated_from_tiled: bool,
+}
+
+impl MoveSurfaceGrab {
+    /// Put the window back where it was when the grab started.
+    ///
+    /// Tiled windows that swapped places with other windows are left where they are.
+    fn restore(&self, state: &mut State) {
+        if !self.window.alive() {
+            return;
+        }
+
+        let is_floating = self
+            .window
+            .with_state(|state| state.floating_or_tiled.is_floating());
+
+        if is_floating {
+            let size = self.window.geometry().size;
+            self.window.with_state_mut(|state| {
+                state.floating_or_tiled = FloatingOrTiled::Floating(Rectangle::from_loc_and_size(
+                    self.initial_window_loc,
+                    size.to_f64(),
+                ));
+            });
+
+            let initial_window_loc = self.initial_window_loc.to_i32_round();
+            state
+                .pinnacle
+                .space
+                .map_element(self.window.clone(), initial_window_loc, false);
+
+            if let Some(surface) = self.window.x11_surface() {
+                if !surface.is_override_redirect() {
+                    let geo = surface.geometry();
+                    let new_geo = Rectangle::from_loc_and_size(initial_window_loc, geo.size);
+                    surface
+                        .configure(new_geo)
+                        .expect("failed to configure x11 win");
+                }
+            }
+
+            if self.floated_from_tiled {
+                self.window.toggle_floating();
+            }
+        }
+
+        if let Some(output) = self.window.output(&state.pinnacle) {
+            state.pinnacle.request_layout(&output);
+            state.schedule_render(&output);
+        }
+    }
+
+    /// Tile the moved window at the position of the tiled window under `location`.
+    ///
+    /// Nothing happens if the moved window isn't floating or there's no tiled window
+    /// on the same output under `location`.
+    fn drop_onto_tile(&self, state: &mut State, location: Point<f64, Logical>) {
+        if !self.window.alive()
+            || !self
+                .window
+                .with_state(|state| state.floating_or_tiled.is_floating())
+        {
+            return;
+        }
+
+        let Some(output) = self.window.output(&state.pinnacle) else {
+            return;
+        };
+
+        let window_under = state
+            .pinnacle
+            .space
+            .elements()
+            .rev()
+            .filter(|&win| win != &self.window)
+            .filter(|win| {
+                win.with_state(|state| {
+                    state.floating_or_tiled.is_tiled() && state.fullscreen_or_maximized.is_neither()
+                })
+            })
+            .find(|&win| {
+                state
+                    .pinnacle
+                    .space
+                    .element_location(win)
+                    .is_some_and(|loc| {
+                        Rectangle::from_loc_and_size(loc, win.geometry().size)
+                            .contains(location.to_i32_round())
+                    })
+            })
+            .cloned();
+
+        let Some(window_under) = window_under else {
+            return;
+        };
+
+        if window_under.output(&state.pinnacle).as_ref() != Some(&output) {
+            return;
+        }
+
+        debug!("Tiling dropped window");
+        self.window.toggle_floating();
+        state
+            .pinnacle
+            .move_window_to_position_of(&self.window, &window_under);
+        state.schedule_render(&output);
+    }
 }
 
 impl PointerGrab<State> for MoveSurfaceGrab {
@@ -125,7 +238,9 @@ impl PointerGrab<State> for MoveSurfaceGrab {
             }
         } else {
             let delta = event.location - self.start_data.location;
-            let new_loc = (self.initial_window_loc.to_f64() + delta).to_i32_round();
+            // Keep the fractional location so floating windows can land on any physical pixel
+            let new_loc_f64 = self.initial_window_loc + delta;
+            let new_loc = new_loc_f64.to_i32_round();
             state
                 .pinnacle
                 .space
@@ -140,8 +255,9 @@ impl PointerGrab<State> for MoveSurfaceGrab {
 
             self.window.with_state_mut(|state| {
                 if state.floating_or_tiled.is_floating() {
-                    state.floating_or_tiled =
-                        FloatingOrTiled::Floating(Rectangle::from_loc_and_size(new_loc, size));
+                    state.floating_or_tiled = FloatingOrTiled::Floating(
+                        Rectangle::from_loc_and_size(new_loc_f64, size.to_f64()),
+                    );
                 }
             });
 
@@ -181,6 +297,9 @@ impl PointerGrab<State> for MoveSurfaceGrab {
         handle.button(data, event);
 
         if !handle.current_pressed().contains(&self.start_data.button) {
+            if self.tile_on_drop {
+                self.drop_onto_tile(data, handle.current_location());
+            }
             handle.unset_grab(self, data, event.serial, event.time, true);
         }
     }
@@ -198,7 +317,16 @@ impl PointerGrab<State> for MoveSurfaceGrab {
         &self.start_data
     }
 
-    fn unset(&mut self, _data: &mut State) {}
+    fn unset(&mut self, data: &mut State) {
+        if let Some(ActiveGrab { canceled: true, .. }) =
+            data.pinnacle.take_active_grab(GrabKind::Move, &self.window)
+        {
+            self.restore(data);
+        } else {
+            self.window
+                .with_state_mut(|state| state.relative_geometry = None);
+        }
+    }
 
     fn gesture_swipe_begin(
         &mut self,
@@ -283,7 +411,11 @@ impl State {
                 return;
             };
 
-            let initial_window_loc = self
+            if window.is_geometry_pinned() {
+                return;
+            }
+
+            let space_loc = self
                 .pinnacle
                 .space
                 .element_location(&window)
@@ -291,23 +423,35 @@ impl State {
 
             let grab = MoveSurfaceGrab {
                 start_data,
-                window,
-                initial_window_loc,
+                window: window.clone(),
+                initial_window_loc: window.precise_loc(space_loc),
+                tile_on_drop: false,
+                floated_from_tiled: false,
             };
 
             pointer.set_grab(self, grab, serial, Focus::Clear);
+            self.pinnacle.active_grab = Some(ActiveGrab {
+                kind: GrabKind::Move,
+                window,
+                canceled: false,
+            });
         } else {
             debug!("No grab start data for grab, cancelling");
         }
     }
 
     /// The compositor initiated a move grab e.g. you hold the mod key and drag.
+    ///
+    /// If `float_tiled` is true, a tiled window is made floating at its current geometry
+    /// so it follows the pointer instead of swapping places with other tiled windows,
+    /// and dropping the window over a tiled window tiles it at that window's position.
     pub fn move_request_server(
         &mut self,
         surface: &WlSurface,
         seat: &Seat<State>,
         serial: Serial,
         button_used: u32,
+        float_tiled: bool,
     ) {
         let pointer = seat.get_pointer().expect("seat had no pointer");
         let Some(window) = self.pinnacle.window_for_surface(surface) else {
@@ -315,26 +459,55 @@ impl State {
             return;
         };
 
-        let initial_window_loc = self
-            .pinnacle
-            .space
-            .element_location(&window)
-            .expect("move request was called on an unmapped window");
+        if window.is_geometry_pinned() {
+            return;
+        }
+
+        let Some(space_loc) = self.pinnacle.space.element_location(&window) else {
+            warn!("Window is not mapped, cancelling move request");
+            return;
+        };
+
+        let floated_from_tiled = float_tiled
+            && window.with_state(|state| {
+                state.floating_or_tiled.is_tiled() && state.fullscreen_or_maximized.is_neither()
+            });
+
+        if floated_from_tiled {
+            let current_geo = Rectangle::from_loc_and_size(space_loc, window.geometry().size);
+
+            // Floating a tiled window restores its stored floating geometry,
+            // so store the current one to keep it in place
+            window.with_state_mut(|state| {
+                state.floating_or_tiled = FloatingOrTiled::Tiled(Some(current_geo.to_f64()));
+            });
+            window.toggle_floating();
+
+            if let Some(output) = window.output(&self.pinnacle) {
+                self.pinnacle.request_layout(&output);
+                self.schedule_render(&output);
+            }
+        }
 
         let start_data = smithay::input::pointer::GrabStartData {
-            focus: pointer
-                .current_focus()
-                .map(|focus| (focus, initial_window_loc)),
+            focus: pointer.current_focus().map(|focus| (focus, space_loc)),
             button: button_used,
             location: pointer.current_location(),
         };
 
         let grab = MoveSurfaceGrab {
             start_data,
-            window,
-            initial_window_loc,
+            window: window.clone(),
+            initial_window_loc: window.precise_loc(space_loc),
+            tile_on_drop: float_tiled,
+            floated_from_tiled,
         };
 
         pointer.set_grab(self, grab, serial, Focus::Clear);
+        self.pinnacle.active_grab = Some(ActiveGrab {
+            kind: GrabKind::Move,
+            window,
+            canceled: false,
+        });
     }
 }
@@ -21,6 +21,7 @@ use smithay::{
 };
 
 use crate::{
+    grab::{ActiveGrab, GrabKind},
     state::{Pinnacle, State, WithState},
     window::{window_state::FloatingOrTiled, WindowElement},
 };
@@ -252,7 +253,26 @@ impl PointerGrab<State> for ResizeSurfaceGrab {
         &self.start_data
     }
 
-    fn unset(&mut self, _data: &mut State) {
+    fn unset(&mut self, data: &mut State) {
+        if let Some(ActiveGrab { canceled: true, .. }) = data
+            .pinnacle
+            .take_active_grab(GrabKind::Resize, &self.window)
+        {
+            // Ungrabbing sends the last size, so sending the initial one puts the window back
+            self.last_window_size = self.initial_window_rect.size;
+
+            if let Some(surface) = self.window.x11_surface() {
+                if !surface.is_override_redirect() && self.window.alive() {
+                    surface
+                        .configure(self.initial_window_rect)
+                        .expect("failed to configure x11 win");
+                }
+            }
+        } else {
+            self.window
+                .with_state_mut(|state| state.relative_geometry = None);
+        }
+
         self.ungrab();
     }
 
@@ -371,6 +391,7 @@ impl Pinnacle {
         let Some(mut window_loc) = self.space.element_location(&window) else {
             return;
         };
+        let space_loc = window_loc;
         let geometry = window.geometry();
 
         let new_loc: Option<(Option<i32>, Option<i32>)> = surface.with_state_mut(|state| {
@@ -422,10 +443,21 @@ impl Pinnacle {
             .expect("called element_geometry on unmapped window")
             .size;
 
+        // Keep the fractional part of a coordinate the resize didn't move
+        let mut floating_loc = window.precise_loc(space_loc);
+        if let Some(new_x) = new_loc.0 {
+            floating_loc.x = f64::from(new_x);
+        }
+        if let Some(new_y) = new_loc.1 {
+            floating_loc.y = f64::from(new_y);
+        }
+
         window.with_state_mut(|state| {
             if state.floating_or_tiled.is_floating() {
-                state.floating_or_tiled =
-                    FloatingOrTiled::Floating(Rectangle::from_loc_and_size(window_loc, size));
+                state.floating_or_tiled = FloatingOrTiled::Floating(Rectangle::from_loc_and_size(
+                    floating_loc,
+                    size.to_f64(),
+                ));
             }
         });
 
@@ -464,7 +496,9 @@ impl State {
             };
 
             // TODO: check for fullscreen/maximized (probably shouldn't matter)
-            if window.with_state(|state| state.floating_or_tiled.is_tiled()) {
+            if window.with_state(|state| state.floating_or_tiled.is_tiled())
+                || window.is_geometry_pinned()
+            {
                 return;
             }
 
@@ -487,7 +521,7 @@ impl State {
 
             let grab = ResizeSurfaceGrab::start(
                 start_data,
-                window,
+                window.clone(),
                 edges,
                 Rectangle::from_loc_and_size(initial_window_loc, initial_window_size),
                 button_used,
@@ -495,6 +529,11 @@ impl State {
 
             if let Some(grab) = grab {
                 pointer.set_grab(self, grab, serial, Focus::Clear);
+                self.pinnacle.active_grab = Some(ActiveGrab {
+                    kind: GrabKind::Resize,
+                    window,
+                    canceled: false,
+                });
             }
         }
     }
@@ -515,15 +554,16 @@ impl State {
             return;
         };
 
-        if window.with_state(|state| state.floating_or_tiled.is_tiled()) {
+        if window.with_state(|state| state.floating_or_tiled.is_tiled())
+            || window.is_geometry_pinned()
+        {
             return;
         }
 
-        let initial_window_loc = self
-            .pinnacle
-            .space
-            .element_location(&window)
-            .expect("resize request called on unmapped window");
+        let Some(initial_window_loc) = self.pinnacle.space.element_location(&window) else {
+            tracing::error!("Window is not mapped, cancelling resize request");
+            return;
+        };
         let initial_window_size = window.geometry().size;
 
         if let Some(window) = self.pinnacle.window_for_surface(surface) {
@@ -546,7 +586,7 @@ impl State {
 
         let grab = ResizeSurfaceGrab::start(
             start_data,
-            window,
+            window.clone(),
             edges,
             Rectangle::from_loc_and_size(initial_window_loc, initial_window_size),
             button_used,
@@ -554,6 +594,11 @@ impl State {
 
         if let Some(grab) = grab {
             pointer.set_grab(self, grab, serial, Focus::Clear);
+            self.pinnacle.active_grab = Some(ActiveGrab {
+                kind: GrabKind::Resize,
+                window,
+                canceled: false,
+            });
         }
     }
 }
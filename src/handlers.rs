@@ -7,9 +7,10 @@ use std::{mem, os::fd::OwnedFd, time::Duration};
 
 use smithay::{
     backend::renderer::utils::{self, with_renderer_surface_state},
-    delegate_compositor, delegate_data_control, delegate_data_device, delegate_fractional_scale,
-    delegate_layer_shell, delegate_output, delegate_presentation, delegate_primary_selection,
-    delegate_relative_pointer, delegate_seat, delegate_shm, delegate_viewporter,
+    delegate_compositor, delegate_content_type, delegate_data_control, delegate_data_device,
+    delegate_fractional_scale, delegate_idle_inhibit, delegate_layer_shell, delegate_output,
+    delegate_presentation, delegate_primary_selection, delegate_relative_pointer, delegate_seat,
+    delegate_shm, delegate_viewporter, delegate_xdg_activation,
     desktop::{
         self, find_popup_root_surface, get_popup_toplevel_coords, layer_map_for_output,
         utils::surface_primary_scanout_output, PopupKind, WindowSurfaceType,
@@ -36,12 +37,13 @@ use smithay::{
         },
         dmabuf,
         fractional_scale::{self, FractionalScaleHandler},
+        idle_inhibit::IdleInhibitHandler,
         output::OutputHandler,
         seat::WaylandFocus,
         selection::{
             data_device::{
-                set_data_device_focus, ClientDndGrabHandler, DataDeviceHandler, DataDeviceState,
-                ServerDndGrabHandler,
+                self, set_data_device_focus, ClientDndGrabHandler, DataDeviceHandler,
+                DataDeviceState, ServerDndGrabHandler,
             },
             primary_selection::{
                 set_primary_focus, PrimarySelectionHandler, PrimarySelectionState,
@@ -54,10 +56,13 @@ use smithay::{
             xdg::{PopupSurface, XdgPopupSurfaceData, XdgToplevelSurfaceData},
         },
         shm::{ShmHandler, ShmState},
+        xdg_activation::{
+            XdgActivationHandler, XdgActivationState, XdgActivationToken, XdgActivationTokenData,
+        },
     },
     xwayland::{X11Wm, XWaylandClientData},
 };
-use tracing::{error, trace, warn};
+use tracing::{debug, error, trace, warn};
 
 use crate::{
     backend::Backend,
@@ -133,7 +138,9 @@ impl CompositorHandler for State {
             if let Some(window) = self.pinnacle.window_for_surface(&root) {
                 window.on_commit();
                 if let Some(loc) = window.with_state_mut(|state| state.target_loc.take()) {
-                    self.pinnacle.space.map_element(window.clone(), loc, false);
+                    if !window.is_minimized() {
+                        self.pinnacle.space.map_element(window.clone(), loc, false);
+                    }
                 }
             }
         };
@@ -160,7 +167,6 @@ impl CompositorHandler for State {
                 if let Some(output) = self.pinnacle.focused_output() {
                     tracing::debug!("Placing toplevel");
                     new_window.place_on_output(output);
-                    output.with_state_mut(|state| state.focus_stack.set_focus(new_window.clone()));
                 }
 
                 // FIXME: I'm mapping way offscreen here then sending a frame to prevent a window from
@@ -168,11 +174,25 @@ impl CompositorHandler for State {
                 // |      because I don't set a target geometry before the initial configure.
                 self.pinnacle
                     .space
-                    .map_element(new_window.clone(), (1000000, 0), true);
-
-                self.pinnacle.raise_window(new_window.clone(), true);
+                    .map_element(new_window.clone(), (1000000, 0), false);
 
                 self.pinnacle.apply_window_rules(&new_window);
+                self.pinnacle.float_if_tiling_paused(&new_window);
+
+                if !self.pinnacle.apply_tag_window_limits(&new_window) {
+                    return;
+                }
+
+                let focus = self.pinnacle.should_focus_new_window(&new_window);
+
+                if let Some(output) = self.pinnacle.focused_output() {
+                    output.with_state_mut(|state| match focus {
+                        true => state.focus_stack.set_focus(new_window.clone()),
+                        false => state.focus_stack.add_unfocused(new_window.clone()),
+                    });
+                }
+
+                self.pinnacle.raise_window(new_window.clone(), focus);
 
                 if let Some(focused_output) = self.pinnacle.focused_output().cloned() {
                     self.pinnacle.request_layout(&focused_output);
@@ -184,18 +204,20 @@ impl CompositorHandler for State {
                     );
                 }
 
-                self.pinnacle.loop_handle.insert_idle(move |state| {
-                    state
-                        .pinnacle
-                        .seat
-                        .get_keyboard()
-                        .expect("Seat had no keyboard") // FIXME: actually handle error
-                        .set_focus(
-                            state,
-                            Some(KeyboardFocusTarget::Window(new_window)),
-                            SERIAL_COUNTER.next_serial(),
-                        );
-                });
+                if focus {
+                    self.pinnacle.loop_handle.insert_idle(move |state| {
+                        state
+                            .pinnacle
+                            .seat
+                            .get_keyboard()
+                            .expect("Seat had no keyboard") // FIXME: actually handle error
+                            .set_focus(
+                                state,
+                                Some(KeyboardFocusTarget::Window(new_window)),
+                                SERIAL_COUNTER.next_serial(),
+                            );
+                    });
+                }
             } else if new_window.toplevel().is_some() {
                 new_window.on_commit();
                 self.pinnacle.ensure_initial_configure(surface);
@@ -319,7 +341,20 @@ impl Pinnacle {
             map.layer_for_surface(surface, WindowSurfaceType::TOPLEVEL)
                 .is_some()
         }) {
-            layer_map_for_output(output).arrange();
+            let output = output.clone();
+
+            let zone_changed = {
+                let mut map = layer_map_for_output(&output);
+                let old_zone = map.non_exclusive_zone();
+                map.arrange();
+                map.non_exclusive_zone() != old_zone
+            };
+
+            // Tiled windows and their outer gaps are placed relative to the exclusive zone,
+            // so they need to be laid out again when it changes
+            if zone_changed {
+                self.request_layout(&output);
+            }
 
             let initial_configure_sent = compositor::with_states(surface, |states| {
                 states
@@ -332,7 +367,7 @@ impl Pinnacle {
             });
 
             if !initial_configure_sent {
-                layer_map_for_output(output)
+                layer_map_for_output(&output)
                     .layer_for_surface(surface, WindowSurfaceType::TOPLEVEL)
                     .expect("no layer for surface")
                     .layer_surface()
@@ -345,15 +380,22 @@ impl Pinnacle {
 impl ClientDndGrabHandler for State {
     fn started(
         &mut self,
-        _source: Option<WlDataSource>,
+        source: Option<WlDataSource>,
         icon: Option<WlSurface>,
         _seat: Seat<Self>,
     ) {
         self.pinnacle.dnd_icon = icon;
+        self.pinnacle.dnd_mime_types = source
+            .and_then(|source| {
+                data_device::with_source_metadata(&source, |metadata| metadata.mime_types.clone())
+                    .ok()
+            })
+            .unwrap_or_default();
     }
 
     fn dropped(&mut self, _seat: Seat<Self>) {
         self.pinnacle.dnd_icon = None;
+        self.pinnacle.dnd_mime_types.clear();
     }
 }
 
@@ -368,8 +410,13 @@ impl SelectionHandler for State {
         source: Option<SelectionSource>,
         _seat: Seat<Self>,
     ) {
+        let mime_types = source.map(|source| source.mime_types());
+
+        self.pinnacle
+            .set_selection_mime_types(ty, mime_types.clone().unwrap_or_default());
+
         if let Some(xwm) = self.pinnacle.xwm.as_mut() {
-            if let Err(err) = xwm.new_selection(ty, source.map(|source| source.mime_types())) {
+            if let Err(err) = xwm.new_selection(ty, mime_types) {
                 tracing::warn!(?err, ?ty, "Failed to set Xwayland selection");
             }
         }
@@ -393,6 +440,16 @@ impl SelectionHandler for State {
     }
 }
 
+impl Pinnacle {
+    /// Record the mime types advertised by the current selection of the given type.
+    pub fn set_selection_mime_types(&mut self, ty: SelectionTarget, mime_types: Vec<String>) {
+        match ty {
+            SelectionTarget::Clipboard => self.clipboard_mime_types = mime_types,
+            SelectionTarget::Primary => self.primary_selection_mime_types = mime_types,
+        }
+    }
+}
+
 impl DataDeviceHandler for State {
     fn data_device_state(&self) -> &DataDeviceState {
         &self.pinnacle.data_device_state
@@ -452,6 +509,11 @@ delegate_output!(State);
 
 delegate_viewporter!(State);
 
+delegate_content_type!(State);
+
+/// Marks surfaces whose client asked for fractional scale updates.
+pub struct FractionalScaleSurface;
+
 impl FractionalScaleHandler for State {
     fn new_fractional_scale(&mut self, surface: WlSurface) {
         // comment yanked from anvil
@@ -472,6 +534,8 @@ impl FractionalScaleHandler for State {
         }
 
         compositor::with_states(&surface, |states| {
+            states.data_map.insert_if_missing(|| FractionalScaleSurface);
+
             let primary_scanout_output =
                 desktop::utils::surface_primary_scanout_output(&surface, states)
                     .or_else(|| {
@@ -530,6 +594,13 @@ impl WlrLayerShellHandler for State {
         let output = output
             .as_ref()
             .and_then(Output::from_resource)
+            .or_else(|| {
+                self.pinnacle
+                    .config
+                    .primary_output
+                    .as_ref()
+                    .and_then(|name| name.output(&self.pinnacle))
+            })
             .or_else(|| self.pinnacle.space.outputs().next().cloned());
 
         let Some(output) = output else {
@@ -626,17 +697,89 @@ impl GammaControlHandler for State {
     }
 
     fn gamma_control_destroyed(&mut self, output: &Output) {
-        let Backend::Udev(udev) = &mut self.backend else {
+        if !matches!(self.backend, Backend::Udev(_)) {
             warn!("Resetting gamma is not supported on the winit backend");
             return;
+        }
+
+        // Go back to the compositor's gamma instead of linear if there is a preset
+        self.apply_gamma_preset(output);
+    }
+}
+delegate_gamma_control!(State);
+
+impl IdleInhibitHandler for State {
+    fn inhibit(&mut self, surface: WlSurface) {
+        self.pinnacle.idle_state.inhibitors.insert(surface);
+    }
+
+    fn uninhibit(&mut self, surface: WlSurface) {
+        self.pinnacle.idle_state.inhibitors.remove(&surface);
+
+        // Idle time counts from when the last inhibitor goes away
+        if !self.pinnacle.idle_state.is_inhibited() {
+            self.pinnacle.idle_state.reset_idle_time();
+        }
+    }
+}
+delegate_idle_inhibit!(State);
+
+/// How long an activation token stays valid after it was created.
+const ACTIVATION_TOKEN_TIMEOUT: Duration = Duration::from_secs(10);
+
+impl XdgActivationHandler for State {
+    fn activation_state(&mut self) -> &mut XdgActivationState {
+        &mut self.pinnacle.xdg_activation_state
+    }
+
+    fn request_activation(
+        &mut self,
+        _token: XdgActivationToken,
+        token_data: XdgActivationTokenData,
+        surface: WlSurface,
+    ) {
+        let Some(window) = self.pinnacle.window_for_surface(&surface) else {
+            return;
         };
 
-        if let Err(err) = udev.set_gamma(output, None) {
-            warn!("Failed to set gamma for output {}: {err}", output.name());
+        // Windows exempt from focus stealing prevention can always activate themselves
+        let exempt = window.with_state(|state| state.focus_steal_exempt);
+
+        if !exempt && !self.pinnacle.activation_token_is_valid(&token_data) {
+            debug!("Denied activation request for window, marking it urgent");
+            self.pinnacle.set_urgent(&window);
+            return;
         }
+
+        self.activate_window(&window);
+    }
+}
+delegate_xdg_activation!(State);
+
+impl Pinnacle {
+    /// Returns whether an activation token is allowed to activate a window.
+    ///
+    /// To prevent focus stealing, a token is only valid if it was created recently with
+    /// an input serial from a client that hasn't lost keyboard focus since.
+    fn activation_token_is_valid(&self, token_data: &XdgActivationTokenData) -> bool {
+        if token_data.timestamp.elapsed() > ACTIVATION_TOKEN_TIMEOUT {
+            return false;
+        }
+
+        let Some((serial, seat)) = token_data.serial.as_ref() else {
+            return false;
+        };
+
+        if Seat::<State>::from_resource(seat).as_ref() != Some(&self.seat) {
+            return false;
+        }
+
+        self.seat
+            .get_keyboard()
+            .and_then(|keyboard| keyboard.last_enter())
+            .is_some_and(|last_enter| serial.is_no_older_than(&last_enter))
     }
 }
-delegate_gamma_control!(State);
 
 impl Pinnacle {
     fn position_popup(&self, popup: &PopupSurface) {
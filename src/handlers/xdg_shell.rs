@@ -26,7 +26,7 @@ use tracing::trace;
 use crate::{
     focus::keyboard::KeyboardFocusTarget,
     state::{State, WithState},
-    window::WindowElement,
+    window::{window_state::TiledEdges, WindowElement},
 };
 
 impl XdgShellHandler for State {
@@ -35,25 +35,36 @@ impl XdgShellHandler for State {
     }
 
     fn new_toplevel(&mut self, surface: ToplevelSurface) {
-        surface.with_pending_state(|state| {
-            state.states.set(xdg_toplevel::State::TiledTop);
-            state.states.set(xdg_toplevel::State::TiledBottom);
-            state.states.set(xdg_toplevel::State::TiledLeft);
-            state.states.set(xdg_toplevel::State::TiledRight);
-        });
-
         let window = WindowElement::new(Window::new_wayland_window(surface.clone()));
+        // New windows start out tiled. The first layout narrows down the edges,
+        // and floating the window clears them.
+        window.set_tiled_edges(TiledEdges::all());
         self.pinnacle.new_windows.push(window);
     }
 
     fn toplevel_destroyed(&mut self, surface: ToplevelSurface) {
         tracing::debug!("toplevel destroyed");
+        let destroyed = self
+            .pinnacle
+            .windows
+            .iter()
+            .find(|window| {
+                window
+                    .wl_surface()
+                    .is_some_and(|surf| &surf == surface.wl_surface())
+            })
+            .cloned();
+
         self.pinnacle.windows.retain(|window| {
             window
                 .wl_surface()
                 .is_some_and(|surf| &surf != surface.wl_surface())
         });
 
+        if let Some(destroyed) = destroyed {
+            self.pinnacle.spawn_last_window_fallback(&destroyed);
+        }
+
         self.pinnacle.z_index_stack.retain(|window| {
             window
                 .wl_surface()
@@ -329,11 +340,17 @@ impl XdgShellHandler for State {
         self.pinnacle.request_layout(&output);
     }
 
-    fn minimize_request(&mut self, _surface: ToplevelSurface) {
-        // TODO:
-        // if let Some(window) = self.window_for_surface(surface.wl_surface()) {
-        //     self.space.unmap_elem(&window);
-        // }
+    fn minimize_request(&mut self, surface: ToplevelSurface) {
+        let Some(window) = self.pinnacle.window_for_surface(surface.wl_surface()) else {
+            return;
+        };
+
+        self.pinnacle.set_window_minimized(&window, true);
+
+        if let Some(output) = window.output(&self.pinnacle) {
+            self.update_focus(&output);
+            self.schedule_render(&output);
+        }
     }
 
     // TODO: impl the rest of the fns in XdgShellHandler
@@ -83,7 +83,7 @@ impl XwmHandler for State {
             unreachable!()
         };
 
-        self.pinnacle.space.map_element(window.clone(), loc, true);
+        self.pinnacle.space.map_element(window.clone(), loc, false);
         surface.set_mapped(true).expect("failed to map x11 window");
 
         let bbox = Rectangle::from_loc_and_size(loc, bbox.size);
@@ -100,33 +100,46 @@ impl XwmHandler for State {
 
         if should_float(surface) {
             window.with_state_mut(|state| {
-                state.floating_or_tiled = FloatingOrTiled::Floating(bbox);
+                state.floating_or_tiled = FloatingOrTiled::Floating(bbox.to_f64());
             });
         }
 
         // TODO: will an unmap -> map duplicate the window
         self.pinnacle.windows.push(window.clone());
-        self.pinnacle.raise_window(window.clone(), true);
 
         self.pinnacle.apply_window_rules(&window);
+        self.pinnacle.float_if_tiling_paused(&window);
+
+        if !self.pinnacle.apply_tag_window_limits(&window) {
+            return;
+        }
+
+        let focus = self.pinnacle.should_focus_new_window(&window);
+
+        self.pinnacle.raise_window(window.clone(), focus);
 
         if let Some(output) = window.output(&self.pinnacle) {
-            output.with_state_mut(|state| state.focus_stack.set_focus(window.clone()));
+            output.with_state_mut(|state| match focus {
+                true => state.focus_stack.set_focus(window.clone()),
+                false => state.focus_stack.add_unfocused(window.clone()),
+            });
             self.pinnacle.request_layout(&output);
         }
 
-        self.pinnacle.loop_handle.insert_idle(move |state| {
-            state
-                .pinnacle
-                .seat
-                .get_keyboard()
-                .expect("Seat had no keyboard") // FIXME: actually handle error
-                .set_focus(
-                    state,
-                    Some(KeyboardFocusTarget::Window(window)),
-                    SERIAL_COUNTER.next_serial(),
-                );
-        });
+        if focus {
+            self.pinnacle.loop_handle.insert_idle(move |state| {
+                state
+                    .pinnacle
+                    .seat
+                    .get_keyboard()
+                    .expect("Seat had no keyboard") // FIXME: actually handle error
+                    .set_focus(
+                        state,
+                        Some(KeyboardFocusTarget::Window(window)),
+                        SERIAL_COUNTER.next_serial(),
+                    );
+            });
+        }
     }
 
     fn mapped_override_redirect_window(&mut self, _xwm: XwmId, surface: X11Surface) {
@@ -185,6 +198,8 @@ impl XwmHandler for State {
             self.pinnacle
                 .windows
                 .retain(|elem| win.wl_surface() != elem.wl_surface());
+            // X11 windows are removed when they're withdrawn, which is how they close
+            self.pinnacle.spawn_last_window_fallback(&win);
             self.pinnacle
                 .z_index_stack
                 .retain(|elem| win.wl_surface() != elem.wl_surface());
@@ -254,6 +269,8 @@ impl XwmHandler for State {
                 .windows
                 .retain(|elem| win.wl_surface() != elem.wl_surface());
 
+            self.pinnacle.spawn_last_window_fallback(&win);
+
             self.pinnacle
                 .z_index_stack
                 .retain(|elem| win.wl_surface() != elem.wl_surface());
@@ -303,7 +320,8 @@ impl XwmHandler for State {
             .find(|win| win.x11_surface() == Some(&window))
             .map(|win| {
                 win.is_x11_override_redirect()
-                    || win.with_state(|state| state.floating_or_tiled.is_floating())
+                    || (win.with_state(|state| state.floating_or_tiled.is_floating())
+                        && !win.is_geometry_pinned())
             })
             .unwrap_or(false);
 
@@ -453,7 +471,7 @@ impl XwmHandler for State {
 
         // We use the server one and not the client because windows like Steam don't provide
         // GrabStartData, so we need to create it ourselves.
-        self.move_request_server(&wl_surf, &seat, SERIAL_COUNTER.next_serial(), button);
+        self.move_request_server(&wl_surf, &seat, SERIAL_COUNTER.next_serial(), button, false);
     }
 
     fn allow_selection_access(&mut self, xwm: XwmId, _selection: SelectionTarget) -> bool {
@@ -503,6 +521,9 @@ impl XwmHandler for State {
     }
 
     fn new_selection(&mut self, _xwm: XwmId, selection: SelectionTarget, mime_types: Vec<String>) {
+        self.pinnacle
+            .set_selection_mime_types(selection, mime_types.clone());
+
         match selection {
             SelectionTarget::Clipboard => {
                 set_data_device_selection(
@@ -524,6 +545,9 @@ impl XwmHandler for State {
     }
 
     fn cleared_selection(&mut self, _xwm: XwmId, selection: SelectionTarget) {
+        self.pinnacle
+            .set_selection_mime_types(selection, Vec::new());
+
         match selection {
             SelectionTarget::Clipboard => {
                 if current_data_device_selection_userdata(&self.pinnacle.seat).is_some() {
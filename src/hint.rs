@@ -0,0 +1,220 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Window hints for picking a window with the keyboard.
+//!
+//! While hints are shown, every visible window has a letter drawn over it.
+//! Typing a letter picks its window, and Escape cancels.
+
+use smithay::{
+    backend::{allocator::Fourcc, renderer::element::memory::MemoryRenderBuffer},
+    desktop::space::SpaceElement,
+    utils::{IsAlive, Logical, Point, Size, Transform},
+};
+use tokio::sync::oneshot;
+use xkbcommon::xkb::Keysym;
+
+use crate::{
+    state::{State, WithState},
+    window::{window_state::WindowId, WindowElement},
+};
+
+/// Labels in the order they're handed out, home row first.
+const LABELS: &str = "asdfghjklqwertyuiopzxcvbnm";
+
+/// How many pixels wide and tall each dot of a glyph is
+const DOT_SIZE: i32 = 4;
+
+/// The padding around a glyph, in dots
+const PADDING: i32 = 2;
+
+const GLYPH_WIDTH: i32 = 5;
+const GLYPH_HEIGHT: i32 = 7;
+
+/// The background of a label, in little-endian ARGB
+const BACKGROUND: [u8; 4] = [0x2e, 0x1e, 0x1e, 0xff];
+/// The color of a label's letter, in little-endian ARGB
+const FOREGROUND: [u8; 4] = [0xaf, 0xe2, 0xf9, 0xff];
+
+/// 5x7 bitmaps for the letters A through Z. Each row's five lowest bits are its dots.
+#[rustfmt::skip]
+const FONT: [[u8; GLYPH_HEIGHT as usize]; 26] = [
+    [0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001], // A
+    [0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110], // B
+    [0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110], // C
+    [0b11110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b11110], // D
+    [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111], // E
+    [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000], // F
+    [0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111], // G
+    [0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001], // H
+    [0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110], // I
+    [0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100], // J
+    [0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001], // K
+    [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111], // L
+    [0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001], // M
+    [0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001], // N
+    [0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110], // O
+    [0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000], // P
+    [0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101], // Q
+    [0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001], // R
+    [0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110], // S
+    [0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100], // T
+    [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110], // U
+    [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100], // V
+    [0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010], // W
+    [0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001], // X
+    [0b10001, 0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100], // Y
+    [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111], // Z
+];
+
+/// A letter drawn over a window.
+#[derive(Debug)]
+pub struct WindowHint {
+    /// The key that picks this window
+    pub label: char,
+    pub window: WindowElement,
+    /// The rendered label
+    pub buffer: MemoryRenderBuffer,
+}
+
+impl WindowHint {
+    fn new(label: char, window: WindowElement) -> Self {
+        let glyph = FONT[(label as u8 - b'a') as usize];
+
+        let width = (GLYPH_WIDTH + PADDING * 2) * DOT_SIZE;
+        let height = (GLYPH_HEIGHT + PADDING * 2) * DOT_SIZE;
+
+        let mut pixels = Vec::with_capacity((width * height * 4) as usize);
+        for y in 0..height {
+            for x in 0..width {
+                let dot_x = x / DOT_SIZE - PADDING;
+                let dot_y = y / DOT_SIZE - PADDING;
+
+                let lit = (0..GLYPH_WIDTH).contains(&dot_x)
+                    && (0..GLYPH_HEIGHT).contains(&dot_y)
+                    && glyph[dot_y as usize] & (1 << (GLYPH_WIDTH - 1 - dot_x)) != 0;
+
+                pixels.extend(if lit { FOREGROUND } else { BACKGROUND });
+            }
+        }
+
+        let buffer = MemoryRenderBuffer::from_slice(
+            &pixels,
+            Fourcc::Argb8888,
+            (width, height),
+            1,
+            Transform::Normal,
+            None,
+        );
+
+        Self {
+            label,
+            window,
+            buffer,
+        }
+    }
+
+    /// The logical size of this hint's label.
+    pub fn size() -> Size<i32, Logical> {
+        (
+            (GLYPH_WIDTH + PADDING * 2) * DOT_SIZE,
+            (GLYPH_HEIGHT + PADDING * 2) * DOT_SIZE,
+        )
+            .into()
+    }
+
+    /// Get where this hint's label is drawn, centered over its window at `window_loc`.
+    pub fn location(&self, window_loc: Point<i32, Logical>) -> Point<i32, Logical> {
+        let center = window_loc + self.window.geometry().size.downscale(2).to_point();
+        center - Self::size().downscale(2).to_point()
+    }
+}
+
+/// Hints that are waiting for a window to be picked.
+#[derive(Debug)]
+pub struct WindowHints {
+    pub hints: Vec<WindowHint>,
+    /// Sends the picked window to whoever asked for hints
+    sender: oneshot::Sender<Option<WindowId>>,
+}
+
+impl State {
+    /// Draw hints over all visible windows and wait for one to be picked.
+    ///
+    /// The picked window, or `None` if hints were canceled, is sent through `sender`.
+    /// If hints were already being shown, they're canceled first.
+    pub fn show_window_hints(&mut self, sender: oneshot::Sender<Option<WindowId>>) {
+        self.finish_window_hints(None);
+
+        let mut windows = self
+            .pinnacle
+            .space
+            .elements()
+            .filter(|win| win.is_on_active_tag() && !win.is_x11_override_redirect())
+            .filter_map(|win| {
+                self.pinnacle
+                    .space
+                    .element_location(win)
+                    .map(|loc| (win.clone(), loc))
+            })
+            .collect::<Vec<_>>();
+
+        // Hand out labels in reading order
+        windows.sort_by_key(|(_, loc)| (loc.y, loc.x));
+
+        let hints = windows
+            .into_iter()
+            .zip(LABELS.chars())
+            .map(|((win, _), label)| WindowHint::new(label, win))
+            .collect();
+
+        self.pinnacle.window_hints = Some(WindowHints { hints, sender });
+
+        for output in self.pinnacle.space.outputs().cloned().collect::<Vec<_>>() {
+            self.schedule_render(&output);
+        }
+    }
+
+    /// Pick the window whose label was typed, or cancel hints if Escape was pressed.
+    ///
+    /// Other keys are ignored.
+    pub fn select_window_hint(&mut self, keysym: Keysym) {
+        let Some(hints) = self.pinnacle.window_hints.as_ref() else {
+            return;
+        };
+
+        if keysym == Keysym::Escape {
+            self.finish_window_hints(None);
+            return;
+        }
+
+        let Some(key_char) = keysym.key_char().map(|c| c.to_ascii_lowercase()) else {
+            return;
+        };
+
+        let window = hints
+            .hints
+            .iter()
+            .find(|hint| hint.label == key_char && hint.window.alive())
+            .map(|hint| hint.window.clone());
+
+        if let Some(window) = window {
+            self.finish_window_hints(Some(window));
+        }
+    }
+
+    /// Stop showing hints and send `window` to whoever asked for them.
+    fn finish_window_hints(&mut self, window: Option<WindowElement>) {
+        let Some(hints) = self.pinnacle.window_hints.take() else {
+            return;
+        };
+
+        // The config may have gone away while waiting
+        let _ = hints
+            .sender
+            .send(window.map(|win| win.with_state(|state| state.id)));
+
+        for output in self.pinnacle.space.outputs().cloned().collect::<Vec<_>>() {
+            self.schedule_render(&output);
+        }
+    }
+}
@@ -0,0 +1,203 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Idle detection for running config actions after a period of inactivity.
+
+use std::{
+    collections::HashSet,
+    time::{Duration, Instant},
+};
+
+use pinnacle_api_defs::pinnacle::input::v0alpha1::SetIdleActionResponse;
+use smithay::{
+    reexports::{
+        calloop::{
+            timer::{TimeoutAction, Timer},
+            LoopHandle, RegistrationToken,
+        },
+        wayland_server::protocol::wl_surface::WlSurface,
+    },
+    utils::IsAlive,
+};
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::warn;
+
+use crate::state::{Pinnacle, State};
+
+/// An action the config runs after some time without input.
+struct IdleStage {
+    /// How long there must be no input for before the stage becomes idle
+    timeout: Duration,
+    /// Notifies the config when the stage becomes idle and when it resumes
+    sender: UnboundedSender<Result<SetIdleActionResponse, tonic::Status>>,
+    /// Whether the timeout has passed since the last input
+    idle: bool,
+}
+
+pub struct IdleState {
+    stages: Vec<IdleStage>,
+    /// When the last input event happened
+    last_activity: Instant,
+    /// The timer that makes stages idle, if one is running
+    timer_token: Option<RegistrationToken>,
+    /// Surfaces that are inhibiting idle through `zwp_idle_inhibit_manager_v1`
+    pub inhibitors: HashSet<WlSurface>,
+}
+
+impl Default for IdleState {
+    fn default() -> Self {
+        Self {
+            stages: Vec::new(),
+            last_activity: Instant::now(),
+            timer_token: None,
+            inhibitors: HashSet::new(),
+        }
+    }
+}
+
+impl std::fmt::Debug for IdleState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IdleState")
+            .field(
+                "stages",
+                &self
+                    .stages
+                    .iter()
+                    .map(|stage| (stage.timeout, stage.idle))
+                    .collect::<Vec<_>>(),
+            )
+            .field("last_activity", &self.last_activity)
+            .field("timer_token", &self.timer_token)
+            .field("inhibitors", &self.inhibitors)
+            .finish()
+    }
+}
+
+impl IdleState {
+    /// Remove all idle stages.
+    pub fn clear(&mut self, loop_handle: &LoopHandle<State>) {
+        self.stages.clear();
+        if let Some(token) = self.timer_token.take() {
+            loop_handle.remove(token);
+        }
+    }
+
+    /// Start counting idle time from now.
+    pub fn reset_idle_time(&mut self) {
+        self.last_activity = Instant::now();
+    }
+
+    /// Returns whether any surface is inhibiting idle.
+    pub fn is_inhibited(&mut self) -> bool {
+        self.inhibitors.retain(|surface| surface.alive());
+        !self.inhibitors.is_empty()
+    }
+
+    /// The next time a stage becomes idle if there's no more input.
+    fn next_deadline(&self) -> Option<Instant> {
+        self.stages
+            .iter()
+            .filter(|stage| !stage.idle)
+            .map(|stage| self.last_activity + stage.timeout)
+            .min()
+    }
+}
+
+impl Pinnacle {
+    /// Add an idle stage that notifies `sender` after `timeout` without input,
+    /// then again when input resumes.
+    pub fn add_idle_stage(
+        &mut self,
+        timeout: Duration,
+        sender: UnboundedSender<Result<SetIdleActionResponse, tonic::Status>>,
+    ) {
+        self.idle_state.stages.push(IdleStage {
+            timeout,
+            sender,
+            idle: false,
+        });
+
+        self.schedule_idle_timer();
+    }
+
+    /// Record that an input event happened, resuming any idle stages.
+    pub fn notify_activity(&mut self) {
+        self.idle_state.last_activity = Instant::now();
+
+        let mut resumed = false;
+        for stage in self.idle_state.stages.iter_mut().filter(|stage| stage.idle) {
+            stage.idle = false;
+            resumed = true;
+            if let Err(err) = stage
+                .sender
+                .send(Ok(SetIdleActionResponse { idle: Some(false) }))
+            {
+                warn!("Failed to send idle resume to config: {err}");
+            }
+        }
+
+        if resumed {
+            self.schedule_idle_timer();
+        }
+    }
+
+    /// Restart the idle timer at the next deadline, if a stage can still become idle.
+    ///
+    /// The deadline changes when stages are added or resume, so any running timer is replaced.
+    fn schedule_idle_timer(&mut self) {
+        if let Some(token) = self.idle_state.timer_token.take() {
+            self.loop_handle.remove(token);
+        }
+
+        let Some(deadline) = self.idle_state.next_deadline() else {
+            return;
+        };
+
+        let token = self
+            .loop_handle
+            .insert_source(Timer::from_deadline(deadline), |_, _, state| {
+                state.pinnacle.on_idle_timer()
+            })
+            .expect("failed to insert idle timer");
+
+        self.idle_state.timer_token = Some(token);
+    }
+
+    /// Make stages whose timeouts have passed idle and figure out when to check again.
+    fn on_idle_timer(&mut self) -> TimeoutAction {
+        let now = Instant::now();
+
+        // Inhibited time doesn't count towards the timeouts
+        if self.idle_state.is_inhibited() {
+            self.idle_state.last_activity = now;
+        }
+
+        let elapsed = now.saturating_duration_since(self.idle_state.last_activity);
+
+        self.idle_state
+            .stages
+            .retain(|stage| !stage.sender.is_closed());
+
+        for stage in self
+            .idle_state
+            .stages
+            .iter_mut()
+            .filter(|stage| !stage.idle && stage.timeout <= elapsed)
+        {
+            stage.idle = true;
+            if let Err(err) = stage
+                .sender
+                .send(Ok(SetIdleActionResponse { idle: Some(true) }))
+            {
+                warn!("Failed to send idle notification to config: {err}");
+            }
+        }
+
+        match self.idle_state.next_deadline() {
+            Some(deadline) => TimeoutAction::ToInstant(deadline),
+            None => {
+                self.idle_state.timer_token = None;
+                TimeoutAction::Drop
+            }
+        }
+    }
+}
@@ -2,7 +2,11 @@
 
 pub mod libinput;
 
-use std::{collections::HashMap, mem::Discriminant, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    mem::Discriminant,
+    time::{Duration, Instant},
+};
 
 use crate::{
     focus::{keyboard::KeyboardFocusTarget, pointer::PointerFocusTarget},
@@ -23,8 +27,9 @@ use smithay::{
         keyboard::{keysyms, FilterResult, ModifiersState},
         pointer::{AxisFrame, ButtonEvent, MotionEvent, RelativeMotionEvent},
     },
+    output::Output,
     reexports::input::{self, Led},
-    utils::{IsAlive, Logical, Point, SERIAL_COUNTER},
+    utils::{IsAlive, Logical, Point, Rectangle, Serial, SERIAL_COUNTER},
     wayland::{
         compositor,
         shell::wlr_layer::{self, KeyboardInteractivity, LayerSurfaceCachedState},
@@ -32,10 +37,17 @@ use smithay::{
 };
 use tokio::sync::mpsc::UnboundedSender;
 use tracing::info;
-use xkbcommon::xkb::Keysym;
+use xkbcommon::xkb::{self, Keycode, Keysym};
 
 use crate::state::State;
 
+/// The client that set a keybind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct KeybindOwner {
+    /// The pid of the process connected to the gRPC socket, if it could be found
+    pub pid: Option<i32>,
+}
+
 bitflags::bitflags! {
     #[derive(Debug, Hash, Copy, Clone, PartialEq, Eq)]
     pub struct ModifierMask: u8 {
@@ -84,6 +96,63 @@ impl From<&ModifiersState> for ModifierMask {
     }
 }
 
+impl ModifierMask {
+    /// Replace each modifier in this mask that has a remap with the modifier it's remapped to.
+    ///
+    /// Remaps are applied all at once, so two modifiers can be swapped.
+    pub fn remapped(self, remaps: &[(ModifierMask, ModifierMask)]) -> Self {
+        self.iter().fold(ModifierMask::empty(), |acc, modifier| {
+            let to = remaps
+                .iter()
+                .find_map(|(from, to)| (*from == modifier).then_some(*to))
+                .unwrap_or(modifier);
+            acc | to
+        })
+    }
+}
+
+/// How close together two clicks must be to count as a double click by default.
+const DEFAULT_DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+/// How far the pointer must move with a button held to count as a drag by default,
+/// in logical pixels.
+const DEFAULT_DRAG_THRESHOLD: f64 = 8.0;
+
+/// Settings for detecting clicks, double clicks, and drags for mousebinds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClickSettings {
+    /// The maximum time between two clicks for them to count as a double click
+    pub double_click_interval: Duration,
+    /// The distance the pointer must move with a button held for it to count as a drag
+    pub drag_threshold: f64,
+    /// Whether clicking a window focuses it when the button is released instead of pressed.
+    ///
+    /// Releases that end a drag or a move or resize grab don't change focus.
+    pub focus_on_release: bool,
+}
+
+impl Default for ClickSettings {
+    fn default() -> Self {
+        Self {
+            double_click_interval: DEFAULT_DOUBLE_CLICK_INTERVAL,
+            drag_threshold: DEFAULT_DRAG_THRESHOLD,
+            focus_on_release: false,
+        }
+    }
+}
+
+/// A mouse button that is currently held down.
+#[derive(Debug, Clone, Copy)]
+struct ButtonPress {
+    /// The modifiers that were held when the button was pressed
+    mods: ModifierMask,
+    /// Where the button was pressed
+    loc: Point<f64, Logical>,
+    /// Whether the pointer has moved far enough for this press to be a drag
+    dragged: bool,
+    /// Whether this press completed a double click
+    double_clicked: bool,
+}
+
 #[derive(Default)]
 pub struct InputState {
     pub reload_keybind: Option<(ModifierMask, Keysym)>,
@@ -93,12 +162,46 @@ pub struct InputState {
 
     pub keybinds:
         HashMap<(ModifierMask, Keysym), UnboundedSender<Result<SetKeybindResponse, tonic::Status>>>,
+    /// Keybinds that only trigger while another key is held, keyed by
+    /// their modifiers, the held key, and the key that triggers them
+    pub held_keybinds: HashMap<
+        (ModifierMask, Keysym, Keysym),
+        UnboundedSender<Result<SetKeybindResponse, tonic::Status>>,
+    >,
+    /// Who set each keybind in `keybinds` and `held_keybinds`, keyed by
+    /// their modifiers, the held key if any, and the key that triggers them
+    pub keybind_owners: HashMap<(ModifierMask, Option<Keysym>, Keysym), KeybindOwner>,
+    /// Keybinds in `keybinds` and `held_keybinds` that still trigger while the focused window
+    /// has keybinds disabled, keyed the same way as `keybind_owners`
+    pub keybinds_allowed_when_disabled: HashSet<(ModifierMask, Option<Keysym>, Keysym)>,
     pub mousebinds: HashMap<
         (ModifierMask, u32, set_mousebind_request::MouseEdge),
         UnboundedSender<Result<SetMousebindResponse, tonic::Status>>,
     >,
     #[allow(clippy::type_complexity)]
     pub libinput_settings: HashMap<Discriminant<Setting>, Box<dyn Fn(&mut input::Device) + Send>>,
+    pub click_settings: ClickSettings,
+    /// Modifiers that are treated as other modifiers when matching key- and mousebinds
+    pub modifier_remaps: Vec<(ModifierMask, ModifierMask)>,
+
+    /// Mouse buttons that are currently held down
+    button_presses: HashMap<u32, ButtonPress>,
+    /// Mouse buttons whose press was used to pick a window in an overview,
+    /// so their release isn't sent to clients either
+    overview_button_presses: HashSet<u32>,
+    /// Keys pressed while window hints were shown, so their release isn't sent to clients either
+    pub hint_key_presses: HashSet<Keycode>,
+    /// Non-modifier keys that are currently held down and the keysyms they produced
+    held_keys: Vec<(Keycode, Vec<Keysym>)>,
+    /// A key that could start a held keybind and wasn't sent to clients when pressed,
+    /// along with whether a held keybind was triggered while it was held.
+    ///
+    /// If none was, the key is sent to clients when it's released so it can still be typed.
+    pub swallowed_held_key: Option<(Keycode, bool)>,
+    /// The modifiers, button, and time of the last click, used to detect double clicks
+    last_click: Option<(ModifierMask, u32, Instant)>,
+    /// The output the pointer is confined to, if any
+    pub pointer_confinement: Option<Output>,
 
     /// A keyboard focus target stack that is used when there are exclusive keyboard layer
     /// surfaces. When used, the first item is the previous focus before there were any
@@ -112,8 +215,15 @@ impl InputState {
         self.kill_keybind = None;
         self.libinput_devices.clear();
         self.keybinds.clear();
+        self.held_keybinds.clear();
+        self.keybind_owners.clear();
+        self.keybinds_allowed_when_disabled.clear();
         self.mousebinds.clear();
         self.libinput_settings.clear();
+        self.click_settings = ClickSettings::default();
+        self.modifier_remaps.clear();
+        self.last_click = None;
+        self.pointer_confinement = None;
     }
 }
 
@@ -124,8 +234,21 @@ impl std::fmt::Debug for InputState {
             .field("kill_keybind", &self.kill_keybind)
             .field("libinput_devices", &self.libinput_devices)
             .field("keybinds", &self.keybinds)
+            .field("held_keybinds", &self.held_keybinds)
+            .field("keybind_owners", &self.keybind_owners)
+            .field(
+                "keybinds_allowed_when_disabled",
+                &self.keybinds_allowed_when_disabled,
+            )
             .field("mousebinds", &self.mousebinds)
             .field("libinput_settings", &"...")
+            .field("click_settings", &self.click_settings)
+            .field("modifier_remaps", &self.modifier_remaps)
+            .field("button_presses", &self.button_presses)
+            .field("overview_button_presses", &self.overview_button_presses)
+            .field("hint_key_presses", &self.hint_key_presses)
+            .field("last_click", &self.last_click)
+            .field("pointer_confinement", &self.pointer_confinement)
             .finish()
     }
 }
@@ -136,16 +259,57 @@ impl InputState {
     }
 }
 
+/// Find the key that types `keysym` in the first layout of `keymap`.
+///
+/// Returns the key and whether it needs to be shifted.
+fn keycode_for_keysym(keymap: &xkb::Keymap, keysym: Keysym) -> Option<(Keycode, bool)> {
+    let mut found = None;
+
+    keymap.key_for_each(|keymap, keycode| {
+        if found.is_some() {
+            return;
+        }
+
+        // Only the base and shift levels are checked, other levels need modifiers
+        // that can't be pressed reliably across keymaps
+        for level in 0..keymap.num_levels_for_key(keycode, 0).min(2) {
+            if keymap
+                .key_get_syms_by_level(keycode, 0, level)
+                .contains(&keysym)
+            {
+                found = Some((keycode, level == 1));
+                return;
+            }
+        }
+    });
+
+    found
+}
+
 #[derive(Debug)]
 enum KeyAction {
     CallCallback(UnboundedSender<Result<SetKeybindResponse, tonic::Status>>),
     Quit,
     SwitchVt(i32),
     ReloadConfig,
+    SelectWindowHint(Keysym),
+    /// A key that starts a held keybind was pressed, or released after being used
+    Hold,
+    /// A key that could start a held keybind was typed on its own, so send it to clients
+    ReplayHeld(Keycode),
+    /// A key whose press was used by the compositor was released
+    Swallow,
 }
 
 impl State {
     pub fn process_input_event<B: InputBackend>(&mut self, event: InputEvent<B>) {
+        if !matches!(
+            event,
+            InputEvent::DeviceAdded { .. } | InputEvent::DeviceRemoved { .. }
+        ) {
+            self.pinnacle.notify_activity();
+        }
+
         match event {
             // TODO: rest of input events
 
@@ -179,6 +343,12 @@ impl State {
                 .contains(point.to_i32_round())
         })?;
 
+        // Windows in an overview don't receive pointer input, but layer surfaces still do
+        let overview_open = self
+            .pinnacle
+            .overview(output)
+            .is_some_and(|overview| overview.is_open());
+
         let output_geo = self
             .pinnacle
             .space
@@ -224,17 +394,49 @@ impl State {
 
         let window_under =
             |windows: &[&WindowElement]| -> Option<(PointerFocusTarget, Point<i32, Logical>)> {
+                if overview_open {
+                    return None;
+                }
+
                 windows.iter().find_map(|win| {
-                    let loc = self
+                    let factor = win.with_state(|state| state.scale);
+
+                    if factor == 1.0 {
+                        let loc = self
+                            .pinnacle
+                            .space
+                            .element_location(win)
+                            .expect("called elem loc on unmapped win")
+                            - win.geometry().loc;
+
+                        return win
+                            .surface_under(point - loc.to_f64(), WindowSurfaceType::ALL)
+                            .map(|(surf, surf_loc)| {
+                                (PointerFocusTarget::WlSurface(surf), surf_loc + loc)
+                            });
+                    }
+
+                    // Scaled windows are rendered scaled around the top left of their geometry,
+                    // so undo that to find the point in the window's own coordinates
+                    let origin = self
                         .pinnacle
                         .space
                         .element_location(win)
                         .expect("called elem loc on unmapped win")
-                        - win.geometry().loc;
-
-                    win.surface_under(point - loc.to_f64(), WindowSurfaceType::ALL)
+                        .to_f64()
+                        - win.geometry().loc.to_f64().upscale(factor);
+                    let local = (point - origin).downscale(factor);
+
+                    // The surface-local location sent to clients is the pointer location
+                    // minus the focus location. Offset the focus location so that lands on
+                    // `local` instead of the unscaled location.
+                    win.surface_under(local, WindowSurfaceType::ALL)
                         .map(|(surf, surf_loc)| {
-                            (PointerFocusTarget::WlSurface(surf), surf_loc + loc)
+                            let focus_loc = point - local + surf_loc.to_f64();
+                            (
+                                PointerFocusTarget::WlSurface(surf),
+                                focus_loc.to_i32_round(),
+                            )
                         })
                 })
             };
@@ -305,11 +507,6 @@ impl State {
 
     fn keyboard<I: InputBackend>(&mut self, event: I::KeyboardKeyEvent) {
         let serial = SERIAL_COUNTER.next_serial();
-        let time = event.time_msec();
-        let press_state = event.state();
-
-        let reload_keybind = self.pinnacle.input_state.reload_keybind;
-        let kill_keybind = self.pinnacle.input_state.kill_keybind;
 
         let keyboard = self
             .pinnacle
@@ -397,36 +594,166 @@ impl State {
             }
         }
 
+        self.handle_key(event.key_code(), event.state(), event.time_msec(), true);
+    }
+
+    /// Send a key to the keyboard, checking it against keybinds if `match_keybinds` is true.
+    pub fn handle_key(
+        &mut self,
+        keycode: Keycode,
+        press_state: KeyState,
+        time: u32,
+        match_keybinds: bool,
+    ) {
+        let serial = SERIAL_COUNTER.next_serial();
+
+        let reload_keybind = self.pinnacle.input_state.reload_keybind;
+        let kill_keybind = self.pinnacle.input_state.kill_keybind;
+
+        let keyboard = self
+            .pinnacle
+            .seat
+            .get_keyboard()
+            .expect("Seat has no keyboard");
+
+        // Windows can have keybinds disabled while they're focused so they receive all keys
+        let keybinds_disabled = keyboard.current_focus().is_some_and(|focus| {
+            matches!(
+                focus,
+                KeyboardFocusTarget::Window(win) if win.with_state(|state| state.keybinds_disabled)
+            )
+        });
+
         let action = keyboard.input(
             self,
-            event.key_code(),
+            keycode,
             press_state,
             serial,
             time,
             |state, modifiers, keysym| {
+                let keysyms = keysym
+                    .raw_syms()
+                    .iter()
+                    .copied()
+                    .chain([keysym.modified_sym()])
+                    .collect::<Vec<_>>();
+
+                let held_keys = &mut state.pinnacle.input_state.held_keys;
+                held_keys.retain(|(code, _)| *code != keycode);
+                if press_state == KeyState::Pressed && !keysym.modified_sym().is_modifier_key() {
+                    held_keys.push((keycode, keysyms.clone()));
+                }
+
+                // Window hints take all key presses until a window is picked,
+                // except for the kill and reload keybinds
+                if state.pinnacle.window_hints.is_some() && press_state == KeyState::Pressed {
+                    let mod_mask = ModifierMask::from(modifiers);
+                    let mod_sym = keysym.modified_sym();
+
+                    if match_keybinds && kill_keybind == Some((mod_mask, mod_sym)) {
+                        return FilterResult::Intercept(KeyAction::Quit);
+                    } else if match_keybinds && reload_keybind == Some((mod_mask, mod_sym)) {
+                        return FilterResult::Intercept(KeyAction::ReloadConfig);
+                    }
+
+                    state.pinnacle.input_state.hint_key_presses.insert(keycode);
+                    return FilterResult::Intercept(KeyAction::SelectWindowHint(mod_sym));
+                }
+
+                if press_state == KeyState::Released
+                    && state.pinnacle.input_state.hint_key_presses.remove(&keycode)
+                {
+                    return FilterResult::Intercept(KeyAction::Swallow);
+                }
+
+                if !match_keybinds {
+                    return FilterResult::Forward;
+                }
+
+                if press_state == KeyState::Released {
+                    let swallowed = &mut state.pinnacle.input_state.swallowed_held_key;
+                    if let Some((code, used)) = *swallowed {
+                        if code == keycode {
+                            swallowed.take();
+                            return FilterResult::Intercept(match used {
+                                true => KeyAction::Hold,
+                                false => KeyAction::ReplayHeld(keycode),
+                            });
+                        }
+                    }
+                }
+
                 if press_state == KeyState::Pressed {
                     let mod_mask = ModifierMask::from(modifiers);
+                    // The kill and reload keybinds use the real modifiers so a bad remap
+                    // can't lock you out of them
+                    let bind_mask = mod_mask.remapped(&state.pinnacle.input_state.modifier_remaps);
 
                     let raw_sym = keysym.raw_syms().iter().next();
                     let mod_sym = keysym.modified_sym();
 
-                    if let (Some(sender), _) | (None, Some(sender)) = (
-                        state
-                            .pinnacle
-                            .input_state
-                            .keybinds
-                            .get(&(mod_mask, mod_sym)),
-                        raw_sym.and_then(|raw_sym| {
-                            state
-                                .pinnacle
-                                .input_state
-                                .keybinds
-                                .get(&(mod_mask, *raw_sym))
-                        }),
-                    ) {
+                    let input_state = &state.pinnacle.input_state;
+
+                    // The kill and reload keybinds and keybinds that opted in
+                    // still work when keybinds are disabled
+                    let allowed = |held_sym: Option<Keysym>, sym: Keysym| {
+                        !keybinds_disabled
+                            || input_state
+                                .keybinds_allowed_when_disabled
+                                .contains(&(bind_mask, held_sym, sym))
+                    };
+
+                    let keybind = [Some(&mod_sym), raw_sym]
+                        .into_iter()
+                        .flatten()
+                        .filter(|sym| allowed(None, **sym))
+                        .find_map(|sym| input_state.keybinds.get(&(bind_mask, *sym)));
+
+                    let held_keybind = input_state
+                        .held_keys
+                        .iter()
+                        .filter(|(code, _)| *code != keycode)
+                        .flat_map(|(_, held_syms)| held_syms)
+                        .find_map(|held_sym| {
+                            keysyms
+                                .iter()
+                                .filter(|sym| allowed(Some(*held_sym), **sym))
+                                .find_map(|sym| {
+                                    input_state.held_keybinds.get(&(bind_mask, *held_sym, *sym))
+                                })
+                        });
+
+                    let starts_held_keybind =
+                        input_state
+                            .held_keybinds
+                            .keys()
+                            .any(|(mask, held_sym, sym)| {
+                                *mask == bind_mask
+                                    && keysyms.contains(held_sym)
+                                    && allowed(Some(*held_sym), *sym)
+                            });
+
+                    // Keybinds with a held key are more specific, so they win
+                    if let Some(sender) = held_keybind {
+                        let sender = sender.clone();
+                        if let Some((_, used)) = &mut state.pinnacle.input_state.swallowed_held_key
+                        {
+                            *used = true;
+                        }
+                        return FilterResult::Intercept(KeyAction::CallCallback(sender));
+                    }
+
+                    if let Some(sender) = keybind {
                         return FilterResult::Intercept(KeyAction::CallCallback(sender.clone()));
                     }
 
+                    // The held key acts like a modifier, so it doesn't reach the window
+                    // unless it's released without triggering a keybind
+                    if starts_held_keybind {
+                        state.pinnacle.input_state.swallowed_held_key = Some((keycode, false));
+                        return FilterResult::Intercept(KeyAction::Hold);
+                    }
+
                     if kill_keybind == Some((mod_mask, mod_sym)) {
                         return FilterResult::Intercept(KeyAction::Quit);
                     } else if reload_keybind == Some((mod_mask, mod_sym)) {
@@ -460,10 +787,61 @@ impl State {
                     .start_config(Some(self.pinnacle.config.dir(&self.pinnacle.xdg_base_dirs)))
                     .expect("failed to restart config");
             }
-            None => (),
+            Some(KeyAction::SelectWindowHint(keysym)) => {
+                self.select_window_hint(keysym);
+            }
+            Some(KeyAction::ReplayHeld(keycode)) => {
+                self.handle_key(keycode, KeyState::Pressed, time, false);
+                self.handle_key(keycode, KeyState::Released, time, false);
+            }
+            Some(KeyAction::Hold | KeyAction::Swallow) | None => (),
         }
     }
 
+    /// Press and release the key that types `keysym`, as if it were typed on a keyboard.
+    ///
+    /// The key goes to the focused surface. Shift is held down for keysyms that
+    /// need it, like uppercase letters. If `match_keybinds` is false, the key isn't
+    /// checked against keybinds, including the kill and reload keybinds.
+    ///
+    /// Returns false and does nothing if no key in the current keymap types `keysym`.
+    pub fn send_synthetic_key(&mut self, keysym: Keysym, match_keybinds: bool) -> bool {
+        let keyboard = self
+            .pinnacle
+            .seat
+            .get_keyboard()
+            .expect("Seat has no keyboard");
+
+        let (key, shift) = keyboard.with_xkb_state(self, |context| {
+            let xkb = context.xkb().lock().unwrap();
+            // SAFETY: The keymap is only read here and not kept around
+            let keymap = unsafe { xkb.keymap() };
+            (
+                keycode_for_keysym(keymap, keysym),
+                keycode_for_keysym(keymap, Keysym::Shift_L),
+            )
+        });
+
+        let Some((keycode, needs_shift)) = key else {
+            return false;
+        };
+
+        let time = Duration::from(self.pinnacle.clock.now()).as_millis() as u32;
+
+        let shift = needs_shift.then_some(shift).flatten();
+
+        if let Some((shift, _)) = shift {
+            self.handle_key(shift, KeyState::Pressed, time, match_keybinds);
+        }
+        self.handle_key(keycode, KeyState::Pressed, time, match_keybinds);
+        self.handle_key(keycode, KeyState::Released, time, match_keybinds);
+        if let Some((shift, _)) = shift {
+            self.handle_key(shift, KeyState::Released, time, match_keybinds);
+        }
+
+        true
+    }
+
     fn pointer_button<I: InputBackend>(&mut self, event: I::PointerButtonEvent) {
         let pointer = self
             .pinnacle
@@ -484,69 +862,133 @@ impl State {
 
         let pointer_loc = pointer.current_location();
 
-        let mod_mask = ModifierMask::from(keyboard.modifier_state());
+        let mod_mask = ModifierMask::from(keyboard.modifier_state())
+            .remapped(&self.pinnacle.input_state.modifier_remaps);
 
         let mouse_edge = match button_state {
             ButtonState::Released => set_mousebind_request::MouseEdge::Release,
             ButtonState::Pressed => set_mousebind_request::MouseEdge::Press,
         };
 
-        if let Some(stream) = self
-            .pinnacle
-            .input_state
-            .mousebinds
-            .get(&(mod_mask, button, mouse_edge))
-        {
-            let _ = stream.send(Ok(SetMousebindResponse {}));
-            return;
-        }
-
-        // If the button was clicked, focus on the window below if exists, else
-        // unfocus on windows.
-        if button_state == ButtonState::Pressed {
-            if let Some((focus, _)) = self.pointer_focus_target_under(pointer_loc) {
-                // NOTE: *Do not* set keyboard focus to an override redirect window. This leads
-                // |     to wonky things like right-click menus not correctly getting pointer
-                // |     clicks or showing up at all.
-
-                // TODO: use update_keyboard_focus from anvil
+        match button_state {
+            ButtonState::Pressed => {
+                // Clicks on layer surfaces, like panels, still go through
+                let overview_output = self
+                    .pinnacle
+                    .space
+                    .output_under(pointer_loc)
+                    .next()
+                    .filter(|op| {
+                        self.pinnacle
+                            .overview(op)
+                            .is_some_and(|overview| overview.is_open())
+                    })
+                    .filter(|_| self.pointer_focus_target_under(pointer_loc).is_none())
+                    .cloned();
 
-                if let Some(window) = focus.window_for(self) {
-                    self.pinnacle.raise_window(window.clone(), true);
-                    if let Some(output) = window.output(&self.pinnacle) {
-                        output.with_state_mut(|state| state.focus_stack.set_focus(window.clone()));
-                    }
+                if let Some(output) = overview_output {
+                    self.select_overview_window(&output, pointer_loc);
+                    self.pinnacle
+                        .input_state
+                        .overview_button_presses
+                        .insert(button);
+                    return;
                 }
-
-                if !matches!(
-                    focus.window_for(self),
-                    Some(window) if window.is_x11_override_redirect()
-                ) && focus.popup_for(self).is_none()
+            }
+            ButtonState::Released => {
+                if self
+                    .pinnacle
+                    .input_state
+                    .overview_button_presses
+                    .remove(&button)
                 {
-                    keyboard.set_focus(self, focus.to_keyboard_focus_target(self), serial);
+                    return;
                 }
+            }
+        }
 
-                for window in self.pinnacle.space.elements() {
-                    if let Some(toplevel) = window.toplevel() {
-                        toplevel.send_configure();
-                    }
+        // Click, double click, and drag mousebinds consume both the press and release
+        // of their button so clients don't see half of the gesture.
+        let mut consumed = false;
+        let mut dragged = false;
+
+        match button_state {
+            ButtonState::Pressed => {
+                let double_click_interval = self
+                    .pinnacle
+                    .input_state
+                    .click_settings
+                    .double_click_interval;
+                let double_clicked =
+                    self.pinnacle
+                        .input_state
+                        .last_click
+                        .take()
+                        .is_some_and(|(mods, btn, time)| {
+                            mods == mod_mask
+                                && btn == button
+                                && time.elapsed() <= double_click_interval
+                        });
+
+                self.pinnacle.input_state.button_presses.insert(
+                    button,
+                    ButtonPress {
+                        mods: mod_mask,
+                        loc: pointer_loc,
+                        dragged: false,
+                        double_clicked,
+                    },
+                );
+
+                if double_clicked {
+                    self.send_mousebind(
+                        mod_mask,
+                        button,
+                        set_mousebind_request::MouseEdge::DoubleClick,
+                    );
                 }
-            } else {
-                if let Some(focused_op) = self.pinnacle.focused_output() {
-                    focused_op.with_state_mut(|state| {
-                        state.focus_stack.unset_focus();
-                        for window in state.focus_stack.stack.iter() {
-                            window.set_activate(false);
-                            if let Some(toplevel) = window.toplevel() {
-                                toplevel.send_configure();
-                            }
+
+                consumed = self.has_gesture_mousebind(mod_mask, button);
+            }
+            ButtonState::Released => {
+                if let Some(press) = self.pinnacle.input_state.button_presses.remove(&button) {
+                    dragged = press.dragged;
+                    if !press.dragged {
+                        self.send_mousebind(
+                            press.mods,
+                            button,
+                            set_mousebind_request::MouseEdge::Click,
+                        );
+
+                        // Don't let the click that finished a double click start another one
+                        if !press.double_clicked {
+                            self.pinnacle.input_state.last_click =
+                                Some((press.mods, button, Instant::now()));
                         }
-                    });
+                    }
+
+                    consumed = self.has_gesture_mousebind(press.mods, button);
                 }
-                keyboard.set_focus(self, None, serial);
+            }
+        }
+
+        if self.send_mousebind(mod_mask, button, mouse_edge) || consumed {
+            return;
+        }
+
+        let focus_on_release = self.pinnacle.input_state.click_settings.focus_on_release;
+        let should_focus = match button_state {
+            ButtonState::Pressed => !focus_on_release,
+            // Don't focus after dragging something out of a window or moving one around
+            ButtonState::Released => {
+                focus_on_release && !dragged && self.pinnacle.active_grab.is_none()
             }
         };
 
+        if should_focus {
+            self.click_to_focus(pointer_loc, serial);
+        }
+
         pointer.button(
             self,
             &ButtonEvent {
@@ -559,6 +1001,131 @@ impl State {
         pointer.frame(self);
     }
 
+    /// Focus the window under `pointer_loc` as if it was clicked,
+    /// or unfocus windows if there is none.
+    pub fn click_to_focus(&mut self, pointer_loc: Point<f64, Logical>, serial: Serial) {
+        let keyboard = self
+            .pinnacle
+            .seat
+            .get_keyboard()
+            .expect("Seat has no keyboard");
+
+        if let Some((focus, _)) = self.pointer_focus_target_under(pointer_loc) {
+            // NOTE: *Do not* set keyboard focus to an override redirect window. This leads
+            // |     to wonky things like right-click menus not correctly getting pointer
+            // |     clicks or showing up at all.
+
+            // TODO: use update_keyboard_focus from anvil
+
+            if let Some(window) = focus.window_for(self) {
+                self.pinnacle.raise_window(window.clone(), true);
+                if let Some(output) = window.output(&self.pinnacle) {
+                    output.with_state_mut(|state| state.focus_stack.set_focus(window.clone()));
+                }
+            }
+
+            let window_output = focus
+                .window_for(self)
+                .and_then(|window| window.output(&self.pinnacle));
+
+            if !matches!(
+                focus.window_for(self),
+                Some(window) if window.is_x11_override_redirect()
+            ) && focus.popup_for(self).is_none()
+            {
+                match window_output {
+                    // Go through the usual focus path so the previously focused window
+                    // can react to losing focus, like leaving fullscreen
+                    Some(output) => self.update_focus(&output),
+                    None => keyboard.set_focus(self, focus.to_keyboard_focus_target(self), serial),
+                }
+            }
+
+            for window in self.pinnacle.space.elements() {
+                if let Some(toplevel) = window.toplevel() {
+                    toplevel.send_configure();
+                }
+            }
+        } else {
+            if let Some(focused_op) = self.pinnacle.focused_output().cloned() {
+                focused_op.with_state_mut(|state| {
+                    state.focus_stack.unset_focus();
+                    for window in state.focus_stack.stack.iter() {
+                        if window.with_state(|state| state.activation_pinned) {
+                            continue;
+                        }
+                        window.set_activate(false);
+                        if let Some(toplevel) = window.toplevel() {
+                            toplevel.send_configure();
+                        }
+                    }
+                });
+                self.update_focus(&focused_op);
+            } else {
+                keyboard.set_focus(self, None, serial);
+            }
+        }
+    }
+
+    /// Notify the config of the mousebind for `mods`, `button` and `edge`, if there is one.
+    ///
+    /// Returns whether there was such a mousebind.
+    fn send_mousebind(
+        &self,
+        mods: ModifierMask,
+        button: u32,
+        edge: set_mousebind_request::MouseEdge,
+    ) -> bool {
+        match self
+            .pinnacle
+            .input_state
+            .mousebinds
+            .get(&(mods, button, edge))
+        {
+            Some(stream) => {
+                let _ = stream.send(Ok(SetMousebindResponse {}));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns whether there is a click, double click, or drag mousebind for `mods` and `button`.
+    fn has_gesture_mousebind(&self, mods: ModifierMask, button: u32) -> bool {
+        [
+            set_mousebind_request::MouseEdge::Click,
+            set_mousebind_request::MouseEdge::DoubleClick,
+            set_mousebind_request::MouseEdge::Drag,
+        ]
+        .into_iter()
+        .any(|edge| {
+            self.pinnacle
+                .input_state
+                .mousebinds
+                .contains_key(&(mods, button, edge))
+        })
+    }
+
+    /// Mark held buttons as dragging if the pointer moved far enough from where they were
+    /// pressed, notifying the config of any drag mousebinds.
+    fn detect_mouse_drags(&mut self, pointer_loc: Point<f64, Logical>) {
+        let threshold = self.pinnacle.input_state.click_settings.drag_threshold;
+
+        let mut dragged = Vec::new();
+
+        for (button, press) in self.pinnacle.input_state.button_presses.iter_mut() {
+            let delta = pointer_loc - press.loc;
+            if !press.dragged && (delta.x * delta.x + delta.y * delta.y).sqrt() >= threshold {
+                press.dragged = true;
+                dragged.push((press.mods, *button));
+            }
+        }
+
+        for (mods, button) in dragged {
+            self.send_mousebind(mods, button, set_mousebind_request::MouseEdge::Drag);
+        }
+    }
+
     fn pointer_axis<I: InputBackend>(&mut self, event: I::PointerAxisEvent) {
         let source = event.source();
 
@@ -603,9 +1170,50 @@ impl State {
         pointer.frame(self);
     }
 
+    /// Confine the pointer to `output`, or let it move freely across outputs if `None`.
+    ///
+    /// If the pointer is currently outside of `output`, it is moved to the nearest point inside it.
+    pub fn set_pointer_confinement(&mut self, output: Option<Output>) {
+        self.pinnacle.input_state.pointer_confinement = output;
+
+        let Some(pointer) = self.pinnacle.seat.get_pointer() else {
+            return;
+        };
+
+        let current_loc = pointer.current_location();
+        let pointer_loc = self.clamp_coords(current_loc);
+
+        if pointer_loc == current_loc {
+            return;
+        }
+
+        if let Some(output) = self.pinnacle.input_state.pointer_confinement.clone() {
+            self.pinnacle.output_focus_stack.set_focus(output);
+        }
+
+        let pointer_focus = self.pointer_focus_target_under(pointer_loc);
+
+        pointer.motion(
+            self,
+            pointer_focus,
+            &MotionEvent {
+                location: pointer_loc,
+                serial: SERIAL_COUNTER.next_serial(),
+                time: Duration::from(self.pinnacle.clock.now()).as_millis() as u32,
+            },
+        );
+
+        pointer.frame(self);
+
+        if let Some(output) = self.pinnacle.focused_output().cloned() {
+            self.schedule_render(&output);
+        }
+    }
+
     /// Clamp pointer coordinates inside outputs.
     ///
     /// This returns the nearest point inside an output.
+    /// If the pointer is confined to an output, this returns the nearest point inside it.
     fn clamp_coords(&self, pos: Point<f64, Logical>) -> Point<f64, Logical> {
         if self.pinnacle.space.outputs().next().is_none() {
             return pos;
@@ -613,27 +1221,41 @@ impl State {
 
         let (pos_x, pos_y) = pos.into();
 
+        let confinement_geo = self
+            .pinnacle
+            .input_state
+            .pointer_confinement
+            .as_ref()
+            .and_then(|op| self.pinnacle.space.output_geometry(op));
+
+        if let Some(geo) = confinement_geo {
+            return clamp_inside(pos, geo);
+        }
+
+        // Motion within or across outputs is left alone so it stays continuous at shared edges
+        if self.pinnacle.space.output_under(pos).next().is_some() {
+            return pos;
+        }
+
+        // Clamp into each output separately so the pointer follows the logical arrangement
+        // even when neighboring outputs have different sizes or scales
         let nearest_points = self.pinnacle.space.outputs().map(|op| {
-            let size = self
+            let geo = self
                 .pinnacle
                 .space
                 .output_geometry(op)
-                .expect("called output_geometry on unmapped output")
-                .size;
-            let loc = op.current_location();
-            let pos_x = pos_x.clamp(loc.x as f64, (loc.x + size.w) as f64);
-            let pos_y = pos_y.clamp(loc.y as f64, (loc.y + size.h) as f64);
-            (pos_x, pos_y)
+                .expect("called output_geometry on unmapped output");
+            clamp_inside(pos, geo)
         });
 
-        let nearest_point = nearest_points.min_by(|(x1, y1), (x2, y2)| {
+        let nearest_point = nearest_points.min_by(|p1, p2| {
             f64::total_cmp(
-                &((pos_x - x1).powi(2) + (pos_y - y1).powi(2)).sqrt(),
-                &((pos_x - x2).powi(2) + (pos_y - y2).powi(2)).sqrt(),
+                &((pos_x - p1.x).powi(2) + (pos_y - p1.y).powi(2)),
+                &((pos_x - p2.x).powi(2) + (pos_y - p2.y).powi(2)),
             )
         });
 
-        nearest_point.map(|point| point.into()).unwrap_or(pos)
+        nearest_point.unwrap_or(pos)
     }
 
     /// Handle an absolute pointer motion event.
@@ -655,8 +1277,15 @@ impl State {
         };
 
         let pointer_loc = event.position_transformed(output_geo.size) + output_geo.loc.to_f64();
+        let pointer_loc = if self.pinnacle.input_state.pointer_confinement.is_some() {
+            self.clamp_coords(pointer_loc)
+        } else {
+            pointer_loc
+        };
         let serial = SERIAL_COUNTER.next_serial();
 
+        self.detect_mouse_drags(pointer_loc);
+
         if let Some(output) = self
             .pinnacle
             .space
@@ -689,12 +1318,22 @@ impl State {
         };
 
         let mut pointer_loc = pointer.current_location();
+
+        let previous_output = self
+            .pinnacle
+            .space
+            .output_under(pointer_loc)
+            .next()
+            .cloned();
+
         pointer_loc += event.delta();
 
         // clamp to screen limits
         // this event is never generated by winit
         pointer_loc = self.clamp_coords(pointer_loc);
 
+        self.detect_mouse_drags(pointer_loc);
+
         if let Some(output) = self
             .pinnacle
             .space
@@ -729,8 +1368,29 @@ impl State {
 
         pointer.frame(self);
 
-        if let Some(output) = self.pinnacle.focused_output().cloned() {
+        let current_output = self.pinnacle.focused_output().cloned();
+
+        // The output the pointer left keeps showing the cursor, whether on its cursor plane
+        // or drawn into its last frame, until it renders again
+        if let Some(output) = previous_output.filter(|op| Some(op) != current_output.as_ref()) {
+            self.schedule_render(&output);
+        }
+
+        if let Some(output) = current_output {
             self.schedule_render(&output);
         }
     }
 }
+
+/// Clamp `pos` to the nearest point inside `geo`.
+///
+/// Output geometries don't contain their right and bottom edges, so the pointer is kept strictly
+/// inside. Otherwise it could rest on an edge that isn't on any output, such as the far edge of
+/// the last output or the part of a shorter output's edge that has no neighbor.
+fn clamp_inside(pos: Point<f64, Logical>, geo: Rectangle<i32, Logical>) -> Point<f64, Logical> {
+    let max_x = (geo.loc.x + geo.size.w - 1).max(geo.loc.x);
+    let max_y = (geo.loc.y + geo.size.h - 1).max(geo.loc.y);
+    let pos_x = pos.x.clamp(geo.loc.x as f64, max_x as f64);
+    let pos_y = pos.y.clamp(geo.loc.y as f64, max_y as f64);
+    (pos_x, pos_y).into()
+}
@@ -5,7 +5,10 @@ use std::{
     time::Duration,
 };
 
-use pinnacle_api_defs::pinnacle::layout::v0alpha1::{layout_request::Geometries, LayoutResponse};
+use pinnacle_api_defs::pinnacle::{
+    layout::v0alpha1::{layout_request::Geometries, LayoutResponse, ReportedTagLayout},
+    tag,
+};
 use smithay::{
     desktop::{layer_map_for_output, WindowSurface},
     output::Output,
@@ -19,23 +22,29 @@ use tracing::warn;
 use crate::{
     output::OutputName,
     state::{Pinnacle, State, WithState},
+    tag::{Gaps, TagId},
     window::{
-        window_state::{FloatingOrTiled, FullscreenOrMaximized},
+        window_state::{FloatingOrTiled, FullscreenOrMaximized, TiledEdges},
         WindowElement,
     },
 };
 
 impl Pinnacle {
+    /// Move windows on `output` to their new geometries.
+    ///
+    /// If `geometries` is `None`, tiled windows are left where they are.
+    /// `lone_window_gaps` is the gap the layout client reported leaving around a lone window.
     fn update_windows_with_geometries(
         &mut self,
         output: &Output,
-        geometries: Vec<Rectangle<i32, Logical>>,
+        geometries: Option<Vec<Rectangle<i32, Logical>>>,
+        lone_window_gaps: i32,
     ) {
         let windows_on_foc_tags = output.with_state(|state| {
             let focused_tags = state.focused_tags().collect::<Vec<_>>();
             self.windows
                 .iter()
-                .filter(|win| !win.is_x11_override_redirect())
+                .filter(|win| !win.is_x11_override_redirect() && !win.is_minimized())
                 .filter(|win| {
                     win.with_state(|state| state.tags.iter().any(|tg| focused_tags.contains(&tg)))
                 })
@@ -59,38 +68,101 @@ impl Pinnacle {
             map.non_exclusive_zone()
         };
 
-        let mut zipped = tiled_windows.zip(geometries.into_iter().map(|mut geo| {
-            geo.loc += output_geo.loc + non_exclusive_geo.loc;
-            geo
-        }));
+        if let Some(geometries) = geometries {
+            let lone_window = self.smart_gaps_active(output, geometries.len());
+            let lone_window_width = (geometries.len() == 1)
+                .then(|| output.with_state(|state| state.lone_window_width))
+                .flatten();
+
+            let gaps = self.tiling_gaps(output, geometries.len());
+            let (inner, outer) = (gaps.inner as i32, gaps.outer as i32);
+
+            let usable_geo = Rectangle::from_loc_and_size(
+                output_geo.loc + non_exclusive_geo.loc,
+                non_exclusive_geo.size,
+            );
+
+            let geometries = geometries
+                .into_iter()
+                .map(|mut geo| {
+                    if lone_window {
+                        // Grow the window over the gap its layout client left around it
+                        geo.loc +=
+                            usable_geo.loc - Point::from((lone_window_gaps, lone_window_gaps));
+                        geo.size.w += lone_window_gaps * 2;
+                        geo.size.h += lone_window_gaps * 2;
+                        geo = geo.intersection(usable_geo).unwrap_or(usable_geo);
+                    } else {
+                        geo.loc += usable_geo.loc + Point::from((outer, outer));
+                        geo.loc += Point::from((inner, inner));
+                        geo.size.w = i32::max(geo.size.w - inner * 2, 1);
+                        geo.size.h = i32::max(geo.size.h - inner * 2, 1);
+                    }
 
-        for (win, geo) in zipped.by_ref() {
-            win.change_geometry(geo);
-        }
+                    lone_window_width.map_or(geo, |width| width.constrain(geo))
+                })
+                .collect::<Vec<_>>();
+
+            let mut zipped = tiled_windows.zip(geometries.iter().copied().enumerate());
+
+            for (win, (i, geo)) in zipped.by_ref() {
+                win.change_geometry(geo);
+
+                let others = geometries
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .map(|(_, other)| *other)
+                    .collect::<Vec<_>>();
+                win.set_tiled_edges(TiledEdges::bordering(
+                    geo,
+                    usable_geo,
+                    &others,
+                    outer + inner * 2,
+                ));
+            }
 
-        let (remaining_wins, _remaining_geos) = zipped.unzip::<_, _, Vec<_>, Vec<_>>();
+            let (remaining_wins, _remaining_geos) = zipped.unzip::<_, _, Vec<_>, Vec<_>>();
 
-        for win in remaining_wins {
-            assert!(win.with_state(|state| state.floating_or_tiled.is_floating()));
-            win.toggle_floating();
+            for win in remaining_wins {
+                assert!(win.with_state(|state| state.floating_or_tiled.is_floating()));
+                win.toggle_floating();
+            }
         }
 
         for window in windows_on_foc_tags.iter() {
             match window.with_state(|state| state.fullscreen_or_maximized) {
                 FullscreenOrMaximized::Fullscreen => {
                     window.change_geometry(output_geo);
+                    window.set_tiled_edges(TiledEdges::all());
                 }
                 FullscreenOrMaximized::Maximized => {
-                    window.change_geometry(Rectangle::from_loc_and_size(
+                    let mut geo = Rectangle::from_loc_and_size(
                         output_geo.loc + non_exclusive_geo.loc,
                         non_exclusive_geo.size,
-                    ));
+                    );
+
+                    if window.with_state(|state| state.maximize_gaps) {
+                        // Line the window up with the edges of tiled windows
+                        let gaps = output
+                            .with_state(|state| state.focused_tags().next().map(|tag| tag.gaps()))
+                            .unwrap_or_default();
+                        let inset = (gaps.outer + gaps.inner) as i32;
+
+                        geo.loc += Point::from((inset, inset));
+                        geo.size.w = i32::max(geo.size.w - inset * 2, 1);
+                        geo.size.h = i32::max(geo.size.h - inset * 2, 1);
+                    }
+
+                    window.change_geometry(geo);
+                    window.set_tiled_edges(TiledEdges::all());
                 }
                 FullscreenOrMaximized::Neither => {
                     if let FloatingOrTiled::Floating(rect) =
                         window.with_state(|state| state.floating_or_tiled)
                     {
-                        window.change_geometry(rect);
+                        window.change_geometry(rect.to_i32_round());
+                        window.set_tiled_edges(TiledEdges::empty());
                     }
                 }
             }
@@ -149,6 +221,36 @@ impl Pinnacle {
         self.fixup_z_layering();
     }
 
+    /// Get the gaps to apply to `tiled_count` tiled windows on `output`.
+    ///
+    /// This uses the gaps of the first focused tag on the output.
+    /// If that tag has smart gaps enabled and there is at most one tiled window,
+    /// there are no gaps.
+    fn tiling_gaps(&self, output: &Output, tiled_count: usize) -> Gaps {
+        if self.smart_gaps_active(output, tiled_count) {
+            return Gaps::default();
+        }
+
+        output
+            .with_state(|state| state.focused_tags().next().map(|tag| tag.gaps()))
+            .unwrap_or_default()
+    }
+
+    /// Returns whether `tiled_count` tiled windows on `output` should have their gaps removed.
+    ///
+    /// This is the case when the first focused tag has smart gaps enabled
+    /// and there is at most one tiled window. A lone window also loses the gap
+    /// its layout client reported leaving around it.
+    fn smart_gaps_active(&self, output: &Output, tiled_count: usize) -> bool {
+        tiled_count <= 1
+            && output.with_state(|state| {
+                state
+                    .focused_tags()
+                    .next()
+                    .is_some_and(|tag| tag.gaps().smart)
+            })
+    }
+
     /// Swaps two windows in the main window vec and updates all windows.
     pub fn swap_window_positions(&mut self, win1: &WindowElement, win2: &WindowElement) {
         let win1_index = self.windows.iter().position(|win| win == win1);
@@ -162,6 +264,27 @@ impl Pinnacle {
             self.layout_state.pending_swap = true;
         }
     }
+
+    /// Move `window` to where `target` is in the window order, shifting `target` and
+    /// the windows after it back by one.
+    pub fn move_window_to_position_of(&mut self, window: &WindowElement, target: &WindowElement) {
+        let Some(index) = self.windows.iter().position(|win| win == window) else {
+            return;
+        };
+
+        let window = self.windows.remove(index);
+
+        let Some(target_index) = self.windows.iter().position(|win| win == target) else {
+            self.windows.insert(index, window);
+            return;
+        };
+
+        self.windows.insert(target_index, window.clone());
+
+        if let Some(output) = window.output(self) {
+            self.request_layout(&output);
+        }
+    }
 }
 
 /// A monotonically increasing identifier for layout requests.
@@ -172,6 +295,12 @@ pub struct LayoutRequestId(pub u32);
 pub struct LayoutState {
     pub layout_request_sender: Option<UnboundedSender<Result<LayoutResponse, Status>>>,
     pub pending_swap: bool,
+    /// Whether every output will be laid out once the event loop is idle
+    relayout_all_queued: bool,
+    /// Whether the layout client isn't asked to tile windows
+    pub tiling_paused: bool,
+    /// Layout state the layout client reported for each tag
+    pub reported_tag_layouts: HashMap<TagId, ReportedTagLayout>,
     id_maps: HashMap<Output, LayoutRequestId>,
     pending_requests: HashMap<Output, Vec<(LayoutRequestId, Vec<WindowElement>)>>,
     old_requests: HashMap<Output, HashSet<LayoutRequestId>>,
@@ -179,6 +308,19 @@ pub struct LayoutState {
 
 impl Pinnacle {
     pub fn request_layout(&mut self, output: &Output) {
+        // Tags may have just become active on an output that connected while headless
+        self.unpark_windows(output);
+
+        // Changing which windows are displayed almost always requests a layout,
+        // so suspended states are kept up to date here
+        self.update_suspended_windows();
+
+        if self.layout_state.tiling_paused {
+            // Tiled windows stay put, but everything else still needs to be placed
+            self.update_windows_with_geometries(output, None, 0);
+            return;
+        }
+
         let Some(sender) = self.layout_state.layout_request_sender.as_ref() else {
             warn!("Layout requested but no client has connected to the layout service");
             return;
@@ -188,7 +330,7 @@ impl Pinnacle {
             let focused_tags = state.focused_tags().collect::<Vec<_>>();
             self.windows
                 .iter()
-                .filter(|win| !win.is_x11_override_redirect())
+                .filter(|win| !win.is_x11_override_redirect() && !win.is_minimized())
                 .filter(|win| {
                     win.with_state(|state| state.tags.iter().any(|tg| focused_tags.contains(&tg)))
                 })
@@ -206,10 +348,15 @@ impl Pinnacle {
             .cloned()
             .collect::<Vec<_>>();
 
+        let outer_gaps = self.tiling_gaps(output, windows.len()).outer as i32;
+
         let (output_width, output_height) = {
             let map = layer_map_for_output(output);
             let zone = map.non_exclusive_zone();
-            (zone.size.w, zone.size.h)
+            (
+                i32::max(zone.size.w - outer_gaps * 2, 0),
+                i32::max(zone.size.h - outer_gaps * 2, 0),
+            )
         };
 
         let window_ids = windows
@@ -220,6 +367,15 @@ impl Pinnacle {
         let tag_ids =
             output.with_state(|state| state.focused_tags().map(|tag| tag.id().0).collect());
 
+        let tiling_direction = output
+            .with_state(|state| {
+                state
+                    .focused_tags()
+                    .next()
+                    .and_then(|tag| tag.tiling_direction())
+            })
+            .map(|direction| tag::v0alpha1::TilingDirection::from(direction) as i32);
+
         let id = self
             .layout_state
             .id_maps
@@ -240,10 +396,96 @@ impl Pinnacle {
             tag_ids,
             output_width: Some(output_width as u32),
             output_height: Some(output_height as u32),
+            tiling_direction,
         }));
 
         *id = LayoutRequestId(id.0 + 1);
     }
+
+    /// Pause or resume tiling.
+    ///
+    /// While tiling is paused, tiled windows stay where they are and new windows open
+    /// floating. Resuming lays out every output again.
+    pub fn set_tiling_paused(&mut self, paused: bool) {
+        if self.layout_state.tiling_paused == paused {
+            return;
+        }
+
+        self.layout_state.tiling_paused = paused;
+
+        if !paused {
+            self.relayout_all();
+        }
+    }
+
+    /// Float `window` centered on its output if tiling is paused and it would be tiled.
+    ///
+    /// This is used for new windows so they don't open offscreen waiting for a layout.
+    pub fn float_if_tiling_paused(&self, window: &WindowElement) {
+        if !self.layout_state.tiling_paused
+            || !window.with_state(|state| state.floating_or_tiled.is_tiled())
+        {
+            return;
+        }
+
+        self.float_window_centered(window);
+    }
+
+    /// Float `window` centered in the usable area of its output.
+    ///
+    /// Windows that are already floating keep their floating size.
+    pub fn float_window_centered(&self, window: &WindowElement) {
+        let Some(output) = window.output(self) else {
+            return;
+        };
+        let Some(output_geo) = self.space.output_geometry(&output) else {
+            return;
+        };
+
+        let floating_or_tiled = window.with_state(|state| state.floating_or_tiled);
+
+        let zone = layer_map_for_output(&output).non_exclusive_zone();
+        let size = match floating_or_tiled {
+            FloatingOrTiled::Floating(rect) => rect.size,
+            FloatingOrTiled::Tiled(_) => window.geometry().size.to_f64(),
+        };
+        let zone = zone.to_f64();
+        let loc = output_geo.loc.to_f64()
+            + zone.loc
+            + Point::from(((zone.size.w - size.w) / 2.0, (zone.size.h - size.h) / 2.0));
+        let rect = Rectangle::from_loc_and_size(loc, size);
+
+        window.with_state_mut(|state| {
+            state.floating_or_tiled = match floating_or_tiled {
+                FloatingOrTiled::Floating(_) => FloatingOrTiled::Floating(rect),
+                FloatingOrTiled::Tiled(_) => FloatingOrTiled::Tiled(Some(rect)),
+            };
+        });
+
+        if floating_or_tiled.is_tiled() {
+            window.toggle_floating();
+        }
+    }
+
+    /// Lay out and render every output once the event loop is idle.
+    ///
+    /// Calling this multiple times before then still only lays out each output once.
+    pub fn relayout_all(&mut self) {
+        if self.layout_state.relayout_all_queued {
+            return;
+        }
+
+        self.layout_state.relayout_all_queued = true;
+
+        self.loop_handle.insert_idle(|state| {
+            state.pinnacle.layout_state.relayout_all_queued = false;
+
+            for output in state.pinnacle.space.outputs().cloned().collect::<Vec<_>>() {
+                state.pinnacle.request_layout(&output);
+                state.schedule_render(&output);
+            }
+        });
+    }
 }
 
 impl State {
@@ -252,6 +494,7 @@ impl State {
             request_id: Some(request_id),
             output_name: Some(output_name),
             geometries,
+            lone_window_gaps,
         } = geometries
         else {
             anyhow::bail!("One or more `geometries` fields were None");
@@ -312,8 +555,11 @@ impl State {
             anyhow::bail!("Attempted to layout but one or more dimensions were null");
         };
 
-        self.pinnacle
-            .update_windows_with_geometries(&output, geometries);
+        self.pinnacle.update_windows_with_geometries(
+            &output,
+            Some(geometries),
+            lone_window_gaps.unwrap_or_default() as i32,
+        );
 
         self.schedule_render(&output);
 
@@ -2,15 +2,19 @@
 
 pub mod api;
 pub mod backend;
+pub mod backlight;
 pub mod cli;
 pub mod config;
 pub mod cursor;
 pub mod focus;
 pub mod grab;
 pub mod handlers;
+pub mod hint;
+pub mod idle;
 pub mod input;
 pub mod layout;
 pub mod output;
+pub mod overview;
 pub mod protocol;
 pub mod render;
 pub mod state;
@@ -131,6 +131,7 @@ async fn main() -> anyhow::Result<()> {
 
     event_loop.run(None, &mut state, |state| {
         state.update_pointer_focus();
+        state.update_output_dim();
         state.pinnacle.fixup_z_layering();
         state.pinnacle.space.refresh();
         state.pinnacle.popup_manager.cleanup();
@@ -2,19 +2,27 @@
 
 use std::{cell::RefCell, num::NonZeroU32};
 
-use pinnacle_api_defs::pinnacle::signal::v0alpha1::{OutputMoveResponse, OutputResizeResponse};
+use pinnacle_api_defs::pinnacle::signal::v0alpha1::{
+    OutputDisconnectResponse, OutputHeadlessResponse, OutputMoveResponse, OutputResizeResponse,
+};
 use smithay::{
-    desktop::layer_map_for_output,
+    desktop::{layer_map_for_output, utils::surface_primary_scanout_output},
     output::{Mode, Output, Scale},
-    utils::{Logical, Point, Transform},
+    reexports::wayland_server::protocol::wl_surface::WlSurface,
+    utils::{IsAlive, Logical, Point, Rectangle, Transform},
+    wayland::{compositor::SurfaceData, fractional_scale::with_fractional_scale},
 };
-use tracing::info;
+use tracing::{info, warn};
 
 use crate::{
+    backend::Backend,
+    backlight::Backlight,
     focus::WindowKeyboardFocusStack,
+    overview::Overview,
     protocol::screencopy::Screencopy,
-    state::{Pinnacle, WithState},
-    tag::Tag,
+    state::{Pinnacle, State, WithState},
+    tag::{Tag, TagSet},
+    window::WindowElement,
 };
 
 /// A unique identifier for an output.
@@ -42,6 +50,128 @@ pub struct OutputState {
     pub focus_stack: WindowKeyboardFocusStack,
     pub screencopy: Option<Screencopy>,
     pub serial: Option<NonZeroU32>,
+    pub overview: Option<Overview>,
+    /// The tags that were active before the most recent tag switch
+    pub previous_active_tags: Vec<Tag>,
+    /// How much the magnifier is zoomed in, or `None` if it's off
+    pub magnification: Option<f64>,
+    /// How wide the only tiled window can be, or `None` if it fills the output
+    pub lone_window_width: Option<LoneWindowWidth>,
+    /// Windows focused while this output was focused, with the most recently focused last
+    pub focus_history: Vec<WindowElement>,
+    /// The backlight found for this output, or `None` if it hasn't been looked up yet
+    pub backlight: Option<Option<Backlight>>,
+}
+
+/// A limit on how wide the only tiled window on an output can be.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoneWindowWidth {
+    /// The most logical pixels wide the window can be
+    pub max_width: i32,
+    pub alignment: LoneWindowAlignment,
+}
+
+/// Where a lone tiled window goes when it's narrower than the space it would fill.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LoneWindowAlignment {
+    #[default]
+    Center,
+    Left,
+    Right,
+}
+
+impl LoneWindowWidth {
+    /// Shrink `geo` to the max width, lining it up inside where it was.
+    ///
+    /// Geometries that are already narrow enough are returned as is.
+    pub fn constrain(&self, mut geo: Rectangle<i32, Logical>) -> Rectangle<i32, Logical> {
+        let extra = geo.size.w - self.max_width;
+        if extra <= 0 {
+            return geo;
+        }
+
+        geo.loc.x += match self.alignment {
+            LoneWindowAlignment::Center => extra / 2,
+            LoneWindowAlignment::Left => 0,
+            LoneWindowAlignment::Right => extra,
+        };
+        geo.size.w = self.max_width;
+        geo
+    }
+}
+
+/// Gamma the compositor sets on an output, like a night light.
+///
+/// Presets are kept when outputs are unplugged and set again when they come back.
+/// Gamma control clients like wlsunset take priority over presets while they're running.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GammaPreset {
+    /// The color temperature in Kelvin. 6500 is neutral.
+    pub temperature: u32,
+    /// How bright the output is, from 0.0 to 1.0
+    pub brightness: f32,
+    pub gamma: f32,
+}
+
+impl Default for GammaPreset {
+    fn default() -> Self {
+        Self {
+            temperature: 6500,
+            brightness: 1.0,
+            gamma: 1.0,
+        }
+    }
+}
+
+impl GammaPreset {
+    /// Get the red, green, and blue gamma ramps for this preset with `size` entries each.
+    pub fn ramps(&self, size: usize) -> [Vec<u16>; 3] {
+        let whitepoint = whitepoint(self.temperature);
+        // The curve fit isn't quite white at 6500K, so scale relative to it
+        let neutral = whitepoint(6500);
+        let denom = size.saturating_sub(1).max(1) as f32;
+
+        let ramp = |channel: usize| {
+            let factor = (whitepoint[channel] / neutral[channel]).min(1.0);
+            (0..size)
+                .map(|i| {
+                    let value = (i as f32 / denom).powf(1.0 / self.gamma) * self.brightness;
+                    ((value * factor).clamp(0.0, 1.0) * u16::MAX as f32).round() as u16
+                })
+                .collect::<Vec<_>>()
+        };
+
+        [ramp(0), ramp(1), ramp(2)]
+    }
+}
+
+/// Approximate the RGB whitepoint of a color temperature in Kelvin.
+///
+/// This uses Tanner Helland's curve fit of blackbody colors.
+fn whitepoint(temperature: u32) -> [f32; 3] {
+    let temp = temperature.clamp(1000, 40000) as f32 / 100.0;
+
+    let red = if temp <= 66.0 {
+        255.0
+    } else {
+        329.69873 * (temp - 60.0).powf(-0.13320476)
+    };
+
+    let green = if temp <= 66.0 {
+        99.4708 * temp.ln() - 161.11957
+    } else {
+        288.12216 * (temp - 60.0).powf(-0.075514846)
+    };
+
+    let blue = if temp >= 66.0 {
+        255.0
+    } else if temp <= 19.0 {
+        0.0
+    } else {
+        138.51773 * (temp - 10.0).ln() - 305.0448
+    };
+
+    [red, green, blue].map(|channel| (channel / 255.0).clamp(0.0, 1.0))
 }
 
 impl WithState for Output {
@@ -74,6 +204,46 @@ impl OutputState {
     pub fn focused_tags(&self) -> impl Iterator<Item = &Tag> {
         self.tags.iter().filter(|tag| tag.active())
     }
+
+    /// Remember `tags` as the previously active tags if they differ from the currently active ones.
+    pub fn set_previous_active_tags(&mut self, tags: Vec<Tag>) {
+        if !self.focused_tags().eq(tags.iter()) {
+            self.previous_active_tags = tags;
+        }
+    }
+}
+
+impl State {
+    /// Set `output`'s gamma to its gamma preset, or reset it to linear if it has none.
+    ///
+    /// This does nothing while a gamma control client controls the output.
+    pub fn apply_gamma_preset(&mut self, output: &Output) {
+        if self
+            .pinnacle
+            .gamma_control_manager_state
+            .gamma_controls
+            .contains_key(output)
+        {
+            return;
+        }
+
+        let Backend::Udev(udev) = &mut self.backend else {
+            return;
+        };
+
+        let preset = self
+            .pinnacle
+            .gamma_presets
+            .get(&OutputName(output.name()))
+            .copied();
+
+        if let Err(err) = udev.apply_gamma_preset(output, preset) {
+            warn!(
+                "Failed to apply gamma preset for output {}: {err}",
+                output.name()
+            );
+        }
+    }
 }
 
 impl Pinnacle {
@@ -91,6 +261,19 @@ impl Pinnacle {
         if let Some(location) = location {
             info!(?location);
             self.space.map_output(output, location);
+
+            // Pinned windows move along with their output
+            let pinned_windows = self
+                .windows
+                .iter()
+                .filter(|win| win.is_geometry_pinned() && win.output(self).as_ref() == Some(output))
+                .cloned()
+                .collect::<Vec<_>>();
+
+            for win in pinned_windows {
+                self.apply_relative_geometry(&win);
+            }
+
             self.signal_state.output_move.signal(|buf| {
                 buf.push_back(OutputMoveResponse {
                     output_name: Some(output.name()),
@@ -101,6 +284,25 @@ impl Pinnacle {
         }
         if mode.is_some() || transform.is_some() || scale.is_some() {
             layer_map_for_output(output).arrange();
+
+            let relative_windows = self
+                .windows
+                .iter()
+                .filter(|win| {
+                    win.with_state(|state| state.relative_geometry.is_some())
+                        && win.output(self).as_ref() == Some(output)
+                })
+                .cloned()
+                .collect::<Vec<_>>();
+
+            for win in relative_windows {
+                self.apply_relative_geometry(&win);
+            }
+
+            if scale.is_some() {
+                self.send_preferred_fractional_scale(output);
+            }
+
             self.signal_state.output_resize.signal(|buf| {
                 let geo = self.space.output_geometry(output);
                 buf.push_back(OutputResizeResponse {
@@ -114,4 +316,193 @@ impl Pinnacle {
             output.set_preferred(mode);
         }
     }
+
+    /// Tell surfaces on `output` to use its current scale as their preferred fractional scale.
+    ///
+    /// Surfaces that get rendered are updated after each repaint, but ones that aren't,
+    /// like windows on inactive tags, would otherwise keep rendering at the old scale
+    /// until they're shown again. Surfaces scanned out on a different output are skipped.
+    fn send_preferred_fractional_scale(&self, output: &Output) {
+        let scale = output.current_scale().fractional_scale();
+
+        let update_surface = |surface: &WlSurface, states: &SurfaceData| {
+            let primary_output = surface_primary_scanout_output(surface, states);
+            if primary_output.is_some_and(|primary| &primary != output) {
+                return;
+            }
+
+            with_fractional_scale(states, |fractional_scale| {
+                fractional_scale.set_preferred_scale(scale);
+            });
+        };
+
+        for win in self
+            .windows
+            .iter()
+            .filter(|win| win.output(self).as_ref() == Some(output))
+        {
+            win.with_surfaces(update_surface);
+        }
+
+        for layer in layer_map_for_output(output).layers() {
+            layer.with_surfaces(update_surface);
+        }
+    }
+
+    /// Add the configured default tags to a newly connected `output`, activating the first one.
+    ///
+    /// This does nothing if there are no default tags or `output` already has tags.
+    pub fn add_default_tags(&mut self, output: &Output) {
+        if self.config.default_output_tags.is_empty()
+            || output.with_state(|state| !state.tags.is_empty())
+        {
+            return;
+        }
+
+        let new_tags = self
+            .config
+            .default_output_tags
+            .iter()
+            .cloned()
+            .map(Tag::new)
+            .collect::<Vec<_>>();
+
+        self.config
+            .connector_saved_states
+            .entry(OutputName(output.name()))
+            .or_default()
+            .tags
+            .extend(new_tags.clone());
+
+        output.with_state_mut(|state| state.tags.extend(new_tags.clone()));
+
+        if let Some(first) = new_tags.first() {
+            first.set_active(true, self);
+        }
+    }
+
+    /// Remove a disconnected `output` from the space.
+    ///
+    /// If this was the last output, the compositor becomes headless until one connects again.
+    /// Windows keep their tags in the meantime.
+    pub fn remove_output(&mut self, output: &Output) {
+        for layer in layer_map_for_output(output).layers() {
+            layer.layer_surface().send_close();
+        }
+
+        self.space.unmap_output(output);
+        self.output_focus_stack.remove(output);
+        self.gamma_control_manager_state.output_removed(output);
+
+        self.signal_state.output_disconnect.signal(|buffer| {
+            buffer.push_back(OutputDisconnectResponse {
+                output_name: Some(output.name()),
+            })
+        });
+
+        if self.space.outputs().next().is_none() && !self.headless {
+            info!("All outputs disconnected, running headless");
+            self.headless = true;
+            self.signal_state.output_headless.signal(|buffer| {
+                buffer.push_back(OutputHeadlessResponse {
+                    headless: Some(true),
+                })
+            });
+        }
+    }
+
+    /// Stop being headless now that `output` connected.
+    ///
+    /// This should be called after `output` has its tags. Windows whose tags are
+    /// no longer on any output, including ones that opened while headless, are parked
+    /// and moved to `output`'s active tags once it has some.
+    pub fn leave_headless(&mut self, output: &Output) {
+        if !self.headless {
+            return;
+        }
+
+        info!("Output {} connected, no longer headless", output.name());
+        self.headless = false;
+        self.signal_state.output_headless.signal(|buffer| {
+            buffer.push_back(OutputHeadlessResponse {
+                headless: Some(false),
+            })
+        });
+
+        self.parked_windows = self
+            .windows
+            .iter()
+            .filter(|win| !win.is_x11_override_redirect())
+            .filter(|win| {
+                win.with_state(|state| state.tags.iter().all(|tag| tag.output(self).is_none()))
+            })
+            .cloned()
+            .collect();
+
+        self.request_layout(output);
+    }
+
+    /// Move parked windows to `output`'s active tags.
+    ///
+    /// This does nothing if `output` has no active tags yet, so windows stay parked
+    /// until the config sets some up. This is called whenever a layout is requested,
+    /// which happens after every tag change.
+    pub fn unpark_windows(&mut self, output: &Output) {
+        if self.parked_windows.is_empty() {
+            return;
+        }
+
+        let tags = output.with_state(|state| state.focused_tags().cloned().collect::<TagSet>());
+        if tags.is_empty() {
+            return;
+        }
+
+        for win in std::mem::take(&mut self.parked_windows) {
+            if win.alive() {
+                win.with_state_mut(|state| state.tags = tags.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lone_window_width_shrinks_wide_geometries_into_alignment() {
+        let geo = Rectangle::from_loc_and_size((100, 20), (3000, 1000));
+
+        let constrain = |alignment| {
+            LoneWindowWidth {
+                max_width: 2000,
+                alignment,
+            }
+            .constrain(geo)
+        };
+
+        assert_eq!(
+            constrain(LoneWindowAlignment::Center),
+            Rectangle::from_loc_and_size((600, 20), (2000, 1000))
+        );
+        assert_eq!(
+            constrain(LoneWindowAlignment::Left),
+            Rectangle::from_loc_and_size((100, 20), (2000, 1000))
+        );
+        assert_eq!(
+            constrain(LoneWindowAlignment::Right),
+            Rectangle::from_loc_and_size((1100, 20), (2000, 1000))
+        );
+    }
+
+    #[test]
+    fn lone_window_width_keeps_narrow_geometries() {
+        let geo = Rectangle::from_loc_and_size((0, 0), (1500, 1000));
+        let width = LoneWindowWidth {
+            max_width: 2000,
+            alignment: LoneWindowAlignment::Right,
+        };
+
+        assert_eq!(width.constrain(geo), geo);
+    }
 }
@@ -0,0 +1,276 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! An exposé-style overview that shows windows side by side, scaled down to fit an output.
+
+use std::time::{Duration, Instant};
+
+use smithay::{
+    desktop::space::SpaceElement,
+    output::Output,
+    utils::{Logical, Point, Rectangle},
+};
+
+use crate::{
+    state::{Pinnacle, State, WithState},
+    window::WindowElement,
+};
+
+/// How long the overview takes to animate in and out.
+const ANIMATION_DURATION: Duration = Duration::from_millis(200);
+
+/// The gap between windows in the overview, in logical pixels.
+const GAP: i32 = 24;
+
+/// The state of an overview on an output.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Overview {
+    /// Whether windows from all tags on the output are shown instead of only active ones
+    pub all_tags: bool,
+    state: OverviewState,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum OverviewState {
+    Opening(Instant),
+    Closing(Instant),
+}
+
+impl Overview {
+    pub fn new(all_tags: bool) -> Self {
+        Self {
+            all_tags,
+            state: OverviewState::Opening(Instant::now()),
+        }
+    }
+
+    /// Returns whether this overview is open or opening.
+    pub fn is_open(&self) -> bool {
+        matches!(self.state, OverviewState::Opening(_))
+    }
+
+    /// Start animating this overview out.
+    pub fn close(&mut self) {
+        if let OverviewState::Opening(start) = self.state {
+            // Start closing from wherever the opening animation currently is
+            let remaining = ANIMATION_DURATION.saturating_sub(start.elapsed());
+            self.state =
+                OverviewState::Closing(Instant::now().checked_sub(remaining).unwrap_or(start));
+        }
+    }
+
+    /// How far the overview is towards being fully open, from 0.0 to 1.0.
+    pub fn progress(&self) -> f64 {
+        let (start, opening) = match self.state {
+            OverviewState::Opening(start) => (start, true),
+            OverviewState::Closing(start) => (start, false),
+        };
+
+        let progress =
+            (start.elapsed().as_secs_f64() / ANIMATION_DURATION.as_secs_f64()).clamp(0.0, 1.0);
+
+        // Ease out cubic
+        let eased = 1.0 - (1.0 - progress).powi(3);
+
+        if opening {
+            eased
+        } else {
+            1.0 - eased
+        }
+    }
+
+    /// Returns whether this overview is currently animating in or out.
+    pub fn is_animating(&self) -> bool {
+        let start = match self.state {
+            OverviewState::Opening(start) | OverviewState::Closing(start) => start,
+        };
+        start.elapsed() < ANIMATION_DURATION
+    }
+
+    /// Returns whether this overview has finished closing and can be dropped.
+    fn is_finished(&self) -> bool {
+        !self.is_open() && !self.is_animating()
+    }
+}
+
+/// Compute grid slots for `count` windows inside `area`.
+fn grid_slots(area: Rectangle<i32, Logical>, count: usize) -> Vec<Rectangle<i32, Logical>> {
+    if count == 0 {
+        return Vec::new();
+    }
+
+    let cols = (count as f64).sqrt().ceil() as i32;
+    let rows = (count as i32 + cols - 1) / cols;
+
+    let slot_w = ((area.size.w - GAP * (cols + 1)) / cols).max(1);
+    let slot_h = ((area.size.h - GAP * (rows + 1)) / rows).max(1);
+
+    (0..count as i32)
+        .map(|i| {
+            let row = i / cols;
+            let col = i % cols;
+
+            // Center the last row if it isn't full
+            let in_row = if row == rows - 1 {
+                count as i32 - row * cols
+            } else {
+                cols
+            };
+            let row_offset = (cols - in_row) * (slot_w + GAP) / 2;
+
+            Rectangle::from_loc_and_size(
+                (
+                    area.loc.x + GAP + row_offset + col * (slot_w + GAP),
+                    area.loc.y + GAP + row * (slot_h + GAP),
+                ),
+                (slot_w, slot_h),
+            )
+        })
+        .collect()
+}
+
+/// Fit a window of `size` into `slot`, keeping its aspect ratio and never scaling it up.
+///
+/// Returns the location of the window's geometry and the scale it should be drawn at.
+fn fit_into_slot(
+    size: smithay::utils::Size<i32, Logical>,
+    slot: Rectangle<i32, Logical>,
+) -> (Point<f64, Logical>, f64) {
+    if size.w <= 0 || size.h <= 0 {
+        return (slot.loc.to_f64(), 1.0);
+    }
+
+    let scale = (slot.size.w as f64 / size.w as f64)
+        .min(slot.size.h as f64 / size.h as f64)
+        .min(1.0);
+
+    let scaled_w = size.w as f64 * scale;
+    let scaled_h = size.h as f64 * scale;
+
+    let loc = Point::from((
+        slot.loc.x as f64 + (slot.size.w as f64 - scaled_w) / 2.0,
+        slot.loc.y as f64 + (slot.size.h as f64 - scaled_h) / 2.0,
+    ));
+
+    (loc, scale)
+}
+
+/// Where a window is drawn in the overview.
+#[derive(Debug, Clone)]
+pub struct OverviewTarget {
+    pub window: WindowElement,
+    /// The global location of the window's geometry when the overview is fully open
+    pub loc: Point<f64, Logical>,
+    /// The scale of the window when the overview is fully open
+    pub scale: f64,
+}
+
+impl OverviewTarget {
+    /// The global area the window takes up when the overview is fully open.
+    pub fn rect(&self) -> Rectangle<f64, Logical> {
+        Rectangle::from_loc_and_size(self.loc, self.window.geometry().size.to_f64() * self.scale)
+    }
+}
+
+impl Pinnacle {
+    /// Get the overview on `output`, if there is one.
+    pub fn overview(&self, output: &Output) -> Option<Overview> {
+        output.with_state(|state| state.overview)
+    }
+
+    /// Open an overview on `output`.
+    ///
+    /// If `all_tags` is true, windows on all tags on the output will be shown,
+    /// not just those on active tags.
+    pub fn open_overview(&mut self, output: &Output, all_tags: bool) {
+        output.with_state_mut(|state| {
+            if !state.overview.is_some_and(|overview| overview.is_open()) {
+                state.overview = Some(Overview::new(all_tags));
+            }
+        });
+    }
+
+    /// Close the overview on `output`, animating it out.
+    pub fn close_overview(&mut self, output: &Output) {
+        output.with_state_mut(|state| {
+            if let Some(overview) = state.overview.as_mut() {
+                overview.close();
+            }
+        });
+    }
+
+    /// Drop the overview on `output` if it has finished animating out.
+    ///
+    /// Returns whether or not the overview needs more frames to be rendered.
+    pub fn refresh_overview(&mut self, output: &Output) -> bool {
+        output.with_state_mut(|state| {
+            if state
+                .overview
+                .is_some_and(|overview| overview.is_finished())
+            {
+                state.overview.take();
+            }
+            state
+                .overview
+                .is_some_and(|overview| overview.is_animating())
+        })
+    }
+
+    /// Get where each window in the overview on `output` is drawn when fully open.
+    pub fn overview_targets(&self, output: &Output) -> Vec<OverviewTarget> {
+        let Some(overview) = self.overview(output) else {
+            return Vec::new();
+        };
+
+        let Some(output_geo) = self.space.output_geometry(output) else {
+            return Vec::new();
+        };
+
+        let windows = self
+            .windows
+            .iter()
+            .filter(|win| !win.is_x11_override_redirect())
+            .filter(|win| win.output(self).as_ref() == Some(output))
+            .filter(|win| overview.all_tags || win.is_on_active_tag())
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let slots = grid_slots(output_geo, windows.len());
+
+        windows
+            .into_iter()
+            .zip(slots)
+            .map(|(window, slot)| {
+                let (loc, scale) = fit_into_slot(window.geometry().size, slot);
+                OverviewTarget { window, loc, scale }
+            })
+            .collect()
+    }
+}
+
+impl State {
+    /// Select the window under `point` in the overview on `output`, closing the overview.
+    ///
+    /// If the window is on inactive tags, this switches to its first tag.
+    /// The selected window is focused and raised.
+    ///
+    /// Returns whether or not a window was selected.
+    pub fn select_overview_window(&mut self, output: &Output, point: Point<f64, Logical>) -> bool {
+        let window = self
+            .pinnacle
+            .overview_targets(output)
+            .into_iter()
+            .find(|target| target.rect().contains(point))
+            .map(|target| target.window);
+
+        self.pinnacle.close_overview(output);
+
+        let Some(window) = window else {
+            self.schedule_render(output);
+            return false;
+        };
+
+        self.activate_window(&window);
+
+        true
+    }
+}
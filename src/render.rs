@@ -2,14 +2,20 @@
 
 use std::{ops::Deref, sync::Mutex};
 
+use anyhow::{anyhow, ensure};
 use smithay::{
-    backend::renderer::{
-        element::{
-            surface::WaylandSurfaceRenderElement,
-            utils::{CropRenderElement, RelocateRenderElement, RescaleRenderElement},
-            AsRenderElements, RenderElementStates, Wrap,
+    backend::{
+        allocator::Fourcc,
+        renderer::{
+            damage::OutputDamageTracker,
+            element::{
+                memory::{MemoryRenderBuffer, MemoryRenderBufferRenderElement},
+                surface::WaylandSurfaceRenderElement,
+                utils::{CropRenderElement, RelocateRenderElement, RescaleRenderElement},
+                AsRenderElements, Kind, RenderElementStates, Wrap,
+            },
+            Bind, ExportMem, ImportAll, ImportMem, Offscreen, Renderer, Texture,
         },
-        ImportAll, ImportMem, Renderer, Texture,
     },
     desktop::{
         layer_map_for_output,
@@ -24,18 +30,21 @@ use smithay::{
     output::Output,
     reexports::wayland_server::protocol::wl_surface::WlSurface,
     render_elements,
-    utils::{Logical, Physical, Point, Scale},
+    utils::{Logical, Physical, Point, Rectangle, Scale, Size, Transform},
     wayland::{compositor, shell::wlr_layer},
 };
 
 use crate::{
     backend::Backend,
-    state::{State, WithState},
+    hint::{WindowHint, WindowHints},
+    overview::OverviewTarget,
+    state::{Pinnacle, State, WithState},
     window::WindowElement,
 };
 
 use self::pointer::{PointerElement, PointerRenderElement};
 
+pub mod frame_timing;
 pub mod pointer;
 
 render_elements! {
@@ -51,6 +60,32 @@ render_elements! {
     Surface = WaylandSurfaceRenderElement<R>,
     Pointer = PointerRenderElement<R>,
     Transform = TransformRenderElement<R, E>,
+    Memory = MemoryRenderBufferRenderElement<R>,
+}
+
+/// State for dimming outputs that don't have keyboard focus.
+#[derive(Debug)]
+pub struct OutputDimState {
+    /// A single black pixel stretched over dimmed outputs
+    buffer: MemoryRenderBuffer,
+    /// The output that had focus when outputs were last rendered
+    focused_output: Option<Output>,
+}
+
+impl Default for OutputDimState {
+    fn default() -> Self {
+        Self {
+            buffer: MemoryRenderBuffer::from_slice(
+                &[0, 0, 0, 0xff],
+                Fourcc::Argb8888,
+                (1, 1),
+                1,
+                Transform::Normal,
+                None,
+            ),
+            focused_output: None,
+        }
+    }
 }
 
 impl<R> AsRenderElements<R> for WindowElement
@@ -83,6 +118,7 @@ fn layer_render_elements<R>(
     output: &Output,
     renderer: &mut R,
     scale: Scale<f64>,
+    offset: Point<i32, Physical>,
 ) -> LayerRenderElements<R>
 where
     R: Renderer + ImportAll,
@@ -102,7 +138,7 @@ where
                 .map(|geo| (surface, geo.loc))
         })
         .map(|(surface, loc)| {
-            let loc = loc.to_physical_precise_round(scale);
+            let loc = loc.to_physical_precise_round(scale) - offset;
             let render_elements = surface
                 .render_elements::<WaylandSurfaceRenderElement<R>>(renderer, loc, scale, 1.0);
             (surface.layer(), render_elements)
@@ -136,6 +172,7 @@ fn window_render_elements<R>(
     space: &Space<WindowElement>,
     renderer: &mut R,
     scale: Scale<f64>,
+    offset: Point<i32, Physical>,
 ) -> (
     Vec<OutputRenderElement<R, WaylandSurfaceRenderElement<R>>>,
     Vec<OutputRenderElement<R, WaylandSurfaceRenderElement<R>>>,
@@ -156,17 +193,34 @@ where
                 last_fullscreen_split_at = i + 1;
             }
 
+            let space_loc = space.element_location(win).unwrap_or((0, 0).into());
+
+            // Floating windows can have a fractional location. Use it so the window snaps
+            // to the nearest physical pixel instead of the nearest logical one.
+            let win_loc = win.precise_loc(space_loc);
+
+            // Scaled windows are scaled around the top left of their geometry
+            // so they stay in place while growing towards the bottom right
+            let factor = win.with_state(|state| state.scale);
+
             // subtract win.geometry().loc to align decorations correctly
-            let loc = (
-                space.element_location(win) .unwrap_or((0, 0).into())
-                    - win.geometry().loc
-                    - output.current_location()
-                )
-                .to_physical_precise_round(scale);
+            let loc = (win_loc
+                - win.geometry().loc.to_f64().upscale(factor)
+                - output.current_location().to_f64())
+            .to_physical_precise_round(scale)
+                - offset;
 
             win.render_elements::<WaylandSurfaceRenderElement<R>>(renderer, loc, scale, 1.0)
                 .into_iter()
-                .map(OutputRenderElement::from)
+                .map(move |elem| {
+                    if factor == 1.0 {
+                        OutputRenderElement::from(elem)
+                    } else {
+                        OutputRenderElement::from(TransformRenderElement::from(
+                            RescaleRenderElement::from_element(elem, loc, factor),
+                        ))
+                    }
+                })
         }).collect::<Vec<_>>();
 
     let rest = fullscreen_and_up.split_off(last_fullscreen_split_at);
@@ -174,6 +228,128 @@ where
     (fullscreen_and_up, rest)
 }
 
+/// Get render elements for windows in an overview.
+///
+/// Windows are interpolated between their current location and size and their
+/// overview targets according to `progress`.
+fn overview_render_elements<R>(
+    output: &Output,
+    targets: &[OverviewTarget],
+    progress: f64,
+    space: &Space<WindowElement>,
+    renderer: &mut R,
+    scale: Scale<f64>,
+    offset: Point<i32, Physical>,
+) -> Vec<OutputRenderElement<R, WaylandSurfaceRenderElement<R>>>
+where
+    R: Renderer + ImportAll + ImportMem,
+    <R as Renderer>::TextureId: Clone + 'static,
+{
+    let output_loc = output.current_location().to_f64();
+
+    targets
+        .iter()
+        .flat_map(|target| {
+            let win = &target.window;
+            let on_active_tag = win.is_on_active_tag();
+
+            // Windows on inactive tags aren't visible, so they fade in at their target instead
+            let from_loc = space
+                .element_location(win)
+                .filter(|_| on_active_tag)
+                .map(|loc| loc.to_f64())
+                .unwrap_or(target.loc);
+
+            let loc = from_loc + (target.loc - from_loc).upscale(progress);
+            let factor = 1.0 + (target.scale - 1.0) * progress;
+            let alpha = if on_active_tag { 1.0 } else { progress as f32 };
+
+            // subtract win.geometry().loc to align decorations correctly
+            let render_loc = (loc - output_loc - win.geometry().loc.to_f64().upscale(factor))
+                .to_physical_precise_round(scale)
+                - offset;
+
+            win.render_elements::<WaylandSurfaceRenderElement<R>>(
+                renderer, render_loc, scale, alpha,
+            )
+            .into_iter()
+            .map(move |elem| {
+                OutputRenderElement::from(TransformRenderElement::from(
+                    RescaleRenderElement::from_element(elem, render_loc, factor),
+                ))
+            })
+        })
+        .collect()
+}
+
+/// Get render elements for the labels of window hints on `output`.
+fn hint_render_elements<R>(
+    output: &Output,
+    window_hints: &WindowHints,
+    space: &Space<WindowElement>,
+    renderer: &mut R,
+    scale: Scale<f64>,
+    offset: Point<i32, Physical>,
+) -> Vec<OutputRenderElement<R, WaylandSurfaceRenderElement<R>>>
+where
+    R: Renderer + ImportAll + ImportMem,
+    <R as Renderer>::TextureId: Clone + 'static,
+{
+    let output_loc = output.current_location();
+
+    window_hints
+        .hints
+        .iter()
+        .filter(|hint| space.outputs_for_element(&hint.window).contains(output))
+        .filter_map(|hint| {
+            let window_loc = space.element_location(&hint.window)?;
+            let loc =
+                (hint.location(window_loc) - output_loc).to_physical_precise_round(scale) - offset;
+
+            MemoryRenderBufferRenderElement::from_buffer(
+                renderer,
+                loc.to_f64(),
+                &hint.buffer,
+                None,
+                None,
+                Some(WindowHint::size()),
+                Kind::Unspecified,
+            )
+            .map_err(|err| tracing::warn!("Failed to render window hint: {err}"))
+            .ok()
+        })
+        .map(OutputRenderElement::from)
+        .collect()
+}
+
+/// Get a black overlay over all of `output` with an alpha of `amount`.
+fn dim_render_element<R>(
+    output: &Output,
+    buffer: &MemoryRenderBuffer,
+    amount: f32,
+    space: &Space<WindowElement>,
+    renderer: &mut R,
+) -> Option<OutputRenderElement<R, WaylandSurfaceRenderElement<R>>>
+where
+    R: Renderer + ImportAll + ImportMem,
+    <R as Renderer>::TextureId: Clone + 'static,
+{
+    let size = space.output_geometry(output)?.size;
+
+    MemoryRenderBufferRenderElement::from_buffer(
+        renderer,
+        (0.0, 0.0),
+        buffer,
+        Some(amount),
+        None,
+        Some(size),
+        Kind::Unspecified,
+    )
+    .map_err(|err| tracing::warn!("Failed to render output dim: {err}"))
+    .ok()
+    .map(OutputRenderElement::from)
+}
+
 pub fn pointer_render_elements<R>(
     output: &Output,
     renderer: &mut R,
@@ -237,18 +413,42 @@ where
 ///
 /// Render elements will be pulled from the provided windows,
 /// with the first window being at the top and subsequent ones beneath.
+///
+/// If the output has an overview open, windows will be drawn at their `overview_targets` instead.
+///
+/// If the output is magnified, everything but the pointer is zoomed in around `pointer_location`.
+///
+/// If `window_hints` are being shown, their labels are drawn above everything but the pointer.
+///
+/// If `dim` is set, the output is covered by its buffer with its alpha, beneath window hints.
+#[allow(clippy::too_many_arguments)]
 pub fn output_render_elements<R, T>(
     output: &Output,
     renderer: &mut R,
     space: &Space<WindowElement>,
     windows: &[WindowElement],
+    overview_targets: &[OverviewTarget],
+    window_hints: Option<&WindowHints>,
+    dim: Option<(&MemoryRenderBuffer, f32)>,
+    pointer_location: Point<f64, Logical>,
 ) -> Vec<OutputRenderElement<R, WaylandSurfaceRenderElement<R>>>
 where
     R: Renderer<TextureId = T> + ImportAll + ImportMem,
     <R as Renderer>::TextureId: 'static,
     T: Texture + Clone,
 {
-    let scale = Scale::from(output.current_scale().fractional_scale());
+    let output_scale = output.current_scale().fractional_scale();
+    let magnification = output
+        .with_state(|state| state.magnification)
+        .unwrap_or(1.0);
+
+    // Magnifying renders everything at a larger scale, then shifts it back so whatever is under
+    // the pointer stays there. Because of that the view pans with the pointer and pointer input
+    // still lands where it would without magnification.
+    let scale = Scale::from(output_scale * magnification);
+    let offset = magnifier_focus(output, space, pointer_location)
+        .upscale(magnification - 1.0)
+        .to_physical_precise_round(output_scale);
 
     let mut output_render_elements: Vec<OutputRenderElement<_, _>> = Vec::new();
 
@@ -273,13 +473,29 @@ where
     //     ));
     // });
 
+    if let Some(window_hints) = window_hints {
+        output_render_elements.extend(hint_render_elements(
+            output,
+            window_hints,
+            space,
+            renderer,
+            scale,
+            offset,
+        ));
+    }
+
+    if let Some((buffer, amount)) = dim {
+        output_render_elements.extend(dim_render_element(output, buffer, amount, space, renderer));
+    }
+
     let o_r_elements = override_redirect_windows.iter().flat_map(|surf| {
         surf.render_elements::<WaylandSurfaceRenderElement<R>>(
             renderer,
             space
                 .element_location(surf)
                 .unwrap_or((0, 0).into())
-                .to_physical_precise_round(scale),
+                .to_physical_precise_round(scale)
+                - offset,
             scale,
             1.0,
         )
@@ -294,10 +510,24 @@ where
         bottom,
         top,
         overlay,
-    } = layer_render_elements(output, renderer, scale);
+    } = layer_render_elements(output, renderer, scale, offset);
 
     let (fullscreen_and_up_elements, rest_of_window_elements) =
-        window_render_elements::<R>(output, &windows, space, renderer, scale);
+        match output.with_state(|state| state.overview) {
+            Some(overview) => (
+                overview_render_elements::<R>(
+                    output,
+                    overview_targets,
+                    overview.progress(),
+                    space,
+                    renderer,
+                    scale,
+                    offset,
+                ),
+                Vec::new(),
+            ),
+            None => window_render_elements::<R>(output, &windows, space, renderer, scale, offset),
+        };
 
     // Elements render from top to bottom
 
@@ -311,6 +541,76 @@ where
     output_render_elements
 }
 
+/// Get the point, local to `output`, that the magnifier zooms in on.
+///
+/// This is wherever the pointer is, clamped to the output if the pointer is on another one.
+fn magnifier_focus(
+    output: &Output,
+    space: &Space<WindowElement>,
+    pointer_location: Point<f64, Logical>,
+) -> Point<f64, Logical> {
+    let Some(output_geo) = space.output_geometry(output) else {
+        return (0.0, 0.0).into();
+    };
+
+    let local = pointer_location - output_geo.loc.to_f64();
+    let size = output_geo.size.to_f64();
+
+    (local.x.clamp(0.0, size.w), local.y.clamp(0.0, size.h)).into()
+}
+
+/// Render a single window to an offscreen buffer and read back its contents.
+///
+/// The window is rendered by itself, so this works for windows on inactive tags as well.
+///
+/// Returns the physical size of the capture along with its pixels in RGBA8888.
+pub fn capture_window<R, T>(
+    renderer: &mut R,
+    window: &WindowElement,
+    scale: Scale<f64>,
+) -> anyhow::Result<(Size<i32, Physical>, Vec<u8>)>
+where
+    R: Renderer + ImportAll + ImportMem + Offscreen<T> + ExportMem,
+    <R as Renderer>::TextureId: Texture + Clone + 'static,
+    T: Texture,
+{
+    let geometry = window.geometry();
+    let size = geometry.size.to_physical_precise_round(scale);
+
+    ensure!(size.w > 0 && size.h > 0, "window has no size");
+
+    let buffer_size = size.to_logical(1).to_buffer(1, Transform::Normal);
+
+    let offscreen: T = renderer
+        .create_buffer(Fourcc::Abgr8888, buffer_size)
+        .map_err(|err| anyhow!("{err}"))?;
+    renderer.bind(offscreen).map_err(|err| anyhow!("{err}"))?;
+
+    // subtract geometry.loc to align decorations correctly
+    let loc = (Point::from((0, 0)) - geometry.loc).to_physical_precise_round(scale);
+    let elements =
+        window.render_elements::<WaylandSurfaceRenderElement<R>>(renderer, loc, scale, 1.0);
+
+    let mut damage_tracker = OutputDamageTracker::new(size, scale, Transform::Normal);
+    damage_tracker
+        .render_output(renderer, 0, &elements, [0.0, 0.0, 0.0, 0.0])
+        .map_err(|err| anyhow!("{err:?}"))?;
+
+    let mapping = renderer
+        .copy_framebuffer(
+            Rectangle::from_loc_and_size((0, 0), buffer_size),
+            Fourcc::Abgr8888,
+        )
+        .map_err(|err| anyhow!("{err}"))?;
+
+    let pixels = renderer
+        .map_texture(&mapping)
+        .map_err(|err| anyhow!("{err}"))?
+        .to_vec();
+
+    Ok((size, pixels))
+}
+
 // TODO: docs
 pub fn take_presentation_feedback(
     output: &Output,
@@ -345,6 +645,16 @@ pub fn take_presentation_feedback(
     output_presentation_feedback
 }
 
+impl Pinnacle {
+    /// Get the overlay to dim `output` with and its alpha,
+    /// or `None` if it has focus or dimming is off.
+    pub fn output_dim(&self, output: &Output) -> Option<(&MemoryRenderBuffer, f32)> {
+        let amount = self.config.inactive_output_dim;
+        (amount > 0.0 && self.focused_output() != Some(output))
+            .then_some((&self.output_dim_state.buffer, amount))
+    }
+}
+
 impl State {
     /// Schedule a new render. This does nothing on the winit backend.
     pub fn schedule_render(&mut self, output: &Output) {
@@ -352,4 +662,22 @@ impl State {
             udev.schedule_render(&self.pinnacle.loop_handle, output);
         }
     }
+
+    /// Rerender all outputs if the focused output changed while inactive outputs are dimmed,
+    /// moving the dim off of the newly focused output.
+    pub fn update_output_dim(&mut self) {
+        let focused_output = self.pinnacle.focused_output().cloned();
+        if self.pinnacle.output_dim_state.focused_output == focused_output {
+            return;
+        }
+        self.pinnacle.output_dim_state.focused_output = focused_output;
+
+        if self.pinnacle.config.inactive_output_dim <= 0.0 {
+            return;
+        }
+
+        for output in self.pinnacle.space.outputs().cloned().collect::<Vec<_>>() {
+            self.schedule_render(&output);
+        }
+    }
 }
@@ -0,0 +1,184 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Per-output frame timing collection for performance debugging.
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use pinnacle_api_defs::pinnacle::render::v0alpha1::WatchFrameTimingsResponse;
+use smithay::{
+    output::Output,
+    reexports::calloop::{
+        timer::{TimeoutAction, Timer},
+        RegistrationToken,
+    },
+};
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::{info, warn};
+
+use crate::state::Pinnacle;
+
+/// Timing data for a single rendered frame.
+#[derive(Debug, Clone, Copy)]
+pub struct FrameTiming {
+    /// How long it took to render the frame
+    pub render_time: Duration,
+    /// How many damaged regions were redrawn, if known
+    pub damage_rects: Option<usize>,
+    /// Whether the frame was directly scanned out from a client buffer
+    pub direct_scanout: bool,
+    /// Whether the cursor was put on a hardware cursor plane instead of being composited
+    pub cursor_plane: bool,
+}
+
+/// Accumulated frame timings for one output over a reporting interval.
+#[derive(Debug, Default, Clone, Copy)]
+struct OutputFrameStats {
+    frames: u32,
+    total_render_time: Duration,
+    max_render_time: Duration,
+    damage_rects: Option<usize>,
+    direct_scanout_frames: u32,
+    cursor_plane_frames: u32,
+}
+
+/// Collects frame timings and periodically reports a summary per output.
+///
+/// Summaries are logged and sent to every watcher. They're reported on a timer,
+/// so outputs that didn't render anything are reported with zero frames.
+#[derive(Debug)]
+pub struct FrameTimings {
+    /// How often summaries are reported, or `None` if collection is disabled
+    interval: Option<Duration>,
+    last_report: Instant,
+    stats: HashMap<String, OutputFrameStats>,
+    watchers: Vec<UnboundedSender<Result<WatchFrameTimingsResponse, tonic::Status>>>,
+    /// The timer that reports summaries
+    timer_token: Option<RegistrationToken>,
+}
+
+impl Default for FrameTimings {
+    fn default() -> Self {
+        Self {
+            interval: None,
+            last_report: Instant::now(),
+            stats: HashMap::new(),
+            watchers: Vec::new(),
+            timer_token: None,
+        }
+    }
+}
+
+impl FrameTimings {
+    /// Send future summaries to `sender`.
+    pub fn add_watcher(
+        &mut self,
+        sender: UnboundedSender<Result<WatchFrameTimingsResponse, tonic::Status>>,
+    ) {
+        self.watchers.push(sender);
+    }
+
+    /// Record a frame rendered on `output`.
+    pub fn record(&mut self, output: &Output, timing: FrameTiming) {
+        if self.interval.is_none() {
+            return;
+        }
+
+        let stats = self.stats.entry(output.name()).or_default();
+        stats.frames += 1;
+        stats.total_render_time += timing.render_time;
+        stats.max_render_time = stats.max_render_time.max(timing.render_time);
+        if let Some(damage_rects) = timing.damage_rects {
+            *stats.damage_rects.get_or_insert(0) += damage_rects;
+        }
+        if timing.direct_scanout {
+            stats.direct_scanout_frames += 1;
+        }
+        if timing.cursor_plane {
+            stats.cursor_plane_frames += 1;
+        }
+    }
+
+    /// Report a summary for each of `output_names` and start a new interval.
+    ///
+    /// Timings from outputs that aren't in `output_names` anymore are dropped.
+    fn report(&mut self, output_names: Vec<String>) {
+        let elapsed = self.last_report.elapsed();
+
+        for output_name in output_names {
+            let stats = self.stats.remove(&output_name).unwrap_or_default();
+            let average_render_time = stats.total_render_time / stats.frames.max(1);
+
+            info!(
+                output = %output_name,
+                frames = stats.frames,
+                fps = stats.frames as f64 / elapsed.as_secs_f64(),
+                avg_render_time = ?average_render_time,
+                max_render_time = ?stats.max_render_time,
+                damage_rects = ?stats.damage_rects,
+                direct_scanout_frames = stats.direct_scanout_frames,
+                cursor_plane_frames = stats.cursor_plane_frames,
+                "Frame timings"
+            );
+
+            let response = WatchFrameTimingsResponse {
+                output_name: Some(output_name),
+                frames: Some(stats.frames),
+                interval_ms: Some(elapsed.as_millis() as u32),
+                average_render_time_us: Some(average_render_time.as_micros() as u64),
+                max_render_time_us: Some(stats.max_render_time.as_micros() as u64),
+                damage_rects: stats.damage_rects.map(|rects| rects as u32),
+                direct_scanout_frames: Some(stats.direct_scanout_frames),
+                cursor_plane_frames: Some(stats.cursor_plane_frames),
+            };
+
+            self.watchers
+                .retain(|watcher| watcher.send(Ok(response.clone())).is_ok());
+        }
+
+        self.stats.clear();
+        self.last_report = Instant::now();
+    }
+}
+
+impl Pinnacle {
+    /// Set how often frame timing summaries are reported, or disable them with `None`
+    /// or a zero interval.
+    ///
+    /// This discards any timings collected so far.
+    pub fn set_frame_timings_interval(&mut self, interval: Option<Duration>) {
+        let interval = interval.filter(|interval| !interval.is_zero());
+
+        if let Some(token) = self.frame_timings.timer_token.take() {
+            self.loop_handle.remove(token);
+        }
+
+        self.frame_timings.interval = interval;
+        self.frame_timings.stats.clear();
+        self.frame_timings.last_report = Instant::now();
+
+        let Some(interval) = interval else {
+            return;
+        };
+
+        let insert_ret =
+            self.loop_handle
+                .insert_source(Timer::from_duration(interval), move |_, _, state| {
+                    let output_names = state
+                        .pinnacle
+                        .space
+                        .outputs()
+                        .map(|output| output.name())
+                        .collect();
+                    state.pinnacle.frame_timings.report(output_names);
+                    TimeoutAction::ToDuration(interval)
+                });
+
+        match insert_ret {
+            Ok(token) => self.frame_timings.timer_token = Some(token),
+            Err(err) => warn!("Failed to start frame timing reports: {err}"),
+        }
+    }
+}
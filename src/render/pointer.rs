@@ -15,6 +15,11 @@ use smithay::{
     utils::{Physical, Point, Scale},
 };
 
+/// The cursor image.
+///
+/// This renders as [`element::Kind::Cursor`] by default so the udev backend can put it on
+/// a hardware cursor plane, letting the pointer move without recompositing the output.
+/// Cursors larger than the plane, or outputs without one, fall back to being composited.
 pub struct PointerElement<T: Texture> {
     texture: Option<TextureBuffer<T>>,
     status: CursorImageStatus,
@@ -6,9 +6,13 @@ use crate::{
     config::Config,
     cursor::Cursor,
     focus::OutputFocusStack,
-    grab::resize_grab::ResizeSurfaceState,
+    grab::{resize_grab::ResizeSurfaceState, ActiveGrab},
+    hint::WindowHints,
+    idle::IdleState,
     layout::LayoutState,
+    output::{GammaPreset, OutputName},
     protocol::{gamma_control::GammaControlManagerState, screencopy::ScreencopyManagerState},
+    render::{frame_timing::FrameTimings, OutputDimState},
     window::WindowElement,
 };
 use anyhow::Context;
@@ -27,8 +31,10 @@ use smithay::{
     utils::{Clock, Monotonic, Point, Size},
     wayland::{
         compositor::{self, CompositorClientState, CompositorState},
+        content_type::ContentTypeState,
         dmabuf::DmabufFeedback,
         fractional_scale::FractionalScaleManagerState,
+        idle_inhibit::IdleInhibitManagerState,
         output::OutputManagerState,
         relative_pointer::RelativePointerManagerState,
         selection::{
@@ -39,10 +45,11 @@ use smithay::{
         shm::ShmState,
         socket::ListeningSocketSource,
         viewporter::ViewporterState,
+        xdg_activation::XdgActivationState,
     },
     xwayland::{X11Wm, XWayland, XWaylandEvent},
 };
-use std::{cell::RefCell, path::PathBuf, sync::Arc, time::Duration};
+use std::{cell::RefCell, collections::HashMap, path::PathBuf, sync::Arc, time::Duration};
 use sysinfo::{ProcessRefreshKind, RefreshKind};
 use tracing::{error, info, warn};
 use xdg::BaseDirectories;
@@ -82,22 +89,51 @@ pub struct Pinnacle {
     pub screencopy_manager_state: ScreencopyManagerState,
     pub gamma_control_manager_state: GammaControlManagerState,
     pub relative_pointer_manager_state: RelativePointerManagerState,
+    pub xdg_activation_state: XdgActivationState,
+    pub content_type_state: ContentTypeState,
+    pub idle_inhibit_manager_state: IdleInhibitManagerState,
 
     /// The state of key and mousebinds along with libinput settings
     pub input_state: InputState,
+    /// Idle actions from the config and surfaces inhibiting them
+    pub idle_state: IdleState,
+    /// Gamma the compositor keeps on outputs, even across replugs
+    pub gamma_presets: HashMap<OutputName, GammaPreset>,
 
     pub output_focus_stack: OutputFocusStack,
     pub z_index_stack: Vec<WindowElement>,
+    /// Windows in the order they were focused on any output, with the most recently focused last.
+    ///
+    /// Each output also keeps its own history in [`OutputState::focus_history`](crate::output::OutputState::focus_history).
+    pub focus_history: Vec<WindowElement>,
 
     pub popup_manager: PopupManager,
 
+    /// The interactive move or resize that is in progress, if any
+    pub active_grab: Option<ActiveGrab>,
+    /// Hints drawn over windows while waiting for one to be picked
+    pub window_hints: Option<WindowHints>,
+
     pub cursor_status: CursorImageStatus,
     pub dnd_icon: Option<WlSurface>,
 
+    /// The mime types advertised by the current clipboard selection
+    pub clipboard_mime_types: Vec<String>,
+    /// The mime types advertised by the current primary selection
+    pub primary_selection_mime_types: Vec<String>,
+    /// The mime types advertised by the source of an ongoing drag-and-drop
+    pub dnd_mime_types: Vec<String>,
+
     /// The main window vec
     pub windows: Vec<WindowElement>,
     pub new_windows: Vec<WindowElement>,
 
+    /// Whether all outputs have disconnected
+    pub headless: bool,
+    /// Windows left without an output after being headless, waiting for active tags
+    /// on the output that connected
+    pub parked_windows: Vec<WindowElement>,
+
     pub config: Config,
 
     // xwayland stuff
@@ -115,6 +151,10 @@ pub struct Pinnacle {
     pub signal_state: SignalState,
 
     pub layout_state: LayoutState,
+
+    /// Frame timings collected for performance debugging
+    pub frame_timings: FrameTimings,
+    pub output_dim_state: OutputDimState,
 }
 
 impl State {
@@ -153,6 +193,11 @@ impl State {
             info!("Fd raise success!");
         }
 
+        warn!(
+            "The deprecated `wl_shell` protocol is not supported; \
+            apps that only support `wl_shell` need a compatibility layer like XWayland"
+        );
+
         loop_handle.insert_source(socket, |stream, _metadata, state| {
             state
                 .pinnacle
@@ -280,11 +325,22 @@ impl State {
                 relative_pointer_manager_state: RelativePointerManagerState::new::<Self>(
                     &display_handle,
                 ),
+                xdg_activation_state: XdgActivationState::new::<Self>(&display_handle),
+                content_type_state: ContentTypeState::new::<Self>(&display_handle),
+                // `wl_shell` is deliberately not advertised. Windows can only be created
+                // from xdg-shell toplevels or X11 surfaces, so `wl_shell` surfaces could never
+                // be mapped, and clients that prefer `wl_shell` when it's advertised would pick
+                // it over xdg-shell and show nothing. Apps that only support `wl_shell`, like
+                // ones forced onto Qt's `wl-shell` integration before Qt 5.11, need XWayland.
+                idle_inhibit_manager_state: IdleInhibitManagerState::new::<Self>(&display_handle),
 
                 input_state: InputState::new(),
+                idle_state: IdleState::default(),
+                gamma_presets: HashMap::new(),
 
                 output_focus_stack: OutputFocusStack::default(),
                 z_index_stack: Vec::new(),
+                focus_history: Vec::new(),
 
                 config: Config::new(no_config, config_dir),
 
@@ -292,11 +348,21 @@ impl State {
 
                 dnd_icon: None,
 
+                clipboard_mime_types: Vec::new(),
+                primary_selection_mime_types: Vec::new(),
+                dnd_mime_types: Vec::new(),
+
                 popup_manager: PopupManager::default(),
 
+                active_grab: None,
+                window_hints: None,
+
                 windows: Vec::new(),
                 new_windows: Vec::new(),
 
+                headless: false,
+                parked_windows: Vec::new(),
+
                 xwayland,
                 xwm: None,
                 xdisplay: None,
@@ -313,6 +379,9 @@ impl State {
                 signal_state: SignalState::default(),
 
                 layout_state: LayoutState::default(),
+
+                frame_timings: FrameTimings::default(),
+                output_dim_state: OutputDimState::default(),
             },
         };
 
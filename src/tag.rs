@@ -5,14 +5,25 @@ use std::{
     hash::Hash,
     rc::Rc,
     sync::atomic::{AtomicU32, Ordering},
+    time::Duration,
 };
 
-use smithay::output::Output;
+use smithay::{
+    output::Output,
+    reexports::calloop::timer::{TimeoutAction, Timer},
+    utils::IsAlive,
+};
 
-use crate::state::{Pinnacle, State, WithState};
+use crate::{
+    state::{Pinnacle, WithState},
+    window::WindowElement,
+};
 
 static TAG_ID_COUNTER: AtomicU32 = AtomicU32::new(0);
 
+/// How long a window rejected by a full tag has to close before it's let back onto its tags.
+pub const REJECTED_WINDOW_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// A unique id for a [`Tag`].
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy, serde::Serialize, serde::Deserialize)]
 pub struct TagId(pub u32);
@@ -47,8 +58,64 @@ struct TagInner {
     id: TagId,
     /// The name of this tag.
     name: String,
+    /// The name shown for this tag instead of `name`, if set.
+    display_name: Option<String>,
     /// Whether this tag is active or not.
     active: bool,
+    /// The gaps applied to tiled windows when this tag is focused.
+    gaps: Gaps,
+    /// Whether floating windows with this tag are hidden.
+    floating_hidden: bool,
+    /// The most windows that can tile on this tag, if limited.
+    window_limit: Option<WindowLimit>,
+    /// How layouts split the master area from the stack on this tag, if overridden.
+    tiling_direction: Option<TilingDirection>,
+}
+
+/// A cap on how many windows tile on a tag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowLimit {
+    /// The most tiled windows the tag can have
+    pub max: u32,
+    /// What happens to new windows that would go over the limit
+    pub overflow: OverflowPolicy,
+}
+
+/// What happens to a new window that would tile on a tag that's at its window limit.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Make the window floating
+    #[default]
+    Float,
+    /// Move the window to the next tag on the same output that isn't full,
+    /// making it floating if there is none
+    NextTag,
+    /// Close the window
+    Reject,
+}
+
+/// Which way a layout splits its master area from the stack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TilingDirection {
+    /// The master area is beside the stack
+    Horizontal,
+    /// The master area is above or below the stack
+    Vertical,
+}
+
+/// Gaps the compositor applies around tiled windows, in logical pixels.
+///
+/// These are applied on top of whatever geometries the layout client responds with.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Gaps {
+    /// The gap around each individual window
+    pub inner: u32,
+    /// The gap inset from the edges of the output
+    pub outer: u32,
+    /// Whether to remove gaps when there is only one tiled window.
+    ///
+    /// This also drops the gap the layout client reports leaving around the window.
+    pub smart: bool,
 }
 
 impl PartialEq for TagInner {
@@ -76,14 +143,54 @@ impl Tag {
         self.0.borrow().name.clone()
     }
 
+    pub fn display_name(&self) -> Option<String> {
+        self.0.borrow().display_name.clone()
+    }
+
+    pub fn set_display_name(&self, display_name: Option<String>) {
+        self.0.borrow_mut().display_name = display_name;
+    }
+
     pub fn active(&self) -> bool {
         self.0.borrow().active
     }
 
-    pub fn set_active(&self, active: bool, state: &mut State) {
+    pub fn gaps(&self) -> Gaps {
+        self.0.borrow().gaps
+    }
+
+    pub fn set_gaps(&self, gaps: Gaps) {
+        self.0.borrow_mut().gaps = gaps;
+    }
+
+    pub fn floating_hidden(&self) -> bool {
+        self.0.borrow().floating_hidden
+    }
+
+    pub fn set_floating_hidden(&self, hidden: bool) {
+        self.0.borrow_mut().floating_hidden = hidden;
+    }
+
+    pub fn window_limit(&self) -> Option<WindowLimit> {
+        self.0.borrow().window_limit
+    }
+
+    pub fn set_window_limit(&self, limit: Option<WindowLimit>) {
+        self.0.borrow_mut().window_limit = limit;
+    }
+
+    pub fn tiling_direction(&self) -> Option<TilingDirection> {
+        self.0.borrow().tiling_direction
+    }
+
+    pub fn set_tiling_direction(&self, direction: Option<TilingDirection>) {
+        self.0.borrow_mut().tiling_direction = direction;
+    }
+
+    pub fn set_active(&self, active: bool, pinnacle: &mut Pinnacle) {
         self.0.borrow_mut().active = active;
 
-        state.pinnacle.signal_state.tag_active.signal(|buf| {
+        pinnacle.signal_state.tag_active.signal(|buf| {
             buf.push_back(
                 pinnacle_api_defs::pinnacle::signal::v0alpha1::TagActiveResponse {
                     tag_id: Some(self.id().0),
@@ -99,7 +206,12 @@ impl Tag {
         Self(Rc::new(RefCell::new(TagInner {
             id: TagId::next(),
             name,
+            display_name: None,
             active: false,
+            gaps: Gaps::default(),
+            floating_hidden: false,
+            window_limit: None,
+            tiling_direction: None,
         })))
     }
 
@@ -114,3 +226,277 @@ impl Tag {
             .cloned()
     }
 }
+
+impl Pinnacle {
+    /// Returns whether `tag` has as many tiled windows as its window limit allows,
+    /// not counting `window`.
+    fn is_tag_full(&self, tag: &Tag, window: &WindowElement) -> bool {
+        let Some(limit) = tag.window_limit() else {
+            return false;
+        };
+
+        let tiled_count = self
+            .windows
+            .iter()
+            .filter(|win| *win != window)
+            .filter(|win| {
+                win.with_state(|state| {
+                    state.floating_or_tiled.is_tiled() && state.tags.contains(tag)
+                })
+            })
+            .count();
+
+        tiled_count >= limit.max as usize
+    }
+
+    /// Handle a new window that would tile on a tag that's at its window limit,
+    /// according to that tag's overflow policy.
+    ///
+    /// This should be called after window rules have been applied.
+    ///
+    /// Returns false if the window was rejected and is being closed. The window is unmapped
+    /// until it closes. If its client ignores the close for [`REJECTED_WINDOW_TIMEOUT`],
+    /// it's put back on its original tags as a floating window instead.
+    pub fn apply_tag_window_limits(&mut self, window: &WindowElement) -> bool {
+        if window.with_state(|state| state.floating_or_tiled.is_floating()) {
+            return true;
+        }
+
+        let full_tag = window.with_state(|state| {
+            state
+                .tags
+                .iter()
+                .find(|tag| self.is_tag_full(tag, window))
+                .cloned()
+        });
+
+        let Some(full_tag) = full_tag else {
+            return true;
+        };
+
+        let overflow = full_tag
+            .window_limit()
+            .map(|limit| limit.overflow)
+            .unwrap_or_default();
+
+        tracing::debug!(
+            "Tag {} is full, handling new window with {overflow:?}",
+            full_tag.name()
+        );
+
+        match overflow {
+            OverflowPolicy::Float => window.toggle_floating(),
+            OverflowPolicy::NextTag => {
+                let next_tag = full_tag.output(self).and_then(|output| {
+                    output.with_state(|state| {
+                        state
+                            .tags
+                            .iter()
+                            .skip_while(|tag| *tag != &full_tag)
+                            .skip(1)
+                            .find(|tag| !self.is_tag_full(tag, window))
+                            .cloned()
+                    })
+                });
+
+                match next_tag {
+                    Some(tag) => window.with_state_mut(|state| {
+                        state.tags = std::iter::once(tag).collect();
+                    }),
+                    None => window.toggle_floating(),
+                }
+            }
+            OverflowPolicy::Reject => {
+                // Take the window off its tags so it isn't shown while it closes
+                let tags = window.with_state_mut(|state| std::mem::take(&mut state.tags));
+                self.space.unmap_elem(window);
+                window.close();
+
+                let window = window.clone();
+                let insert_ret = self.loop_handle.insert_source(
+                    Timer::from_duration(REJECTED_WINDOW_TIMEOUT),
+                    move |_, _, state| {
+                        state
+                            .pinnacle
+                            .restore_rejected_window(&window, tags.clone());
+                        TimeoutAction::Drop
+                    },
+                );
+
+                if let Err(err) = insert_ret {
+                    tracing::warn!("Failed to start timeout for rejected window: {err}");
+                }
+
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Put a window that was rejected by a full tag back on `tags` if it still hasn't closed.
+    ///
+    /// It floats so the tag's window limit still holds for tiled windows.
+    fn restore_rejected_window(&mut self, window: &WindowElement, tags: TagSet) {
+        if !window.alive() || !self.windows.contains(window) {
+            return;
+        }
+
+        tracing::debug!("Rejected window didn't close, putting it back on its tags");
+
+        window.with_state_mut(|state| state.tags = tags);
+        if window.with_state(|state| state.floating_or_tiled.is_tiled()) {
+            window.toggle_floating();
+        }
+
+        if let Some(output) = window.output(self) {
+            output.with_state_mut(|state| state.focus_stack.add_unfocused(window.clone()));
+            self.request_layout(&output);
+        }
+    }
+}
+
+/// A set of tags that keeps the order tags were added in.
+///
+/// Windows use this to store their tags. The first tag determines which output
+/// a window is on, so insertion order is preserved.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TagSet(Vec<Tag>);
+
+impl TagSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `tag` to the end of this set.
+    ///
+    /// Returns false and does nothing if the tag was already in the set.
+    pub fn insert(&mut self, tag: Tag) -> bool {
+        if self.contains(&tag) {
+            return false;
+        }
+        self.0.push(tag);
+        true
+    }
+
+    /// Remove `tag` from this set.
+    ///
+    /// Returns whether the tag was in the set.
+    pub fn remove(&mut self, tag: &Tag) -> bool {
+        let len = self.0.len();
+        self.0.retain(|tg| tg != tag);
+        self.0.len() != len
+    }
+
+    /// Add `tag` if it isn't in this set, otherwise remove it.
+    pub fn toggle(&mut self, tag: Tag) {
+        if !self.remove(&tag) {
+            self.0.push(tag);
+        }
+    }
+
+    /// Replace the tag in this set that has the same id as `tag` with `tag`.
+    ///
+    /// This is used to point windows at new tags with reused ids after a config reload.
+    pub fn replace(&mut self, tag: Tag) {
+        if let Some(tg) = self.0.iter_mut().find(|tg| tg.id() == tag.id()) {
+            *tg = tag;
+        }
+    }
+
+    pub fn contains(&self, tag: &Tag) -> bool {
+        self.0.contains(tag)
+    }
+
+    /// Get the tag that was added first.
+    pub fn first(&self) -> Option<&Tag> {
+        self.0.first()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Tag> {
+        self.0.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl FromIterator<Tag> for TagSet {
+    fn from_iter<T: IntoIterator<Item = Tag>>(iter: T) -> Self {
+        let mut set = Self::new();
+        for tag in iter {
+            set.insert(tag);
+        }
+        set
+    }
+}
+
+impl<'a> IntoIterator for &'a TagSet {
+    type Item = &'a Tag;
+    type IntoIter = std::slice::Iter<'a, Tag>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tag_set_insert_is_idempotent() {
+        let tag = Tag::new("1".to_string());
+        let mut set = TagSet::new();
+
+        assert!(set.insert(tag.clone()));
+        assert!(!set.insert(tag.clone()));
+
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn tag_set_remove_is_idempotent() {
+        let tag = Tag::new("1".to_string());
+        let mut set = TagSet::from_iter([tag.clone()]);
+
+        assert!(set.remove(&tag));
+        assert!(!set.remove(&tag));
+
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn tag_set_toggle_adds_then_removes() {
+        let tag = Tag::new("1".to_string());
+        let mut set = TagSet::new();
+
+        set.toggle(tag.clone());
+        assert!(set.contains(&tag));
+
+        set.toggle(tag.clone());
+        assert!(!set.contains(&tag));
+    }
+
+    #[test]
+    fn tag_set_keeps_insertion_order_without_duplicates() {
+        let tags = ["1", "2", "3"].map(|name| Tag::new(name.to_string()));
+        let set = TagSet::from_iter([
+            tags[1].clone(),
+            tags[0].clone(),
+            tags[1].clone(),
+            tags[2].clone(),
+        ]);
+
+        assert_eq!(
+            set.iter().cloned().collect::<Vec<_>>(),
+            vec![tags[1].clone(), tags[0].clone(), tags[2].clone()]
+        );
+        assert_eq!(set.first(), Some(&tags[1]));
+    }
+}
@@ -2,25 +2,71 @@
 
 pub mod rules;
 
-use std::{cell::RefCell, ops::Deref};
+use std::{cell::RefCell, ops::Deref, process::Stdio, time::Instant};
 
 use smithay::{
-    desktop::{space::SpaceElement, Window, WindowSurface},
+    backend::renderer::utils::with_renderer_surface_state,
+    desktop::{layer_map_for_output, space::SpaceElement, Window, WindowSurface},
     output::Output,
-    reexports::wayland_server::protocol::wl_surface::WlSurface,
-    utils::{IsAlive, Logical, Point, Rectangle},
-    wayland::{compositor, seat::WaylandFocus, shell::xdg::XdgToplevelSurfaceData},
+    reexports::{
+        calloop::timer::{TimeoutAction, Timer},
+        wayland_protocols::{
+            wp::content_type::v1::server::wp_content_type_v1, xdg::shell::server::xdg_toplevel,
+        },
+        wayland_server::{protocol::wl_surface::WlSurface, Resource},
+    },
+    utils::{Buffer, IsAlive, Logical, Point, Rectangle, Size},
+    wayland::{
+        compositor::{self, SurfaceAttributes},
+        content_type::ContentTypeSurfaceCachedState,
+        fractional_scale,
+        seat::WaylandFocus,
+        shell::xdg::XdgToplevelSurfaceData,
+        viewporter::ViewportCachedState,
+    },
 };
 
-use crate::state::{Pinnacle, WithState};
+use tracing::warn;
 
-use self::window_state::WindowElementState;
+use crate::{
+    handlers::FractionalScaleSurface,
+    state::{Pinnacle, WithState},
+    tag::TagSet,
+};
+
+use self::window_state::{FloatingOrTiled, RelativeGeometry, SnapRegion, WindowElementState};
 
 pub mod window_state;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct WindowElement(Window);
 
+/// The kind of content a window displays, as declared through `wp_content_type_v1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum ContentType {
+    None,
+    Photo,
+    Video,
+    Game,
+}
+
+/// How a window's client scales its main surface, for diagnosing blurry windows.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SurfaceInfo {
+    /// The version of `wl_surface` the client bound
+    pub wl_surface_version: u32,
+    /// The buffer scale from `wl_surface.set_buffer_scale`
+    pub buffer_scale: i32,
+    /// The size of the last committed buffer, if there is one
+    pub buffer_size: Option<Size<i32, Buffer>>,
+    /// Whether the surface has a source or destination set through `wp_viewporter`
+    pub uses_viewporter: bool,
+    /// Whether the client asked for `wp_fractional_scale_v1` updates on the surface
+    pub uses_fractional_scale: bool,
+    /// The fractional scale last sent to the surface
+    pub preferred_scale: Option<f64>,
+}
+
 impl Deref for WindowElement {
     type Target = Window;
 
@@ -98,6 +144,63 @@ impl WindowElement {
         }
     }
 
+    /// Get the content type this window declared.
+    ///
+    /// This is `None` for X11 windows.
+    pub fn content_type(&self) -> Option<ContentType> {
+        let WindowSurface::Wayland(toplevel) = self.0.underlying_surface() else {
+            return None;
+        };
+
+        compositor::with_states(toplevel.wl_surface(), |states| {
+            match states
+                .cached_state
+                .current::<ContentTypeSurfaceCachedState>()
+                .content_type()
+            {
+                wp_content_type_v1::Type::Photo => Some(ContentType::Photo),
+                wp_content_type_v1::Type::Video => Some(ContentType::Video),
+                wp_content_type_v1::Type::Game => Some(ContentType::Game),
+                _ => Some(ContentType::None),
+            }
+        })
+    }
+
+    /// Get how this window's client scales its main surface, for debugging.
+    ///
+    /// This is `None` if the window has no surface yet.
+    pub fn surface_info(&self) -> Option<SurfaceInfo> {
+        let surface = self.wl_surface()?;
+
+        let buffer_size = with_renderer_surface_state(&surface, |state| {
+            state
+                .buffer_size()
+                .map(|size| size.to_buffer(state.buffer_scale(), state.buffer_transform()))
+        })
+        .flatten();
+
+        let info = compositor::with_states(&surface, |states| {
+            let viewport = states.cached_state.current::<ViewportCachedState>();
+
+            SurfaceInfo {
+                wl_surface_version: surface.version(),
+                buffer_scale: states
+                    .cached_state
+                    .current::<SurfaceAttributes>()
+                    .buffer_scale,
+                buffer_size,
+                uses_viewporter: viewport.src.is_some() || viewport.size.is_some(),
+                uses_fractional_scale: states.data_map.get::<FractionalScaleSurface>().is_some(),
+                preferred_scale: fractional_scale::with_fractional_scale(
+                    states,
+                    |fractional_scale| fractional_scale.preferred_scale(),
+                ),
+            }
+        });
+
+        Some(info)
+    }
+
     /// Get the output this window is on.
     ///
     /// This method gets the first tag the window has and returns its output.
@@ -107,11 +210,38 @@ impl WindowElement {
         self.with_state(|st| st.tags.first().and_then(|tag| tag.output(pinnacle)))
     }
 
-    /// Returns whether or not this window has an active tag.
+    /// Returns whether or not this window has an active tag that shows it.
+    ///
+    /// Floating windows aren't shown by tags that have their floating windows hidden,
+    /// and minimized windows aren't shown by any tag.
     ///
     /// RefCell Safety: This calls `with_state` on `self`.
     pub fn is_on_active_tag(&self) -> bool {
-        self.with_state(|state| state.tags.iter().any(|tag| tag.active()))
+        self.with_state(|state| {
+            let floating = state.floating_or_tiled.is_floating();
+            !state.minimized
+                && state
+                    .tags
+                    .iter()
+                    .any(|tag| tag.active() && !(floating && tag.floating_hidden()))
+        })
+    }
+
+    /// Returns whether this window is minimized.
+    ///
+    /// RefCell Safety: This calls `with_state` on `self`.
+    pub fn is_minimized(&self) -> bool {
+        self.with_state(|state| state.minimized)
+    }
+
+    /// Returns whether this window has an active tag but is hidden because it's floating
+    /// and its active tags have their floating windows hidden.
+    ///
+    /// RefCell Safety: This calls `with_state` on `self`.
+    pub fn is_floating_hidden(&self) -> bool {
+        !self.is_on_active_tag()
+            && !self.is_minimized()
+            && self.with_state(|state| state.tags.iter().any(|tag| tag.active()))
     }
 
     /// Place this window on the given output, giving it the output's focused tags.
@@ -120,13 +250,11 @@ impl WindowElement {
     pub fn place_on_output(&self, output: &Output) {
         self.with_state_mut(|state| {
             state.tags = output.with_state(|state| {
-                let output_tags = state.focused_tags().cloned().collect::<Vec<_>>();
+                let output_tags = state.focused_tags().cloned().collect::<TagSet>();
                 if !output_tags.is_empty() {
                     output_tags
-                } else if let Some(first_tag) = state.tags.first() {
-                    vec![first_tag.clone()]
                 } else {
-                    vec![]
+                    state.tags.first().cloned().into_iter().collect()
                 }
             });
 
@@ -141,6 +269,36 @@ impl WindowElement {
     pub fn is_x11_override_redirect(&self) -> bool {
         matches!(self.x11_surface(), Some(surface) if surface.is_override_redirect())
     }
+
+    /// Returns whether this window is drawn as activated.
+    ///
+    /// For Wayland windows, this is the pending `activated` xdg-toplevel state.
+    pub fn is_activated(&self) -> bool {
+        match self.x11_surface() {
+            Some(surface) => surface.is_activated(),
+            None => self.toplevel().is_some_and(|toplevel| {
+                toplevel.with_pending_state(|state| {
+                    state.states.contains(xdg_toplevel::State::Activated)
+                })
+            }),
+        }
+    }
+
+    /// Ask this window's client to close it.
+    pub fn close(&self) {
+        match self.underlying_surface() {
+            WindowSurface::Wayland(toplevel) => toplevel.send_close(),
+            WindowSurface::X11(surface) => {
+                if !surface.is_override_redirect() {
+                    if let Err(err) = surface.close() {
+                        tracing::error!("failed to close x11 window: {err}");
+                    }
+                } else {
+                    warn!("tried to close OR window");
+                }
+            }
+        }
+    }
 }
 
 impl SpaceElement for WindowElement {
@@ -210,6 +368,249 @@ impl WithState for WindowElement {
 }
 
 impl Pinnacle {
+    /// Move floating `window` to its relative geometry, resolved against the usable area
+    /// of its output.
+    ///
+    /// This does nothing if `window` isn't floating or has no relative geometry.
+    pub fn apply_relative_geometry(&mut self, window: &WindowElement) {
+        let Some(relative) = window.with_state(|state| {
+            state
+                .relative_geometry
+                .filter(|_| state.floating_or_tiled.is_floating())
+        }) else {
+            return;
+        };
+
+        let Some(output) = window.output(self) else {
+            return;
+        };
+
+        let Some(output_geo) = self.space.output_geometry(&output) else {
+            return;
+        };
+
+        let mut area = layer_map_for_output(&output).non_exclusive_zone();
+        area.loc += output_geo.loc;
+
+        let rect = relative.resolve(area);
+
+        window.with_state_mut(|state| {
+            state.floating_or_tiled = FloatingOrTiled::Floating(rect);
+        });
+
+        self.request_layout(&output);
+    }
+
+    /// Store the floating geometry of pinned `window` relative to its output's usable area
+    /// so it stays in the same place when the output changes.
+    ///
+    /// This does nothing if `window` isn't pinned or already has a relative geometry.
+    pub fn pin_relative_geometry(&mut self, window: &WindowElement) {
+        if !window.is_geometry_pinned()
+            || window.with_state(|state| state.relative_geometry.is_some())
+        {
+            return;
+        }
+
+        let FloatingOrTiled::Floating(rect) = window.with_state(|state| state.floating_or_tiled)
+        else {
+            return;
+        };
+
+        let Some(output) = window.output(self) else {
+            return;
+        };
+
+        let Some(output_geo) = self.space.output_geometry(&output) else {
+            return;
+        };
+
+        let mut area = layer_map_for_output(&output).non_exclusive_zone();
+        area.loc += output_geo.loc;
+
+        let relative = RelativeGeometry::from_rect(rect, area);
+        window.with_state_mut(|state| {
+            state.relative_geometry = relative;
+            state.relative_geometry_from_pin = true;
+        });
+    }
+
+    /// Drop the relative geometry [`Pinnacle::pin_relative_geometry`] stored for `window`
+    /// once it's no longer pinned, so it stops following its output.
+    ///
+    /// A relative geometry set by the config is kept.
+    pub fn unpin_relative_geometry(&mut self, window: &WindowElement) {
+        if window.is_geometry_pinned() {
+            return;
+        }
+
+        window.with_state_mut(|state| {
+            if state.relative_geometry_from_pin {
+                state.relative_geometry = None;
+                state.relative_geometry_from_pin = false;
+            }
+        });
+    }
+
+    /// Float `window` over `region` of its output's usable area.
+    ///
+    /// If the window is already snapped to a region in `region`'s cycle,
+    /// it moves to the next region in the cycle instead.
+    pub fn snap_window(&mut self, window: &WindowElement, region: SnapRegion) {
+        let current = window.with_state(|state| {
+            state
+                .relative_geometry
+                .filter(|_| state.floating_or_tiled.is_floating())
+                .and_then(SnapRegion::from_relative_geometry)
+        });
+
+        let region = region.next_from(current);
+
+        window.with_state_mut(|state| {
+            state.relative_geometry = Some(region.relative_geometry());
+            state.relative_geometry_from_pin = false;
+        });
+
+        if !window.with_state(|state| state.floating_or_tiled.is_floating()) {
+            window.toggle_floating();
+        }
+
+        self.apply_relative_geometry(window);
+    }
+
+    /// Minimize or restore `window`.
+    ///
+    /// Minimizing unmaps the window but keeps it in [`Pinnacle::windows`].
+    /// Restoring maps it back where it was, if it was mapped, and lays out its output again.
+    pub fn set_window_minimized(&mut self, window: &WindowElement, minimized: bool) {
+        if window.is_minimized() == minimized || window.is_x11_override_redirect() {
+            return;
+        }
+
+        if minimized {
+            let loc = self.space.element_location(window);
+            window.with_state_mut(|state| {
+                state.minimized = true;
+                state.minimized_loc = loc;
+                state.target_loc = None;
+            });
+            self.space.unmap_elem(window);
+        } else {
+            let loc = window.with_state_mut(|state| {
+                state.minimized = false;
+                state.minimized_loc.take()
+            });
+            // Windows that weren't mapped are placed by the layout below
+            if let Some(loc) = loc {
+                self.space.map_element(window.clone(), loc, false);
+                self.raise_window(window.clone(), false);
+            }
+        }
+
+        if let Some(output) = window.output(self) {
+            self.request_layout(&output);
+        }
+    }
+
+    /// Set the suspended state on windows that aren't displayed and unset it on the rest.
+    ///
+    /// This lets well-behaved clients stop rendering while on inactive tags or hidden.
+    /// Configures are only sent to windows whose state changed.
+    pub fn update_suspended_windows(&self) {
+        for win in self.windows.iter() {
+            let Some(toplevel) = win.toplevel() else {
+                continue;
+            };
+
+            let suspended = !win.is_on_active_tag();
+
+            let changed = toplevel.with_pending_state(|state| {
+                if state.states.contains(xdg_toplevel::State::Suspended) == suspended {
+                    return false;
+                }
+
+                if suspended {
+                    state.states.set(xdg_toplevel::State::Suspended);
+                } else {
+                    state.states.unset(xdg_toplevel::State::Suspended);
+                }
+
+                true
+            });
+
+            if changed {
+                toplevel.send_pending_configure();
+            }
+        }
+    }
+
+    /// Mark `window` as wanting attention if it isn't already.
+    ///
+    /// If an urgency timeout is configured, the flag is cleared once it elapses
+    /// even if the window never gets focused.
+    pub fn set_urgent(&self, window: &WindowElement) {
+        if window.with_state(|state| state.urgent.is_some()) {
+            return;
+        }
+
+        let urgent_since = Instant::now();
+        window.with_state_mut(|state| state.urgent = Some(urgent_since));
+
+        let Some(timeout) = self.config.urgency_timeout else {
+            return;
+        };
+
+        let window = window.clone();
+        let insert_ret =
+            self.loop_handle
+                .insert_source(Timer::from_duration(timeout), move |_, _, _| {
+                    window.with_state_mut(|state| {
+                        // Only clear the flag this timer was started for
+                        if state.urgent == Some(urgent_since) {
+                            state.urgent = None;
+                        }
+                    });
+                    TimeoutAction::Drop
+                });
+
+        if let Err(err) = insert_ret {
+            warn!("Failed to start urgency timeout: {err}");
+        }
+    }
+
+    /// Spawn the config's last window fallback if the config closed `window`
+    /// while it was the last open window and no other window has opened since.
+    ///
+    /// This should be called after destroyed `window` is removed from [`Pinnacle::windows`].
+    pub fn spawn_last_window_fallback(&self, window: &WindowElement) {
+        if !window.with_state(|state| state.closed_as_last_window) {
+            return;
+        }
+
+        if self
+            .windows
+            .iter()
+            .any(|win| !win.is_x11_override_redirect())
+        {
+            return;
+        }
+
+        // Leave something to get back to when a stray keybind closes everything
+        let Some((arg0, args)) = self.config.last_window_fallback.split_first() else {
+            return;
+        };
+
+        if let Err(err) = tokio::process::Command::new(arg0)
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        {
+            warn!("Failed to spawn last window fallback `{arg0}`: {err}");
+        }
+    }
+
     /// Returns the [Window] associated with a given [WlSurface].
     pub fn window_for_surface(&self, surface: &WlSurface) -> Option<WindowElement> {
         self.space
@@ -232,4 +633,53 @@ impl Pinnacle {
             .find(|&win| win.wl_surface().is_some_and(|surf| &surf == surface))
             .cloned()
     }
+
+    /// Set whether `window` is drawn as activated without changing keyboard focus.
+    ///
+    /// Only one window is activated at a time, so activating `window` deactivates every
+    /// other window. The activated state of `window` is pinned, so keyboard focus updates
+    /// leave it alone until a window newly gains keyboard focus.
+    pub fn set_window_activated(&self, window: &WindowElement, activated: bool) {
+        for win in self.windows.iter() {
+            if win == window {
+                win.set_activate(activated);
+                win.with_state_mut(|state| state.activation_pinned = true);
+            } else if activated && win.is_activated() {
+                win.set_activate(false);
+                win.with_state_mut(|state| state.activation_pinned = false);
+            } else {
+                continue;
+            }
+
+            if let Some(toplevel) = win.toplevel() {
+                toplevel.send_pending_configure();
+            }
+        }
+    }
+
+    /// Activate `focused`, which has keyboard focus, and deactivate every other window.
+    ///
+    /// A window whose activated state is pinned by [`Pinnacle::set_window_activated`]
+    /// is left alone, unless `newly_focused` is true. Then `focused` just gained keyboard
+    /// focus and takes the activated state back, unpinning every window.
+    pub fn activate_focused_window(&self, focused: &WindowElement, newly_focused: bool) {
+        for win in self.windows.iter() {
+            if newly_focused {
+                win.with_state_mut(|state| state.activation_pinned = false);
+            } else if win.with_state(|state| state.activation_pinned) {
+                continue;
+            }
+
+            let activated = win == focused;
+            if win.is_activated() == activated {
+                continue;
+            }
+
+            win.set_activate(activated);
+
+            if let Some(toplevel) = win.toplevel() {
+                toplevel.send_pending_configure();
+            }
+        }
+    }
 }
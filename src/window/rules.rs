@@ -8,11 +8,31 @@ use crate::{
     window::window_state,
 };
 
-use super::WindowElement;
+use super::{ContentType, WindowElement};
 
-use std::num::NonZeroU32;
+use std::{
+    num::NonZeroU32,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use crate::{
+    output::OutputName,
+    tag::{TagId, TagSet},
+    window::window_state::FullscreenOrMaximized,
+};
+
+/// A unique identifier for each added window rule.
+#[derive(Debug, Hash, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct WindowRuleId(pub u32);
 
-use crate::{output::OutputName, tag::TagId, window::window_state::FullscreenOrMaximized};
+static WINDOW_RULE_ID_COUNTER: AtomicU32 = AtomicU32::new(0);
+
+impl WindowRuleId {
+    /// Get the next available window rule id. This always starts at 0.
+    pub fn next() -> Self {
+        Self(WINDOW_RULE_ID_COUNTER.fetch_add(1, Ordering::Relaxed))
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, Default)]
 pub struct WindowRuleCondition {
@@ -31,6 +51,9 @@ pub struct WindowRuleCondition {
     /// This condition is met when the tag matches.
     #[serde(default)]
     pub tag: Option<Vec<TagId>>,
+    /// This condition is met when the content type matches.
+    #[serde(default)]
+    pub content_type: Option<Vec<ContentType>>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -39,18 +62,43 @@ enum AllOrAny {
     Any,
 }
 
+/// The result of evaluating a [`WindowRuleCondition`] against a window.
+///
+/// Each field corresponds to the field of the same name in the condition
+/// and is `None` if that sub-condition wasn't specified.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ConditionEvaluation {
+    /// Whether the condition as a whole is met.
+    pub matched: bool,
+    pub cond_any: Option<Vec<ConditionEvaluation>>,
+    pub cond_all: Option<Vec<ConditionEvaluation>>,
+    pub class: Option<bool>,
+    pub title: Option<bool>,
+    pub tag: Option<bool>,
+    pub content_type: Option<bool>,
+}
+
 impl WindowRuleCondition {
     /// RefCell Safety: This method uses RefCells on `window`.
     pub fn is_met(&self, pinnacle: &Pinnacle, window: &WindowElement) -> bool {
-        Self::is_met_inner(self, pinnacle, window, AllOrAny::All)
+        self.evaluate(pinnacle, window).matched
     }
 
-    fn is_met_inner(
+    /// Evaluate this condition against `window`, recording which sub-conditions passed.
+    ///
+    /// Unlike [`Self::is_met`], this evaluates every nested condition without short-circuiting.
+    ///
+    /// RefCell Safety: This method uses RefCells on `window`.
+    pub fn evaluate(&self, pinnacle: &Pinnacle, window: &WindowElement) -> ConditionEvaluation {
+        Self::evaluate_inner(self, pinnacle, window, AllOrAny::All)
+    }
+
+    fn evaluate_inner(
         &self,
         pinnacle: &Pinnacle,
         window: &WindowElement,
         all_or_any: AllOrAny,
-    ) -> bool {
+    ) -> ConditionEvaluation {
         tracing::debug!("{self:#?}");
 
         let WindowRuleCondition {
@@ -59,85 +107,85 @@ impl WindowRuleCondition {
             class,
             title,
             tag,
+            content_type,
         } = self;
 
-        match all_or_any {
-            AllOrAny::All => {
-                let cond_any = if let Some(cond_any) = cond_any {
-                    cond_any
-                        .iter()
-                        .any(|cond| Self::is_met_inner(cond, pinnacle, window, AllOrAny::Any))
-                } else {
-                    true
-                };
-                let cond_all = if let Some(cond_all) = cond_all {
-                    cond_all
-                        .iter()
-                        .all(|cond| Self::is_met_inner(cond, pinnacle, window, AllOrAny::All))
-                } else {
-                    true
-                };
-                let classes = if let Some(classes) = class {
-                    classes
-                        .iter()
-                        .all(|class| window.class().as_ref() == Some(class))
-                } else {
-                    true
-                };
-                let titles = if let Some(titles) = title {
-                    titles
-                        .iter()
-                        .all(|title| window.title().as_ref() == Some(title))
-                } else {
-                    true
-                };
-                let tags = if let Some(tag_ids) = tag {
-                    let mut tags = tag_ids.iter().filter_map(|tag_id| tag_id.tag(pinnacle));
-                    tags.all(|tag| window.with_state(|state| state.tags.contains(&tag)))
-                } else {
-                    true
-                };
-
-                tracing::debug!("{cond_all} {cond_any} {classes} {titles} {tags}");
-                cond_all && cond_any && classes && titles && tags
-            }
-            AllOrAny::Any => {
-                let cond_any = if let Some(cond_any) = cond_any {
-                    cond_any
-                        .iter()
-                        .any(|cond| Self::is_met_inner(cond, pinnacle, window, AllOrAny::Any))
-                } else {
-                    false
-                };
-                let cond_all = if let Some(cond_all) = cond_all {
-                    cond_all
-                        .iter()
-                        .all(|cond| Self::is_met_inner(cond, pinnacle, window, AllOrAny::All))
-                } else {
-                    false
-                };
-                let classes = if let Some(classes) = class {
-                    classes
-                        .iter()
-                        .any(|class| window.class().as_ref() == Some(class))
-                } else {
-                    false
-                };
-                let titles = if let Some(titles) = title {
-                    titles
-                        .iter()
-                        .any(|title| window.title().as_ref() == Some(title))
-                } else {
-                    false
-                };
-                let tags = if let Some(tag_ids) = tag {
-                    let mut tags = tag_ids.iter().filter_map(|tag_id| tag_id.tag(pinnacle));
-                    tags.any(|tag| window.with_state(|state| state.tags.contains(&tag)))
-                } else {
-                    false
-                };
-                cond_all || cond_any || classes || titles || tags
-            }
+        // Within an `all` every listed value must match, within an `any` only one needs to.
+        let check = |matches: &mut dyn Iterator<Item = bool>| match all_or_any {
+            AllOrAny::All => matches.all(|matched| matched),
+            AllOrAny::Any => matches.any(|matched| matched),
+        };
+
+        let cond_any = cond_any.as_ref().map(|cond_any| {
+            cond_any
+                .iter()
+                .map(|cond| Self::evaluate_inner(cond, pinnacle, window, AllOrAny::Any))
+                .collect::<Vec<_>>()
+        });
+        let cond_all = cond_all.as_ref().map(|cond_all| {
+            cond_all
+                .iter()
+                .map(|cond| Self::evaluate_inner(cond, pinnacle, window, AllOrAny::All))
+                .collect::<Vec<_>>()
+        });
+        let class = class.as_ref().map(|classes| {
+            let window_class = window.class();
+            check(
+                &mut classes
+                    .iter()
+                    .map(|class| window_class.as_ref() == Some(class)),
+            )
+        });
+        let title = title.as_ref().map(|titles| {
+            let window_title = window.title();
+            check(
+                &mut titles
+                    .iter()
+                    .map(|title| window_title.as_ref() == Some(title)),
+            )
+        });
+        let tag = tag.as_ref().map(|tag_ids| {
+            check(
+                &mut tag_ids
+                    .iter()
+                    .filter_map(|tag_id| tag_id.tag(pinnacle))
+                    .map(|tag| window.with_state(|state| state.tags.contains(&tag))),
+            )
+        });
+        let content_type = content_type.as_ref().map(|content_types| {
+            let window_content_type = window.content_type();
+            check(
+                &mut content_types
+                    .iter()
+                    .map(|content_type| window_content_type.as_ref() == Some(content_type)),
+            )
+        });
+
+        let any_matched = cond_any
+            .as_ref()
+            .map(|evals| evals.iter().any(|eval| eval.matched));
+        let all_matched = cond_all
+            .as_ref()
+            .map(|evals| evals.iter().all(|eval| eval.matched));
+
+        let results = [any_matched, all_matched, class, title, tag, content_type];
+
+        // Unspecified sub-conditions don't fail an `all` and don't satisfy an `any`.
+        let matched = match all_or_any {
+            AllOrAny::All => results.iter().all(|res| res.unwrap_or(true)),
+            AllOrAny::Any => results.iter().any(|res| res.unwrap_or(false)),
+        };
+
+        tracing::debug!(?results, matched);
+
+        ConditionEvaluation {
+            matched,
+            cond_any,
+            cond_all,
+            class,
+            title,
+            tag,
+            content_type,
         }
     }
 }
@@ -163,6 +211,28 @@ pub struct WindowRule {
     /// when set to floating.
     #[serde(default)]
     pub location: Option<(i32, i32)>,
+    /// Exempt the window from focus stealing prevention, allowing it to always
+    /// activate itself.
+    #[serde(default)]
+    pub focus_steal_exempt: Option<bool>,
+    /// Disable keybinds while the window is focused, forwarding the keys to it instead.
+    #[serde(default)]
+    pub disable_keybinds: Option<bool>,
+    /// Set whether the window gets keyboard focus when it opens.
+    #[serde(default)]
+    pub focus_on_open: Option<bool>,
+    /// Leave the window out of taskbars.
+    #[serde(default)]
+    pub skip_taskbar: Option<bool>,
+    /// Skip the window when cycling through windows.
+    #[serde(default)]
+    pub skip_cycle: Option<bool>,
+    /// Only apply this rule to the next window that matches, then remove it.
+    #[serde(default)]
+    pub once: Option<bool>,
+    /// Set whether the window leaves fullscreen when it loses keyboard focus.
+    #[serde(default)]
+    pub unfullscreen_on_focus_loss: Option<bool>,
 }
 
 // TODO: just skip serializing fields on the other FloatingOrTiled
@@ -173,10 +243,63 @@ pub enum FloatingOrTiled {
 }
 
 impl Pinnacle {
+    /// Returns whether `window` should get keyboard focus when it opens.
+    ///
+    /// Windows that open on an output other than the one focus is locked to never get focus.
+    ///
+    /// This should be called after window rules have been applied.
+    pub fn should_focus_new_window(&self, window: &WindowElement) -> bool {
+        if let Some(locked) = self.output_focus_stack.locked() {
+            if window.output(self).as_ref() != Some(locked) {
+                return false;
+            }
+        }
+
+        window
+            .with_state(|state| state.focus_on_open)
+            .unwrap_or(self.config.focus_new_windows)
+    }
+
+    /// Returns whether `window` should leave fullscreen when it loses keyboard focus.
+    pub fn should_unfullscreen_on_focus_loss(&self, window: &WindowElement) -> bool {
+        window
+            .with_state(|state| state.unfullscreen_on_focus_loss)
+            .unwrap_or(self.config.unfullscreen_on_focus_loss)
+    }
+
+    /// Apply matching window rules to `window`, which is opening.
+    ///
+    /// Rules that only apply once are removed after applying.
+    /// If the config asked for the next window to open on the focused tags,
+    /// `window` keeps the tags it opened with.
     pub fn apply_window_rules(&mut self, window: &WindowElement) {
+        // New windows are placed on the focused tags before rules are applied
+        let opened_tags = std::mem::take(&mut self.config.open_next_on_focused_tag)
+            .then(|| window.with_state(|state| state.tags.clone()));
+
+        self.apply_window_rules_inner(window, true);
+
+        if let Some(tags) = opened_tags {
+            window.with_state_mut(|state| state.tags = tags);
+        }
+    }
+
+    fn apply_window_rules_inner(&mut self, window: &WindowElement, is_opening: bool) {
         tracing::debug!("Applying window rules");
-        for (cond, rule) in self.config.window_rules.iter() {
+        let mut used_once_rules = Vec::new();
+
+        for (rule_id, cond, rule) in self.config.window_rules.iter() {
+            // Rules that apply once are meant for the next window that opens
+            let once = rule.once == Some(true);
+            if once && !is_opening {
+                continue;
+            }
+
             if cond.is_met(self, window) {
+                if once {
+                    used_once_rules.push(*rule_id);
+                }
+
                 let WindowRule {
                     output,
                     tags,
@@ -184,6 +307,13 @@ impl Pinnacle {
                     fullscreen_or_maximized,
                     size,
                     location,
+                    focus_steal_exempt,
+                    disable_keybinds,
+                    focus_on_open,
+                    skip_taskbar,
+                    skip_cycle,
+                    once: _,
+                    unfullscreen_on_focus_loss,
                 } = rule;
 
                 // TODO: If both `output` and `tags` are specified, `tags` will apply over
@@ -192,7 +322,7 @@ impl Pinnacle {
                 if let Some(output_name) = output {
                     if let Some(output) = output_name.output(self) {
                         let tags = output
-                            .with_state(|state| state.focused_tags().cloned().collect::<Vec<_>>());
+                            .with_state(|state| state.focused_tags().cloned().collect::<TagSet>());
 
                         window.with_state_mut(|state| state.tags = tags.clone());
                     }
@@ -202,7 +332,7 @@ impl Pinnacle {
                     let tags = tag_ids
                         .iter()
                         .filter_map(|tag_id| tag_id.tag(self))
-                        .collect::<Vec<_>>();
+                        .collect::<TagSet>();
 
                     window.with_state_mut(|state| state.tags = tags.clone());
                 }
@@ -223,7 +353,33 @@ impl Pinnacle {
                 }
 
                 if let Some(fs_or_max) = fullscreen_or_maximized {
-                    window.with_state_mut(|state| state.fullscreen_or_maximized = *fs_or_max);
+                    if is_opening {
+                        // Opening windows get configured with their state when they map
+                        window.with_state_mut(|state| state.fullscreen_or_maximized = *fs_or_max);
+                    } else {
+                        // Go through the toggles so windows that are already open
+                        // get their new state
+                        let current = window.with_state(|state| state.fullscreen_or_maximized);
+                        match fs_or_max {
+                            FullscreenOrMaximized::Fullscreen => {
+                                if !current.is_fullscreen() {
+                                    window.toggle_fullscreen();
+                                }
+                            }
+                            FullscreenOrMaximized::Maximized => {
+                                if !current.is_maximized() {
+                                    window.toggle_maximized();
+                                }
+                            }
+                            FullscreenOrMaximized::Neither => {
+                                if current.is_fullscreen() {
+                                    window.toggle_fullscreen();
+                                } else if current.is_maximized() {
+                                    window.toggle_maximized();
+                                }
+                            }
+                        }
+                    }
                 }
 
                 if let Some((w, h)) = size {
@@ -233,7 +389,7 @@ impl Pinnacle {
 
                     match window.with_state(|state| state.floating_or_tiled) {
                         window_state::FloatingOrTiled::Floating(mut rect) => {
-                            rect.size = (u32::from(*w) as i32, u32::from(*h) as i32).into();
+                            rect.size = (u32::from(*w) as f64, u32::from(*h) as f64).into();
                             window.with_state_mut(|state| {
                                 state.floating_or_tiled =
                                     window_state::FloatingOrTiled::Floating(rect)
@@ -241,7 +397,7 @@ impl Pinnacle {
                         }
                         window_state::FloatingOrTiled::Tiled(mut rect) => {
                             if let Some(rect) = rect.as_mut() {
-                                rect.size = (u32::from(*w) as i32, u32::from(*h) as i32).into();
+                                rect.size = (u32::from(*w) as f64, u32::from(*h) as f64).into();
                             }
                             window.with_state_mut(|state| {
                                 state.floating_or_tiled = window_state::FloatingOrTiled::Tiled(rect)
@@ -250,22 +406,50 @@ impl Pinnacle {
                     }
                 }
 
+                if let Some(exempt) = focus_steal_exempt {
+                    window.with_state_mut(|state| state.focus_steal_exempt = *exempt);
+                }
+
+                if let Some(disable) = disable_keybinds {
+                    window.with_state_mut(|state| state.keybinds_disabled = *disable);
+                }
+
+                if let Some(focus) = focus_on_open {
+                    window.with_state_mut(|state| state.focus_on_open = Some(*focus));
+                }
+
+                if let Some(skip) = skip_taskbar {
+                    window.with_state_mut(|state| state.skip_taskbar = *skip);
+                }
+
+                if let Some(skip) = skip_cycle {
+                    window.with_state_mut(|state| state.skip_cycle = *skip);
+                }
+
+                if let Some(unfullscreen) = unfullscreen_on_focus_loss {
+                    window.with_state_mut(|state| {
+                        state.unfullscreen_on_focus_loss = Some(*unfullscreen)
+                    });
+                }
+
                 if let Some(loc) = location {
                     match window.with_state(|state| state.floating_or_tiled) {
                         window_state::FloatingOrTiled::Floating(mut rect) => {
-                            rect.loc = (*loc).into();
+                            rect.loc = Point::from(*loc).to_f64();
                             window.with_state_mut(|state| {
                                 state.floating_or_tiled =
                                     window_state::FloatingOrTiled::Floating(rect)
                             });
-                            self.space.map_element(window.clone(), *loc, false);
+                            if !window.is_minimized() {
+                                self.space.map_element(window.clone(), *loc, false);
+                            }
                         }
                         window_state::FloatingOrTiled::Tiled(rect) => {
                             // If the window is tiled, don't set the size. Instead, set
                             // what the size will be when it gets set to floating.
                             let rect = rect.unwrap_or_else(|| {
                                 let size = window.geometry().size;
-                                Rectangle::from_loc_and_size(Point::from(*loc), size)
+                                Rectangle::from_loc_and_size(Point::from(*loc), size).to_f64()
                             });
 
                             window.with_state_mut(|state| {
@@ -277,5 +461,26 @@ impl Pinnacle {
                 }
             }
         }
+
+        self.config
+            .window_rules
+            .retain(|(rule_id, _, _)| !used_once_rules.contains(rule_id));
+    }
+
+    /// Apply window rules to all open windows again, as if they just opened.
+    ///
+    /// Rules set absolute values, so applying them more than once
+    /// has the same effect as applying them once. Rules that apply once
+    /// are left for the next window that opens.
+    pub fn reapply_window_rules(&mut self) {
+        for window in self.windows.clone() {
+            if window.is_x11_override_redirect() {
+                continue;
+            }
+            self.apply_window_rules_inner(&window, false);
+        }
+
+        self.relayout_all();
+        self.update_suspended_windows();
     }
 }
@@ -1,6 +1,9 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 
-use std::sync::atomic::{AtomicU32, Ordering};
+use std::{
+    sync::atomic::{AtomicU32, Ordering},
+    time::{Duration, Instant},
+};
 
 use smithay::{
     desktop::{space::SpaceElement, WindowSurface},
@@ -10,7 +13,7 @@ use smithay::{
 
 use crate::{
     state::{Pinnacle, WithState},
-    tag::Tag,
+    tag::TagSet,
 };
 
 use super::WindowElement;
@@ -43,13 +46,151 @@ pub struct WindowElementState {
     /// The id of this window.
     pub id: WindowId,
     /// What tags the window is currently on.
-    pub tags: Vec<Tag>,
+    pub tags: TagSet,
     pub floating_or_tiled: FloatingOrTiled,
     pub fullscreen_or_maximized: FullscreenOrMaximized,
     pub target_loc: Option<Point<i32, Logical>>,
+    /// Whether this window can always activate itself through xdg-activation,
+    /// bypassing focus stealing prevention.
+    pub focus_steal_exempt: bool,
+    /// When this window was marked as wanting attention, if it currently does.
+    ///
+    /// This is cleared when the window gains keyboard focus.
+    pub urgent: Option<Instant>,
+    /// Whether keybinds are disabled while this window is focused,
+    /// forwarding those keys to the window instead.
+    pub keybinds_disabled: bool,
+    /// Whether this window should get keyboard focus when it opens,
+    /// overriding [`Config::focus_new_windows`][crate::config::Config::focus_new_windows].
+    pub focus_on_open: Option<bool>,
+    /// Whether this window leaves fullscreen when it loses keyboard focus, overriding
+    /// [`Config::unfullscreen_on_focus_loss`][crate::config::Config::unfullscreen_on_focus_loss].
+    pub unfullscreen_on_focus_loss: Option<bool>,
+    /// A unique label the config gave this window to find it by.
+    pub label: Option<String>,
+    /// The time on the compositor's monotonic clock when this window last gained keyboard focus,
+    /// or `None` if it never has.
+    pub last_focused: Option<Duration>,
+    /// The factor this window is rendered at on top of its output's scale.
+    ///
+    /// This is always within [`WindowElementState::SCALE_RANGE`].
+    pub scale: f64,
+    /// The floating geometry the config gave this window as fractions of its output.
+    ///
+    /// This is resolved again when the output's mode or scale changes and is cleared
+    /// when the window gets an absolute geometry or is moved or resized interactively.
+    pub relative_geometry: Option<RelativeGeometry>,
+    /// Whether `relative_geometry` was stored when the window was pinned
+    /// instead of being given by the config, so unpinning clears it.
+    pub relative_geometry_from_pin: bool,
+    /// Whether this window should be left out of taskbars.
+    pub skip_taskbar: bool,
+    /// Whether this window is skipped when cycling through windows
+    /// with `FocusPrevious` or `JumpToWindow`.
+    pub skip_cycle: bool,
+    /// Whether this window's floating geometry is pinned in place.
+    ///
+    /// Pinned floating windows can't be moved or resized interactively or by their client
+    /// and keep their position relative to their output when it changes.
+    pub geometry_pinned: bool,
+    /// Whether this window keeps its tag's gaps around it when maximized.
+    pub maximize_gaps: bool,
+    /// Which of this window's edges are tiled, updated on every layout.
+    pub tiled_edges: TiledEdges,
+    /// Whether this window is told which of its edges are tiled.
+    ///
+    /// When this is off, the window is told none of its edges are tiled.
+    pub tiled_edges_enabled: bool,
+    /// Whether this window is minimized.
+    ///
+    /// Minimized windows are unmapped and left out of layouts and focus
+    /// until they're restored.
+    pub minimized: bool,
+    /// Where this window was mapped when it was minimized.
+    ///
+    /// This is `None` if the window wasn't mapped, in which case the layout
+    /// places it when it's restored.
+    pub minimized_loc: Option<Point<i32, Logical>>,
+    /// Whether this window's activated state was set independently of keyboard focus.
+    ///
+    /// Keyboard focus updates leave the activated state of pinned windows alone.
+    /// This is unset when any window newly gains keyboard focus.
+    pub activation_pinned: bool,
+    /// Whether the config closed this window while it was the last open one,
+    /// so the last window fallback is spawned once it's destroyed.
+    pub closed_as_last_window: bool,
+}
+
+impl WindowElementState {
+    /// The range window scales are clamped to.
+    pub const SCALE_RANGE: std::ops::RangeInclusive<f64> = 0.25..=4.0;
+
+    /// The edges this window is told are tiled.
+    pub fn sent_tiled_edges(&self) -> TiledEdges {
+        if self.tiled_edges_enabled {
+            self.tiled_edges
+        } else {
+            TiledEdges::empty()
+        }
+    }
 }
 
 impl WindowElement {
+    /// Returns whether this window is floating with its geometry pinned in place.
+    ///
+    /// RefCell Safety: This method uses a [`RefCell`] on this window.
+    pub fn is_geometry_pinned(&self) -> bool {
+        self.with_state(|state| state.geometry_pinned && state.floating_or_tiled.is_floating())
+    }
+
+    /// Get the precise location of this window when it's mapped at `space_loc`.
+    ///
+    /// Floating windows keep a fractional location and are mapped at it rounded.
+    /// While that's where they are, the fractional location is returned.
+    ///
+    /// RefCell Safety: This method uses a [`RefCell`] on this window.
+    pub fn precise_loc(&self, space_loc: Point<i32, Logical>) -> Point<f64, Logical> {
+        self.with_state(|state| match state.floating_or_tiled {
+            FloatingOrTiled::Floating(rect)
+                if rect.loc.to_i32_round() == space_loc
+                    && state.fullscreen_or_maximized.is_neither() =>
+            {
+                rect.loc
+            }
+            _ => space_loc.to_f64(),
+        })
+    }
+
+    /// Tell this window which of its edges are tiled.
+    ///
+    /// The edges are sent with the next configure. If tiled edges are turned off
+    /// for this window, no edges are sent as tiled.
+    ///
+    /// RefCell Safety: This method uses a [`RefCell`] on this window.
+    pub fn set_tiled_edges(&self, edges: TiledEdges) {
+        let sent_edges = self.with_state_mut(|state| {
+            state.tiled_edges = edges;
+            state.sent_tiled_edges()
+        });
+
+        if let WindowSurface::Wayland(toplevel) = self.underlying_surface() {
+            toplevel.with_pending_state(|state| {
+                for (edge, xdg_state) in [
+                    (TiledEdges::TOP, xdg_toplevel::State::TiledTop),
+                    (TiledEdges::BOTTOM, xdg_toplevel::State::TiledBottom),
+                    (TiledEdges::LEFT, xdg_toplevel::State::TiledLeft),
+                    (TiledEdges::RIGHT, xdg_toplevel::State::TiledRight),
+                ] {
+                    if sent_edges.contains(edge) {
+                        state.states.set(xdg_state);
+                    } else {
+                        state.states.unset(xdg_state);
+                    }
+                }
+            });
+        }
+    }
+
     /// RefCell Safety: This method uses a [`RefCell`] on this window.
     pub fn toggle_floating(&self) {
         match self.with_state(|state| state.floating_or_tiled) {
@@ -60,14 +201,14 @@ impl WindowElement {
                 self.set_tiled_states();
             }
             FloatingOrTiled::Tiled(prev_rect) => {
-                let prev_rect = prev_rect.unwrap_or_else(|| self.geometry());
+                let prev_rect = prev_rect.unwrap_or_else(|| self.geometry().to_f64());
 
                 self.with_state_mut(|state| {
                     state.floating_or_tiled = FloatingOrTiled::Floating(prev_rect);
                 });
 
                 // TODO: maybe move this into update_windows
-                self.change_geometry(prev_rect);
+                self.change_geometry(prev_rect.to_i32_round());
                 self.set_floating_states();
             }
         }
@@ -86,11 +227,8 @@ impl WindowElement {
                         toplevel.with_pending_state(|state| {
                             state.states.unset(xdg_toplevel::State::Maximized);
                             state.states.set(xdg_toplevel::State::Fullscreen);
-                            state.states.set(xdg_toplevel::State::TiledTop);
-                            state.states.set(xdg_toplevel::State::TiledLeft);
-                            state.states.set(xdg_toplevel::State::TiledBottom);
-                            state.states.set(xdg_toplevel::State::TiledRight);
                         });
+                        self.set_tiled_edges(TiledEdges::all());
                     }
                     WindowSurface::X11(surface) => {
                         if !surface.is_override_redirect() {
@@ -111,7 +249,7 @@ impl WindowElement {
 
                 match self.with_state(|state| state.floating_or_tiled) {
                     FloatingOrTiled::Floating(current_rect) => {
-                        self.change_geometry(current_rect);
+                        self.change_geometry(current_rect.to_i32_round());
                         self.set_floating_states();
                     }
                     FloatingOrTiled::Tiled(_) => self.set_tiled_states(),
@@ -133,11 +271,8 @@ impl WindowElement {
                         toplevel.with_pending_state(|state| {
                             state.states.set(xdg_toplevel::State::Maximized);
                             state.states.unset(xdg_toplevel::State::Fullscreen);
-                            state.states.set(xdg_toplevel::State::TiledTop);
-                            state.states.set(xdg_toplevel::State::TiledLeft);
-                            state.states.set(xdg_toplevel::State::TiledBottom);
-                            state.states.set(xdg_toplevel::State::TiledRight);
                         });
+                        self.set_tiled_edges(TiledEdges::all());
                     }
                     WindowSurface::X11(surface) => {
                         if !surface.is_override_redirect() {
@@ -158,7 +293,7 @@ impl WindowElement {
 
                 match self.with_state(|state| state.floating_or_tiled) {
                     FloatingOrTiled::Floating(current_rect) => {
-                        self.change_geometry(current_rect);
+                        self.change_geometry(current_rect.to_i32_round());
                         self.set_floating_states();
                     }
                     FloatingOrTiled::Tiled(_) => self.set_tiled_states(),
@@ -168,18 +303,15 @@ impl WindowElement {
     }
 
     /// Unsets maximized and fullscreen states for both wayland and xwayland windows
-    /// and unsets tiled states for wayland windows.
+    /// and unsets tiled edges.
     fn set_floating_states(&self) {
         match self.underlying_surface() {
             WindowSurface::Wayland(toplevel) => {
                 toplevel.with_pending_state(|state| {
                     state.states.unset(xdg_toplevel::State::Maximized);
                     state.states.unset(xdg_toplevel::State::Fullscreen);
-                    state.states.unset(xdg_toplevel::State::TiledTop);
-                    state.states.unset(xdg_toplevel::State::TiledLeft);
-                    state.states.unset(xdg_toplevel::State::TiledBottom);
-                    state.states.unset(xdg_toplevel::State::TiledRight);
                 });
+                self.set_tiled_edges(TiledEdges::empty());
             }
             WindowSurface::X11(surface) => {
                 if !surface.is_override_redirect() {
@@ -195,18 +327,16 @@ impl WindowElement {
     }
 
     /// Unsets maximized and fullscreen states for both wayland and xwayland windows
-    /// and sets tiled states for wayland windows.
+    /// and sets tiled edges.
     fn set_tiled_states(&self) {
         match self.underlying_surface() {
             WindowSurface::Wayland(toplevel) => {
                 toplevel.with_pending_state(|state| {
                     state.states.unset(xdg_toplevel::State::Maximized);
                     state.states.unset(xdg_toplevel::State::Fullscreen);
-                    state.states.set(xdg_toplevel::State::TiledTop);
-                    state.states.set(xdg_toplevel::State::TiledLeft);
-                    state.states.set(xdg_toplevel::State::TiledBottom);
-                    state.states.set(xdg_toplevel::State::TiledRight);
                 });
+                // The layout narrows these down to the edges that border something
+                self.set_tiled_edges(TiledEdges::all());
             }
             WindowSurface::X11(surface) => {
                 if !surface.is_override_redirect() {
@@ -222,16 +352,247 @@ impl WindowElement {
     }
 }
 
+/// A floating geometry given as fractions of an output's usable area.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RelativeGeometry {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl RelativeGeometry {
+    /// Get the geometry `rect` takes up as fractions of `area`.
+    ///
+    /// Returns `None` if `area` is empty.
+    pub fn from_rect(rect: Rectangle<f64, Logical>, area: Rectangle<i32, Logical>) -> Option<Self> {
+        let area = area.to_f64();
+
+        if area.size.w <= 0.0 || area.size.h <= 0.0 {
+            return None;
+        }
+
+        Some(Self {
+            x: (rect.loc.x - area.loc.x) / area.size.w,
+            y: (rect.loc.y - area.loc.y) / area.size.h,
+            width: rect.size.w / area.size.w,
+            height: rect.size.h / area.size.h,
+        })
+    }
+
+    /// Resolve this geometry against `area`.
+    ///
+    /// The result is clamped to fit inside `area`, so a geometry that reaches past its
+    /// edges is shrunk or moved back in.
+    pub fn resolve(&self, area: Rectangle<i32, Logical>) -> Rectangle<f64, Logical> {
+        let area = area.to_f64();
+
+        let width = (area.size.w * self.width).round().min(area.size.w).max(0.0);
+        let height = (area.size.h * self.height)
+            .round()
+            .min(area.size.h)
+            .max(0.0);
+
+        let x = (area.loc.x + area.size.w * self.x)
+            .min(area.loc.x + area.size.w - width)
+            .max(area.loc.x);
+        let y = (area.loc.y + area.size.h * self.y)
+            .min(area.loc.y + area.size.h - height)
+            .max(area.loc.y);
+
+        Rectangle::from_loc_and_size((x, y), (width, height))
+    }
+}
+
+/// A half or quarter of an output's usable area that windows can be snapped to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapRegion {
+    LeftHalf,
+    RightHalf,
+    TopHalf,
+    BottomHalf,
+    TopLeftQuarter,
+    TopRightQuarter,
+    BottomLeftQuarter,
+    BottomRightQuarter,
+}
+
+impl SnapRegion {
+    const ALL: [SnapRegion; 8] = [
+        SnapRegion::LeftHalf,
+        SnapRegion::RightHalf,
+        SnapRegion::TopHalf,
+        SnapRegion::BottomHalf,
+        SnapRegion::TopLeftQuarter,
+        SnapRegion::TopRightQuarter,
+        SnapRegion::BottomLeftQuarter,
+        SnapRegion::BottomRightQuarter,
+    ];
+
+    /// The relative geometry this region covers.
+    pub fn relative_geometry(&self) -> RelativeGeometry {
+        let (x, y, width, height) = match self {
+            SnapRegion::LeftHalf => (0.0, 0.0, 0.5, 1.0),
+            SnapRegion::RightHalf => (0.5, 0.0, 0.5, 1.0),
+            SnapRegion::TopHalf => (0.0, 0.0, 1.0, 0.5),
+            SnapRegion::BottomHalf => (0.0, 0.5, 1.0, 0.5),
+            SnapRegion::TopLeftQuarter => (0.0, 0.0, 0.5, 0.5),
+            SnapRegion::TopRightQuarter => (0.5, 0.0, 0.5, 0.5),
+            SnapRegion::BottomLeftQuarter => (0.0, 0.5, 0.5, 0.5),
+            SnapRegion::BottomRightQuarter => (0.5, 0.5, 0.5, 0.5),
+        };
+
+        RelativeGeometry {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// The region that covers `geometry` exactly, if any.
+    pub fn from_relative_geometry(geometry: RelativeGeometry) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|region| region.relative_geometry() == geometry)
+    }
+
+    /// The regions snapping to this one cycles through, starting with this one.
+    ///
+    /// Halves cycle into the quarters inside them and quarters go clockwise.
+    fn cycle(&self) -> &'static [SnapRegion] {
+        use SnapRegion::*;
+        match self {
+            LeftHalf => &[LeftHalf, TopLeftQuarter, BottomLeftQuarter],
+            RightHalf => &[RightHalf, TopRightQuarter, BottomRightQuarter],
+            TopHalf => &[TopHalf, TopLeftQuarter, TopRightQuarter],
+            BottomHalf => &[BottomHalf, BottomLeftQuarter, BottomRightQuarter],
+            TopLeftQuarter => &[
+                TopLeftQuarter,
+                TopRightQuarter,
+                BottomRightQuarter,
+                BottomLeftQuarter,
+            ],
+            TopRightQuarter => &[
+                TopRightQuarter,
+                BottomRightQuarter,
+                BottomLeftQuarter,
+                TopLeftQuarter,
+            ],
+            BottomRightQuarter => &[
+                BottomRightQuarter,
+                BottomLeftQuarter,
+                TopLeftQuarter,
+                TopRightQuarter,
+            ],
+            BottomLeftQuarter => &[
+                BottomLeftQuarter,
+                TopLeftQuarter,
+                TopRightQuarter,
+                BottomRightQuarter,
+            ],
+        }
+    }
+
+    /// Get the region a window snapped to `current` should go to when snapped to this region.
+    ///
+    /// If `current` is in this region's cycle, this is the next region after it.
+    /// Otherwise it's this region.
+    pub fn next_from(&self, current: Option<SnapRegion>) -> SnapRegion {
+        let cycle = self.cycle();
+        current
+            .and_then(|current| cycle.iter().position(|region| *region == current))
+            .map(|idx| cycle[(idx + 1) % cycle.len()])
+            .unwrap_or(*self)
+    }
+}
+
+bitflags::bitflags! {
+    /// Edges of a window that are up against the edge of the usable area
+    /// or another tiled window.
+    ///
+    /// These are sent to xdg toplevels as the `tiled_*` states. Clients often
+    /// drop their shadows and rounded corners on tiled edges.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct TiledEdges: u8 {
+        const TOP = 1;
+        const BOTTOM = 1 << 1;
+        const LEFT = 1 << 2;
+        const RIGHT = 1 << 3;
+    }
+}
+
+impl TiledEdges {
+    /// Get the edges of tiled window geometry `geo` that border the edge of `area`
+    /// or one of the `others`.
+    ///
+    /// Edges up to `tolerance` pixels apart still border each other so gaps
+    /// don't untile them.
+    pub fn bordering(
+        geo: Rectangle<i32, Logical>,
+        area: Rectangle<i32, Logical>,
+        others: &[Rectangle<i32, Logical>],
+        tolerance: i32,
+    ) -> Self {
+        let (left, top) = (geo.loc.x, geo.loc.y);
+        let (right, bottom) = (left + geo.size.w, top + geo.size.h);
+
+        let near = |a: i32, b: i32| (a - b).abs() <= tolerance;
+        let overlaps_x = |other: &Rectangle<i32, Logical>| {
+            other.loc.x < right && left < other.loc.x + other.size.w
+        };
+        let overlaps_y = |other: &Rectangle<i32, Logical>| {
+            other.loc.y < bottom && top < other.loc.y + other.size.h
+        };
+
+        let mut edges = TiledEdges::empty();
+
+        if near(top, area.loc.y)
+            || others
+                .iter()
+                .any(|other| overlaps_x(other) && near(top, other.loc.y + other.size.h))
+        {
+            edges |= TiledEdges::TOP;
+        }
+        if near(bottom, area.loc.y + area.size.h)
+            || others
+                .iter()
+                .any(|other| overlaps_x(other) && near(bottom, other.loc.y))
+        {
+            edges |= TiledEdges::BOTTOM;
+        }
+        if near(left, area.loc.x)
+            || others
+                .iter()
+                .any(|other| overlaps_y(other) && near(left, other.loc.x + other.size.w))
+        {
+            edges |= TiledEdges::LEFT;
+        }
+        if near(right, area.loc.x + area.size.w)
+            || others
+                .iter()
+                .any(|other| overlaps_y(other) && near(right, other.loc.x))
+        {
+            edges |= TiledEdges::RIGHT;
+        }
+
+        edges
+    }
+}
+
 /// Whether a window is floating or tiled
 #[derive(Debug, Clone, Copy)]
 pub enum FloatingOrTiled {
     /// The window is floating with the specified geometry.
-    Floating(Rectangle<i32, Logical>),
+    ///
+    /// The geometry is fractional so floating windows can be placed on any physical pixel
+    /// on fractionally scaled outputs. It gets snapped to the physical pixel grid when rendered.
+    Floating(Rectangle<f64, Logical>),
     /// The window is tiled.
     ///
     /// The previous geometry it had when it was floating is stored here.
     /// This is so when it becomes floating again, it returns to this geometry.
-    Tiled(Option<Rectangle<i32, Logical>>),
+    Tiled(Option<Rectangle<f64, Logical>>),
 }
 
 impl FloatingOrTiled {
@@ -291,10 +652,131 @@ impl WindowElementState {
         Self {
             id: WindowId::next(),
             // loc_request_state: LocationRequestState::Idle,
-            tags: vec![],
+            tags: TagSet::new(),
             floating_or_tiled: FloatingOrTiled::Tiled(None),
             fullscreen_or_maximized: FullscreenOrMaximized::Neither,
             target_loc: None,
+            focus_steal_exempt: false,
+            urgent: None,
+            keybinds_disabled: false,
+            focus_on_open: None,
+            unfullscreen_on_focus_loss: None,
+            label: None,
+            last_focused: None,
+            scale: 1.0,
+            relative_geometry: None,
+            relative_geometry_from_pin: false,
+            skip_taskbar: false,
+            skip_cycle: false,
+            geometry_pinned: false,
+            maximize_gaps: false,
+            tiled_edges: TiledEdges::all(),
+            tiled_edges_enabled: true,
+            minimized: false,
+            minimized_loc: None,
+            activation_pinned: false,
+            closed_as_last_window: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapping_to_the_same_half_cycles_through_its_quarters() {
+        let region = SnapRegion::LeftHalf;
+
+        let first = region.next_from(None);
+        let second = region.next_from(Some(first));
+        let third = region.next_from(Some(second));
+
+        assert_eq!(first, SnapRegion::LeftHalf);
+        assert_eq!(second, SnapRegion::TopLeftQuarter);
+        assert_eq!(third, SnapRegion::BottomLeftQuarter);
+        assert_eq!(region.next_from(Some(third)), SnapRegion::LeftHalf);
+    }
+
+    #[test]
+    fn snapping_from_an_unrelated_region_starts_the_cycle() {
+        assert_eq!(
+            SnapRegion::RightHalf.next_from(Some(SnapRegion::TopLeftQuarter)),
+            SnapRegion::RightHalf
+        );
+    }
+
+    #[test]
+    fn relative_geometry_from_rect_resolves_back_to_rect() {
+        let area = Rectangle::from_loc_and_size((100, 50), (1000, 800));
+        let rect = Rectangle::from_loc_and_size((350.0, 250.0), (500.0, 200.0));
+
+        let relative = RelativeGeometry::from_rect(rect, area).unwrap();
+
+        assert_eq!(relative.resolve(area), rect);
+        assert_eq!(
+            RelativeGeometry::from_rect(rect, Rectangle::from_loc_and_size((0, 0), (0, 0))),
+            None
+        );
+    }
+
+    #[test]
+    fn relative_geometry_resolves_inside_area() {
+        let area = Rectangle::from_loc_and_size((100, 50), (1000, 800));
+        let relative = |x, y, width, height| RelativeGeometry {
+            x,
+            y,
+            width,
+            height,
+        };
+
+        assert_eq!(relative(0.0, 0.0, 1.0, 1.0).resolve(area), area.to_f64());
+        assert_eq!(relative(1.0, 1.0, 1.0, 1.0).resolve(area), area.to_f64());
+        assert_eq!(
+            relative(0.75, 0.0, 0.5, 0.5).resolve(area),
+            Rectangle::from_loc_and_size((600.0, 50.0), (500.0, 400.0))
+        );
+        assert_eq!(
+            relative(-0.5, 1.5, 2.0, 0.25).resolve(area),
+            Rectangle::from_loc_and_size((100.0, 650.0), (1000.0, 200.0))
+        );
+    }
+
+    #[test]
+    fn snap_regions_round_trip_through_relative_geometry() {
+        for region in SnapRegion::ALL {
+            assert_eq!(
+                SnapRegion::from_relative_geometry(region.relative_geometry()),
+                Some(region)
+            );
         }
     }
+
+    #[test]
+    fn tiled_edges_border_the_area_and_neighbors() {
+        let area = Rectangle::from_loc_and_size((0, 0), (1000, 800));
+        let left = Rectangle::from_loc_and_size((5, 5), (490, 790));
+        let right_top = Rectangle::from_loc_and_size((505, 5), (490, 390));
+
+        assert_eq!(
+            TiledEdges::bordering(left, area, &[right_top], 10),
+            TiledEdges::all()
+        );
+        assert_eq!(
+            TiledEdges::bordering(right_top, area, &[left], 10),
+            TiledEdges::TOP | TiledEdges::LEFT | TiledEdges::RIGHT
+        );
+    }
+
+    #[test]
+    fn tiled_edges_ignore_windows_that_dont_overlap() {
+        let area = Rectangle::from_loc_and_size((0, 0), (1000, 800));
+        let center = Rectangle::from_loc_and_size((250, 200), (500, 400));
+        let corner = Rectangle::from_loc_and_size((0, 0), (250, 200));
+
+        assert_eq!(
+            TiledEdges::bordering(center, area, &[corner], 0),
+            TiledEdges::empty()
+        );
+    }
 }
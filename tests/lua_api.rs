@@ -218,8 +218,9 @@ mod window {
 
             with_state(&sender, |state| {
                 assert_eq!(state.pinnacle.config.window_rules.len(), 1);
+                let (_, cond, rule) = &state.pinnacle.config.window_rules[0];
                 assert_eq!(
-                    state.pinnacle.config.window_rules[0],
+                    (cond.clone(), rule.clone()),
                     (
                         WindowRuleCondition {
                             class: Some(vec!["firefox".to_string()]),
@@ -255,8 +256,9 @@ mod window {
 
             with_state(&sender, |state| {
                 assert_eq!(state.pinnacle.config.window_rules.len(), 2);
+                let (_, cond, rule) = &state.pinnacle.config.window_rules[1];
                 assert_eq!(
-                    state.pinnacle.config.window_rules[1],
+                    (cond.clone(), rule.clone()),
                     (
                         WindowRuleCondition {
                             cond_all: Some(vec![WindowRuleCondition {
@@ -39,6 +39,90 @@ fn setup_rust(run: impl FnOnce(ApiModules) + Send + 'static) -> JoinHandle<()> {
     })
 }
 
+mod state {
+    use super::*;
+
+    #[tokio::main]
+    #[self::test]
+    async fn snapshot() -> anyhow::Result<()> {
+        test_api(|_sender| {
+            run_rust(|api| {
+                let op = api.output.get_focused().unwrap();
+                let tags = api.tag.add(&op, ["1", "2"]);
+                tags[0].set_active(true);
+
+                let snapshot = api.pinnacle.state_snapshot();
+
+                assert!(snapshot.windows.is_empty());
+                assert!(snapshot.focused_window.is_none());
+                assert_eq!(snapshot.focused_output, Some(op.clone()));
+
+                assert_eq!(snapshot.outputs.len(), 1);
+                assert_eq!(snapshot.outputs[0].output, op);
+                assert!(snapshot.outputs[0].focused);
+                assert_eq!(snapshot.outputs[0].tags, tags);
+                assert_eq!(snapshot.outputs[0].active_tags, vec![tags[0].clone()]);
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn backend() -> anyhow::Result<()> {
+        test_api(|_sender| {
+            run_rust(|api| {
+                assert_eq!(
+                    api.pinnacle.backend(),
+                    Some(pinnacle_api::pinnacle::Backend::Dummy)
+                );
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn tiling_paused() -> anyhow::Result<()> {
+        test_api(|sender| {
+            run_rust(|api| {
+                api.layout.set_tiling_paused(true);
+            });
+
+            with_state(&sender, |state| {
+                assert!(state.pinnacle.layout_state.tiling_paused);
+            });
+
+            run_rust(|api| {
+                api.layout.toggle_tiling_paused();
+            });
+
+            with_state(&sender, |state| {
+                assert!(!state.pinnacle.layout_state.tiling_paused);
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn xwayland_info() -> anyhow::Result<()> {
+        test_api(|_sender| {
+            run_rust(|api| {
+                let info = api.pinnacle.xwayland_info().unwrap();
+
+                // There's only a display to connect to while XWayland is running
+                assert_eq!(info.running, info.display.is_some());
+            });
+
+            Ok(())
+        })
+    }
+}
+
 mod output {
     use pinnacle::state::WithState;
     use pinnacle_api::output::{Alignment, OutputId, OutputLoc, OutputSetup, UpdateLocsOn};
@@ -316,6 +400,195 @@ mod output {
         })
     }
 
+    #[tokio::main]
+    #[self::test]
+    async fn set_inactive_output_dim() -> anyhow::Result<()> {
+        test_api(|sender| {
+            run_rust(|api| {
+                api.render.set_inactive_output_dim(0.3);
+            });
+
+            sleep_secs(1);
+
+            with_state(&sender, |state| {
+                assert_eq!(state.pinnacle.config.inactive_output_dim, 0.3);
+
+                // The only output has focus, so it isn't dimmed
+                let output = state.pinnacle.focused_output().unwrap();
+                assert!(state.pinnacle.output_dim(output).is_none());
+            });
+
+            run_rust(|api| {
+                api.render.set_inactive_output_dim(0.0);
+            });
+
+            sleep_secs(1);
+
+            with_state(&sender, |state| {
+                assert_eq!(state.pinnacle.config.inactive_output_dim, 0.0);
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn frame_timings_report_idle_outputs() -> anyhow::Result<()> {
+        use std::{
+            sync::{Arc, Mutex},
+            time::Duration,
+        };
+
+        test_api(|_sender| {
+            let reports = Arc::new(Mutex::new(Vec::new()));
+
+            let watched = reports.clone();
+            setup_rust(move |api| {
+                api.render.watch_frame_timings(move |timings| {
+                    watched
+                        .lock()
+                        .unwrap()
+                        .push((timings.output.name().to_string(), timings.frames));
+                });
+                api.render
+                    .set_frame_timings(Some(Duration::from_millis(200)));
+            });
+
+            sleep_secs(1);
+
+            // The dummy output never renders but is still reported
+            let reports = reports.lock().unwrap();
+            assert!(reports
+                .iter()
+                .any(|(name, frames)| name == DUMMY_OUTPUT_NAME && *frames == 0));
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn headless_when_all_outputs_disconnect() -> anyhow::Result<()> {
+        test_api(|sender| {
+            with_state(&sender, |state| {
+                let op = output_for_name(state, DUMMY_OUTPUT_NAME);
+                state.pinnacle.remove_output(&op);
+
+                assert!(state.pinnacle.headless);
+                assert_eq!(state.pinnacle.focused_output(), None);
+
+                state.pinnacle.new_output("First", (300, 200).into());
+
+                assert!(!state.pinnacle.headless);
+                assert_eq!(
+                    state.pinnacle.focused_output().map(|op| op.name()),
+                    Some("First".to_string())
+                );
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn windows_move_to_output_after_headless() -> anyhow::Result<()> {
+        test_api(|sender| {
+            run_rust(|api| {
+                api.tag.set_default_output_tags(["1", "2"]);
+
+                let op = api.output.get_focused().unwrap();
+                api.tag.add(&op, ["Old"])[0].set_active(true);
+                api.process.spawn(["foot"]);
+            });
+
+            sleep_secs(1);
+
+            with_state(&sender, |state| {
+                use pinnacle::state::WithState;
+
+                assert_eq!(state.pinnacle.windows.len(), 1);
+
+                let op = output_for_name(state, DUMMY_OUTPUT_NAME);
+                state.pinnacle.remove_output(&op);
+
+                assert!(state.pinnacle.headless);
+
+                state.pinnacle.new_output("First", (300, 200).into());
+
+                let win = &state.pinnacle.windows[0];
+                assert_eq!(
+                    win.with_state(|st| st.tags.iter().map(|tag| tag.name()).collect::<Vec<_>>()),
+                    vec!["1"]
+                );
+                assert_eq!(
+                    win.output(&state.pinnacle).map(|op| op.name()),
+                    Some("First".to_string())
+                );
+                assert!(win.is_on_active_tag());
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn windows_wait_for_active_tags_after_headless() -> anyhow::Result<()> {
+        test_api(|sender| {
+            run_rust(|api| {
+                let op = api.output.get_focused().unwrap();
+                api.tag.add(&op, ["Old"])[0].set_active(true);
+                api.process.spawn(["foot"]);
+            });
+
+            sleep_secs(1);
+
+            with_state(&sender, |state| {
+                assert_eq!(state.pinnacle.windows.len(), 1);
+
+                let op = output_for_name(state, DUMMY_OUTPUT_NAME);
+                state.pinnacle.remove_output(&op);
+                state.pinnacle.new_output("First", (300, 200).into());
+
+                // The new output has no tags yet
+                assert_eq!(state.pinnacle.parked_windows.len(), 1);
+            });
+
+            run_rust(|api| {
+                let op = api.output.get_by_name("First").unwrap();
+                let tags = api.tag.add(&op, ["1", "2"]);
+
+                tags[0].switch_to();
+                tags[1].switch_to();
+
+                assert_eq!(tags[0].active(), Some(false));
+                assert_eq!(tags[1].active(), Some(true));
+            });
+
+            sleep_secs(1);
+
+            with_state(&sender, |state| {
+                use pinnacle::state::WithState;
+
+                assert!(state.pinnacle.parked_windows.is_empty());
+
+                let win = &state.pinnacle.windows[0];
+                assert_eq!(
+                    win.with_state(|st| st.tags.iter().map(|tag| tag.name()).collect::<Vec<_>>()),
+                    vec!["1"]
+                );
+                assert_eq!(
+                    win.output(&state.pinnacle).map(|op| op.name()),
+                    Some("First".to_string())
+                );
+            });
+
+            Ok(())
+        })
+    }
+
     mod handle {
         use pinnacle_api::output::Transform;
 
@@ -323,41 +596,1950 @@ mod output {
 
         #[tokio::main]
         #[self::test]
-        async fn set_transform() -> anyhow::Result<()> {
+        async fn set_magnification() -> anyhow::Result<()> {
             test_api(|sender| {
+                run_rust(|api| {
+                    let op = api.output.get_focused().unwrap();
+                    op.set_magnification(2.0);
+                    op.increase_magnification(100.0);
+                });
+
+                sleep_secs(1);
+
+                with_state(&sender, |state| {
+                    let op = state.pinnacle.focused_output().unwrap();
+                    assert_eq!(op.with_state(|state| state.magnification), Some(16.0));
+                });
+
                 run_rust(|api| {
                     api.output
                         .get_focused()
                         .unwrap()
-                        .set_transform(Transform::Flipped270);
+                        .decrease_magnification(20.0);
                 });
 
                 sleep_secs(1);
 
                 with_state(&sender, |state| {
                     let op = state.pinnacle.focused_output().unwrap();
-                    assert_eq!(
-                        op.current_transform(),
-                        smithay::utils::Transform::Flipped270
-                    );
+                    assert_eq!(op.with_state(|state| state.magnification), None);
                 });
 
+                Ok(())
+            })
+        }
+
+        #[tokio::main]
+        #[self::test]
+        async fn set_gamma_preset() -> anyhow::Result<()> {
+            use pinnacle::output::OutputName;
+            use pinnacle_api::output::GammaPreset;
+
+            test_api(|sender| {
                 run_rust(|api| {
                     api.output
                         .get_focused()
                         .unwrap()
-                        .set_transform(Transform::_180);
+                        .set_gamma_preset(GammaPreset {
+                            temperature: 3500,
+                            brightness: 0.8,
+                            ..Default::default()
+                        });
+                });
+
+                sleep_secs(1);
+
+                with_state(&sender, |state| {
+                    let preset = state
+                        .pinnacle
+                        .gamma_presets
+                        .get(&OutputName(DUMMY_OUTPUT_NAME.to_string()))
+                        .copied()
+                        .unwrap();
+                    assert_eq!(preset.temperature, 3500);
+                    assert_eq!(preset.brightness, 0.8);
+                    assert_eq!(preset.gamma, 1.0);
+                });
+
+                run_rust(|api| {
+                    api.output.get_focused().unwrap().remove_gamma_preset();
                 });
 
                 sleep_secs(1);
 
+                with_state(&sender, |state| {
+                    assert!(state.pinnacle.gamma_presets.is_empty());
+                });
+
+                Ok(())
+            })
+        }
+
+        #[tokio::main]
+        #[self::test]
+        async fn backlight_lookup_is_cached() -> anyhow::Result<()> {
+            use pinnacle::backlight::Backlight;
+
+            test_api(|sender| {
                 with_state(&sender, |state| {
                     let op = state.pinnacle.focused_output().unwrap();
-                    assert_eq!(op.current_transform(), smithay::utils::Transform::_180);
+                    assert!(op.with_state(|state| state.backlight.is_none()));
+                });
+
+                run_rust(|api| {
+                    api.output.get_focused().unwrap().brightness();
+                });
+
+                sleep_secs(1);
+
+                with_state(&sender, |state| {
+                    // The dummy output has no backlight, and that's remembered
+                    // instead of scanning sysfs again
+                    let op = state.pinnacle.focused_output().unwrap();
+                    assert_eq!(op.with_state(|state| state.backlight.clone()), Some(None));
+                    assert_eq!(Backlight::for_output_cached(op), None);
+                });
+
+                Ok(())
+            })
+        }
+
+        #[tokio::main]
+        #[self::test]
+        async fn set_lone_window_width() -> anyhow::Result<()> {
+            use pinnacle_api::output::{LoneWindowAlignment, LoneWindowWidth};
+
+            test_api(|sender| {
+                run_rust(|api| {
+                    api.output
+                        .get_focused()
+                        .unwrap()
+                        .set_lone_window_width(LoneWindowWidth {
+                            max_width: 1200,
+                            alignment: LoneWindowAlignment::Left,
+                        });
+                });
+
+                sleep_secs(1);
+
+                with_state(&sender, |state| {
+                    let op = state.pinnacle.focused_output().unwrap();
+                    assert_eq!(
+                        op.with_state(|state| state.lone_window_width),
+                        Some(pinnacle::output::LoneWindowWidth {
+                            max_width: 1200,
+                            alignment: pinnacle::output::LoneWindowAlignment::Left,
+                        })
+                    );
+                });
+
+                run_rust(|api| {
+                    let op = api.output.get_focused().unwrap();
+                    assert_eq!(
+                        op.lone_window_width(),
+                        Some(LoneWindowWidth {
+                            max_width: 1200,
+                            alignment: LoneWindowAlignment::Left,
+                        })
+                    );
+                    op.remove_lone_window_width();
+                });
+
+                sleep_secs(1);
+
+                with_state(&sender, |state| {
+                    let op = state.pinnacle.focused_output().unwrap();
+                    assert_eq!(op.with_state(|state| state.lone_window_width), None);
                 });
 
                 Ok(())
             })
         }
+
+        #[tokio::main]
+        #[self::test]
+        async fn set_transform() -> anyhow::Result<()> {
+            test_api(|sender| {
+                run_rust(|api| {
+                    api.output
+                        .get_focused()
+                        .unwrap()
+                        .set_transform(Transform::Flipped270);
+                });
+
+                sleep_secs(1);
+
+                with_state(&sender, |state| {
+                    let op = state.pinnacle.focused_output().unwrap();
+                    assert_eq!(
+                        op.current_transform(),
+                        smithay::utils::Transform::Flipped270
+                    );
+                });
+
+                run_rust(|api| {
+                    api.output
+                        .get_focused()
+                        .unwrap()
+                        .set_transform(Transform::_180);
+                });
+
+                sleep_secs(1);
+
+                with_state(&sender, |state| {
+                    let op = state.pinnacle.focused_output().unwrap();
+                    assert_eq!(op.current_transform(), smithay::utils::Transform::_180);
+                });
+
+                Ok(())
+            })
+        }
+
+        #[tokio::main]
+        #[self::test]
+        async fn lock_focus() -> anyhow::Result<()> {
+            test_api(|sender| {
+                with_state(&sender, |state| {
+                    state.pinnacle.new_output("Second", (300, 200).into());
+                });
+
+                run_rust(|api| {
+                    api.output
+                        .get_by_name(DUMMY_OUTPUT_NAME)
+                        .unwrap()
+                        .lock_focus();
+                });
+
+                with_state(&sender, |state| {
+                    let second_op = output_for_name(state, "Second");
+                    state.pinnacle.output_focus_stack.set_focus(second_op);
+
+                    let focused = state.pinnacle.focused_output().unwrap();
+                    assert_eq!(focused.name(), DUMMY_OUTPUT_NAME);
+                });
+
+                run_rust(|api| {
+                    api.output.unlock_focus();
+                });
+
+                with_state(&sender, |state| {
+                    let second_op = output_for_name(state, "Second");
+                    state.pinnacle.output_focus_stack.set_focus(second_op);
+
+                    let focused = state.pinnacle.focused_output().unwrap();
+                    assert_eq!(focused.name(), "Second");
+                });
+
+                Ok(())
+            })
+        }
+
+        #[tokio::main]
+        #[self::test]
+        async fn set_primary() -> anyhow::Result<()> {
+            test_api(|sender| {
+                run_rust(|api| {
+                    api.output
+                        .get_by_name(DUMMY_OUTPUT_NAME)
+                        .unwrap()
+                        .set_primary();
+                });
+
+                with_state(&sender, |state| {
+                    assert_eq!(
+                        state.pinnacle.config.primary_output,
+                        Some(pinnacle::output::OutputName(DUMMY_OUTPUT_NAME.to_string()))
+                    );
+                });
+
+                run_rust(|api| {
+                    api.output.clear_primary();
+                });
+
+                with_state(&sender, |state| {
+                    assert!(state.pinnacle.config.primary_output.is_none());
+                });
+
+                Ok(())
+            })
+        }
+    }
+}
+
+mod tag {
+    use super::*;
+
+    #[tokio::main]
+    #[self::test]
+    async fn switch_to_previous() -> anyhow::Result<()> {
+        test_api(|_sender| {
+            run_rust(|api| {
+                let op = api.output.get_focused().unwrap();
+                let tags = api.tag.add(&op, ["1", "2", "3"]);
+
+                tags[0].set_active(true);
+                tags[1].set_active(true);
+                tags[2].switch_to();
+
+                api.tag.switch_to_previous(&op);
+                assert_eq!(tags[0].active(), Some(true));
+                assert_eq!(tags[1].active(), Some(true));
+                assert_eq!(tags[2].active(), Some(false));
+
+                api.tag.switch_to_previous(&op);
+                assert_eq!(tags[0].active(), Some(false));
+                assert_eq!(tags[1].active(), Some(false));
+                assert_eq!(tags[2].active(), Some(true));
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn set_active_tags() -> anyhow::Result<()> {
+        test_api(|_sender| {
+            run_rust(|api| {
+                let op = api.output.get_focused().unwrap();
+                let tags = api.tag.add(&op, ["1", "2", "3"]);
+
+                tags[1].set_active(true);
+                api.tag
+                    .set_active_tags(&op, [tags[0].clone(), tags[2].clone()]);
+
+                assert_eq!(tags[0].active(), Some(true));
+                assert_eq!(tags[1].active(), Some(false));
+                assert_eq!(tags[2].active(), Some(true));
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn tag_layout() -> anyhow::Result<()> {
+        test_api(|_sender| {
+            run_rust(|api| {
+                use pinnacle_api::layout::ReportedTagLayout;
+
+                let op = api.output.get_focused().unwrap();
+                let tags = api.tag.add(&op, ["1", "2"]);
+
+                let reported = ReportedTagLayout {
+                    current_layout: Some("dwindle".into()),
+                    layouts: vec!["master_stack".into(), "dwindle".into()],
+                    master_factor: Some(0.6),
+                    master_count: Some(2),
+                };
+
+                api.layout.report_tag_layout(&tags[0], reported.clone());
+
+                let info = api.layout.tag_layout(&tags[0]);
+                assert_eq!(info.reported, Some(reported));
+                assert_eq!(info.tiling_paused, Some(false));
+                assert!(info.inner_gaps.is_some());
+
+                // Nothing was reported for the second tag
+                assert_eq!(api.layout.tag_layout(&tags[1]).reported, None);
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn default_output_tags() -> anyhow::Result<()> {
+        test_api(|sender| {
+            run_rust(|api| {
+                api.tag.set_default_output_tags(["1", "2", "3"]);
+            });
+
+            with_state(&sender, |state| {
+                state.pinnacle.new_output("Second", (300, 200).into());
+
+                let second_op = output_for_name(state, "Second");
+
+                let (names, active) = second_op.with_state(|state| {
+                    (
+                        state.tags.iter().map(|tag| tag.name()).collect::<Vec<_>>(),
+                        state
+                            .tags
+                            .iter()
+                            .map(|tag| tag.active())
+                            .collect::<Vec<_>>(),
+                    )
+                });
+
+                assert_eq!(names, ["1", "2", "3"]);
+                assert_eq!(active, [true, false, false]);
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn set_gaps() -> anyhow::Result<()> {
+        test_api(|_sender| {
+            run_rust(|api| {
+                let op = api.output.get_focused().unwrap();
+                let tags = api.tag.add(&op, ["1"]);
+
+                assert_eq!(tags[0].inner_gaps(), Some(0));
+                assert_eq!(tags[0].outer_gaps(), Some(0));
+                assert_eq!(tags[0].smart_gaps(), Some(false));
+
+                tags[0].set_gaps(4, 8);
+                tags[0].set_smart_gaps(true);
+
+                assert_eq!(tags[0].inner_gaps(), Some(4));
+                assert_eq!(tags[0].outer_gaps(), Some(8));
+                assert_eq!(tags[0].smart_gaps(), Some(true));
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn smart_gaps_remove_reported_layout_gaps() -> anyhow::Result<()> {
+        use pinnacle_api::layout::{CyclingLayoutManager, Gaps, MasterStackLayout};
+
+        test_api(|sender| {
+            setup_rust(|api| {
+                api.layout
+                    .set_manager(CyclingLayoutManager::new([Box::new(MasterStackLayout {
+                        gaps: Gaps::Split { inner: 4, outer: 8 },
+                        ..Default::default()
+                    }) as _]));
+            });
+
+            run_rust(|api| {
+                let op = api.output.get_focused().unwrap();
+                let tags = api.tag.add(&op, ["1"]);
+                tags[0].set_active(true);
+                tags[0].set_smart_gaps(true);
+                api.process.spawn(["foot"]);
+            });
+
+            sleep_secs(1);
+
+            // The lone window fills the output instead of keeping the layout's 12px gap
+            with_state(&sender, |state| {
+                let op = state.pinnacle.focused_output().unwrap().clone();
+                let output_geo = state.pinnacle.space.output_geometry(&op).unwrap();
+                let win = &state.pinnacle.windows[0];
+                assert_eq!(
+                    win.toplevel().unwrap().with_pending_state(|st| st.size),
+                    Some(output_geo.size)
+                );
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn toggle_floating_hidden() -> anyhow::Result<()> {
+        test_api(|_sender| {
+            run_rust(|api| {
+                let op = api.output.get_focused().unwrap();
+                let tags = api.tag.add(&op, ["1"]);
+
+                assert_eq!(tags[0].floating_hidden(), Some(false));
+
+                tags[0].toggle_floating_hidden();
+                assert_eq!(tags[0].floating_hidden(), Some(true));
+
+                tags[0].set_floating_hidden(false);
+                assert_eq!(tags[0].floating_hidden(), Some(false));
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn set_window_limit() -> anyhow::Result<()> {
+        use pinnacle_api::tag::OverflowPolicy;
+
+        test_api(|_sender| {
+            run_rust(|api| {
+                let op = api.output.get_focused().unwrap();
+                let tags = api.tag.add(&op, ["1"]);
+
+                assert_eq!(tags[0].window_limit(), None);
+
+                tags[0].set_window_limit(2, OverflowPolicy::NextTag);
+                let props = tags[0].props();
+                assert_eq!(props.window_limit, Some(2));
+                assert_eq!(props.overflow_policy, Some(OverflowPolicy::NextTag));
+
+                tags[0].set_window_limit(0, OverflowPolicy::Float);
+                assert_eq!(tags[0].window_limit(), Some(2));
+
+                tags[0].remove_window_limit();
+                let props = tags[0].props();
+                assert_eq!(props.window_limit, None);
+                assert_eq!(props.overflow_policy, None);
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn rejected_window_that_ignores_close_floats() -> anyhow::Result<()> {
+        use pinnacle::tag::{OverflowPolicy, WindowLimit, REJECTED_WINDOW_TIMEOUT};
+        use smithay::reexports::wayland_server::Resource;
+
+        /// Send `signal` to the client of every window so stopped clients can't close.
+        fn signal_clients(state: &pinnacle::state::State, signal: &str) {
+            for win in state.pinnacle.windows.iter() {
+                let pid = win
+                    .wl_surface()
+                    .and_then(|surface| surface.client())
+                    .and_then(|client| client.get_credentials(&state.pinnacle.display_handle).ok())
+                    .unwrap()
+                    .pid;
+                std::process::Command::new("kill")
+                    .args([signal, &pid.to_string()])
+                    .status()
+                    .unwrap();
+            }
+        }
+
+        test_api(|sender| {
+            run_rust(|api| {
+                let op = api.output.get_focused().unwrap();
+                api.tag.add(&op, ["1"])[0].set_active(true);
+                api.process.spawn(["foot"]);
+                api.process.spawn(["foot"]);
+            });
+
+            sleep_secs(1);
+
+            with_state(&sender, |state| {
+                assert_eq!(state.pinnacle.windows.len(), 2);
+                signal_clients(state, "-STOP");
+
+                let win = state.pinnacle.windows[1].clone();
+                win.with_state(|st| {
+                    st.tags.first().unwrap().set_window_limit(Some(WindowLimit {
+                        max: 1,
+                        overflow: OverflowPolicy::Reject,
+                    }))
+                });
+
+                // The window is hidden while it's asked to close
+                assert!(!state.pinnacle.apply_tag_window_limits(&win));
+                assert!(win.with_state(|st| st.tags.is_empty()));
+                assert!(!state.pinnacle.space.elements().any(|w| w == &win));
+            });
+
+            sleep_secs(REJECTED_WINDOW_TIMEOUT.as_secs() + 1);
+
+            with_state(&sender, |state| {
+                // The client never closed it, so it's back on its tag
+                let win = &state.pinnacle.windows[1];
+                assert!(win.with_state(|st| !st.tags.is_empty()));
+                assert!(win.with_state(|st| st.floating_or_tiled.is_floating()));
+
+                signal_clients(state, "-KILL");
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn set_tiling_direction() -> anyhow::Result<()> {
+        use pinnacle::state::WithState;
+        use pinnacle_api::tag::TilingDirection;
+
+        test_api(|sender| {
+            run_rust(|api| {
+                let op = api.output.get_focused().unwrap();
+                let tags = api.tag.add(&op, ["1", "2"]);
+
+                assert_eq!(tags[0].tiling_direction(), None);
+
+                // Unset directions toggle as if they were horizontal
+                tags[0].toggle_tiling_direction();
+                assert_eq!(tags[0].tiling_direction(), Some(TilingDirection::Vertical));
+
+                tags[0].set_tiling_direction(TilingDirection::Horizontal);
+                assert_eq!(
+                    tags[0].tiling_direction(),
+                    Some(TilingDirection::Horizontal)
+                );
+
+                tags[1].set_tiling_direction(TilingDirection::Vertical);
+                tags[1].remove_tiling_direction();
+                assert_eq!(tags[1].tiling_direction(), None);
+            });
+
+            with_state(&sender, |state| {
+                let op = state.pinnacle.focused_output().unwrap();
+                let directions = op.with_state(|state| {
+                    state
+                        .tags
+                        .iter()
+                        .map(|tag| tag.tiling_direction())
+                        .collect::<Vec<_>>()
+                });
+                assert_eq!(
+                    directions,
+                    [Some(pinnacle::tag::TilingDirection::Horizontal), None]
+                );
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn set_display_name() -> anyhow::Result<()> {
+        use pinnacle::state::WithState;
+
+        test_api(|sender| {
+            run_rust(|api| {
+                let op = api.output.get_focused().unwrap();
+                let tags = api.tag.add(&op, ["1", "2"]);
+
+                assert_eq!(tags[0].display_name(), None);
+
+                tags[0].set_display_name("www");
+                tags[1].set_display_name("www");
+                tags[1].remove_display_name();
+
+                assert_eq!(tags[0].display_name().as_deref(), Some("www"));
+                assert_eq!(tags[1].display_name(), None);
+
+                // The tag can still be found by its name
+                assert_eq!(api.tag.get("1"), Some(tags[0].clone()));
+            });
+
+            with_state(&sender, |state| {
+                let op = state.pinnacle.focused_output().unwrap();
+                let names = op.with_state(|state| {
+                    state
+                        .tags
+                        .iter()
+                        .map(|tag| (tag.name(), tag.display_name()))
+                        .collect::<Vec<_>>()
+                });
+                assert_eq!(
+                    names,
+                    [
+                        ("1".to_string(), Some("www".to_string())),
+                        ("2".to_string(), None)
+                    ]
+                );
+            });
+
+            Ok(())
+        })
+    }
+}
+
+mod window {
+    use super::*;
+
+    #[tokio::main]
+    #[self::test]
+    async fn jump_to_without_matches() -> anyhow::Result<()> {
+        test_api(|_sender| {
+            run_rust(|api| {
+                assert!(api.window.jump_to(Some("firefox"), None, true).is_none());
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn cancel_grab_without_grab() -> anyhow::Result<()> {
+        test_api(|sender| {
+            run_rust(|api| {
+                assert!(api.window.active_grab().is_none());
+                api.window.cancel_grab();
+            });
+
+            with_state(&sender, |state| {
+                assert!(state.pinnacle.active_grab.is_none());
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn select_window_canceled_with_escape() -> anyhow::Result<()> {
+        use pinnacle_api::xkbcommon::xkb::Keysym;
+
+        test_api(|sender| {
+            let (selected_send, selected_recv) = std::sync::mpsc::channel();
+
+            // Selecting blocks until a hint is picked, so it can't be joined right away
+            let selecting = std::thread::spawn(move || {
+                run_rust_inner(move |api| {
+                    selected_send.send(api.window.select().is_none()).unwrap();
+                });
+            });
+
+            sleep_secs(1);
+
+            with_state(&sender, |state| {
+                assert!(state.pinnacle.window_hints.is_some());
+                state.select_window_hint(Keysym::Escape);
+                assert!(state.pinnacle.window_hints.is_none());
+            });
+
+            selecting.join().unwrap();
+            assert!(selected_recv.recv()?);
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn window_hint_key_release_is_swallowed() -> anyhow::Result<()> {
+        use pinnacle_api::xkbcommon::xkb::Keycode;
+        use smithay::backend::input::KeyState;
+
+        // Evdev keycode for Escape offset by 8, as xkb expects
+        const ESCAPE: Keycode = Keycode::new(9);
+
+        test_api(|sender| {
+            let selecting = std::thread::spawn(|| {
+                run_rust_inner(|api| {
+                    api.window.select();
+                });
+            });
+
+            sleep_secs(1);
+
+            with_state(&sender, |state| {
+                assert!(state.pinnacle.window_hints.is_some());
+
+                state.handle_key(ESCAPE, KeyState::Pressed, 0, true);
+                assert!(state.pinnacle.window_hints.is_none());
+                assert!(state
+                    .pinnacle
+                    .input_state
+                    .hint_key_presses
+                    .contains(&ESCAPE));
+
+                // The release belongs to the press that closed the hints
+                state.handle_key(ESCAPE, KeyState::Released, 0, true);
+                assert!(state.pinnacle.input_state.hint_key_presses.is_empty());
+            });
+
+            selecting.join().unwrap();
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn add_and_remove_window_rules() -> anyhow::Result<()> {
+        use pinnacle_api::window::rules::{WindowRule, WindowRuleCondition};
+
+        test_api(|sender| {
+            run_rust(|api| {
+                let firefox = api
+                    .window
+                    .add_window_rule(
+                        WindowRuleCondition::new().classes(["firefox"]),
+                        WindowRule::new().floating(true),
+                    )
+                    .unwrap();
+                let foot = api
+                    .window
+                    .add_window_rule(
+                        WindowRuleCondition::new().classes(["foot"]),
+                        WindowRule::new().width(500).height(250),
+                    )
+                    .unwrap();
+
+                // Width without a height is rejected
+                assert!(api
+                    .window
+                    .add_window_rule(WindowRuleCondition::new(), WindowRule::new().width(500))
+                    .is_none());
+
+                let rules = api.window.get_window_rules();
+                assert_eq!(rules.len(), 2);
+                assert_eq!(rules[0].0, firefox);
+                assert_eq!(rules[1].0, foot);
+
+                api.window.remove_window_rule(firefox);
+
+                let rules = api.window.get_window_rules();
+                assert_eq!(rules.len(), 1);
+                assert_eq!(rules[0].0, foot);
+            });
+
+            with_state(&sender, |state| {
+                assert_eq!(state.pinnacle.config.window_rules.len(), 1);
+                assert_eq!(
+                    state.pinnacle.config.window_rules[0].1.class,
+                    Some(vec!["foot".to_string()])
+                );
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn skip_taskbar_and_cycle_window_rule() -> anyhow::Result<()> {
+        use pinnacle_api::window::rules::{WindowRule, WindowRuleCondition};
+
+        test_api(|sender| {
+            run_rust(|api| {
+                api.window.add_window_rule(
+                    WindowRuleCondition::new().classes(["scratchpad"]),
+                    WindowRule::new().skip_taskbar(true).skip_cycle(false),
+                );
+            });
+
+            sleep_secs(1);
+
+            with_state(&sender, |state| {
+                let (_, _, rule) = &state.pinnacle.config.window_rules[0];
+                assert_eq!(rule.skip_taskbar, Some(true));
+                assert_eq!(rule.skip_cycle, Some(false));
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn unfullscreen_on_focus_loss() -> anyhow::Result<()> {
+        use pinnacle_api::window::rules::{WindowRule, WindowRuleCondition};
+
+        test_api(|sender| {
+            with_state(&sender, |state| {
+                assert!(!state.pinnacle.config.unfullscreen_on_focus_loss);
+            });
+
+            run_rust(|api| {
+                api.window.set_unfullscreen_on_focus_loss(true);
+                api.window.add_window_rule(
+                    WindowRuleCondition::new().classes(["mpv"]),
+                    WindowRule::new().unfullscreen_on_focus_loss(false),
+                );
+            });
+
+            sleep_secs(1);
+
+            with_state(&sender, |state| {
+                assert!(state.pinnacle.config.unfullscreen_on_focus_loss);
+                let (_, _, rule) = &state.pinnacle.config.window_rules[0];
+                assert_eq!(rule.unfullscreen_on_focus_loss, Some(false));
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn unpinned_window_stays_put_when_output_changes() -> anyhow::Result<()> {
+        use pinnacle::{state::WithState, window::window_state::FloatingOrTiled};
+        use pinnacle_api::util::Geometry;
+        use smithay::output::Scale;
+
+        test_api(|sender| {
+            run_rust(|api| {
+                let op = api.output.get_focused().unwrap();
+                api.tag.add(&op, ["1"])[0].set_active(true);
+                api.process.spawn(["foot"]);
+            });
+
+            sleep_secs(1);
+
+            run_rust(|api| {
+                let win = &api.window.get_all()[0];
+                win.set_floating(true);
+                win.set_floating_geometry(Geometry {
+                    x: 100,
+                    y: 100,
+                    width: 300,
+                    height: 200,
+                });
+                win.set_geometry_pinned(true);
+                win.set_geometry_pinned(false);
+            });
+
+            sleep_secs(1);
+
+            with_state(&sender, |state| {
+                let win = state.pinnacle.windows[0].clone();
+                assert!(win.with_state(|st| st.relative_geometry.is_none()));
+
+                let floating_geo = || match win.with_state(|st| st.floating_or_tiled) {
+                    FloatingOrTiled::Floating(rect) => rect,
+                    FloatingOrTiled::Tiled(_) => panic!("window isn't floating"),
+                };
+                let before = floating_geo();
+
+                let op = state.pinnacle.focused_output().unwrap().clone();
+                state.pinnacle.change_output_state(
+                    &op,
+                    None,
+                    None,
+                    Some(Scale::Fractional(2.0)),
+                    Some((500, 500).into()),
+                );
+
+                assert_eq!(floating_geo(), before);
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn centering_keeps_fractional_floating_geometry() -> anyhow::Result<()> {
+        use pinnacle::{state::WithState, window::window_state::FloatingOrTiled};
+        use smithay::{desktop::layer_map_for_output, utils::Rectangle};
+
+        test_api(|sender| {
+            run_rust(|api| {
+                let op = api.output.get_focused().unwrap();
+                api.tag.add(&op, ["1"])[0].set_active(true);
+                api.process.spawn(["foot"]);
+            });
+
+            sleep_secs(1);
+
+            with_state(&sender, |state| {
+                let win = state.pinnacle.windows[0].clone();
+                win.with_state_mut(|st| {
+                    st.floating_or_tiled = FloatingOrTiled::Floating(Rectangle::from_loc_and_size(
+                        (10.5, 10.5),
+                        (301.0, 201.0),
+                    ));
+                });
+
+                state.pinnacle.float_window_centered(&win);
+
+                let op = state.pinnacle.focused_output().unwrap().clone();
+                let output_loc = state.pinnacle.space.output_geometry(&op).unwrap().loc;
+                let zone = layer_map_for_output(&op).non_exclusive_zone().to_f64();
+
+                let FloatingOrTiled::Floating(rect) = win.with_state(|st| st.floating_or_tiled)
+                else {
+                    panic!("window isn't floating");
+                };
+                assert_eq!(rect.size, (301.0, 201.0).into());
+                assert_eq!(
+                    rect.loc.x,
+                    f64::from(output_loc.x) + zone.loc.x + (zone.size.w - 301.0) / 2.0
+                );
+                assert_eq!(
+                    rect.loc.y,
+                    f64::from(output_loc.y) + zone.loc.y + (zone.size.h - 201.0) / 2.0
+                );
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn clicking_another_window_unfullscreens() -> anyhow::Result<()> {
+        use pinnacle::state::WithState;
+        use smithay::utils::SERIAL_COUNTER;
+
+        test_api(|sender| {
+            with_state(&sender, |state| {
+                state.pinnacle.new_output("Second", (300, 200).into());
+                let second = output_for_name(state, "Second");
+                state.pinnacle.change_output_state(
+                    &second,
+                    None,
+                    None,
+                    None,
+                    Some((10000, 0).into()),
+                );
+            });
+
+            run_rust(|api| {
+                api.window.set_unfullscreen_on_focus_loss(true);
+
+                let first = api.output.get_by_name(DUMMY_OUTPUT_NAME).unwrap();
+                let second = api.output.get_by_name("Second").unwrap();
+                api.tag.add(&first, ["1"])[0].set_active(true);
+                api.tag.add(&second, ["1"])[0].set_active(true);
+
+                api.process.spawn(["foot"]);
+            });
+
+            sleep_secs(1);
+
+            run_rust(|api| {
+                api.process.spawn(["foot"]);
+            });
+
+            sleep_secs(1);
+
+            run_rust(|api| {
+                let second = api.output.get_by_name("Second").unwrap();
+                let windows = api.window.get_all();
+                windows[1].move_to_tag(&second.tags()[0]);
+                windows[0].set_focused(true);
+                windows[0].set_fullscreen(true);
+            });
+
+            sleep_secs(1);
+
+            with_state(&sender, |state| {
+                let fullscreen_win = state.pinnacle.windows[0].clone();
+                let other_win = state.pinnacle.windows[1].clone();
+                assert!(fullscreen_win.with_state(|st| st.fullscreen_or_maximized.is_fullscreen()));
+
+                let loc = state
+                    .pinnacle
+                    .space
+                    .element_geometry(&other_win)
+                    .unwrap()
+                    .loc;
+                state.click_to_focus((loc + (5, 5).into()).to_f64(), SERIAL_COUNTER.next_serial());
+
+                assert!(!fullscreen_win.with_state(|st| st.fullscreen_or_maximized.is_fullscreen()));
+                let second = output_for_name(state, "Second");
+                assert_eq!(state.pinnacle.focused_window(&second), Some(other_win));
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn focus_history_is_per_output() -> anyhow::Result<()> {
+        use pinnacle::state::WithState;
+
+        test_api(|sender| {
+            with_state(&sender, |state| {
+                state.pinnacle.new_output("Second", (300, 200).into());
+                let second = output_for_name(state, "Second");
+                state.pinnacle.change_output_state(
+                    &second,
+                    None,
+                    None,
+                    None,
+                    Some((10000, 0).into()),
+                );
+            });
+
+            run_rust(|api| {
+                let first = api.output.get_by_name(DUMMY_OUTPUT_NAME).unwrap();
+                let second = api.output.get_by_name("Second").unwrap();
+                api.tag.add(&first, ["1"])[0].set_active(true);
+                api.tag.add(&second, ["1"])[0].set_active(true);
+
+                api.process.spawn(["foot"]);
+                api.process.spawn(["foot"]);
+            });
+
+            sleep_secs(1);
+
+            run_rust(|api| {
+                let second = api.output.get_by_name("Second").unwrap();
+                api.window.get_all()[1].move_to_tag(&second.tags()[0]);
+            });
+
+            sleep_secs(1);
+
+            with_state(&sender, |state| {
+                let first_win = state.pinnacle.windows[0].clone();
+                let second_win = state.pinnacle.windows[1].clone();
+
+                state.focus_window(&first_win, false);
+                state.focus_window(&second_win, false);
+                state.focus_window(&first_win, false);
+
+                // The window on the other output doesn't count as previously focused here
+                assert_eq!(state.pinnacle.previously_focused_window(), None);
+
+                let second = output_for_name(state, "Second");
+                assert!(second.with_state(|st| st.focus_history == [second_win.clone()]));
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn labels_are_unique() -> anyhow::Result<()> {
+        test_api(|_sender| {
+            run_rust(|api| {
+                let op = api.output.get_focused().unwrap();
+                api.tag.add(&op, ["1"])[0].set_active(true);
+                api.process.spawn(["foot"]);
+                api.process.spawn(["foot"]);
+            });
+
+            sleep_secs(1);
+
+            run_rust(|api| {
+                let windows = api.window.get_all();
+                windows[0].set_label("editor");
+                // Taking a label another window has does nothing
+                windows[1].set_label("editor");
+
+                assert_eq!(windows[0].label().as_deref(), Some("editor"));
+                assert_eq!(windows[1].label(), None);
+
+                windows[0].clear_label();
+                assert!(api.window.get_by_label("editor").is_none());
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn set_last_window_fallback() -> anyhow::Result<()> {
+        test_api(|sender| {
+            run_rust(|api| {
+                api.window
+                    .set_last_window_fallback(["alacritty", "-e", "htop"]);
+            });
+
+            with_state(&sender, |state| {
+                assert_eq!(
+                    state.pinnacle.config.last_window_fallback,
+                    vec!["alacritty", "-e", "htop"]
+                );
+            });
+
+            run_rust(|api| {
+                api.window.set_last_window_fallback(Vec::<String>::new());
+            });
+
+            with_state(&sender, |state| {
+                assert!(state.pinnacle.config.last_window_fallback.is_empty());
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn last_window_fallback_spawns_once_the_window_is_gone() -> anyhow::Result<()> {
+        use pinnacle::state::WithState;
+
+        test_api(|sender| {
+            run_rust(|api| {
+                let op = api.output.get_focused().unwrap();
+                api.tag.add(&op, ["1"])[0].set_active(true);
+                api.window.set_last_window_fallback(["foot"]);
+                api.process.spawn(["foot"]);
+            });
+
+            sleep_secs(1);
+
+            let (tx, rx) = std::sync::mpsc::channel();
+            with_state(&sender, move |state| {
+                assert_eq!(state.pinnacle.windows.len(), 1);
+                let win = &state.pinnacle.windows[0];
+                tx.send(win.with_state(|st| st.id)).unwrap();
+            });
+            let closed_id = rx.recv()?;
+
+            run_rust(|api| {
+                api.window.get_all()[0].close();
+            });
+
+            sleep_secs(1);
+
+            with_state(&sender, move |state| {
+                // The closed window is gone and the fallback opened a new one
+                assert_eq!(state.pinnacle.windows.len(), 1);
+                let win = &state.pinnacle.windows[0];
+                assert_ne!(win.with_state(|st| st.id), closed_id);
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn open_next_on_focused_tag() -> anyhow::Result<()> {
+        test_api(|sender| {
+            run_rust(|api| {
+                api.window.open_next_on_focused_tag();
+            });
+
+            with_state(&sender, |state| {
+                assert!(state.pinnacle.config.open_next_on_focused_tag);
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn once_window_rule() -> anyhow::Result<()> {
+        use pinnacle::state::WithState;
+        use pinnacle_api::window::rules::{WindowRule, WindowRuleCondition};
+
+        test_api(|sender| {
+            run_rust(|api| {
+                let op = api.output.get_focused().unwrap();
+                api.tag.add(&op, ["1"])[0].set_active(true);
+                api.window.add_window_rule(
+                    WindowRuleCondition::new().classes(["foot"]),
+                    WindowRule::new().floating(true).once(),
+                );
+                api.window.reapply_window_rules();
+            });
+
+            sleep_secs(1);
+
+            with_state(&sender, |state| {
+                // Reapplying rules doesn't use up rules that apply once
+                let (_, _, rule) = &state.pinnacle.config.window_rules[0];
+                assert_eq!(rule.once, Some(true));
+            });
+
+            run_rust(|api| {
+                api.process.spawn(["foot"]);
+            });
+
+            sleep_secs(1);
+
+            with_state(&sender, |state| {
+                // The first matching window uses up the rule
+                assert_eq!(state.pinnacle.windows.len(), 1);
+                assert!(
+                    state.pinnacle.windows[0].with_state(|st| st.floating_or_tiled.is_floating())
+                );
+                assert!(state.pinnacle.config.window_rules.is_empty());
+            });
+
+            run_rust(|api| {
+                api.process.spawn(["foot"]);
+            });
+
+            sleep_secs(1);
+
+            with_state(&sender, |state| {
+                assert_eq!(state.pinnacle.windows.len(), 2);
+                assert!(state.pinnacle.windows[1].with_state(|st| st.floating_or_tiled.is_tiled()));
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn reapply_window_rules_to_open_window() -> anyhow::Result<()> {
+        use pinnacle::state::WithState;
+        use pinnacle_api::window::{
+            rules::{WindowRule, WindowRuleCondition},
+            FullscreenOrMaximized,
+        };
+        use smithay::reexports::wayland_protocols::xdg::shell::server::xdg_toplevel;
+
+        test_api(|sender| {
+            run_rust(|api| {
+                let op = api.output.get_focused().unwrap();
+                api.tag.add(&op, ["1"])[0].set_active(true);
+                api.process.spawn(["foot"]);
+            });
+
+            sleep_secs(1);
+
+            with_state(&sender, |state| {
+                assert_eq!(state.pinnacle.windows.len(), 1);
+                assert!(state.pinnacle.windows[0]
+                    .with_state(|st| st.fullscreen_or_maximized.is_neither()));
+            });
+
+            run_rust(|api| {
+                api.window.add_window_rule(
+                    WindowRuleCondition::new().classes(["foot"]),
+                    WindowRule::new().fullscreen_or_maximized(FullscreenOrMaximized::Maximized),
+                );
+                api.window.reapply_window_rules();
+            });
+
+            sleep_secs(1);
+
+            with_state(&sender, |state| {
+                let win = &state.pinnacle.windows[0];
+                assert!(win.with_state(|st| st.fullscreen_or_maximized.is_maximized()));
+                // The open window was sent its new state
+                assert!(win.toplevel().unwrap().with_pending_state(|st| {
+                    st.states.contains(xdg_toplevel::State::Maximized)
+                }));
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn set_activated_until_focus_changes() -> anyhow::Result<()> {
+        test_api(|_sender| {
+            run_rust(|api| {
+                let op = api.output.get_focused().unwrap();
+                api.tag.add(&op, ["1"])[0].set_active(true);
+                api.process.spawn(["foot"]);
+            });
+
+            sleep_secs(1);
+
+            run_rust(|api| {
+                api.process.spawn(["foot"]);
+            });
+
+            sleep_secs(1);
+
+            run_rust(|api| {
+                let windows = api.window.get_all();
+                assert_eq!(windows[1].focused(), Some(true));
+
+                windows[0].set_activated(true);
+                assert_eq!(windows[0].activated(), Some(true));
+                assert_eq!(windows[1].activated(), Some(false));
+
+                // Focusing another window takes the activated state back
+                windows[1].set_focused(true);
+                assert_eq!(windows[0].activated(), Some(false));
+                assert_eq!(windows[1].activated(), Some(true));
+
+                windows[0].set_focused(true);
+                assert_eq!(windows[0].activated(), Some(true));
+                assert_eq!(windows[1].activated(), Some(false));
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn set_focused_keeps_stacking_order() -> anyhow::Result<()> {
+        test_api(|sender| {
+            run_rust(|api| {
+                let op = api.output.get_focused().unwrap();
+                api.tag.add(&op, ["1"])[0].set_active(true);
+                api.process.spawn(["foot"]);
+            });
+
+            sleep_secs(1);
+
+            run_rust(|api| {
+                api.process.spawn(["foot"]);
+            });
+
+            sleep_secs(1);
+
+            // The positions of the windows in `windows`, from bottom to top
+            fn stack(state: &pinnacle::state::State) -> Vec<Option<usize>> {
+                state
+                    .pinnacle
+                    .z_index_stack
+                    .iter()
+                    .map(|win| state.pinnacle.windows.iter().position(|w| w == win))
+                    .collect()
+            }
+
+            with_state(&sender, |state| {
+                assert_eq!(stack(state), vec![Some(0), Some(1)]);
+            });
+
+            run_rust(|api| {
+                api.window.get_all()[0].set_focused(true);
+            });
+
+            sleep_secs(1);
+
+            with_state(&sender, |state| {
+                assert_eq!(stack(state), vec![Some(0), Some(1)]);
+            });
+
+            run_rust(|api| {
+                api.window.get_all()[0].focus_no_raise();
+            });
+
+            sleep_secs(1);
+
+            with_state(&sender, |state| {
+                assert_eq!(stack(state), vec![Some(0), Some(1)]);
+
+                let win = state.pinnacle.windows[0].clone();
+                state.focus_window(&win, true);
+                assert_eq!(stack(state), vec![Some(1), Some(0)]);
+            });
+
+            Ok(())
+        })
+    }
+
+    /// A layout that splits the output into columns and records how many windows it laid out.
+    struct CountingLayout(std::sync::Arc<std::sync::atomic::AtomicUsize>);
+
+    impl pinnacle_api::layout::LayoutManager for CountingLayout {
+        fn active_layout(
+            &mut self,
+            _args: &pinnacle_api::layout::LayoutArgs,
+        ) -> &dyn pinnacle_api::layout::LayoutGenerator {
+            self
+        }
+    }
+
+    impl pinnacle_api::layout::LayoutGenerator for CountingLayout {
+        fn layout(
+            &self,
+            args: &pinnacle_api::layout::LayoutArgs,
+        ) -> Vec<pinnacle_api::util::Geometry> {
+            let count = args.windows.len() as u32;
+            self.0
+                .store(count as usize, std::sync::atomic::Ordering::SeqCst);
+
+            (0..count)
+                .map(|i| pinnacle_api::util::Geometry {
+                    x: (args.output_width / count * i) as i32,
+                    y: 0,
+                    width: args.output_width / count,
+                    height: args.output_height,
+                })
+                .collect()
+        }
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn minimize_and_restore() -> anyhow::Result<()> {
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        test_api(|sender| {
+            let laid_out = Arc::new(AtomicUsize::new(0));
+
+            let counter = laid_out.clone();
+            setup_rust(move |api| {
+                api.layout.set_manager(CountingLayout(counter));
+            });
+
+            run_rust(|api| {
+                let op = api.output.get_focused().unwrap();
+                api.tag.add(&op, ["1"])[0].set_active(true);
+                api.process.spawn(["foot"]);
+            });
+
+            sleep_secs(1);
+
+            run_rust(|api| {
+                api.process.spawn(["foot"]);
+            });
+
+            sleep_secs(1);
+
+            assert_eq!(laid_out.load(Ordering::SeqCst), 2);
+
+            run_rust(|api| {
+                let windows = api.window.get_all();
+                windows[1].set_minimized(true);
+
+                // Minimized windows are still around
+                assert_eq!(api.window.get_all().len(), 2);
+                assert_eq!(windows[1].minimized(), Some(true));
+            });
+
+            sleep_secs(1);
+
+            // The layout skips the minimized window
+            assert_eq!(laid_out.load(Ordering::SeqCst), 1);
+
+            with_state(&sender, |state| {
+                let shown = &state.pinnacle.windows[0];
+                let minimized = &state.pinnacle.windows[1];
+                assert!(!state.pinnacle.space.elements().any(|win| win == minimized));
+
+                // Focus moves off the minimized window and cycling skips it
+                let op = state.pinnacle.focused_output().unwrap().clone();
+                assert_eq!(state.pinnacle.focused_window(&op).as_ref(), Some(shown));
+                assert_eq!(state.pinnacle.previously_focused_window(), None);
+            });
+
+            run_rust(|api| {
+                api.window.get_all()[1].set_minimized(false);
+            });
+
+            sleep_secs(1);
+
+            assert_eq!(laid_out.load(Ordering::SeqCst), 2);
+
+            with_state(&sender, |state| {
+                let restored = &state.pinnacle.windows[1];
+                assert!(!restored.is_minimized());
+                assert!(state.pinnacle.space.elements().any(|win| win == restored));
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn xdg_minimize_request() -> anyhow::Result<()> {
+        use smithay::wayland::shell::xdg::XdgShellHandler;
+
+        test_api(|sender| {
+            run_rust(|api| {
+                let op = api.output.get_focused().unwrap();
+                api.tag.add(&op, ["1"])[0].set_active(true);
+                api.process.spawn(["foot"]);
+            });
+
+            sleep_secs(1);
+
+            with_state(&sender, |state| {
+                let win = state.pinnacle.windows[0].clone();
+                state.minimize_request(win.toplevel().unwrap().clone());
+
+                assert!(win.is_minimized());
+                assert!(!state.pinnacle.space.elements().any(|w| w == &win));
+            });
+
+            run_rust(|api| {
+                assert_eq!(api.window.get_all()[0].minimized(), Some(true));
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn set_tag_lays_out_the_old_output() -> anyhow::Result<()> {
+        test_api(|sender| {
+            run_rust(|api| {
+                let op = api.output.get_by_name(DUMMY_OUTPUT_NAME).unwrap();
+                api.tag.add(&op, ["1"])[0].set_active(true);
+                api.process.spawn(["foot"]);
+            });
+
+            sleep_secs(1);
+
+            with_state(&sender, |state| {
+                state.pinnacle.new_output("Second", (500, 500).into());
+            });
+
+            sleep_secs(1);
+
+            run_rust(|api| {
+                let second = api.output.get_by_name("Second").unwrap();
+                api.tag.add(&second, ["2"])[0].set_active(true);
+
+                let win = &api.window.get_all()[0];
+                let op = api.output.get_by_name(DUMMY_OUTPUT_NAME).unwrap();
+                let second = api.output.get_by_name("Second").unwrap();
+                win.set_tag(&api.tag.get_on_output("2", &second).unwrap(), true);
+                win.set_tag(&api.tag.get_on_output("1", &op).unwrap(), false);
+            });
+
+            sleep_secs(1);
+
+            with_state(&sender, |state| {
+                let original_op = output_for_name(state, DUMMY_OUTPUT_NAME);
+                let second_op = output_for_name(state, "Second");
+                let win = &state.pinnacle.windows[0];
+
+                // The window left the first output instead of staying where it was tiled
+                let outputs = state.pinnacle.space.outputs_for_element(win);
+                assert!(!outputs.contains(&original_op));
+                assert!(outputs.contains(&second_op));
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn copy_to_tag() -> anyhow::Result<()> {
+        use pinnacle::state::WithState;
+
+        test_api(|sender| {
+            run_rust(|api| {
+                let op = api.output.get_focused().unwrap();
+                api.tag.add(&op, ["1", "2", "3"])[0].set_active(true);
+                api.process.spawn(["foot"]);
+            });
+
+            sleep_secs(1);
+
+            run_rust(|api| {
+                let win = &api.window.get_all()[0];
+                win.copy_to_tag(&api.tag.get("3").unwrap());
+            });
+
+            sleep_secs(1);
+
+            with_state(&sender, |state| {
+                assert_eq!(
+                    state.pinnacle.windows[0].with_state(|st| st
+                        .tags
+                        .iter()
+                        .map(|tag| tag.name())
+                        .collect::<Vec<_>>()),
+                    vec!["1", "3"]
+                );
+            });
+
+            // The window shows up on both tags
+            for tag in ["3", "1"] {
+                run_rust(move |api| {
+                    api.tag.get(tag).unwrap().switch_to();
+                });
+
+                sleep_secs(1);
+
+                with_state(&sender, |state| {
+                    let win = &state.pinnacle.windows[0];
+                    assert!(win.is_on_active_tag());
+                    assert!(state.pinnacle.space.elements().any(|w| w == win));
+                });
+            }
+
+            Ok(())
+        })
+    }
+}
+
+mod input {
+    use super::*;
+
+    #[tokio::main]
+    #[self::test]
+    async fn confine_pointer() -> anyhow::Result<()> {
+        test_api(|sender| {
+            run_rust(|api| {
+                let op = api.output.get_focused().unwrap();
+                api.input.confine_pointer(&op);
+            });
+
+            with_state(&sender, |state| {
+                assert_eq!(
+                    state
+                        .pinnacle
+                        .input_state
+                        .pointer_confinement
+                        .as_ref()
+                        .map(|op| op.name()),
+                    Some(DUMMY_OUTPUT_NAME.to_string())
+                );
+            });
+
+            run_rust(|api| {
+                api.input.release_pointer();
+            });
+
+            with_state(&sender, |state| {
+                assert!(state.pinnacle.input_state.pointer_confinement.is_none());
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn pointer() -> anyhow::Result<()> {
+        test_api(|_sender| {
+            run_rust(|api| {
+                let pointer = api.input.pointer();
+
+                assert!(pointer.location.is_some());
+                assert_eq!(
+                    pointer.output.map(|op| op.name().to_string()),
+                    Some(DUMMY_OUTPUT_NAME.to_string())
+                );
+                assert!(pointer.window.is_none());
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn set_modifier_remaps() -> anyhow::Result<()> {
+        use pinnacle::input::ModifierMask;
+        use pinnacle_api::input::Mod;
+
+        test_api(|sender| {
+            run_rust(|api| {
+                api.input
+                    .set_modifier_remaps([(Mod::Alt, Mod::Super), (Mod::Super, Mod::Alt)]);
+            });
+
+            sleep_secs(1);
+
+            with_state(&sender, |state| {
+                let remaps = &state.pinnacle.input_state.modifier_remaps;
+                assert_eq!(
+                    (ModifierMask::ALT | ModifierMask::SHIFT).remapped(remaps),
+                    ModifierMask::SUPER | ModifierMask::SHIFT
+                );
+                assert_eq!(
+                    (ModifierMask::ALT | ModifierMask::SUPER).remapped(remaps),
+                    ModifierMask::ALT | ModifierMask::SUPER
+                );
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn set_focus_on_release() -> anyhow::Result<()> {
+        test_api(|sender| {
+            run_rust(|api| {
+                api.input.set_focus_on_release(true);
+            });
+
+            sleep_secs(1);
+
+            with_state(&sender, |state| {
+                let settings = &state.pinnacle.input_state.click_settings;
+                assert!(settings.focus_on_release);
+                // Other click settings are left alone
+                assert_eq!(
+                    settings.drag_threshold,
+                    pinnacle::input::ClickSettings::default().drag_threshold
+                );
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn seats() -> anyhow::Result<()> {
+        test_api(|_sender| {
+            run_rust(|api| {
+                let seats = api.input.seats();
+
+                assert_eq!(seats.len(), 1);
+                assert_eq!(seats[0].keyboard, Some(true));
+                assert_eq!(seats[0].pointer, Some(true));
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn keybinds() -> anyhow::Result<()> {
+        use pinnacle::input::{KeybindOwner, ModifierMask};
+        use pinnacle_api::{input::Mod, xkbcommon::xkb::Keysym};
+
+        test_api(|sender| {
+            with_state(&sender, |state| {
+                let mods = ModifierMask::SUPER | ModifierMask::SHIFT;
+                let (keybind_sender, _) = tokio::sync::mpsc::unbounded_channel();
+
+                let input_state = &mut state.pinnacle.input_state;
+                input_state
+                    .keybinds
+                    .insert((mods, Keysym::q), keybind_sender);
+                input_state.keybind_owners.insert(
+                    (mods, None, Keysym::q),
+                    KeybindOwner {
+                        pid: Some(std::process::id() as i32),
+                    },
+                );
+            });
+
+            run_rust(|api| {
+                let keybinds = api.input.keybinds();
+
+                assert_eq!(keybinds.len(), 1);
+                assert_eq!(keybinds[0].mods, vec![Mod::Shift, Mod::Super]);
+                assert_eq!(keybinds[0].key, Keysym::q);
+                assert_eq!(keybinds[0].held_key, None);
+                assert_eq!(keybinds[0].owner_pid, Some(std::process::id() as i32));
+                assert!(keybinds[0].owner_name.is_some());
+                // The receiving end was dropped
+                assert!(!keybinds[0].connected);
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn keybind_allow_when_disabled() -> anyhow::Result<()> {
+        use std::collections::HashSet;
+
+        use pinnacle::input::ModifierMask;
+        use pinnacle_api::{input::Mod, xkbcommon::xkb::Keysym};
+
+        test_api(|sender| {
+            setup_rust(|api| {
+                api.input.keybind([Mod::Super], 'q', || ());
+                api.input
+                    .keybind_allow_when_disabled([Mod::Super], 'w', || ());
+            });
+
+            sleep_secs(1);
+
+            with_state(&sender, |state| {
+                let input_state = &state.pinnacle.input_state;
+                assert_eq!(input_state.keybinds.len(), 2);
+                assert_eq!(
+                    input_state.keybinds_allowed_when_disabled,
+                    HashSet::from([(ModifierMask::SUPER, None, Keysym::w)])
+                );
+            });
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn shorter_idle_stage_added_later() -> anyhow::Result<()> {
+        use std::{
+            sync::{
+                atomic::{AtomicBool, Ordering},
+                Arc,
+            },
+            time::Duration,
+        };
+
+        test_api(|_sender| {
+            let long_idle = Arc::new(AtomicBool::new(false));
+            let short_idle = Arc::new(AtomicBool::new(false));
+
+            let long = long_idle.clone();
+            setup_rust(move |api| {
+                api.input.on_idle(
+                    Duration::from_secs(60),
+                    move || long.store(true, Ordering::SeqCst),
+                    || (),
+                );
+            });
+
+            sleep_secs(1);
+
+            let short = short_idle.clone();
+            setup_rust(move |api| {
+                api.input.on_idle(
+                    Duration::from_secs(1),
+                    move || short.store(true, Ordering::SeqCst),
+                    || (),
+                );
+            });
+
+            sleep_secs(3);
+
+            // The short stage doesn't wait for the long stage's deadline
+            assert!(short_idle.load(Ordering::SeqCst));
+            assert!(!long_idle.load(Ordering::SeqCst));
+
+            Ok(())
+        })
+    }
+
+    #[tokio::main]
+    #[self::test]
+    async fn held_key_typed_alone_is_replayed() -> anyhow::Result<()> {
+        use pinnacle_api::xkbcommon::xkb::{Keycode, Keysym};
+        use smithay::backend::input::KeyState;
+        use std::sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        };
+
+        // Evdev keycodes offset by 8, as xkb expects
+        const SPACE: Keycode = Keycode::new(65);
+        const H: Keycode = Keycode::new(43);
+
+        test_api(|sender| {
+            let triggered = Arc::new(AtomicBool::new(false));
+
+            let flag = triggered.clone();
+            setup_rust(move |api| {
+                api.input
+                    .held_keybind([], "space", 'h', move || flag.store(true, Ordering::SeqCst));
+            });
+
+            sleep_secs(1);
+
+            // The chord fires, so the held space isn't typed
+            with_state(&sender, |state| {
+                state.handle_key(SPACE, KeyState::Pressed, 0, true);
+                assert!(state.pinnacle.input_state.swallowed_held_key.is_some());
+                state.handle_key(H, KeyState::Pressed, 0, true);
+                state.handle_key(H, KeyState::Released, 0, true);
+                assert_eq!(
+                    state.pinnacle.input_state.swallowed_held_key,
+                    Some((SPACE, true))
+                );
+                state.handle_key(SPACE, KeyState::Released, 0, true);
+                assert!(state.pinnacle.input_state.swallowed_held_key.is_none());
+            });
+
+            sleep_secs(1);
+
+            assert!(triggered.swap(false, Ordering::SeqCst));
+
+            // Space on its own is replayed on release instead of being eaten
+            with_state(&sender, |state| {
+                assert!(state.send_synthetic_key(Keysym::space, true));
+                assert!(state.pinnacle.input_state.swallowed_held_key.is_none());
+            });
+
+            sleep_secs(1);
+
+            assert!(!triggered.load(Ordering::SeqCst));
+
+            Ok(())
+        })
     }
 }
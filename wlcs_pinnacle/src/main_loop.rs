@@ -128,8 +128,9 @@ fn handle_event(event: WlcsEvent, state: &mut State) {
                 }
 
                 window.with_state_mut(|state| {
-                    state.floating_or_tiled =
-                        FloatingOrTiled::Floating(Rectangle::from_loc_and_size(location, size));
+                    state.floating_or_tiled = FloatingOrTiled::Floating(
+                        Rectangle::from_loc_and_size(location, size).to_f64(),
+                    );
                 });
 
                 for output in state.pinnacle.space.outputs_for_element(&window) {